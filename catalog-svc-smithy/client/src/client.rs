@@ -0,0 +1,67 @@
+//! The generated Smithy client, configured from a [Profile].
+
+use std::fmt;
+
+use catalog_client_api::Client;
+use catalog_client_api::config::interceptors::BeforeTransmitInterceptorContextMut;
+use catalog_client_api::config::{ConfigBag, Intercept, RuntimeComponents};
+use catalog_client_api::error::BoxError;
+
+use crate::profile::Profile;
+
+/// Adds the profile's credentials to every request, like the axum and Smithy servers expect them.
+struct Credentials {
+    header: &'static str,
+    value: String,
+}
+
+impl Credentials {
+    fn of(profile: &Profile) -> Option<Self> {
+        if let Some(token) = &profile.token {
+            return Some(Self {
+                header: "authorization",
+                value: format!("Bearer {token}"),
+            });
+        }
+        profile.api_key.as_ref().map(|api_key| Self {
+            header: "x-api-key",
+            value: api_key.clone(),
+        })
+    }
+}
+
+// Keeps the secret out of client debug logs
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("header", &self.header)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Intercept for Credentials {
+    fn name(&self) -> &'static str {
+        "Credentials"
+    }
+
+    fn modify_before_signing(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        context
+            .request_mut()
+            .headers_mut()
+            .insert(self.header, self.value.clone());
+        Ok(())
+    }
+}
+
+pub fn build(profile: &Profile) -> Client {
+    let mut config = catalog_client_api::Config::builder().endpoint_url(profile.endpoint());
+    if let Some(credentials) = Credentials::of(profile) {
+        config = config.interceptor(credentials);
+    }
+    Client::from_conf(config.build())
+}
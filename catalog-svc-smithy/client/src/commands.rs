@@ -0,0 +1,426 @@
+//! The `items` subcommands.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use catalog_client_api::Client;
+use catalog_client_api::types::CatalogItemSort;
+use clap::{Args, Subcommand, ValueEnum};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::error::{CliError, from_sdk};
+use crate::item::{Item, ItemBody, parse_decimal, smithy_category};
+use crate::output::{self, OutputFormat};
+
+/// Largest page the service returns.
+const MAX_PAGE_SIZE: i64 = 100;
+
+#[derive(Debug, Subcommand)]
+pub enum ItemsCommand {
+    /// Create an item.
+    Create(CreateArgs),
+    /// Show an item.
+    Get { item_id: String },
+    /// List items, fetching every page unless limited.
+    List(ListArgs),
+    /// Change some fields of an item; the others keep their current values.
+    Update(UpdateArgs),
+    /// Delete items.
+    Delete {
+        #[arg(required = true)]
+        item_ids: Vec<String>,
+    },
+    /// Create the items of a JSON or CSV file (as written by `export`), reporting each failure.
+    Import(ImportArgs),
+    /// Write every item as JSON or CSV.
+    Export(ExportArgs),
+    /// Multiply the prices of items, rounded to cents (e.g. `--multiplier 1.1` for +10%).
+    ///
+    /// The service reprices all matching items in one transaction: either every item is repriced
+    /// or none is.
+    Reprice(RepriceArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CreateArgs {
+    #[arg(long)]
+    name: String,
+    #[arg(long)]
+    description: String,
+    /// `Books` or `Electronics`.
+    #[arg(long)]
+    category: String,
+    /// Date with day resolution (YYYY-MM-DD).
+    #[arg(long)]
+    date: String,
+    #[arg(long)]
+    brand: Option<String>,
+    /// Decimal price, e.g. `19.99`.
+    #[arg(long)]
+    price: String,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SortArg {
+    /// Oldest first.
+    CreatedAt,
+    /// Highest average rating first.
+    Rating,
+}
+
+impl From<SortArg> for CatalogItemSort {
+    fn from(sort: SortArg) -> Self {
+        match sort {
+            SortArg::CreatedAt => CatalogItemSort::CreatedAt,
+            SortArg::Rating => CatalogItemSort::Rating,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    /// Stop after this many items.
+    #[arg(long)]
+    max_items: Option<usize>,
+    /// Items fetched per request (1 to 100).
+    #[arg(long, default_value_t = MAX_PAGE_SIZE, value_parser = clap::value_parser!(i64).range(1..=MAX_PAGE_SIZE))]
+    page_size: i64,
+    /// Items to skip.
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(i64).range(0..))]
+    offset: i64,
+    #[arg(long, value_enum)]
+    sort: Option<SortArg>,
+}
+
+#[derive(Debug, Args)]
+pub struct UpdateArgs {
+    item_id: String,
+    #[arg(long)]
+    name: Option<String>,
+    #[arg(long)]
+    description: Option<String>,
+    #[arg(long)]
+    category: Option<String>,
+    #[arg(long)]
+    date: Option<String>,
+    /// New brand; an empty value removes it.
+    #[arg(long)]
+    brand: Option<String>,
+    #[arg(long)]
+    price: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum FileFormat {
+    Json,
+    Csv,
+}
+
+impl FileFormat {
+    /// `format`, or else the one of `path`'s extension (JSON unless `.csv`).
+    fn resolve(format: Option<FileFormat>, path: Option<&Path>) -> FileFormat {
+        format.unwrap_or_else(|| {
+            match path.and_then(Path::extension).and_then(|ext| ext.to_str()) {
+                Some(ext) if ext.eq_ignore_ascii_case("csv") => FileFormat::Csv,
+                _ => FileFormat::Json,
+            }
+        })
+    }
+}
+
+impl From<FileFormat> for OutputFormat {
+    fn from(format: FileFormat) -> Self {
+        match format {
+            FileFormat::Json => OutputFormat::Json,
+            FileFormat::Csv => OutputFormat::Csv,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct ImportArgs {
+    /// File of items with `name`, `description`, `category`, `date`, `brand` and `price`.
+    file: PathBuf,
+    /// Format of the file (default: from its extension).
+    #[arg(long, value_enum)]
+    format: Option<FileFormat>,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Write to this file instead of standard output.
+    #[arg(long)]
+    file: Option<PathBuf>,
+    /// Format (default: from the file extension, else JSON).
+    #[arg(long, value_enum)]
+    format: Option<FileFormat>,
+}
+
+#[derive(Debug, Args)]
+pub struct RepriceArgs {
+    /// Factor applied to prices; must be greater than zero.
+    #[arg(long, value_parser = parse_decimal)]
+    multiplier: Decimal,
+    /// Only items of this category.
+    #[arg(long)]
+    category: Option<String>,
+    /// Only items of this brand.
+    #[arg(long)]
+    brand: Option<String>,
+    /// Show the new prices without changing anything.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+pub async fn run(
+    client: &Client,
+    format: OutputFormat,
+    command: ItemsCommand,
+    out: &mut impl Write,
+) -> Result<(), CliError> {
+    match command {
+        ItemsCommand::Create(args) => {
+            let body = ItemBody {
+                name: args.name,
+                description: args.description,
+                category: args.category,
+                date: args.date,
+                brand: args.brand,
+                price: args.price,
+            };
+            let item = create(client, &body).await?;
+            output::write_item(format, &item, out)
+        }
+        ItemsCommand::Get { item_id } => {
+            let item = get(client, &item_id).await?;
+            output::write_item(format, &item, out)
+        }
+        ItemsCommand::List(args) => {
+            let items = list(
+                client,
+                args.offset,
+                args.page_size,
+                args.max_items,
+                args.sort,
+            )
+            .await?;
+            output::write_items(format, &items, out)
+        }
+        ItemsCommand::Update(args) => {
+            let item = get(client, &args.item_id).await?;
+            let mut body = ItemBody::of(&item);
+            body.name = args.name.unwrap_or(body.name);
+            body.description = args.description.unwrap_or(body.description);
+            body.category = args.category.unwrap_or(body.category);
+            body.date = args.date.unwrap_or(body.date);
+            if let Some(brand) = args.brand {
+                body.brand = Some(brand).filter(|brand| !brand.is_empty());
+            }
+            body.price = args.price.unwrap_or(body.price);
+            let item = update(client, &args.item_id, &body).await?;
+            output::write_item(format, &item, out)
+        }
+        ItemsCommand::Delete { item_ids } => {
+            let total = item_ids.len();
+            let mut failures = Vec::new();
+            for item_id in item_ids {
+                match client.delete_catalog_item().item_id(&item_id).send().await {
+                    Ok(_) => eprintln!("deleted {item_id}"),
+                    Err(err) => {
+                        let err = from_sdk(err);
+                        eprintln!("{item_id}: {err}");
+                        failures.push(err);
+                    }
+                }
+            }
+            bulk_result(total, failures)
+        }
+        ItemsCommand::Import(args) => {
+            let file_format = FileFormat::resolve(args.format, Some(args.file.as_path()));
+            let bodies = read_bodies(&args.file, file_format)?;
+            let total = bodies.len();
+            let mut created = Vec::new();
+            let mut failures = Vec::new();
+            for (index, body) in bodies.iter().enumerate() {
+                match create(client, body).await {
+                    Ok(item) => created.push(item),
+                    Err(err) => {
+                        eprintln!("item {} ({:?}): {err}", index + 1, body.name);
+                        failures.push(err);
+                    }
+                }
+            }
+            output::write_items(format, &created, out)?;
+            bulk_result(total, failures)
+        }
+        ItemsCommand::Export(args) => {
+            let items = list(client, 0, MAX_PAGE_SIZE, None, None).await?;
+            let file_format = FileFormat::resolve(args.format, args.file.as_deref());
+            match args.file {
+                Some(path) => {
+                    let mut file = std::io::BufWriter::new(fs::File::create(&path)?);
+                    output::write_items(file_format.into(), &items, &mut file)?;
+                    file.flush()?;
+                    eprintln!("exported {} items to {}", items.len(), path.display());
+                    Ok(())
+                }
+                None => output::write_items(file_format.into(), &items, out),
+            }
+        }
+        ItemsCommand::Reprice(args) => reprice(client, format, args, out).await,
+    }
+}
+
+async fn get(client: &Client, item_id: &str) -> Result<Item, CliError> {
+    let output = client
+        .get_catalog_item()
+        .item_id(item_id)
+        .send()
+        .await
+        .map_err(from_sdk)?;
+    Ok(Item::from(&output))
+}
+
+async fn create(client: &Client, body: &ItemBody) -> Result<Item, CliError> {
+    let output = client
+        .create_catalog_item()
+        .name(&body.name)
+        .description(&body.description)
+        .category(body.smithy_category()?)
+        .date(&body.date)
+        .set_brand(body.brand.clone())
+        .price(&body.price)
+        .send()
+        .await
+        .map_err(from_sdk)?;
+    Ok(Item::from(&output))
+}
+
+async fn update(client: &Client, item_id: &str, body: &ItemBody) -> Result<Item, CliError> {
+    let output = client
+        .update_catalog_item()
+        .item_id(item_id)
+        .name(&body.name)
+        .description(&body.description)
+        .category(body.smithy_category()?)
+        .date(&body.date)
+        .set_brand(body.brand.clone())
+        .price(&body.price)
+        .send()
+        .await
+        .map_err(from_sdk)?;
+    Ok(Item::from(&output))
+}
+
+/// Items from `offset` on, page by page until the last page or `max_items`.
+async fn list(
+    client: &Client,
+    mut offset: i64,
+    page_size: i64,
+    max_items: Option<usize>,
+    sort: Option<SortArg>,
+) -> Result<Vec<Item>, CliError> {
+    let mut items = Vec::new();
+    loop {
+        let page = client
+            .list_catalog_items()
+            .limit(page_size)
+            .offset(offset)
+            .set_sort(sort.map(CatalogItemSort::from))
+            .send()
+            .await
+            .map_err(from_sdk)?;
+        items.extend(page.items().iter().map(Item::from));
+        if let Some(max_items) = max_items
+            && items.len() >= max_items
+        {
+            items.truncate(max_items);
+            break;
+        }
+        if !page.has_more() || page.items().is_empty() {
+            break;
+        }
+        offset += i64::try_from(page.items().len()).unwrap_or(MAX_PAGE_SIZE);
+    }
+    Ok(items)
+}
+
+fn read_bodies(path: &Path, format: FileFormat) -> Result<Vec<ItemBody>, CliError> {
+    let unreadable = |err: &dyn std::fmt::Display| {
+        CliError::Usage(format!("cannot read items from {}: {err}", path.display()))
+    };
+    match format {
+        FileFormat::Json => {
+            let file = fs::File::open(path).map_err(|err| unreadable(&err))?;
+            serde_json::from_reader(std::io::BufReader::new(file)).map_err(|err| unreadable(&err))
+        }
+        FileFormat::Csv => csv::Reader::from_path(path)
+            .map_err(|err| unreadable(&err))?
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .map_err(|err| unreadable(&err)),
+    }
+}
+
+/// Applies `args.multiplier` to the matching items with a single call to the service, and prints
+/// the repriced items; with `dry_run`, only lists the items and prints their new prices.
+async fn reprice(
+    client: &Client,
+    format: OutputFormat,
+    args: RepriceArgs,
+    out: &mut impl Write,
+) -> Result<(), CliError> {
+    if args.multiplier <= Decimal::ZERO {
+        return Err(CliError::Usage(
+            "the multiplier must be greater than zero".to_string(),
+        ));
+    }
+    let category = args.category.as_deref().map(smithy_category).transpose()?;
+    if !args.dry_run {
+        let output = client
+            .reprice_catalog_items()
+            .multiplier(args.multiplier.to_string())
+            .set_category(category)
+            .set_brand(args.brand)
+            .send()
+            .await
+            .map_err(from_sdk)?;
+        let repriced: Vec<Item> = output.items().iter().map(Item::from).collect();
+        return output::write_items(format, &repriced, out);
+    }
+
+    let items = list(client, 0, MAX_PAGE_SIZE, None, None).await?;
+    let matching = items.into_iter().filter(|item| {
+        args.category
+            .as_ref()
+            .is_none_or(|category| &item.category == category)
+            && args
+                .brand
+                .as_ref()
+                .is_none_or(|brand| item.brand.as_ref() == Some(brand))
+    });
+    let mut repriced = Vec::new();
+    for mut item in matching {
+        let price = parse_decimal(&item.price).map_err(CliError::Invalid)?;
+        // Rounded like the service's repricing, to the stored NUMERIC(10, 2)
+        let new_price = (price * args.multiplier)
+            .round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero);
+        if new_price != price {
+            item.price = new_price.to_string();
+            repriced.push(item);
+        }
+    }
+    output::write_items(format, &repriced, out)
+}
+
+/// Outcome of a command applied to `total` items: the error itself when there was only one
+/// item, otherwise [CliError::Partial] if any failed.
+fn bulk_result(total: usize, mut failures: Vec<CliError>) -> Result<(), CliError> {
+    let failed = failures.len();
+    match failures.pop() {
+        None => Ok(()),
+        Some(err) if total == 1 => Err(err),
+        Some(_) => Err(CliError::Partial { failed, total }),
+    }
+}
@@ -0,0 +1,86 @@
+//! CLI errors and the process exit codes they map to.
+
+use catalog_client_api::config::http::HttpResponse;
+use catalog_client_api::error::{DisplayErrorContext, ProvideErrorMetadata, SdkError};
+
+/// Exit codes, so scripts can tell failures apart. Clap itself exits with 2 on bad usage.
+pub mod exit_code {
+    pub const FAILURE: u8 = 1;
+    pub const USAGE: u8 = 2;
+    pub const NOT_FOUND: u8 = 3;
+    pub const INVALID: u8 = 4;
+    pub const UNAUTHORIZED: u8 = 5;
+    pub const SERVICE: u8 = 6;
+    pub const UNAVAILABLE: u8 = 7;
+    pub const PARTIAL: u8 = 8;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    /// Bad arguments, profile or input file.
+    #[error("{0}")]
+    Usage(String),
+    #[error("not found: {0}")]
+    NotFound(String),
+    /// The service rejected the request (400), e.g. a validation error.
+    #[error("invalid request: {0}")]
+    Invalid(String),
+    #[error("not authorized: {0}")]
+    Unauthorized(String),
+    #[error("service error (HTTP {status}): {message}")]
+    Service { status: u16, message: String },
+    /// No response: connection refused, timeout, ...
+    #[error("service unavailable: {0}")]
+    Unavailable(String),
+    /// Some items of a bulk command failed (each reported as it happened).
+    #[error("{failed} of {total} items failed")]
+    Partial { failed: usize, total: usize },
+    #[error("invalid profile configuration: {0}")]
+    Config(#[from] config::ConfigError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Usage(_) | CliError::Config(_) => exit_code::USAGE,
+            CliError::NotFound(_) => exit_code::NOT_FOUND,
+            CliError::Invalid(_) => exit_code::INVALID,
+            CliError::Unauthorized(_) => exit_code::UNAUTHORIZED,
+            CliError::Service { .. } => exit_code::SERVICE,
+            CliError::Unavailable(_) => exit_code::UNAVAILABLE,
+            CliError::Partial { .. } => exit_code::PARTIAL,
+            CliError::Io(_) | CliError::Csv(_) | CliError::Json(_) => exit_code::FAILURE,
+        }
+    }
+}
+
+/// Maps a failed operation by the HTTP status of its response; operations without a response
+/// failed to connect or timed out.
+pub fn from_sdk<E>(err: SdkError<E, HttpResponse>) -> CliError
+where
+    E: ProvideErrorMetadata + std::error::Error + Send + Sync + 'static,
+{
+    if matches!(err, SdkError::ConstructionFailure(_)) {
+        return CliError::Usage(DisplayErrorContext(&err).to_string());
+    }
+    let message = err
+        .message()
+        .map(str::to_string)
+        .unwrap_or_else(|| DisplayErrorContext(&err).to_string());
+    match err
+        .raw_response()
+        .map(|response| response.status().as_u16())
+    {
+        Some(404) => CliError::NotFound(message),
+        Some(400) => CliError::Invalid(message),
+        Some(401 | 403) => CliError::Unauthorized(message),
+        Some(status) => CliError::Service { status, message },
+        None => CliError::Unavailable(DisplayErrorContext(&err).to_string()),
+    }
+}
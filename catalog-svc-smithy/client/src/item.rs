@@ -0,0 +1,120 @@
+//! Catalog items as printed, exported and imported by the CLI.
+
+use std::str::FromStr;
+
+use catalog_client_api::operation::create_catalog_item::CreateCatalogItemOutput;
+use catalog_client_api::operation::get_catalog_item::GetCatalogItemOutput;
+use catalog_client_api::operation::update_catalog_item::UpdateCatalogItemOutput;
+use catalog_client_api::primitives::{DateTime, DateTimeFormat};
+use catalog_client_api::types::{CatalogItem, Category};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::error::CliError;
+
+/// A catalog item, flattened so the same columns work as a table, JSON or CSV. `export` writes
+/// this shape and `import` reads it back (server-assigned columns are ignored).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Item {
+    pub item_id: String,
+    pub name: String,
+    pub slug: String,
+    pub description: String,
+    pub category: String,
+    pub date: String,
+    pub brand: Option<String>,
+    pub price: String,
+    pub created_at: String,
+    pub modified_at: String,
+    pub rating_average: Option<String>,
+    pub rating_count: i64,
+    pub stock: Option<i64>,
+}
+
+fn format_timestamp(timestamp: &DateTime) -> String {
+    timestamp
+        .fmt(DateTimeFormat::DateTime)
+        .unwrap_or_else(|_| timestamp.secs().to_string())
+}
+
+/// The Smithy outputs of get, create and update repeat the members of [CatalogItem].
+macro_rules! item_from_smithy {
+    ($($smithy:ty),+) => {$(
+        impl From<&$smithy> for Item {
+            fn from(item: &$smithy) -> Self {
+                Self {
+                    item_id: item.item_id().to_string(),
+                    name: item.name().to_string(),
+                    slug: item.slug().to_string(),
+                    description: item.description().to_string(),
+                    category: item.category().as_str().to_string(),
+                    date: item.date().to_string(),
+                    brand: item.brand().map(str::to_string),
+                    price: item.price().to_string(),
+                    created_at: format_timestamp(item.created_at()),
+                    modified_at: format_timestamp(item.modified_at()),
+                    rating_average: item.rating().average().map(str::to_string),
+                    rating_count: item.rating().count(),
+                    stock: item.stock(),
+                }
+            }
+        }
+    )+};
+}
+
+item_from_smithy!(
+    CatalogItem,
+    CreateCatalogItemOutput,
+    GetCatalogItemOutput,
+    UpdateCatalogItemOutput
+);
+
+/// The client-supplied fields of an item: arguments of `create`, rows of `import`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemBody {
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    pub date: String,
+    #[serde(default)]
+    pub brand: Option<String>,
+    pub price: String,
+}
+
+impl ItemBody {
+    /// The body of `item`, to be changed and sent back by `update`.
+    pub fn of(item: &Item) -> Self {
+        Self {
+            name: item.name.clone(),
+            description: item.description.clone(),
+            category: item.category.clone(),
+            date: item.date.clone(),
+            brand: item.brand.clone(),
+            price: item.price.clone(),
+        }
+    }
+
+    /// `category` as the Smithy enum (see [smithy_category]).
+    pub fn smithy_category(&self) -> Result<Category, CliError> {
+        smithy_category(&self.category)
+    }
+}
+
+/// `category` as the Smithy enum; unknown values are rejected before calling the service.
+pub fn smithy_category(category: &str) -> Result<Category, CliError> {
+    if Category::values().contains(&category) {
+        Ok(Category::from(category))
+    } else {
+        Err(CliError::Usage(format!(
+            "unknown category {category:?}, expected one of {:?}",
+            Category::values()
+        )))
+    }
+}
+
+/// Parses a decimal price or multiplier argument.
+pub fn parse_decimal(value: &str) -> Result<Decimal, String> {
+    Decimal::from_str(value).map_err(|err| format!("invalid decimal {value:?}: {err}"))
+}
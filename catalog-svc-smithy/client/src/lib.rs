@@ -0,0 +1,9 @@
+//! Building blocks of `catalog-cli`: profiles, the configured Smithy client, the `items`
+//! commands, output formats and the errors with their exit codes.
+
+pub mod client;
+pub mod commands;
+pub mod error;
+pub mod item;
+pub mod output;
+pub mod profile;
@@ -1,25 +1,62 @@
-use clap::Parser;
-use tracing_subscriber::{EnvFilter, prelude::*};
+//! `catalog-cli`: administers the catalog through the Smithy-generated client.
+//!
+//! Exit codes: 0 on success, 2 for bad usage, 3 when an item is not found, 4 when the service
+//! rejects a request, 5 when not authorized, 6 for other service errors, 7 when the service
+//! can't be reached, 8 when some items of a bulk command failed and 9 when an item changed
+//! concurrently (see [catalog_svc_smithy_client::error::exit_code]).
+
+use std::path::PathBuf;
+use std::process::ExitCode;
 
-pub const DEFAULT_ADDRESS: &str = "127.0.0.1";
-pub const DEFAULT_PORT: u16 = 8888;
+use catalog_svc_smithy_client::client;
+use catalog_svc_smithy_client::commands::{self, ItemsCommand};
+use catalog_svc_smithy_client::error::CliError;
+use catalog_svc_smithy_client::output::OutputFormat;
+use catalog_svc_smithy_client::profile::{self, Profile};
+use clap::{Parser, Subcommand};
+use tracing_subscriber::{EnvFilter, prelude::*};
 
 #[derive(Parser, Debug)]
-#[clap(author, version, about, long_about = None)]
+#[command(
+    author,
+    version,
+    about = "Administer the catalog through its Smithy API"
+)]
 struct Args {
-    /// Hyper server bind address.
-    #[clap(short, long, action, default_value = DEFAULT_ADDRESS)]
-    address: String,
-    /// Hyper server bind port.
-    #[clap(short, long, action, default_value_t = DEFAULT_PORT)]
-    port: u16,
+    /// Profile to use from the profiles file.
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    /// Profiles file [default: ~/.config/catalog-cli/profiles.toml].
+    #[arg(long, global = true)]
+    profiles_file: Option<PathBuf>,
+    /// Service URL, overriding the profile's [default: http://127.0.0.1:3031].
+    #[arg(long, global = true)]
+    endpoint: Option<String>,
+    /// Bearer token, overriding the profile's credentials.
+    #[arg(long, global = true)]
+    token: Option<String>,
+    /// API key, overriding the profile's credentials.
+    #[arg(long, global = true, conflicts_with = "token")]
+    api_key: Option<String>,
+    #[arg(short, long, global = true, value_enum, default_value_t)]
+    output: OutputFormat,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Manage catalog items.
+    #[command(subcommand)]
+    Items(ItemsCommand),
 }
 
-/// Setup `tracing::subscriber` to read the log level from RUST_LOG environment variable.
+/// Setup `tracing::subscriber` to read the log level from RUST_LOG environment variable. Logs go
+/// to standard error, keeping standard output for the command's result.
 pub fn setup_tracing() {
-    let format = tracing_subscriber::fmt::layer();
+    let format = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
     let filter = EnvFilter::try_from_default_env()
-        .or_else(|_| EnvFilter::try_new("info"))
+        .or_else(|_| EnvFilter::try_new("warn"))
         .expect("EnvFilter should be well-formatted");
     tracing_subscriber::registry()
         .with(format)
@@ -27,23 +64,29 @@ pub fn setup_tracing() {
         .init();
 }
 
+async fn run(args: Args) -> Result<(), CliError> {
+    let profile = profile::load(args.profiles_file, args.profile.as_deref())?.merge(Profile {
+        endpoint: args.endpoint,
+        token: args.token,
+        api_key: args.api_key,
+    });
+    tracing::info!("connecting to {}", profile.endpoint());
+    let client = client::build(&profile);
+    let mut out = std::io::stdout().lock();
+    match args.command {
+        Command::Items(command) => commands::run(&client, args.output, command, &mut out).await,
+    }
+}
+
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
     let args = Args::parse();
     setup_tracing();
-    let service_endpoint = format!("http://{}:{}", args.address, args.port);
-    tracing::info!("connecting to {service_endpoint}");
-
-    let config = catalog_client_api::Config::builder()
-        .endpoint_url(service_endpoint)
-        .build();
-    let client = catalog_client_api::Client::from_conf(config);
-
-    let response = client
-        .hello_world()
-        .send()
-        .await
-        .expect("failed to create order");
-
-    println!("Response: {:?}", response.message);
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(err.exit_code())
+        }
+    }
 }
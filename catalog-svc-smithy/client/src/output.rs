@@ -0,0 +1,100 @@
+//! Rendering items as an aligned table, JSON or CSV.
+
+use std::io::Write;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::error::CliError;
+use crate::item::Item;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns with the main fields, for people.
+    #[default]
+    Table,
+    /// Every field; one object for single items, an array otherwise.
+    Json,
+    /// Every field, with a header row.
+    Csv,
+}
+
+const TABLE_HEADER: [&str; 8] = [
+    "ID", "NAME", "CATEGORY", "BRAND", "PRICE", "STOCK", "RATING", "MODIFIED",
+];
+
+fn table_row(item: &Item) -> [String; 8] {
+    [
+        item.item_id.clone(),
+        item.name.clone(),
+        item.category.clone(),
+        item.brand.clone().unwrap_or_default(),
+        item.price.clone(),
+        item.stock
+            .map(|stock| stock.to_string())
+            .unwrap_or_default(),
+        item.rating_average
+            .as_ref()
+            .map(|average| format!("{average} ({})", item.rating_count))
+            .unwrap_or_default(),
+        item.modified_at.clone(),
+    ]
+}
+
+fn write_table(items: &[Item], out: &mut impl Write) -> Result<(), CliError> {
+    let rows: Vec<_> = items.iter().map(table_row).collect();
+    let mut widths = TABLE_HEADER.map(|title| title.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let header = TABLE_HEADER.map(str::to_string);
+    for row in std::iter::once(&header).chain(&rows) {
+        let line: Vec<_> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        writeln!(out, "{}", line.join("  ").trim_end())?;
+    }
+    Ok(())
+}
+
+fn write_json(value: &impl Serialize, out: &mut impl Write) -> Result<(), CliError> {
+    serde_json::to_writer_pretty(&mut *out, value)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+fn write_csv(items: &[Item], out: &mut impl Write) -> Result<(), CliError> {
+    let mut writer = csv::Writer::from_writer(out);
+    for item in items {
+        writer.serialize(item)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes a list of items.
+pub fn write_items(
+    format: OutputFormat,
+    items: &[Item],
+    out: &mut impl Write,
+) -> Result<(), CliError> {
+    match format {
+        OutputFormat::Table => write_table(items, out),
+        OutputFormat::Json => write_json(&items, out),
+        OutputFormat::Csv => write_csv(items, out),
+    }
+}
+
+/// Writes the single item of `get`, `create` or `update`.
+pub fn write_item(format: OutputFormat, item: &Item, out: &mut impl Write) -> Result<(), CliError> {
+    match format {
+        OutputFormat::Json => write_json(item, out),
+        OutputFormat::Table | OutputFormat::Csv => {
+            write_items(format, std::slice::from_ref(item), out)
+        }
+    }
+}
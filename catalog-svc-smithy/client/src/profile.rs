@@ -0,0 +1,89 @@
+//! Named profiles holding the endpoint and credentials of a catalog deployment.
+//!
+//! Profiles are tables of a TOML file, by default `~/.config/catalog-cli/profiles.toml`:
+//!
+//! ```toml
+//! [default]
+//! endpoint = "http://127.0.0.1:3031"
+//! token = "<jwt>"
+//!
+//! [staging]
+//! endpoint = "https://catalog.staging.example.com"
+//! api_key = "<key>"
+//! ```
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use config::{Config, File, FileFormat};
+use serde::Deserialize;
+
+use crate::error::CliError;
+
+/// Profile used when none is selected.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Endpoint when neither the profile nor the command line sets one: the Smithy front-end's
+/// default `smithy.port`.
+pub const DEFAULT_ENDPOINT: &str = "http://127.0.0.1:3031";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Profile {
+    pub endpoint: Option<String>,
+    /// Bearer token (JWT), sent as `Authorization: Bearer <token>`.
+    pub token: Option<String>,
+    /// API key, sent as `X-Api-Key` (ignored when `token` is set).
+    pub api_key: Option<String>,
+}
+
+impl Profile {
+    /// Values of `overrides` that are set replace those of this profile. Credentials are replaced
+    /// as a whole: an overriding API key also drops the profile's token, and vice versa.
+    pub fn merge(self, overrides: Profile) -> Profile {
+        let (token, api_key) = if overrides.token.is_some() || overrides.api_key.is_some() {
+            (overrides.token, overrides.api_key)
+        } else {
+            (self.token, self.api_key)
+        };
+        Profile {
+            endpoint: overrides.endpoint.or(self.endpoint),
+            token,
+            api_key,
+        }
+    }
+
+    pub fn endpoint(&self) -> &str {
+        self.endpoint.as_deref().unwrap_or(DEFAULT_ENDPOINT)
+    }
+}
+
+fn default_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME")?;
+    Some(PathBuf::from(home).join(".config/catalog-cli/profiles.toml"))
+}
+
+/// Loads profile `name` from `path` (or the default file). A missing file or a missing
+/// `default` profile means no settings; a missing named profile is an error.
+pub fn load(path: Option<PathBuf>, name: Option<&str>) -> Result<Profile, CliError> {
+    let explicit = name.is_some();
+    let name = name.unwrap_or(DEFAULT_PROFILE);
+    let Some(path) = path.or_else(default_path) else {
+        return Ok(Profile::default());
+    };
+    let profiles: HashMap<String, Profile> = Config::builder()
+        .add_source(
+            File::from(path.as_path())
+                .format(FileFormat::Toml)
+                .required(false),
+        )
+        .build()?
+        .try_deserialize()?;
+    match profiles.get(name) {
+        Some(profile) => Ok(profile.clone()),
+        None if explicit => Err(CliError::Usage(format!(
+            "profile {name:?} not found in {}",
+            path.display()
+        ))),
+        None => Ok(Profile::default()),
+    }
+}
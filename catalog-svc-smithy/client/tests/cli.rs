@@ -0,0 +1,226 @@
+//! Tests of `catalog-cli`: exit codes by failure cause (against a stub answering every request with
+//! one status), output formats and credential overrides.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::process::Command;
+
+use catalog_svc_smithy_client::error::exit_code;
+use catalog_svc_smithy_client::item::Item;
+use catalog_svc_smithy_client::output::{self, OutputFormat};
+use catalog_svc_smithy_client::profile::Profile;
+
+const ITEM_ID: &str = "0196a4d2-7c1e-7b3a-9f2e-3c5d8e1f4a6b";
+
+/// Serves every request with `status` and a JSON error body on a background thread.
+fn stub_endpoint(status: u16) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let mut reader = BufReader::new(&stream);
+            let mut line = String::new();
+            // Requests without a body end at the first empty line
+            while reader.read_line(&mut line).is_ok_and(|read| read > 0) && line != "\r\n" {
+                line.clear();
+            }
+            let body = r#"{"message":"stubbed"}"#;
+            let response = format!(
+                "HTTP/1.1 {status} Stub\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = (&stream).write_all(response.as_bytes());
+        }
+    });
+    format!("http://{addr}")
+}
+
+/// Runs `catalog-cli` without a profiles file; its exit code.
+fn cli(args: &[&str]) -> Option<i32> {
+    Command::new(env!("CARGO_BIN_EXE_catalog-cli"))
+        .args(["--profiles-file", "/nonexistent/profiles.toml"])
+        .args(args)
+        .output()
+        .expect("run catalog-cli")
+        .status
+        .code()
+}
+
+fn exit(code: u8) -> Option<i32> {
+    Some(i32::from(code))
+}
+
+#[test]
+fn exit_codes() {
+    for (status, expected) in [
+        (404, exit_code::NOT_FOUND),
+        (400, exit_code::INVALID),
+        (401, exit_code::UNAUTHORIZED),
+        (403, exit_code::UNAUTHORIZED),
+        (500, exit_code::SERVICE),
+    ] {
+        let endpoint = stub_endpoint(status);
+        assert_eq!(
+            cli(&["--endpoint", &endpoint, "items", "get", ITEM_ID]),
+            exit(expected),
+            "HTTP {status}"
+        );
+    }
+
+    // Nothing listening
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let endpoint = format!("http://{}", listener.local_addr().expect("addr"));
+    drop(listener);
+    assert_eq!(
+        cli(&["--endpoint", &endpoint, "items", "get", ITEM_ID]),
+        exit(exit_code::UNAVAILABLE)
+    );
+
+    // Bulk commands fail partially when some items fail, or with the cause of a single failure
+    let endpoint = stub_endpoint(404);
+    assert_eq!(
+        cli(&["--endpoint", &endpoint, "items", "delete", ITEM_ID, ITEM_ID]),
+        exit(exit_code::PARTIAL)
+    );
+    assert_eq!(
+        cli(&["--endpoint", &endpoint, "items", "delete", ITEM_ID]),
+        exit(exit_code::NOT_FOUND)
+    );
+
+    // Bad usage, checked before calling the service
+    assert_eq!(
+        cli(&[
+            "--endpoint",
+            &endpoint,
+            "items",
+            "reprice",
+            "--multiplier",
+            "0"
+        ]),
+        exit(exit_code::USAGE)
+    );
+    assert_eq!(
+        cli(&[
+            "--endpoint",
+            &endpoint,
+            "items",
+            "reprice",
+            "--multiplier",
+            "1.1",
+            "--category",
+            "bogus"
+        ]),
+        exit(exit_code::USAGE)
+    );
+    assert_eq!(
+        cli(&["--profile", "missing", "items", "get", ITEM_ID]),
+        exit(exit_code::USAGE)
+    );
+    assert_eq!(
+        cli(&["--token", "t", "--api-key", "k", "items", "get", ITEM_ID]),
+        exit(exit_code::USAGE)
+    );
+}
+
+fn item(name: &str, brand: Option<&str>) -> Item {
+    Item {
+        item_id: ITEM_ID.to_string(),
+        name: name.to_string(),
+        slug: "slug".to_string(),
+        description: "Described".to_string(),
+        category: "Books".to_string(),
+        date: "2025-03-01".to_string(),
+        brand: brand.map(str::to_string),
+        price: "19.99".to_string(),
+        created_at: "2025-03-01T10:00:00Z".to_string(),
+        modified_at: "2025-03-02T10:00:00.5Z".to_string(),
+        rating_average: Some("4.5".to_string()),
+        rating_count: 2,
+        stock: None,
+    }
+}
+
+fn render(format: OutputFormat, items: &[Item]) -> String {
+    let mut out = Vec::new();
+    output::write_items(format, items, &mut out).expect("write items");
+    String::from_utf8(out).expect("utf-8")
+}
+
+#[test]
+fn output_formats() {
+    let items = [
+        item("Rust", Some("Acme")),
+        item("A longer name, quoted", None),
+    ];
+
+    let table = render(OutputFormat::Table, &items);
+    let lines: Vec<_> = table.lines().collect();
+    assert_eq!(lines.len(), 3);
+    let header = lines.first().expect("header");
+    assert!(header.starts_with("ID"));
+    assert!(header.ends_with("MODIFIED"));
+    // Columns are aligned
+    let price_column: Vec<_> = lines.iter().filter_map(|line| line.find("19.99")).collect();
+    assert_eq!(price_column.len(), 2);
+    assert!(
+        price_column
+            .windows(2)
+            .all(|pair| pair.first() == pair.last())
+    );
+    assert!(table.contains("4.5 (2)"));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&render(OutputFormat::Json, &items)).expect("JSON");
+    let array = json.as_array().expect("JSON array");
+    assert_eq!(array.len(), 2);
+    let first = array.first().expect("first item");
+    assert_eq!(first.get("itemId").and_then(|v| v.as_str()), Some(ITEM_ID));
+    assert_eq!(first.get("brand").and_then(|v| v.as_str()), Some("Acme"));
+    assert_eq!(first.get("ratingCount").and_then(|v| v.as_i64()), Some(2));
+
+    let csv = render(OutputFormat::Csv, &items);
+    let mut rows = csv.lines();
+    assert_eq!(
+        rows.next(),
+        Some(
+            "itemId,name,slug,description,category,date,brand,price,createdAt,modifiedAt,ratingAverage,ratingCount,stock"
+        )
+    );
+    assert!(
+        rows.nth(1)
+            .is_some_and(|row| row.contains(r#""A longer name, quoted""#))
+    );
+
+    // A single item is an object in JSON
+    let mut out = Vec::new();
+    let first = items.first().expect("first item");
+    output::write_item(OutputFormat::Json, first, &mut out).expect("write item");
+    let json: serde_json::Value = serde_json::from_slice(&out).expect("JSON");
+    assert!(json.is_object());
+}
+
+#[test]
+fn credential_overrides() {
+    let profile = Profile {
+        endpoint: Some("http://catalog.example.com".to_string()),
+        token: Some("profile-token".to_string()),
+        api_key: None,
+    };
+
+    // A flag replaces the profile's credentials, whatever their kind
+    let merged = profile.clone().merge(Profile {
+        api_key: Some("flag-key".to_string()),
+        ..Profile::default()
+    });
+    assert_eq!(merged.token, None);
+    assert_eq!(merged.api_key.as_deref(), Some("flag-key"));
+    assert_eq!(merged.endpoint(), "http://catalog.example.com");
+
+    // Without credential flags, the profile's are kept
+    let merged = profile.merge(Profile {
+        endpoint: Some("http://localhost:3031".to_string()),
+        ..Profile::default()
+    });
+    assert_eq!(merged.token.as_deref(), Some("profile-token"));
+    assert_eq!(merged.endpoint(), "http://localhost:3031");
+}
@@ -0,0 +1,39 @@
+//! Runs every front-end of the catalog in one process, on one [CatalogApp] (and so one PostgreSQL
+//! pool, item cache and set of background tasks): the axum server on `server.port`, the gRPC API
+//! on `grpc.port` when enabled and the Smithy server on `smithy.port`. A shutdown signal stops all
+//! of them together.
+//!
+//! [CatalogApp]: catalog_svc::http_server::CatalogApp
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::server;
+use rust_demo_commons::util;
+
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let app_config = AppConfig::load().expect("failed to load app config");
+    let _tracing = util::tracing::init_tracing_with(&app_config.tracing_settings())?;
+
+    let (app_state, background) = server::build_app(&app_config).await;
+    let mut handles = Vec::new();
+    let (smithy_handle, _addr) = catalog_svc_smithy_server::start_service_and_serve(
+        app_state.clone(),
+        &app_config.server.host,
+        app_config.smithy.port,
+        Duration::from_secs(app_config.server.shutdown_delay_secs),
+    )
+    .await?;
+    handles.push(smithy_handle);
+    if app_config.grpc.enabled {
+        let (grpc_handle, _addr) = server::start_grpc_and_serve(&app_state, &app_config).await?;
+        handles.push(grpc_handle);
+    }
+    let (state, handle, _addr) = server::start_service_and_serve(app_state, app_config).await?;
+    handles.push(handle);
+    let handle = server::join_servers(handles, state.server_shutdown.clone());
+    util::server::graceful_shutdown(state.server_shutdown, handle, background, DRAIN_TIMEOUT).await
+}
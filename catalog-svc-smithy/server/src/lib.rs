@@ -1,80 +1,102 @@
 pub mod server;
 
+use std::io;
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
 
 use catalog_api::server::AddExtensionLayer;
 use catalog_api::server::extension::OperationExtensionExt;
 use catalog_api::server::instrumentation::InstrumentExt;
 use catalog_api::server::layer::alb_health_check::AlbHealthCheckLayer;
 use catalog_api::server::plugin::{HttpPlugins, ModelPlugins};
-use catalog_api::server::request::request_id::ServerRequestIdProviderLayer;
-use catalog_api::{CatalogService, CatalogServiceConfig, error, input, output};
-use catalog_svc::app_config::HttpServerSettings;
+use catalog_api::{CatalogService, CatalogServiceConfig};
 use catalog_svc::http_server::CatalogApp;
 use hyper::StatusCode;
+use hyper::server::conn::AddrIncoming;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
 
+use crate::server::auth::AuthPlugin;
+use crate::server::metrics::{MetricsEndpointLayer, MetricsPlugin};
+use crate::server::request_id::RequestIdLayer;
 use crate::server::{
     create_catalog_item, delete_catalog_item, get_catalog_item, list_catalog_items,
-    update_catalog_item,
+    reprice_catalog_items, update_catalog_item,
 };
 
-/// Handler for HelloWorld: returns "Hello World".
-pub async fn hello_world(
-    input: input::HelloWorldInput,
-) -> Result<output::HelloWorldOutput, error::HelloWorldError> {
-    // Temporary: return 500 when name contains "XXX" (for testing InternalServerError)
-    if input.name.contains("XXX") {
-        return Err(error::HelloWorldError::from(error::InternalServerError {
-            message: Some("Internal server error".into()),
-        }));
-    }
-
-    Ok(output::HelloWorldOutput {
-        message: Some("Hello World".into()),
-    })
-}
-
-pub async fn start_server_and_listen(app_state: CatalogApp, server_settings: HttpServerSettings) {
+/// Start the Smithy front-end on `host:port` on a spawned task. It stops when
+/// `app_state.server_shutdown` is cancelled, after `shutdown_delay`, letting in-flight requests
+/// complete; a failing server cancels the token, so front-ends sharing it shut down too.
+pub async fn start_service_and_serve(
+    app_state: CatalogApp,
+    host: &str,
+    port: u16,
+    shutdown_delay: Duration,
+) -> io::Result<(JoinHandle<io::Result<()>>, SocketAddr)> {
+    let shutdown = app_state.server_shutdown.clone();
     let http_plugins = HttpPlugins::new()
         // Apply the `OperationExtensionPlugin` defined in `aws_smithy_http_server::extension`. This allows other
         // plugins or tests to access a `aws_smithy_http_server::extension::OperationExtension` from
         // `Response::extensions`, or infer routing failure when it's missing.
         .insert_operation_extension()
         // Adds `tracing` spans and events to the request lifecycle.
-        .instrument();
+        .instrument()
+        // Records request count and latency per operation on `/metrics`.
+        .push(MetricsPlugin)
+        // Authenticates the API key or bearer token and checks the operation's required permission.
+        .push(AuthPlugin::new(
+            app_state.auth.clone(),
+            app_state.api_keys.clone(),
+        ));
 
     let model_plugins = ModelPlugins::new();
 
+    let metrics_endpoint = MetricsEndpointLayer::new(app_state.clone());
     let config = CatalogServiceConfig::builder()
-        .layer(AddExtensionLayer::new(app_state))
+        // Handlers take the state as `Extension<Arc<CatalogApp>>`
+        .layer(AddExtensionLayer::new(Arc::new(app_state)))
         .layer(AlbHealthCheckLayer::from_handler("/ping", |_req| async {
             StatusCode::OK
         }))
-        .layer(ServerRequestIdProviderLayer::new())
+        .layer(metrics_endpoint)
+        // Accepts or generates `X-Request-Id`, echoed in responses and error bodies
+        .layer(RequestIdLayer)
         .http_plugin(http_plugins)
         .model_plugin(model_plugins)
         .build();
 
     let app = CatalogService::builder(config)
-        .hello_world(hello_world)
         .create_catalog_item(create_catalog_item)
         .delete_catalog_item(delete_catalog_item)
         .get_catalog_item(get_catalog_item)
         .list_catalog_items(list_catalog_items)
+        .reprice_catalog_items(reprice_catalog_items)
         .update_catalog_item(update_catalog_item)
         .build()
         .expect("failed to build CatalogService");
 
     let make_app = app.into_make_service_with_connect_info::<SocketAddr>();
 
-    let bind: SocketAddr = format!("{}:{}", server_settings.host, server_settings.port)
-        .parse()
-        .expect("unable to parse the server bind address and port");
-    let server = hyper::Server::bind(&bind).serve(make_app);
-
-    tracing::info!("server listening on: {bind:?}");
+    let listener = TcpListener::bind((host, port)).await?;
+    let addr = listener.local_addr()?;
+    let incoming = AddrIncoming::from_listener(listener).map_err(io::Error::other)?;
+    tracing::info!("Catalog Smithy API listening on {addr}");
 
-    if let Err(err) = server.await {
-        eprintln!("server error: {}", err);
-    }
+    let stopped = shutdown.clone();
+    let server = hyper::Server::builder(incoming)
+        .serve(make_app)
+        .with_graceful_shutdown(async move {
+            stopped.cancelled().await;
+            tokio::time::sleep(shutdown_delay).await;
+        });
+    let join_handle = tokio::spawn(async move {
+        let result = server.await;
+        if let Err(err) = &result {
+            tracing::error!("Smithy server failed: {err}");
+            shutdown.cancel();
+        }
+        result.map_err(io::Error::other)
+    });
+    Ok((join_handle, addr))
 }
@@ -1,14 +1,25 @@
+use std::time::Duration;
+
 use catalog_svc::app_config::AppConfig;
 use catalog_svc::server;
 use rust_demo_commons::util;
 
-#[tokio::main]
-async fn main() {
-    util::tracing::init_tracing();
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
 
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
     let app_config = AppConfig::load().expect("failed to load app config");
-    let app_state = server::build_app(&app_config).await;
-    let server_settings = app_config.server;
+    let _tracing = util::tracing::init_tracing_with(&app_config.tracing_settings())?;
 
-    catalog_svc_smithy_server::start_server_and_listen(app_state, server_settings).await;
+    let (app_state, background) = server::build_app(&app_config).await;
+    let shutdown = app_state.server_shutdown.clone();
+    let settings = app_config.server;
+    let (handle, _addr) = catalog_svc_smithy_server::start_service_and_serve(
+        app_state,
+        &settings.host,
+        settings.port,
+        Duration::from_secs(settings.shutdown_delay_secs),
+    )
+    .await?;
+    util::server::graceful_shutdown(shutdown, handle, background, DRAIN_TIMEOUT).await
 }
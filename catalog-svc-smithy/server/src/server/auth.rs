@@ -0,0 +1,161 @@
+//! Bearer-token and API key authentication for the Smithy front-end, mirroring the axum
+//! `authorize` middleware.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use catalog_api::server::body::{Body, BoxBody, boxed};
+use catalog_api::server::operation::OperationShape;
+use catalog_api::server::plugin::{HttpMarker, Plugin};
+use catalog_api::server::shape_id::ShapeId;
+use catalog_svc::api_keys::api::ApiKeysServiceError;
+use catalog_svc::api_keys::service::ApiKeysService;
+use catalog_svc::auth::{API_KEY_HEADER, AuthError, Authenticator, Permission};
+use http::header::AsHeaderName;
+use http::{HeaderMap, HeaderValue, Request, Response, StatusCode, header};
+use tower::Service;
+
+/// HTTP plugin that authenticates every operation (by [API_KEY_HEADER] if present, bearer token
+/// otherwise) and checks its required [Permission].
+#[derive(Clone)]
+pub struct AuthPlugin {
+    auth: Authenticator,
+    api_keys: ApiKeysService,
+}
+
+impl AuthPlugin {
+    pub fn new(auth: Authenticator, api_keys: ApiKeysService) -> Self {
+        Self { auth, api_keys }
+    }
+}
+
+impl<Ser, Op, T> Plugin<Ser, Op, T> for AuthPlugin
+where
+    Op: OperationShape,
+{
+    type Output = AuthService<T>;
+
+    fn apply(&self, inner: T) -> Self::Output {
+        AuthService {
+            inner,
+            auth: self.auth.clone(),
+            api_keys: self.api_keys.clone(),
+            permission: permission_for_operation(Op::ID),
+        }
+    }
+}
+
+impl HttpMarker for AuthPlugin {}
+
+/// Read-only operations need [Permission::Read]; everything else needs [Permission::Write].
+fn permission_for_operation(id: ShapeId) -> Permission {
+    match id.name() {
+        "GetCatalogItem" | "ListCatalogItems" => Permission::Read,
+        _ => Permission::Write,
+    }
+}
+
+/// Service produced by [AuthPlugin]; inserts the resolved `Principal` into request extensions.
+#[derive(Clone)]
+pub struct AuthService<S> {
+    inner: S,
+    auth: Authenticator,
+    api_keys: ApiKeysService,
+    permission: Permission,
+}
+
+impl<S, B> Service<Request<B>> for AuthService<S>
+where
+    S: Service<Request<B>, Response = Response<BoxBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    B: Send + 'static,
+{
+    type Response = Response<BoxBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        // Take the service that was driven to readiness; leave a fresh clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let auth = self.auth.clone();
+        let api_keys = self.api_keys.clone();
+        let permission = self.permission;
+        Box::pin(async move {
+            let api_key = header_value(req.headers(), API_KEY_HEADER);
+            let authorization = header_value(req.headers(), header::AUTHORIZATION);
+            let principal = match api_key {
+                Some(api_key) => api_keys
+                    .authenticate(&api_key)
+                    .await
+                    .map_err(|err| api_key_rejection(&err)),
+                None => auth
+                    .authenticate(authorization.as_deref())
+                    .await
+                    .map_err(|err| rejection(&err)),
+            };
+            let principal = match principal {
+                Ok(principal) => principal,
+                Err(response) => return Ok(response),
+            };
+            if let Err(err) = principal.authorize(permission) {
+                return Ok(rejection(&err));
+            }
+            req.extensions_mut().insert(principal);
+            inner.call(req).await
+        })
+    }
+}
+
+/// `name` as an owned string (empty when it is not visible ASCII), not borrowing the request
+/// across the authentication call.
+fn header_value(headers: &HeaderMap, name: impl AsHeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .map(|value| value.to_str().unwrap_or_default().to_owned())
+}
+
+/// 401 (with `WWW-Authenticate`) or 403 response in the protocol's `{"message": ...}` error shape.
+fn rejection(err: &AuthError) -> Response<BoxBody> {
+    let status = match err {
+        AuthError::MissingToken | AuthError::InvalidToken(_) => StatusCode::UNAUTHORIZED,
+        AuthError::Forbidden(_) => StatusCode::FORBIDDEN,
+    };
+    tracing::debug!(%err, "rejecting Smithy request");
+    error_response(status, &err.to_string())
+}
+
+/// 401 for an unknown, revoked or expired API key; 500 (details logged, not returned) when the
+/// key could not be checked.
+fn api_key_rejection(err: &ApiKeysServiceError) -> Response<BoxBody> {
+    match err {
+        ApiKeysServiceError::InvalidKey(_) => {
+            tracing::debug!(%err, "rejecting Smithy request");
+            error_response(StatusCode::UNAUTHORIZED, &err.to_string())
+        }
+        ApiKeysServiceError::ValidationError(_) | ApiKeysServiceError::InternalError(_) => {
+            tracing::error!(%err, "failed to authenticate Smithy request by API key");
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, "internal error")
+        }
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<BoxBody> {
+    let body = serde_json::json!({ "message": message }).to_string();
+    let mut response = Response::new(boxed(Body::from(body)));
+    *response.status_mut() = status;
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    if status == StatusCode::UNAUTHORIZED {
+        headers.insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    }
+    response
+}
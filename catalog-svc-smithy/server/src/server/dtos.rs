@@ -4,7 +4,7 @@ use std::fmt;
 use catalog_api::model as smithy;
 use catalog_api::output;
 use catalog_api::types as smithy_types;
-use catalog_svc::catalog::api::{CatalogItem, Category};
+use catalog_svc::catalog::api::{CatalogItem, CatalogItemSort, Category, RatingSummary};
 
 /// Error type for DTO conversions between smithy `catalog_api` types and `catalog_svc` types.
 #[derive(Debug)]
@@ -38,6 +38,21 @@ pub fn map_category_to_smithy(value: Category) -> smithy::Category {
     }
 }
 
+pub fn map_sort_from_smithy(value: smithy::CatalogItemSort) -> CatalogItemSort {
+    match value {
+        smithy::CatalogItemSort::CreatedAt => CatalogItemSort::CreatedAt,
+        smithy::CatalogItemSort::Rating => CatalogItemSort::Rating,
+    }
+}
+
+fn rating_to_smithy(value: RatingSummary) -> smithy::RatingSummary {
+    smithy::RatingSummary {
+        average: value.average.map(|average| average.to_string()),
+        count: value.count.into(),
+        histogram: value.histogram.into_iter().map(i64::from).collect(),
+    }
+}
+
 pub fn service_item_to_smithy_item(value: CatalogItem) -> smithy::CatalogItem {
     let created_at = chrono_to_smithy_datetime(value.created_at);
     let modified_at = chrono_to_smithy_datetime(value.modified_at);
@@ -53,8 +68,11 @@ pub fn service_item_to_smithy_item(value: CatalogItem) -> smithy::CatalogItem {
         brand: value.brand,
         price: value.price.to_string(),
         item_id,
+        slug: value.slug,
         created_at,
         modified_at,
+        rating: rating_to_smithy(value.rating),
+        stock: value.stock.map(i64::from),
     }
 }
 
@@ -68,8 +86,11 @@ pub fn service_item_to_create_output(value: CatalogItem) -> output::CreateCatalo
         brand: item.brand,
         price: item.price,
         item_id: item.item_id,
+        slug: item.slug,
         created_at: item.created_at,
         modified_at: item.modified_at,
+        rating: item.rating,
+        stock: item.stock,
     }
 }
 
@@ -83,8 +104,11 @@ pub fn service_item_to_get_output(value: CatalogItem) -> output::GetCatalogItemO
         brand: item.brand,
         price: item.price,
         item_id: item.item_id,
+        slug: item.slug,
         created_at: item.created_at,
         modified_at: item.modified_at,
+        rating: item.rating,
+        stock: item.stock,
     }
 }
 
@@ -98,8 +122,11 @@ pub fn service_item_to_update_output(value: CatalogItem) -> output::UpdateCatalo
         brand: item.brand,
         price: item.price,
         item_id: item.item_id,
+        slug: item.slug,
         created_at: item.created_at,
         modified_at: item.modified_at,
+        rating: item.rating,
+        stock: item.stock,
     }
 }
 
@@ -123,6 +150,7 @@ fn naive_date_to_smithy<D: ToString>(date: D) -> smithy::DateOnly {
         .expect("NaiveDate should always map to DateOnly successfully")
 }
 
+/// Keeps sub-second precision (the database stores microseconds).
 fn chrono_to_smithy_datetime(dt: chrono::DateTime<chrono::Utc>) -> smithy_types::DateTime {
-    smithy_types::DateTime::from_secs(dt.timestamp())
+    smithy_types::DateTime::from_secs_and_nanos(dt.timestamp(), dt.timestamp_subsec_nanos())
 }
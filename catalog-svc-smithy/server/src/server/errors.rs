@@ -1,16 +1,12 @@
 //! Error conversion from domain/DTO errors to Smithy API error types.
 
 use catalog_api::error;
+use catalog_api::model as smithy;
 use catalog_svc::catalog::api::CatalogServiceError;
+use catalog_svc::common::validation::ValidationErrors;
 
 use crate::server::dtos::DtoConversionError;
 
-pub fn dto_internal(err: DtoConversionError) -> error::InternalServerError {
-    error::InternalServerError {
-        message: Some(format!("DTO mapping failure: {err}")),
-    }
-}
-
 pub fn not_found_error_404() -> error::NotFoundError {
     error::NotFoundError {
         message: Some("Resource not found".into()),
@@ -23,49 +19,94 @@ fn catalog_error_to_internal(err: CatalogServiceError) -> error::InternalServerE
     }
 }
 
-fn catalog_error_to_validation(err: CatalogServiceError) -> error::ValidationException {
+/// Maps domain field violations to `ValidationException`, one `field_list` entry per violation
+/// (paths are JSON Pointers into the request body, as in Smithy's own constraint violations).
+fn validation_exception(errors: ValidationErrors) -> error::ValidationException {
     error::ValidationException {
-        message: err.to_string(),
-        field_list: None,
+        message: format!("validation error: {errors}"),
+        field_list: Some(
+            errors
+                .into_violations()
+                .into_iter()
+                .map(|violation| smithy::ValidationExceptionField {
+                    path: violation.path,
+                    message: violation.message,
+                })
+                .collect(),
+        ),
     }
 }
 
 pub fn catalog_error_to_create(err: CatalogServiceError) -> error::CreateCatalogItemError {
     match err {
-        CatalogServiceError::ValidationError(_) => catalog_error_to_validation(err).into(),
+        CatalogServiceError::ValidationError(errors) => validation_exception(errors).into(),
         CatalogServiceError::InternalError(_) => catalog_error_to_internal(err).into(),
     }
 }
 
 pub fn catalog_error_to_get(err: CatalogServiceError) -> error::GetCatalogItemError {
     match err {
-        CatalogServiceError::ValidationError(_) => catalog_error_to_validation(err).into(),
+        CatalogServiceError::ValidationError(errors) => validation_exception(errors).into(),
         CatalogServiceError::InternalError(_) => catalog_error_to_internal(err).into(),
     }
 }
 
 pub fn catalog_error_to_update(err: CatalogServiceError) -> error::UpdateCatalogItemError {
     match err {
-        CatalogServiceError::ValidationError(_) => catalog_error_to_validation(err).into(),
+        CatalogServiceError::ValidationError(errors) => validation_exception(errors).into(),
         CatalogServiceError::InternalError(_) => catalog_error_to_internal(err).into(),
     }
 }
 
 pub fn catalog_error_to_delete(err: CatalogServiceError) -> error::DeleteCatalogItemError {
     match err {
-        CatalogServiceError::ValidationError(_) => catalog_error_to_validation(err).into(),
+        CatalogServiceError::ValidationError(errors) => validation_exception(errors).into(),
         CatalogServiceError::InternalError(_) => catalog_error_to_internal(err).into(),
     }
 }
 
 pub fn catalog_error_to_list(err: CatalogServiceError) -> error::ListCatalogItemsError {
-    catalog_error_to_internal(err).into()
+    match err {
+        CatalogServiceError::ValidationError(errors) => validation_exception(errors).into(),
+        CatalogServiceError::InternalError(_) => catalog_error_to_internal(err).into(),
+    }
+}
+
+pub fn catalog_error_to_reprice(err: CatalogServiceError) -> error::RepriceCatalogItemsError {
+    match err {
+        CatalogServiceError::ValidationError(errors) => validation_exception(errors).into(),
+        CatalogServiceError::InternalError(_) => catalog_error_to_internal(err).into(),
+    }
 }
 
 /// Maps a decimal parse error to a validation error (e.g. invalid price string).
 pub fn price_parse_to_validation(err: impl std::fmt::Display) -> error::ValidationException {
-    error::ValidationException {
-        message: format!("invalid price: {err}"),
-        field_list: None,
-    }
+    validation_exception(ValidationErrors::single(
+        "price",
+        format!("must be a decimal number: {err}"),
+    ))
+}
+
+/// Maps a multiplier that is not a decimal number to a validation error.
+pub fn multiplier_parse_to_validation(err: impl std::fmt::Display) -> error::ValidationException {
+    validation_exception(ValidationErrors::single(
+        "multiplier",
+        format!("must be a decimal number: {err}"),
+    ))
+}
+
+/// Maps an item id that is not a UUID to a validation error on the `itemId` label.
+pub fn uuid_parse_to_validation(err: DtoConversionError) -> error::ValidationException {
+    validation_exception(ValidationErrors::single("itemId", err.to_string()))
+}
+
+/// Maps a query parameter outside `0..=u32::MAX` (e.g. a negative `limit`) to a validation error.
+pub fn query_param_to_validation(
+    name: &str,
+    err: impl std::fmt::Display,
+) -> error::ValidationException {
+    validation_exception(ValidationErrors::single(
+        name,
+        format!("must be a non-negative 32-bit integer: {err}"),
+    ))
 }
@@ -0,0 +1,136 @@
+//! Prometheus metrics for the Smithy front-end: per-operation request metrics (recorded with the
+//! same names as the axum server, with the operation name as `route`) and the `/metrics` endpoint.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use catalog_api::server::body::{Body, BoxBody, boxed};
+use catalog_api::server::operation::OperationShape;
+use catalog_api::server::plugin::{HttpMarker, Plugin};
+use catalog_svc::app_metrics;
+use catalog_svc::http_server::CatalogApp;
+use http::{HeaderValue, Request, Response, header};
+use rust_demo_commons::util::metrics::record_http_request;
+use tower::{Layer, Service, ServiceExt};
+
+const METRICS_PATH: &str = "/metrics";
+
+/// HTTP plugin recording request count and latency per operation.
+#[derive(Clone, Copy, Default)]
+pub struct MetricsPlugin;
+
+impl<Ser, Op, T> Plugin<Ser, Op, T> for MetricsPlugin
+where
+    Op: OperationShape,
+{
+    type Output = MetricsService<T>;
+
+    fn apply(&self, inner: T) -> Self::Output {
+        MetricsService {
+            inner,
+            operation: Op::ID.name(),
+        }
+    }
+}
+
+impl HttpMarker for MetricsPlugin {}
+
+/// Service produced by [MetricsPlugin].
+#[derive(Clone)]
+pub struct MetricsService<S> {
+    inner: S,
+    operation: &'static str,
+}
+
+impl<S, B> Service<Request<B>> for MetricsService<S>
+where
+    S: Service<Request<B>, Response = Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<BoxBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let method = req.method().to_string();
+        let operation = self.operation;
+        let started = Instant::now();
+        let response = self.inner.call(req);
+        Box::pin(async move {
+            let response = response.await?;
+            record_http_request(
+                &method,
+                operation,
+                response.status().as_u16(),
+                started.elapsed(),
+            );
+            Ok(response)
+        })
+    }
+}
+
+/// Layer answering `GET /metrics` with the Prometheus text exposition, like the axum server.
+#[derive(Clone)]
+pub struct MetricsEndpointLayer {
+    app: CatalogApp,
+}
+
+impl MetricsEndpointLayer {
+    pub fn new(app: CatalogApp) -> Self {
+        Self { app }
+    }
+}
+
+impl<S> Layer<S> for MetricsEndpointLayer {
+    type Service = MetricsEndpointService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsEndpointService {
+            inner,
+            app: self.app.clone(),
+        }
+    }
+}
+
+/// Service produced by [MetricsEndpointLayer].
+#[derive(Clone)]
+pub struct MetricsEndpointService<S> {
+    inner: S,
+    app: CatalogApp,
+}
+
+impl<S> Service<Request<Body>> for MetricsEndpointService<S>
+where
+    S: Service<Request<Body>, Response = Response<BoxBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<BoxBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness is checked by `oneshot` on the inner service when a request is forwarded
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        if req.uri().path() == METRICS_PATH {
+            app_metrics::record_pool_stats(&self.app.pg_pool);
+            let mut response = Response::new(boxed(Body::from(self.app.metrics.render())));
+            response.headers_mut().insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("text/plain; version=0.0.4"),
+            );
+            return Box::pin(async move { Ok(response) });
+        }
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(inner.oneshot(req))
+    }
+}
@@ -1,28 +1,35 @@
 //! Catalog service server handlers and state.
 
+pub mod auth;
 pub mod dtos;
 mod errors;
+pub mod metrics;
+pub mod request_id;
 
 use std::str::FromStr;
 use std::sync::Arc;
 
-use catalog_api::error::InternalServerError;
+use catalog_api::error::ValidationException;
 use catalog_api::model as smithy;
 use catalog_api::server::request::extension::Extension;
 use catalog_api::{error, input, output};
 use catalog_svc::catalog::api::{
-    CreateCatalogItemBody, ListCatalogItemsRequest, ListCatalogItemsResponse, UpdateCatalogItemBody,
+    CatalogItemFilter, CreateCatalogItemBody, ListCatalogItemsRequest, ListCatalogItemsResponse,
+    UpdateCatalogItemBody,
 };
 use catalog_svc::http_server::CatalogApp;
 use rust_decimal::Decimal;
 
 use crate::server::dtos::{
-    map_category_from_smithy, service_item_to_create_output, service_item_to_get_output,
-    service_item_to_update_output, service_items_to_smithy_items, uuid_from_smithy,
+    map_category_from_smithy, map_sort_from_smithy, service_item_to_create_output,
+    service_item_to_get_output, service_item_to_update_output, service_items_to_smithy_items,
+    uuid_from_smithy,
 };
 use crate::server::errors::{
     catalog_error_to_create, catalog_error_to_delete, catalog_error_to_get, catalog_error_to_list,
-    catalog_error_to_update, dto_internal, not_found_error_404, price_parse_to_validation,
+    catalog_error_to_reprice, catalog_error_to_update, multiplier_parse_to_validation,
+    not_found_error_404, price_parse_to_validation, query_param_to_validation,
+    uuid_parse_to_validation,
 };
 
 type AppState = CatalogApp;
@@ -55,7 +62,8 @@ pub async fn get_catalog_item(
     input: input::GetCatalogItemInput,
     Extension(state): Extension<Arc<AppState>>,
 ) -> Result<output::GetCatalogItemOutput, error::GetCatalogItemError> {
-    let item_id: uuid::Uuid = uuid_from_smithy(input.item_id()).map_err(dto_internal)?;
+    let item_id: uuid::Uuid =
+        uuid_from_smithy(input.item_id()).map_err(uuid_parse_to_validation)?;
     let item = state
         .catalog
         .get(item_id)
@@ -70,7 +78,7 @@ pub async fn update_catalog_item(
     input: input::UpdateCatalogItemInput,
     Extension(state): Extension<Arc<AppState>>,
 ) -> Result<output::UpdateCatalogItemOutput, error::UpdateCatalogItemError> {
-    let item_id: uuid::Uuid = uuid_from_smithy(&input.item_id).map_err(dto_internal)?;
+    let item_id: uuid::Uuid = uuid_from_smithy(&input.item_id).map_err(uuid_parse_to_validation)?;
 
     let price = Decimal::from_str(&input.price).map_err(price_parse_to_validation)?;
     let body = UpdateCatalogItemBody {
@@ -96,7 +104,8 @@ pub async fn delete_catalog_item(
     input: input::DeleteCatalogItemInput,
     Extension(state): Extension<Arc<AppState>>,
 ) -> Result<output::DeleteCatalogItemOutput, error::DeleteCatalogItemError> {
-    let item_id: uuid::Uuid = uuid_from_smithy(input.item_id()).map_err(dto_internal)?;
+    let item_id: uuid::Uuid =
+        uuid_from_smithy(input.item_id()).map_err(uuid_parse_to_validation)?;
 
     let deleted = state
         .catalog
@@ -116,8 +125,15 @@ pub async fn list_catalog_items(
     Extension(state): Extension<Arc<AppState>>,
 ) -> Result<output::ListCatalogItemsOutput, error::ListCatalogItemsError> {
     let req = ListCatalogItemsRequest {
-        limit: input.limit.map(convert_i64_to_u32).transpose()?,
-        offset: input.offset.map(convert_i64_to_u32).transpose()?,
+        limit: input
+            .limit
+            .map(|limit| page_param("limit", limit))
+            .transpose()?,
+        offset: input
+            .offset
+            .map(|offset| page_param("offset", offset))
+            .transpose()?,
+        sort: input.sort.map(map_sort_from_smithy),
     };
 
     let ListCatalogItemsResponse {
@@ -145,8 +161,30 @@ pub async fn list_catalog_items(
     })
 }
 
-fn convert_i64_to_u32(v: i64) -> Result<u32, InternalServerError> {
-    u32::try_from(v).map_err(|err| InternalServerError {
-        message: Some(format!("Expect u32 value, but: {err}")),
+/// Handler for RepriceCatalogItems: delegates to the domain CatalogService.
+pub async fn reprice_catalog_items(
+    input: input::RepriceCatalogItemsInput,
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<output::RepriceCatalogItemsOutput, error::RepriceCatalogItemsError> {
+    let multiplier =
+        Decimal::from_str(&input.multiplier).map_err(multiplier_parse_to_validation)?;
+    let filter = CatalogItemFilter {
+        category: input.category.map(map_category_from_smithy),
+        brand: input.brand,
+        ..Default::default()
+    };
+
+    let items = state
+        .catalog
+        .increase_prices(multiplier, &filter)
+        .await
+        .map_err(catalog_error_to_reprice)?;
+    Ok(output::RepriceCatalogItemsOutput {
+        items: service_items_to_smithy_items(items),
     })
 }
+
+/// `limit` and `offset` are modeled as `Long`; the service takes them as `u32`, like the axum API.
+fn page_param(name: &str, value: i64) -> Result<u32, ValidationException> {
+    u32::try_from(value).map_err(|err| query_param_to_validation(name, err))
+}
@@ -0,0 +1,115 @@
+//! `X-Request-Id` for the Smithy front-end, matching the axum server: the caller's id is accepted
+//! (or one is generated), recorded on a `request` span, echoed in the response and added to JSON
+//! error bodies as `requestId`.
+//!
+//! Replaces Smithy's `ServerRequestIdProviderLayer`, which always generates a fresh id and so cannot
+//! continue the caller's.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use catalog_api::server::body::{Body, BoxBody, boxed};
+use http::{HeaderValue, Request, Response, header};
+use rust_demo_commons::util::server::request_id::{accept_or_generate, scope_request_id};
+use tower::{Layer, Service};
+use tracing::Instrument;
+
+const X_REQUEST_ID: &str = "x-request-id";
+
+/// Layer assigning every request an `X-Request-Id`.
+#[derive(Clone, Copy, Default)]
+pub struct RequestIdLayer;
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService { inner }
+    }
+}
+
+/// Service produced by [RequestIdLayer].
+#[derive(Clone)]
+pub struct RequestIdService<S> {
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for RequestIdService<S>
+where
+    S: Service<Request<B>, Response = Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<BoxBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        let request_id = accept_or_generate(
+            req.headers()
+                .get(X_REQUEST_ID)
+                .and_then(|value| value.to_str().ok()),
+        );
+        let header = HeaderValue::from_str(&request_id).ok();
+        if let Some(header) = &header {
+            req.headers_mut().insert(X_REQUEST_ID, header.clone());
+        }
+        let span = tracing::info_span!(
+            "request",
+            method = %req.method(),
+            uri = %req.uri(),
+            request_id = %request_id,
+        );
+        let response = span.in_scope(|| self.inner.call(req));
+        Box::pin(
+            async move {
+                let response = scope_request_id(request_id.clone(), response).await?;
+                let mut response =
+                    if response.status().is_client_error() || response.status().is_server_error() {
+                        with_request_id(response, &request_id).await
+                    } else {
+                        response
+                    };
+                if let Some(header) = header {
+                    response.headers_mut().insert(X_REQUEST_ID, header);
+                }
+                Ok(response)
+            }
+            .instrument(span),
+        )
+    }
+}
+
+/// Add `requestId` to a JSON object error body; other bodies are returned unchanged.
+async fn with_request_id(response: Response<BoxBody>, request_id: &str) -> Response<BoxBody> {
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+    let (mut parts, body) = response.into_parts();
+    let bytes = match hyper::body::to_bytes(body).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            tracing::warn!("Failed to read error body: {err}");
+            return Response::from_parts(parts, boxed(Body::empty()));
+        }
+    };
+    let body = match serde_json::from_slice::<serde_json::Value>(&bytes) {
+        Ok(serde_json::Value::Object(mut object)) => {
+            object.insert("requestId".to_string(), request_id.into());
+            let body = serde_json::Value::Object(object).to_string();
+            parts.headers.remove(header::CONTENT_LENGTH);
+            body.into_bytes().into()
+        }
+        _ => bytes,
+    };
+    Response::from_parts(parts, boxed(Body::from(body)))
+}
@@ -0,0 +1,512 @@
+//! Contract tests: the same CRUD scenario runs against the axum and the Smithy front-ends, served
+//! from one [CatalogApp], and both must answer every step with the same status and body.
+//!
+//! Bodies are compared after normalization: server-assigned values (item id, slug, timestamps)
+//! are masked, absent and `null` members are equivalent, and error bodies (problem details vs
+//! Smithy errors) are reduced to the paths of the violated fields.
+
+use std::time::Duration;
+
+use catalog_svc::api_keys::api::CreateApiKeyBody;
+use catalog_svc::app_config::{AppConfig, AuthConfig};
+use catalog_svc::auth::{API_KEY_HEADER, Role};
+use catalog_svc::http_server::CatalogApp;
+use catalog_svc::server;
+use chrono::{DateTime, Utc};
+use reqwest::{Method, StatusCode};
+use rust_demo_commons::util::tests;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+/// Members assigned by the server, different for every created item.
+const SERVER_ASSIGNED: [&str; 4] = ["itemId", "slug", "createdAt", "modifiedAt"];
+
+/// One request/response of a scenario, as compared between front-ends.
+#[derive(Debug, PartialEq)]
+struct Exchange {
+    step: &'static str,
+    status: StatusCode,
+    body: Value,
+}
+
+struct FrontEnd {
+    name: &'static str,
+    http: reqwest::Client,
+    base: String,
+}
+
+impl FrontEnd {
+    /// Status and JSON body (`null` when empty or not JSON) of a request.
+    async fn send(&self, method: Method, path: &str, body: Option<Value>) -> (StatusCode, Value) {
+        let mut request = self.http.request(method, format!("{}{path}", self.base));
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await.expect("request");
+        let status = response.status();
+        let bytes = response.bytes().await.expect("response body");
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    /// `send`, with the body normalized for comparison.
+    async fn exchange(
+        &self,
+        step: &'static str,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Exchange {
+        let (status, body) = self.send(method, path, body).await;
+        Exchange {
+            step,
+            status,
+            body: contract_view(status, body),
+        }
+    }
+
+    /// `exchange` comparing the status only, for requests rejected before reaching a handler
+    /// (the framework's own error bodies differ).
+    async fn status(&self, step: &'static str, method: Method, path: &str) -> Exchange {
+        let (status, _) = self.send(method, path, None).await;
+        Exchange {
+            step,
+            status,
+            body: Value::Null,
+        }
+    }
+}
+
+/// Successful bodies without server-assigned members and `null`s; errors as their violated
+/// field paths (`errors` in problem details, `fieldList` in Smithy validation errors).
+fn contract_view(status: StatusCode, body: Value) -> Value {
+    if status.is_success() {
+        return normalize(body);
+    }
+    let mut paths: Vec<&str> = ["/errors", "/fieldList"]
+        .iter()
+        .filter_map(|pointer| body.pointer(pointer).and_then(Value::as_array))
+        .flatten()
+        .filter_map(|violation| violation.get("path").and_then(Value::as_str))
+        .collect();
+    paths.sort_unstable();
+    json!({ "violations": paths })
+}
+
+fn normalize(value: Value) -> Value {
+    match value {
+        Value::Object(members) => Value::Object(
+            members
+                .into_iter()
+                .filter(|(_, member)| !member.is_null())
+                .map(|(name, member)| {
+                    if SERVER_ASSIGNED.contains(&name.as_str()) {
+                        (name, json!("<server-assigned>"))
+                    } else {
+                        (name, normalize(member))
+                    }
+                })
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(normalize).collect()),
+        value => value,
+    }
+}
+
+fn item_body(name: &str, price: &str) -> Value {
+    json!({
+        "name": name,
+        "description": "Served by both front-ends",
+        "category": "Books",
+        "date": "2025-05-01",
+        "brand": "Contract",
+        "price": price,
+    })
+}
+
+fn timestamp(item: &Value, member: &str) -> DateTime<Utc> {
+    item.get(member)
+        .and_then(Value::as_str)
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+        .map(|value| value.with_timezone(&Utc))
+        .expect("RFC 3339 timestamp")
+}
+
+/// Run the CRUD scenario against `front_end`; the returned exchanges are compared between
+/// front-ends. Timestamps are checked against the stored item here, since they differ per run.
+async fn crud_scenario(front_end: &FrontEnd, state: &CatalogApp) -> Vec<Exchange> {
+    let mut exchanges = Vec::new();
+
+    let (status, created) = front_end
+        .send(
+            Method::POST,
+            "/catalog/items",
+            Some(item_body("Contract Book", "19.99")),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{}: {created}", front_end.name);
+    let item_id: Uuid = created
+        .get("itemId")
+        .and_then(Value::as_str)
+        .and_then(|item_id| item_id.parse().ok())
+        .expect("item id");
+    exchanges.push(Exchange {
+        step: "create",
+        status,
+        body: contract_view(status, created),
+    });
+    let item_path = format!("/catalog/items/{item_id}");
+
+    // Timestamps keep the precision of the stored item
+    let (status, fetched) = front_end.send(Method::GET, &item_path, None).await;
+    assert_eq!(status, StatusCode::OK, "{}: {fetched}", front_end.name);
+    let stored = state
+        .catalog
+        .get(item_id)
+        .await
+        .expect("get")
+        .expect("item exists");
+    assert_eq!(timestamp(&fetched, "createdAt"), stored.created_at);
+    assert_eq!(timestamp(&fetched, "modifiedAt"), stored.modified_at);
+    assert_eq!(
+        fetched.get("slug").and_then(Value::as_str),
+        Some(stored.slug.as_str())
+    );
+    exchanges.push(Exchange {
+        step: "get",
+        status,
+        body: contract_view(status, fetched),
+    });
+
+    exchanges.push(
+        front_end
+            .exchange(
+                "update",
+                Method::POST,
+                &item_path,
+                Some(item_body("Contract Book (2nd ed)", "24.50")),
+            )
+            .await,
+    );
+    exchanges.push(
+        front_end
+            .exchange(
+                "update invalid",
+                Method::POST,
+                &item_path,
+                Some(item_body(" ", "-1")),
+            )
+            .await,
+    );
+    exchanges.push(
+        front_end
+            .exchange(
+                "create invalid",
+                Method::POST,
+                "/catalog/items",
+                Some(item_body("", "-5")),
+            )
+            .await,
+    );
+    exchanges.push(
+        front_end
+            .exchange(
+                "update missing",
+                Method::POST,
+                &format!("/catalog/items/{}", Uuid::new_v4()),
+                Some(item_body("Nobody", "1")),
+            )
+            .await,
+    );
+    exchanges.push(
+        front_end
+            .exchange(
+                "get missing",
+                Method::GET,
+                &format!("/catalog/items/{}", Uuid::new_v4()),
+                None,
+            )
+            .await,
+    );
+    exchanges.push(
+        front_end
+            .status("get malformed id", Method::GET, "/catalog/items/not-a-uuid")
+            .await,
+    );
+
+    // The rest of the catalog changes between runs: compare the page window only
+    let (status, page) = front_end
+        .send(
+            Method::GET,
+            "/catalog/items?limit=1&offset=0&sort=rating",
+            None,
+        )
+        .await;
+    exchanges.push(Exchange {
+        step: "list",
+        status,
+        body: json!({
+            "pagination": page.get("pagination"),
+            "items": page.get("items").and_then(Value::as_array).map(Vec::len),
+        }),
+    });
+    exchanges.push(
+        front_end
+            .status(
+                "list negative limit",
+                Method::GET,
+                "/catalog/items?limit=-1",
+            )
+            .await,
+    );
+    exchanges.push(
+        front_end
+            .status(
+                "list negative offset",
+                Method::GET,
+                "/catalog/items?offset=-3",
+            )
+            .await,
+    );
+    exchanges.push(
+        front_end
+            .status(
+                "list unknown sort",
+                Method::GET,
+                "/catalog/items?sort=price",
+            )
+            .await,
+    );
+
+    exchanges.push(
+        front_end
+            .exchange("delete", Method::DELETE, &item_path, None)
+            .await,
+    );
+    exchanges.push(
+        front_end
+            .exchange("delete again", Method::DELETE, &item_path, None)
+            .await,
+    );
+    exchanges.push(
+        front_end
+            .exchange("get deleted", Method::GET, &item_path, None)
+            .await,
+    );
+    exchanges
+}
+
+#[tokio::test]
+async fn axum_and_smithy_front_ends_agree() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    let (smithy_handle, smithy_addr) = catalog_svc_smithy_server::start_service_and_serve(
+        app_state.clone(),
+        "127.0.0.1",
+        0,
+        Duration::ZERO,
+    )
+    .await
+    .expect("bind Smithy");
+    app_config.server.port = 0;
+    let (state, axum_handle, axum_addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind axum");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let http = reqwest::Client::new();
+    let axum = FrontEnd {
+        name: "axum",
+        http: http.clone(),
+        base: format!("http://{axum_addr}"),
+    };
+    let smithy = FrontEnd {
+        name: "smithy",
+        http,
+        base: format!("http://{smithy_addr}"),
+    };
+
+    let expected = crud_scenario(&axum, &state).await;
+    let actual = crud_scenario(&smithy, &state).await;
+    for (axum_exchange, smithy_exchange) in expected.iter().zip(&actual) {
+        assert_eq!(axum_exchange, smithy_exchange);
+    }
+    assert_eq!(expected.len(), actual.len());
+
+    // Spot-check the agreed answers, so both front-ends can't agree on a wrong one
+    let statuses: Vec<_> = expected
+        .iter()
+        .map(|exchange| (exchange.step, exchange.status.as_u16()))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            ("create", 201),
+            ("get", 200),
+            ("update", 200),
+            ("update invalid", 400),
+            ("create invalid", 400),
+            ("update missing", 404),
+            ("get missing", 404),
+            ("get malformed id", 400),
+            ("list", 200),
+            ("list negative limit", 400),
+            ("list negative offset", 400),
+            ("list unknown sort", 400),
+            ("delete", 204),
+            ("delete again", 404),
+            ("get deleted", 404),
+        ]
+    );
+    let by_step = |step: &str| {
+        expected
+            .iter()
+            .find(|exchange| exchange.step == step)
+            .map(|exchange| exchange.body.clone())
+            .expect("step")
+    };
+    assert_eq!(
+        by_step("update"),
+        json!({
+            "itemId": "<server-assigned>",
+            "slug": "<server-assigned>",
+            "name": "Contract Book (2nd ed)",
+            "description": "Served by both front-ends",
+            "category": "Books",
+            "date": "2025-05-01",
+            "brand": "Contract",
+            "price": "24.50",
+            "createdAt": "<server-assigned>",
+            "modifiedAt": "<server-assigned>",
+            "rating": {"count": 0, "histogram": [0, 0, 0, 0, 0]},
+        })
+    );
+    assert_eq!(
+        by_step("create invalid"),
+        json!({"violations": ["/name", "/price"]})
+    );
+
+    // A price that is not a decimal never reaches the axum handler (its JSON extractor answers
+    // 422); the Smithy front-end reports it as a field violation.
+    let (status, body) = smithy
+        .send(
+            Method::POST,
+            "/catalog/items",
+            Some(item_body("Priceless", "abc")),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        contract_view(status, body),
+        json!({"violations": ["/price"]})
+    );
+
+    state.server_shutdown.cancel();
+    smithy_handle
+        .await
+        .expect("Smithy server task")
+        .expect("serve");
+    axum_handle.await.expect("axum server task").expect("serve");
+}
+
+/// Status of a `method` request to `url`, with `api_key` (if any) in the [API_KEY_HEADER].
+async fn status_with_key(
+    http: &reqwest::Client,
+    method: Method,
+    url: &str,
+    api_key: Option<&str>,
+) -> u16 {
+    let mut request = http.request(method.clone(), url);
+    if let Some(api_key) = api_key {
+        request = request.header(API_KEY_HEADER, api_key);
+    }
+    if method == Method::POST {
+        request = request.json(&item_body("Keyed", "1.00"));
+    }
+    request.send().await.expect("request").status().as_u16()
+}
+
+#[tokio::test]
+async fn front_ends_accept_api_keys() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth = AuthConfig {
+        enabled: true,
+        hs256_secret: Some("contract-api-key-test-secret".to_string()),
+        ..AuthConfig::default()
+    };
+    let (app_state, _background) = server::build_app(&app_config).await;
+    let (smithy_handle, smithy_addr) = catalog_svc_smithy_server::start_service_and_serve(
+        app_state.clone(),
+        "127.0.0.1",
+        0,
+        Duration::ZERO,
+    )
+    .await
+    .expect("bind Smithy");
+    app_config.server.port = 0;
+    let (state, axum_handle, axum_addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind axum");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let reader = state
+        .api_keys
+        .create(CreateApiKeyBody {
+            name: "contract reader".to_string(),
+            scopes: vec![Role::Reader],
+            expires_at: None,
+        })
+        .await
+        .expect("mint key");
+    let key = reader.key.as_str();
+
+    let http = reqwest::Client::new();
+    for base in [
+        format!("http://{axum_addr}"),
+        format!("http://{smithy_addr}"),
+    ] {
+        let items = format!("{base}/catalog/items");
+        let statuses = [
+            status_with_key(&http, Method::GET, &items, Some(key)).await,
+            status_with_key(&http, Method::GET, &items, None).await,
+            status_with_key(&http, Method::GET, &items, Some("ck_unknown")).await,
+            // A reader key cannot write
+            status_with_key(&http, Method::POST, &items, Some(key)).await,
+        ];
+        assert_eq!(statuses, [200, 401, 401, 403], "{base}");
+    }
+
+    // Revoked keys are rejected by both front-ends
+    assert!(
+        state
+            .api_keys
+            .revoke(reader.api_key.key_id)
+            .await
+            .expect("revoke")
+    );
+    for base in [
+        format!("http://{axum_addr}"),
+        format!("http://{smithy_addr}"),
+    ] {
+        let items = format!("{base}/catalog/items");
+        assert_eq!(
+            status_with_key(&http, Method::GET, &items, Some(key)).await,
+            401,
+            "{base}"
+        );
+    }
+
+    state.server_shutdown.cancel();
+    smithy_handle
+        .await
+        .expect("Smithy server task")
+        .expect("serve");
+    axum_handle.await.expect("axum server task").expect("serve");
+}
@@ -0,0 +1,121 @@
+//! Errors of [CatalogClient](super::CatalogClient), classified from the generated client's
+//! [Error](crate::Error) by HTTP status and transport failure.
+
+use reqwest::StatusCode;
+
+use crate::types::{FieldViolation, ProblemDetails};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    /// The item does not exist (404).
+    #[error("catalog item not found")]
+    NotFound,
+    /// The request was invalid (400); `violations` lists the offending fields, if any.
+    #[error("validation failed: {message}")]
+    Validation {
+        message: String,
+        violations: Vec<FieldViolation>,
+    },
+    /// Any other 4xx, e.g. missing credentials (401), a role lacking permission (403) or rate
+    /// limiting (429).
+    #[error("request rejected (HTTP {status}): {message}")]
+    Rejected { status: u16, message: String },
+    /// The service failed to handle the request (5xx).
+    #[error("server error (HTTP {status}): {message}")]
+    Server { status: u16, message: String },
+    /// No response within the configured timeout.
+    #[error("request timed out")]
+    Timeout(#[source] reqwest::Error),
+    /// The request could not be sent or its response could not be read.
+    #[error("connection failed: {0}")]
+    Connection(#[source] reqwest::Error),
+    /// The response did not match the API description.
+    #[error("invalid response: {0}")]
+    InvalidResponse(String),
+    /// The request could not be built, e.g. a header value or page offset out of range.
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+}
+
+impl ClientError {
+    /// Whether another attempt may succeed. Requests that are not idempotent are only retried
+    /// when they could not have reached the service (the connection failed).
+    pub fn is_retryable(&self, idempotent: bool) -> bool {
+        match self {
+            ClientError::Connection(err) if err.is_connect() => true,
+            ClientError::Server { .. } | ClientError::Timeout(_) | ClientError::Connection(_) => {
+                idempotent
+            }
+            _ => false,
+        }
+    }
+
+    /// Problem Details (if the body carried them) reported with `status`.
+    fn from_status(status: StatusCode, problem: Option<ProblemDetails>) -> Self {
+        let message = problem
+            .as_ref()
+            .map(|problem| problem.detail.clone().unwrap_or(problem.title.clone()))
+            .unwrap_or_else(|| status.to_string());
+        let status = status.as_u16();
+        match status {
+            404 => ClientError::NotFound,
+            400 => ClientError::Validation {
+                message,
+                violations: problem.map(|problem| problem.errors).unwrap_or_default(),
+            },
+            500.. => ClientError::Server { status, message },
+            _ => ClientError::Rejected { status, message },
+        }
+    }
+
+    fn from_reqwest(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            ClientError::Timeout(err)
+        } else if err.is_decode() {
+            ClientError::InvalidResponse(err.to_string())
+        } else {
+            ClientError::Connection(err)
+        }
+    }
+
+    /// Classifies an error of the generated client. Operations documenting no error body report
+    /// failures as unexpected responses, whose body is read as Problem Details when possible.
+    pub(crate) async fn from_generated<E: ErrorBody>(err: crate::Error<E>) -> Self {
+        match err {
+            crate::Error::ErrorResponse(response) => {
+                let status = response.status();
+                Self::from_status(status, response.into_inner().into_problem())
+            }
+            crate::Error::UnexpectedResponse(response) => {
+                let status = response.status();
+                Self::from_status(status, response.json().await.ok())
+            }
+            crate::Error::CommunicationError(err)
+            | crate::Error::InvalidUpgrade(err)
+            | crate::Error::ResponseBodyError(err) => Self::from_reqwest(err),
+            crate::Error::InvalidResponsePayload(_, err) => {
+                ClientError::InvalidResponse(err.to_string())
+            }
+            crate::Error::InvalidRequest(message) | crate::Error::Custom(message) => {
+                ClientError::InvalidRequest(message)
+            }
+        }
+    }
+}
+
+/// Error bodies of the generated operations: Problem Details or none.
+pub(crate) trait ErrorBody {
+    fn into_problem(self) -> Option<ProblemDetails>;
+}
+
+impl ErrorBody for ProblemDetails {
+    fn into_problem(self) -> Option<ProblemDetails> {
+        Some(self)
+    }
+}
+
+impl ErrorBody for () {
+    fn into_problem(self) -> Option<ProblemDetails> {
+        None
+    }
+}
@@ -0,0 +1,239 @@
+//! Hand-written layer over the generated [Client](crate::Client) for catalog items: typed
+//! models ([Decimal](rust_decimal::Decimal) prices, [NaiveDate](chrono::NaiveDate) dates), a
+//! [Stream] over every page of the listing, retries with backoff, timeouts and a
+//! [ClientError] telling not-found, validation and server errors apart.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), catalog_svc_client::catalog::ClientError> {
+//! use catalog_svc_client::catalog::{CatalogClient, ClientOptions, Credentials};
+//! use futures::TryStreamExt;
+//!
+//! let client = CatalogClient::new(
+//!     "http://localhost:3030",
+//!     ClientOptions {
+//!         credentials: Some(Credentials::ApiKey("<key>".to_string())),
+//!         ..ClientOptions::default()
+//!     },
+//! )?;
+//! let items: Vec<_> = client.list_all(None).try_collect().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod error;
+mod model;
+mod retry;
+
+use std::time::Duration;
+
+use futures::{Stream, TryStreamExt, stream};
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use uuid::Uuid;
+
+pub use self::error::ClientError;
+pub use self::model::{CatalogItem, CatalogItemInput, CatalogItemPage, Rating};
+pub use self::retry::RetryPolicy;
+use crate::Client;
+use crate::types::{CatalogItemSort, CreateCatalogItemBody, UpdateCatalogItemBody};
+
+/// Credentials sent with every request.
+#[derive(Clone)]
+pub enum Credentials {
+    /// JWT, sent as `Authorization: Bearer <token>`.
+    Bearer(String),
+    /// API key, sent as `X-Api-Key`.
+    ApiKey(String),
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Credentials::Bearer(_) => f.write_str("Bearer(<redacted>)"),
+            Credentials::ApiKey(_) => f.write_str("ApiKey(<redacted>)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    /// Limit for a whole attempt, from connecting to reading the response body.
+    pub timeout: Duration,
+    pub connect_timeout: Duration,
+    pub retry: RetryPolicy,
+    pub credentials: Option<Credentials>,
+    /// Items requested per page by [CatalogClient::list_all].
+    pub page_size: u32,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(15),
+            connect_timeout: Duration::from_secs(5),
+            retry: RetryPolicy::default(),
+            credentials: None,
+            page_size: 100,
+        }
+    }
+}
+
+/// Catalog item operations. Reads, updates and deletes are retried per [RetryPolicy]; creates only
+/// when the connection failed, since a server error or a timeout may follow a successful insert.
+#[derive(Debug, Clone)]
+pub struct CatalogClient {
+    client: Client,
+    retry: RetryPolicy,
+    page_size: u32,
+}
+
+impl CatalogClient {
+    pub fn new(base_url: &str, options: ClientOptions) -> Result<Self, ClientError> {
+        let mut headers = HeaderMap::new();
+        if let Some(credentials) = &options.credentials {
+            let (name, value) = match credentials {
+                Credentials::Bearer(token) => (header::AUTHORIZATION, format!("Bearer {token}")),
+                Credentials::ApiKey(key) => {
+                    (header::HeaderName::from_static("x-api-key"), key.clone())
+                }
+            };
+            let mut value = HeaderValue::try_from(value)
+                .map_err(|_| ClientError::InvalidRequest("invalid credentials".to_string()))?;
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+        let http = reqwest::Client::builder()
+            .timeout(options.timeout)
+            .connect_timeout(options.connect_timeout)
+            .default_headers(headers)
+            .build()
+            .map_err(|err| ClientError::InvalidRequest(err.to_string()))?;
+        Ok(Self::with_client(
+            Client::new_with_client(base_url, http),
+            options.retry,
+            options.page_size,
+        ))
+    }
+
+    /// Wraps an already configured generated client.
+    pub fn with_client(client: Client, retry: RetryPolicy, page_size: u32) -> Self {
+        Self {
+            client,
+            retry,
+            page_size,
+        }
+    }
+
+    /// The generated client, for operations not covered here.
+    pub fn inner(&self) -> &Client {
+        &self.client
+    }
+
+    pub async fn get(&self, item_id: Uuid) -> Result<CatalogItem, ClientError> {
+        let item = self
+            .retry
+            .run(true, || async move {
+                match self.client.get_catalog_item(&item_id).await {
+                    Ok(response) => Ok(response.into_inner()),
+                    Err(err) => Err(ClientError::from_generated(err).await),
+                }
+            })
+            .await?;
+        item.try_into()
+    }
+
+    pub async fn create(&self, input: CatalogItemInput) -> Result<CatalogItem, ClientError> {
+        let body = CreateCatalogItemBody::from(input);
+        let body = &body;
+        let item = self
+            .retry
+            .run(false, || async move {
+                match self.client.create_catalog_item(body).await {
+                    Ok(response) => Ok(response.into_inner()),
+                    Err(err) => Err(ClientError::from_generated(err).await),
+                }
+            })
+            .await?;
+        item.try_into()
+    }
+
+    /// Replaces the fields of an item.
+    pub async fn update(
+        &self,
+        item_id: Uuid,
+        input: CatalogItemInput,
+    ) -> Result<CatalogItem, ClientError> {
+        let body = UpdateCatalogItemBody::from(input);
+        let body = &body;
+        let item = self
+            .retry
+            .run(true, || async move {
+                match self.client.update_catalog_item(&item_id, body).await {
+                    Ok(response) => Ok(response.into_inner()),
+                    Err(err) => Err(ClientError::from_generated(err).await),
+                }
+            })
+            .await?;
+        item.try_into()
+    }
+
+    /// Deletes an item. A retry after a lost response may report [ClientError::NotFound] for an
+    /// item this call deleted.
+    pub async fn delete(&self, item_id: Uuid) -> Result<(), ClientError> {
+        self.retry
+            .run(true, || async move {
+                match self.client.delete_catalog_item(&item_id).await {
+                    Ok(_) => Ok(()),
+                    Err(err) => Err(ClientError::from_generated(err).await),
+                }
+            })
+            .await
+    }
+
+    /// One page of items, starting at `offset`; `limit` defaults to the service's page size.
+    pub async fn list_page(
+        &self,
+        offset: u32,
+        limit: Option<u32>,
+        sort: Option<CatalogItemSort>,
+    ) -> Result<CatalogItemPage, ClientError> {
+        let offset = page_param("offset", offset)?;
+        let limit = limit.map(|limit| page_param("limit", limit)).transpose()?;
+        let page = self
+            .retry
+            .run(true, || async move {
+                match self
+                    .client
+                    .list_catalog_items(limit, Some(offset), sort)
+                    .await
+                {
+                    Ok(response) => Ok(response.into_inner()),
+                    Err(err) => Err(ClientError::from_generated(err).await),
+                }
+            })
+            .await?;
+        page.try_into()
+    }
+
+    /// Every item, fetched page by page (of `page_size` items) as the stream is consumed. Pages
+    /// are read by offset, so items created or deleted meanwhile may shift others across pages.
+    pub fn list_all(
+        &self,
+        sort: Option<CatalogItemSort>,
+    ) -> impl Stream<Item = Result<CatalogItem, ClientError>> + '_ {
+        stream::try_unfold(Some(0u32), move |offset| async move {
+            let Some(offset) = offset else {
+                return Ok(None);
+            };
+            let page = self.list_page(offset, Some(self.page_size), sort).await?;
+            let fetched = u32::try_from(page.items.len()).unwrap_or(u32::MAX);
+            let next = (page.has_more && fetched > 0).then(|| page.offset.saturating_add(fetched));
+            Ok(Some((stream::iter(page.items.into_iter().map(Ok)), next)))
+        })
+        .try_flatten()
+    }
+}
+
+fn page_param(name: &str, value: u32) -> Result<i32, ClientError> {
+    i32::try_from(value)
+        .map_err(|_| ClientError::InvalidRequest(format!("{name} {value} is too large")))
+}
@@ -0,0 +1,171 @@
+//! Catalog items with typed prices, dates and counts, converted from and to the generated
+//! models (which carry decimals and dates as strings).
+
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use super::ClientError;
+use crate::types;
+use crate::types::Category;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogItem {
+    pub item_id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    pub category: Category,
+    pub brand: Option<String>,
+    pub date: NaiveDate,
+    pub price: Decimal,
+    /// Last stock reported by the supplier feed, if any.
+    pub stock: Option<u32>,
+    pub rating: Rating,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+}
+
+/// Summary of an item's approved reviews.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rating {
+    /// Mean rating rounded to two decimals; `None` without reviews.
+    pub average: Option<Decimal>,
+    pub count: u32,
+    /// Number of reviews per rating, from 1 to 5 stars.
+    pub histogram: Vec<u32>,
+}
+
+/// Fields of an item to create, or to replace with an update.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogItemInput {
+    pub name: String,
+    pub description: String,
+    pub category: Category,
+    pub brand: Option<String>,
+    pub date: NaiveDate,
+    pub price: Decimal,
+}
+
+/// One page of [CatalogClient::list_page](super::CatalogClient::list_page).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogItemPage {
+    pub items: Vec<CatalogItem>,
+    /// Offset and page size applied by the service (the size is clamped server-side).
+    pub offset: u32,
+    pub limit: u32,
+    pub has_more: bool,
+    pub total_count: Option<u32>,
+}
+
+fn decimal(field: &str, value: &str) -> Result<Decimal, ClientError> {
+    Decimal::from_str(value)
+        .map_err(|err| ClientError::InvalidResponse(format!("{field} {value:?}: {err}")))
+}
+
+fn count(field: &str, value: i32) -> Result<u32, ClientError> {
+    u32::try_from(value)
+        .map_err(|_| ClientError::InvalidResponse(format!("{field} {value} is negative")))
+}
+
+impl TryFrom<types::CatalogItem> for CatalogItem {
+    type Error = ClientError;
+
+    fn try_from(item: types::CatalogItem) -> Result<Self, Self::Error> {
+        Ok(CatalogItem {
+            price: decimal("price", &item.price)?,
+            stock: item.stock.map(|stock| count("stock", stock)).transpose()?,
+            rating: item.rating.try_into()?,
+            item_id: item.item_id,
+            slug: item.slug,
+            name: item.name,
+            description: item.description,
+            category: item.category,
+            brand: item.brand,
+            date: item.date,
+            created_at: item.created_at,
+            modified_at: item.modified_at,
+        })
+    }
+}
+
+impl TryFrom<types::RatingSummary> for Rating {
+    type Error = ClientError;
+
+    fn try_from(rating: types::RatingSummary) -> Result<Self, Self::Error> {
+        Ok(Rating {
+            average: rating
+                .average
+                .map(|average| decimal("rating average", &average))
+                .transpose()?,
+            count: count("rating count", rating.count)?,
+            histogram: rating
+                .histogram
+                .into_iter()
+                .map(|bucket| count("rating histogram", bucket))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<types::ListCatalogItemsResponse> for CatalogItemPage {
+    type Error = ClientError;
+
+    fn try_from(page: types::ListCatalogItemsResponse) -> Result<Self, Self::Error> {
+        Ok(CatalogItemPage {
+            items: page
+                .items
+                .into_iter()
+                .map(CatalogItem::try_from)
+                .collect::<Result<_, _>>()?,
+            offset: count("offset", page.pagination.offset)?,
+            limit: count("limit", page.pagination.limit)?,
+            has_more: page.has_more,
+            total_count: page
+                .total_count
+                .map(|total| count("total count", total))
+                .transpose()?,
+        })
+    }
+}
+
+impl From<CatalogItemInput> for types::CreateCatalogItemBody {
+    fn from(input: CatalogItemInput) -> Self {
+        types::CreateCatalogItemBody {
+            name: input.name,
+            description: input.description,
+            category: input.category,
+            brand: input.brand,
+            date: input.date.to_string(),
+            price: input.price.to_string(),
+        }
+    }
+}
+
+impl From<CatalogItemInput> for types::UpdateCatalogItemBody {
+    fn from(input: CatalogItemInput) -> Self {
+        types::UpdateCatalogItemBody {
+            name: input.name,
+            description: input.description,
+            category: input.category,
+            brand: input.brand,
+            date: input.date.to_string(),
+            price: input.price.to_string(),
+        }
+    }
+}
+
+impl From<CatalogItem> for CatalogItemInput {
+    fn from(item: CatalogItem) -> Self {
+        CatalogItemInput {
+            name: item.name,
+            description: item.description,
+            category: item.category,
+            brand: item.brand,
+            date: item.date,
+            price: item.price,
+        }
+    }
+}
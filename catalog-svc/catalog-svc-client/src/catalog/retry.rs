@@ -0,0 +1,66 @@
+//! Retrying failed requests with exponential backoff.
+
+use std::time::Duration;
+
+use super::ClientError;
+
+/// How often and how patiently to retry requests failing with a 5xx, a timeout or a connection
+/// error (see [ClientError::is_retryable]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; 0 disables retrying.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each further retry.
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between two attempts.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (starting at 0).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+
+    /// Runs `attempt` until it succeeds, fails with an error that is not retryable, or runs out
+    /// of retries.
+    pub(crate) async fn run<T, F, Fut>(
+        &self,
+        idempotent: bool,
+        mut attempt: F,
+    ) -> Result<T, ClientError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let mut retry = 0;
+        loop {
+            match attempt().await {
+                Err(err) if retry < self.max_retries && err.is_retryable(idempotent) => {
+                    tokio::time::sleep(self.backoff(retry)).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
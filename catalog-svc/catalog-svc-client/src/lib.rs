@@ -1 +1,3 @@
 include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
+
+pub mod catalog;
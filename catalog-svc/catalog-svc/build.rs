@@ -1,3 +1,13 @@
-fn main() {
+use std::env;
+use std::path::PathBuf;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("cargo:rerun-if-changed=migrations");
+
+    // The descriptor set is served by gRPC server reflection
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    tonic_build::configure()
+        .file_descriptor_set_path(out_dir.join("catalog_descriptor.bin"))
+        .compile_protos(&["proto/catalog.proto"], &["proto"])?;
+    Ok(())
 }
@@ -0,0 +1,17 @@
+-- Image attachments of catalog items. Bytes live in the blob store under blob_key / thumbnail_key;
+-- rows are removed together with their item.
+-- position is the zero-based display order within an item.
+CREATE TABLE catalog_item_media (
+    media_id UUID PRIMARY KEY,
+    item_id UUID NOT NULL REFERENCES catalog_items (item_id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    content_type VARCHAR(127) NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    blob_key TEXT NOT NULL,
+    thumbnail_key TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX idx_catalog_item_media_item_id ON catalog_item_media (item_id, position);
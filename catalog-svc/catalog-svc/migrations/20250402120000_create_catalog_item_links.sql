@@ -0,0 +1,14 @@
+-- Directed links between catalog items (item_id -> related_item_id).
+-- kind stored as text: 'accessory' | 'replacement' | 'bundle-member' | 'similar'
+-- Links are removed together with either of their items.
+CREATE TABLE catalog_item_links (
+    item_id UUID NOT NULL REFERENCES catalog_items (item_id) ON DELETE CASCADE,
+    related_item_id UUID NOT NULL REFERENCES catalog_items (item_id) ON DELETE CASCADE,
+    kind VARCHAR(32) NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (item_id, related_item_id, kind),
+    CONSTRAINT catalog_item_links_no_self_link CHECK (item_id <> related_item_id)
+);
+
+-- Supports the cascade from the related side.
+CREATE INDEX idx_catalog_item_links_related_item_id ON catalog_item_links (related_item_id);
@@ -0,0 +1,25 @@
+-- Customer reviews of catalog items.
+-- moderation_state stored as text: 'Pending' | 'Approved' | 'Rejected'; only approved reviews count
+-- towards the item's rating aggregate.
+CREATE TABLE catalog_item_reviews (
+    review_id UUID PRIMARY KEY,
+    item_id UUID NOT NULL REFERENCES catalog_items (item_id) ON DELETE CASCADE,
+    rating SMALLINT NOT NULL CHECK (rating BETWEEN 1 AND 5),
+    title VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    author VARCHAR(255) NOT NULL,
+    moderation_state VARCHAR(32) NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    modified_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX idx_catalog_item_reviews_item_id ON catalog_item_reviews (item_id, moderation_state, created_at);
+
+-- Rating aggregate over approved reviews, maintained on every review write.
+-- rating_histogram holds the number of 1..5 star reviews (index 1 = one star).
+ALTER TABLE catalog_items
+    ADD COLUMN rating_count INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN rating_average NUMERIC(3, 2),
+    ADD COLUMN rating_histogram INTEGER[] NOT NULL DEFAULT '{0,0,0,0,0}';
+
+CREATE INDEX idx_catalog_items_rating ON catalog_items (rating_average DESC NULLS LAST, rating_count DESC);
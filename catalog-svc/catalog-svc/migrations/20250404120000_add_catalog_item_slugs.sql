@@ -0,0 +1,42 @@
+-- Human-readable, unique, URL-safe slugs for catalog items.
+-- catalog_items.slug is the canonical slug; catalog_item_slugs keeps every slug an item has had,
+-- so old slugs keep resolving (and redirect to the canonical one) after renames.
+ALTER TABLE catalog_items ADD COLUMN slug VARCHAR(100);
+
+-- Backfill existing rows from their names (ASCII only; new slugs are transliterated by the service).
+-- Duplicate names get a suffix from the item_id so the backfill can't collide.
+WITH base AS (
+    SELECT
+        item_id,
+        created_at,
+        COALESCE(
+            NULLIF(TRIM(BOTH '-' FROM LEFT(REGEXP_REPLACE(LOWER(name), '[^a-z0-9]+', '-', 'g'), 80)), ''),
+            'item'
+        ) AS base_slug
+    FROM catalog_items
+),
+ranked AS (
+    SELECT
+        item_id,
+        base_slug,
+        ROW_NUMBER() OVER (PARTITION BY base_slug ORDER BY created_at, item_id) AS n
+    FROM base
+)
+UPDATE catalog_items i
+SET slug = CASE WHEN r.n = 1 THEN r.base_slug ELSE r.base_slug || '-' || LEFT(i.item_id::TEXT, 8) END
+FROM ranked r
+WHERE i.item_id = r.item_id;
+
+ALTER TABLE catalog_items ALTER COLUMN slug SET NOT NULL;
+CREATE UNIQUE INDEX idx_catalog_items_slug ON catalog_items (slug);
+
+CREATE TABLE catalog_item_slugs (
+    slug VARCHAR(100) PRIMARY KEY,
+    item_id UUID NOT NULL REFERENCES catalog_items (item_id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX idx_catalog_item_slugs_item_id ON catalog_item_slugs (item_id);
+
+INSERT INTO catalog_item_slugs (slug, item_id, created_at)
+SELECT slug, item_id, modified_at FROM catalog_items;
@@ -0,0 +1,16 @@
+-- API keys for machine clients (X-Api-Key header). Only the SHA-256 of the key is stored;
+-- key_prefix keeps its first characters so admins can recognise a key in listings.
+-- scopes holds the granted roles: 'reader' | 'writer' | 'admin'.
+CREATE TABLE api_keys (
+    key_id UUID PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    key_prefix VARCHAR(16) NOT NULL,
+    key_hash CHAR(64) NOT NULL UNIQUE,
+    scopes TEXT[] NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    expires_at TIMESTAMP,
+    last_used_at TIMESTAMP,
+    revoked_at TIMESTAMP
+);
+
+CREATE INDEX idx_api_keys_created_at ON api_keys (created_at);
@@ -0,0 +1,16 @@
+-- Transactional outbox of catalog change events, written in the same transaction as the item
+-- change and published to Kafka by the outbox relay (at least once, in seq order).
+-- event_type: 'ItemCreated' | 'ItemUpdated' | 'ItemDeleted' | 'PriceChanged'.
+-- headers holds message headers captured at write time (W3C trace context).
+CREATE TABLE catalog_outbox (
+    seq BIGSERIAL PRIMARY KEY,
+    event_id UUID NOT NULL UNIQUE,
+    item_id UUID NOT NULL,
+    event_type VARCHAR(32) NOT NULL,
+    payload JSONB NOT NULL,
+    headers JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP NOT NULL,
+    published_at TIMESTAMP
+);
+
+CREATE INDEX idx_catalog_outbox_unpublished ON catalog_outbox (seq) WHERE published_at IS NULL;
@@ -0,0 +1,14 @@
+-- Supplier feed: stock levels reported by suppliers, and the ids of processed inbound messages.
+-- stock stays NULL until a supplier reports it.
+ALTER TABLE catalog_items ADD COLUMN stock INTEGER CHECK (stock >= 0);
+
+-- Inbound messages already applied, per source, so redelivered messages are skipped.
+-- Recorded in the same transaction as their effect.
+CREATE TABLE inbox_messages (
+    source VARCHAR(64) NOT NULL,
+    message_id VARCHAR(255) NOT NULL,
+    processed_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (source, message_id)
+);
+
+CREATE INDEX idx_inbox_messages_processed_at ON inbox_messages (processed_at);
@@ -0,0 +1,15 @@
+-- Change log of catalog items, appended in the transaction making the change and streamed by
+-- `GET /catalog/items/changes`. change_type: 'created' | 'updated' | 'deleted'.
+-- tx_id is the id of the writing transaction. Readers take entries in (tx_id, seq) order, and only
+-- those of transactions older than every running one, so no entry can later appear before a
+-- position already read (seq alone is assigned before commit and commits out of order).
+CREATE TABLE catalog_item_changes (
+    seq BIGSERIAL PRIMARY KEY,
+    tx_id BIGINT NOT NULL DEFAULT (pg_current_xact_id()::text::bigint),
+    item_id UUID NOT NULL,
+    change_type VARCHAR(16) NOT NULL,
+    changed_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX idx_catalog_item_changes_position ON catalog_item_changes (tx_id, seq);
+CREATE INDEX idx_catalog_item_changes_changed_at ON catalog_item_changes (changed_at);
@@ -0,0 +1,5 @@
+-- Incremental sync (`GET /catalog/items/sync`) reads items, and deleted item ids from the change
+-- log, in (timestamp, item_id) order after a watermark.
+CREATE INDEX idx_catalog_items_modified_at ON catalog_items (modified_at, item_id);
+CREATE INDEX idx_catalog_item_changes_deleted ON catalog_item_changes (changed_at, item_id)
+    WHERE change_type = 'deleted';
@@ -0,0 +1,38 @@
+-- Webhook subscriptions and the log of their deliveries. A delivery is queued for every enabled
+-- subscription to the event's type, in the transaction recording the event, and POSTed by the
+-- delivery worker. status: 'pending' | 'succeeded' | 'failed'.
+CREATE TABLE webhook_subscriptions (
+    subscription_id UUID PRIMARY KEY,
+    url VARCHAR(2048) NOT NULL,
+    event_types VARCHAR(64)[] NOT NULL,
+    secret VARCHAR(255) NOT NULL,
+    enabled BOOLEAN NOT NULL,
+    -- Failed attempts since the last successful one; the subscription is disabled at a limit
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL,
+    modified_at TIMESTAMP NOT NULL,
+    disabled_at TIMESTAMP
+);
+
+CREATE TABLE webhook_deliveries (
+    delivery_id UUID PRIMARY KEY,
+    subscription_id UUID NOT NULL REFERENCES webhook_subscriptions (subscription_id) ON DELETE CASCADE,
+    event_id UUID NOT NULL,
+    event_type VARCHAR(64) NOT NULL,
+    -- The event envelope, sent as the request body
+    payload JSONB NOT NULL,
+    status VARCHAR(16) NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    -- When a pending delivery is due; pushed back while an attempt is in flight
+    next_attempt_at TIMESTAMP,
+    last_attempt_at TIMESTAMP,
+    last_status_code INTEGER,
+    last_error TEXT,
+    created_at TIMESTAMP NOT NULL,
+    completed_at TIMESTAMP
+);
+
+CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries (next_attempt_at)
+    WHERE status = 'pending';
+CREATE INDEX idx_webhook_deliveries_subscription ON webhook_deliveries (subscription_id, created_at);
+CREATE INDEX idx_webhook_deliveries_completed_at ON webhook_deliveries (completed_at);
@@ -0,0 +1,17 @@
+-- Media positions are unique within an item. Uploads lock the item row before appending; the
+-- constraint is deferred so a reorder may swap positions within its transaction.
+UPDATE catalog_item_media m
+SET position = ranked.position
+FROM (
+    SELECT
+        media_id,
+        ROW_NUMBER() OVER (PARTITION BY item_id ORDER BY position, created_at, media_id) - 1
+            AS position
+    FROM catalog_item_media
+) ranked
+WHERE m.media_id = ranked.media_id AND m.position <> ranked.position;
+
+ALTER TABLE catalog_item_media
+    ADD CONSTRAINT catalog_item_media_item_id_position_key UNIQUE (item_id, position)
+    DEFERRABLE INITIALLY DEFERRED;
+DROP INDEX idx_catalog_item_media_item_id;
@@ -0,0 +1,81 @@
+use std::error::Error as StdError;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::auth::Role;
+use crate::common::validation::ValidationErrors;
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Errors that can occur when using [crate::api_keys::service::ApiKeysService].
+#[derive(Error, Debug)]
+pub enum ApiKeysServiceError {
+    #[error("validation error: {0}")]
+    ValidationError(#[source] ValidationErrors),
+
+    /// The presented key is unknown, revoked or expired.
+    #[error("invalid API key: {0}")]
+    InvalidKey(&'static str),
+
+    #[error("internal error: {0}")]
+    InternalError(#[source] BoxError),
+}
+
+/// API key metadata. The key itself is only returned once, when it is minted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKey {
+    pub key_id: Uuid,
+    pub name: String,
+    /// First characters of the key, to recognise it (e.g. `ck_1a2b3c4d`).
+    pub key_prefix: String,
+    /// Roles granted to requests authenticated with this key.
+    pub scopes: Vec<Role>,
+    pub created_at: DateTime<Utc>,
+    /// The key is rejected from this instant on; never expires when absent.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Last successful use, tracked with one-minute granularity.
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Body for minting an API key.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiKeyBody {
+    /// Non-blank, at most 255 characters (e.g. the partner's name).
+    pub name: String,
+    /// Roles granted to the key; at least one.
+    pub scopes: Vec<Role>,
+    /// Optional expiry; must be in the future.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// A freshly minted key: store `key` now, it cannot be retrieved again.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedApiKey {
+    /// Value to send in the `X-Api-Key` header.
+    pub key: String,
+    pub api_key: ApiKey,
+}
+
+/// Query parameters for the list API keys endpoint.
+#[derive(Debug, Default, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(rename_all = "camelCase")]
+pub struct ListApiKeysRequest {
+    /// Also return revoked keys. Defaults to false.
+    pub include_revoked: Option<bool>,
+}
+
+/// Response for the list API keys endpoint (oldest first).
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListApiKeysResponse {
+    pub api_keys: Vec<ApiKey>,
+}
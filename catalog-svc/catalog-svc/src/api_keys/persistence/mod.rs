@@ -0,0 +1,179 @@
+//! SQL repository for [ApiKey]s. Keys are looked up by the SHA-256 hash of their value.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{Executor, FromRow, Postgres};
+use uuid::Uuid;
+
+use crate::api_keys::api::ApiKey;
+use crate::auth::Role;
+use crate::catalog::persistence::RepositoryError;
+
+/// Row type for mapping SELECT results from `api_keys` into [ApiKey].
+#[derive(FromRow)]
+struct ApiKeyRow {
+    key_id: Uuid,
+    name: String,
+    key_prefix: String,
+    scopes: Vec<String>,
+    created_at: NaiveDateTime,
+    expires_at: Option<NaiveDateTime>,
+    last_used_at: Option<NaiveDateTime>,
+    revoked_at: Option<NaiveDateTime>,
+}
+
+impl ApiKeyRow {
+    fn into_api_key(self) -> Result<ApiKey, RepositoryError> {
+        let scopes = self
+            .scopes
+            .into_iter()
+            .map(|scope| {
+                scope
+                    .parse::<Role>()
+                    .map_err(|_| RepositoryError::InvalidRole(scope.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let utc = |ts: NaiveDateTime| DateTime::<Utc>::from_naive_utc_and_offset(ts, Utc);
+        Ok(ApiKey {
+            key_id: self.key_id,
+            name: self.name,
+            key_prefix: self.key_prefix,
+            scopes,
+            created_at: utc(self.created_at),
+            expires_at: self.expires_at.map(utc),
+            last_used_at: self.last_used_at.map(utc),
+            revoked_at: self.revoked_at.map(utc),
+        })
+    }
+}
+
+/// PostgreSQL API key persistence. Each method runs on the given [Executor] (`&PgPool`, `&mut Transaction`, …).
+pub struct ApiKeyRepository;
+
+impl ApiKeyRepository {
+    pub async fn create(
+        executor: impl Executor<'_, Database = Postgres>,
+        api_key: &ApiKey,
+        key_hash: &str,
+    ) -> Result<(), RepositoryError> {
+        let scopes: Vec<String> = api_key.scopes.iter().map(Role::to_string).collect();
+        sqlx::query(
+            r#"
+            INSERT INTO api_keys (
+                key_id,
+                name,
+                key_prefix,
+                key_hash,
+                scopes,
+                created_at,
+                expires_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(api_key.key_id)
+        .bind(&api_key.name)
+        .bind(&api_key.key_prefix)
+        .bind(key_hash)
+        .bind(scopes)
+        .bind(api_key.created_at.naive_utc())
+        .bind(api_key.expires_at.map(|ts| ts.naive_utc()))
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_by_hash(
+        executor: impl Executor<'_, Database = Postgres>,
+        key_hash: &str,
+    ) -> Result<Option<ApiKey>, RepositoryError> {
+        let row = sqlx::query_as::<_, ApiKeyRow>(
+            r#"
+            SELECT
+                key_id,
+                name,
+                key_prefix,
+                scopes,
+                created_at,
+                expires_at,
+                last_used_at,
+                revoked_at
+            FROM api_keys
+            WHERE key_hash = $1
+            "#,
+        )
+        .bind(key_hash)
+        .fetch_optional(executor)
+        .await?;
+
+        row.map(ApiKeyRow::into_api_key).transpose()
+    }
+
+    /// All keys, oldest first; revoked ones only when `include_revoked`.
+    pub async fn list(
+        executor: impl Executor<'_, Database = Postgres>,
+        include_revoked: bool,
+    ) -> Result<Vec<ApiKey>, RepositoryError> {
+        let rows = sqlx::query_as::<_, ApiKeyRow>(
+            r#"
+            SELECT
+                key_id,
+                name,
+                key_prefix,
+                scopes,
+                created_at,
+                expires_at,
+                last_used_at,
+                revoked_at
+            FROM api_keys
+            WHERE $1 OR revoked_at IS NULL
+            ORDER BY created_at, key_id
+            "#,
+        )
+        .bind(include_revoked)
+        .fetch_all(executor)
+        .await?;
+
+        rows.into_iter().map(ApiKeyRow::into_api_key).collect()
+    }
+
+    /// Mark the key revoked (keeping the original revocation time). Returns false if it does not exist.
+    pub async fn revoke(
+        executor: impl Executor<'_, Database = Postgres>,
+        key_id: Uuid,
+        revoked_at: DateTime<Utc>,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET revoked_at = COALESCE(revoked_at, $2)
+            WHERE key_id = $1
+            "#,
+        )
+        .bind(key_id)
+        .bind(revoked_at.naive_utc())
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record a use of the key, skipping the write if it was already recorded within the last minute.
+    pub async fn touch_last_used(
+        executor: impl Executor<'_, Database = Postgres>,
+        key_id: Uuid,
+        used_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET last_used_at = $2
+            WHERE key_id = $1
+              AND (last_used_at IS NULL OR last_used_at < $2 - INTERVAL '1 minute')
+            "#,
+        )
+        .bind(key_id)
+        .bind(used_at.naive_utc())
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+}
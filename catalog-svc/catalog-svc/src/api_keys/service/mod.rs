@@ -0,0 +1,131 @@
+use chrono::Utc;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::api_keys::api::{
+    ApiKey, ApiKeysServiceError, CreateApiKeyBody, CreatedApiKey, ListApiKeysRequest,
+    ListApiKeysResponse,
+};
+use crate::api_keys::persistence::ApiKeyRepository;
+use crate::auth::Principal;
+use crate::catalog::persistence::RepositoryError;
+use crate::common::validation::ValidationErrors;
+
+/// Prefix of every minted key, so leaked keys are easy to spot (e.g. by secret scanners).
+const KEY_PREFIX: &str = "ck_";
+/// Characters of the key kept in clear text for listings: [KEY_PREFIX] plus 8 hex digits.
+const DISPLAY_PREFIX_LEN: usize = KEY_PREFIX.len() + 8;
+
+impl From<RepositoryError> for ApiKeysServiceError {
+    fn from(err: RepositoryError) -> Self {
+        ApiKeysServiceError::InternalError(Box::new(err))
+    }
+}
+
+/// API keys for machine clients, using [ApiKeyRepository] against [PgPool].
+///
+/// Keys are 256 random bits; only their SHA-256 hash is stored, which is enough for
+/// high-entropy secrets and keeps lookups a single indexed query.
+#[derive(Clone)]
+pub struct ApiKeysService {
+    pg_pool: PgPool,
+}
+
+impl ApiKeysService {
+    pub fn new(pg_pool: PgPool) -> Self {
+        Self { pg_pool }
+    }
+
+    /// Mint a new key. The returned [CreatedApiKey::key] is not stored and cannot be shown again.
+    pub async fn create(
+        &self,
+        body: CreateApiKeyBody,
+    ) -> Result<CreatedApiKey, ApiKeysServiceError> {
+        let now = Utc::now();
+        let mut errors = ValidationErrors::new();
+        if body.name.trim().is_empty() {
+            errors.add("name", "must not be empty");
+        } else if body.name.chars().count() > 255 {
+            errors.add("name", "must be at most 255 characters");
+        }
+        if body.scopes.is_empty() {
+            errors.add("scopes", "must grant at least one role");
+        }
+        if body.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            errors.add("expiresAt", "must be in the future");
+        }
+        errors
+            .into_result(())
+            .map_err(ApiKeysServiceError::ValidationError)?;
+
+        let mut secret = [0u8; 32];
+        rand::rng().fill_bytes(&mut secret);
+        let key = format!("{KEY_PREFIX}{}", hex::encode(secret));
+
+        let mut scopes = body.scopes;
+        scopes.sort();
+        scopes.dedup();
+        let api_key = ApiKey {
+            key_id: Uuid::new_v4(),
+            name: body.name,
+            key_prefix: key.chars().take(DISPLAY_PREFIX_LEN).collect(),
+            scopes,
+            created_at: now,
+            expires_at: body.expires_at,
+            last_used_at: None,
+            revoked_at: None,
+        };
+        ApiKeyRepository::create(&self.pg_pool, &api_key, &hash_key(&key)).await?;
+        Ok(CreatedApiKey { key, api_key })
+    }
+
+    /// List keys, oldest first.
+    pub async fn list(
+        &self,
+        req: ListApiKeysRequest,
+    ) -> Result<ListApiKeysResponse, ApiKeysServiceError> {
+        let api_keys =
+            ApiKeyRepository::list(&self.pg_pool, req.include_revoked.unwrap_or(false)).await?;
+        Ok(ListApiKeysResponse { api_keys })
+    }
+
+    /// Revoke a key; it is rejected from now on. Returns false if it does not exist.
+    pub async fn revoke(&self, key_id: Uuid) -> Result<bool, ApiKeysServiceError> {
+        Ok(ApiKeyRepository::revoke(&self.pg_pool, key_id, Utc::now()).await?)
+    }
+
+    /// Resolve the caller from an `X-Api-Key` header value, recording the key's last use.
+    /// The principal's roles are the key's scopes.
+    pub async fn authenticate(&self, key: &str) -> Result<Principal, ApiKeysServiceError> {
+        let api_key = ApiKeyRepository::get_by_hash(&self.pg_pool, &hash_key(key))
+            .await?
+            .ok_or(ApiKeysServiceError::InvalidKey("unknown key"))?;
+        let now = Utc::now();
+        if api_key.revoked_at.is_some() {
+            return Err(ApiKeysServiceError::InvalidKey("key revoked"));
+        }
+        if api_key
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= now)
+        {
+            return Err(ApiKeysServiceError::InvalidKey("key expired"));
+        }
+
+        // Usage tracking is best effort and must not fail the request
+        if let Err(err) =
+            ApiKeyRepository::touch_last_used(&self.pg_pool, api_key.key_id, now).await
+        {
+            tracing::warn!("failed to record use of API key {}: {err}", api_key.key_id);
+        }
+        Ok(Principal {
+            subject: format!("api-key:{}", api_key.key_id),
+            roles: api_key.scopes,
+        })
+    }
+}
+
+fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
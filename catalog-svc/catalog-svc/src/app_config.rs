@@ -1,7 +1,10 @@
 use std::env;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use config::{Config, Environment, File};
+use rust_demo_commons::util::server::RateLimit;
+use rust_demo_commons::util::tracing::{LogFormat, OtlpSettings, TracingSettings};
 use serde::Deserialize;
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use tracing::info;
@@ -10,13 +13,104 @@ use tracing::info;
 pub struct HttpServerSettings {
     pub host: String,
     pub port: u16,
+    /// Seconds the servers keep serving after shutdown starts, the axum server reporting
+    /// not-ready on `/health/ready` meanwhile (default: 0).
+    #[serde(default)]
+    pub shutdown_delay_secs: u64,
+}
+
+/// gRPC API (`catalog.v1.CatalogService` with health checking and reflection), served on its own
+/// port on `server.host`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GrpcConfig {
+    /// Serve the gRPC API (default: true).
+    #[serde(default = "defaults::grpc_enabled")]
+    pub enabled: bool,
+    /// Port of the gRPC server (default: 50051).
+    #[serde(default = "defaults::grpc_port")]
+    pub port: u16,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: defaults::grpc_enabled(),
+            port: defaults::grpc_port(),
+        }
+    }
+}
+
+/// Smithy front-end (`catalog-svc-smithy-server`) when run next to the axum server by
+/// `catalog-launcher`, on `server.host`. Run on its own, it serves on `server.port`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SmithyConfig {
+    /// Port of the Smithy server (default: 3031).
+    #[serde(default = "defaults::smithy_port")]
+    pub port: u16,
+}
+
+impl Default for SmithyConfig {
+    fn default() -> Self {
+        Self {
+            port: defaults::smithy_port(),
+        }
+    }
+}
+
+/// Limits on GraphQL queries served at `/graphql`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphqlConfig {
+    /// Maximum nesting depth of a query (default: 8).
+    #[serde(default = "defaults::graphql_max_depth")]
+    pub max_depth: usize,
+    /// Maximum complexity of a query, one per selected field and the page size for each list
+    /// of items (default: 1000).
+    #[serde(default = "defaults::graphql_max_complexity")]
+    pub max_complexity: usize,
+}
+
+impl Default for GraphqlConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: defaults::graphql_max_depth(),
+            max_complexity: defaults::graphql_max_complexity(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct AppConfig {
     pub server: HttpServerSettings,
+    #[serde(default)]
+    pub grpc: GrpcConfig,
+    #[serde(default)]
+    pub smithy: SmithyConfig,
+    #[serde(default)]
+    pub graphql: GraphqlConfig,
     // TODO: secret string
     pub postgres: PostgresConfig,
+    #[serde(default)]
+    pub media: MediaConfig,
+    #[serde(default)]
+    pub auth: AuthConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    #[serde(default)]
+    pub item_cache: ItemCacheConfig,
+    #[serde(default)]
+    pub events: EventsConfig,
+    #[serde(default)]
+    pub supplier_feed: SupplierFeedConfig,
+    #[serde(default)]
+    pub item_changes: ItemChangesConfig,
+    #[serde(default)]
+    pub item_sync: ItemSyncConfig,
+    #[serde(default)]
+    pub webhooks: WebhooksConfig,
 }
 
 impl AppConfig {
@@ -30,13 +124,23 @@ impl AppConfig {
             .add_source(File::with_name(&config_path).required(false))
             .add_source(Environment::with_prefix("APP").separator("__"));
         let config = builder.build()?;
-        config.try_deserialize::<AppConfig>()
+        let app_config = config.try_deserialize::<AppConfig>()?;
+        app_config.rate_limit.validate()?;
+        Ok(app_config)
     }
 
     #[cfg(feature = "test-utils")]
     pub fn load_tests() -> Self {
         Self::load().expect("failed to load config")
     }
+
+    /// Settings for [rust_demo_commons::util::tracing::init_tracing_with].
+    pub fn tracing_settings(&self) -> TracingSettings {
+        TracingSettings {
+            log_format: self.logging.format,
+            otlp: self.telemetry.otlp(),
+        }
+    }
 }
 
 /// PostgreSQL connection options for use with sqlx.
@@ -71,11 +175,538 @@ pub async fn create_pg_pool(cfg: &PostgresConfig) -> Result<sqlx::PgPool, sqlx::
         .await
 }
 
+/// Item media (image attachments) settings.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MediaConfig {
+    /// Maximum accepted upload size in bytes (default: 10 MiB).
+    #[serde(default = "defaults::max_upload_bytes")]
+    pub max_upload_bytes: usize,
+    /// Bounding box (width and height, in pixels) of generated thumbnails (default: 256).
+    #[serde(default = "defaults::thumbnail_size")]
+    pub thumbnail_size: u32,
+    #[serde(default)]
+    pub blob_store: BlobStoreConfig,
+}
+
+impl Default for MediaConfig {
+    fn default() -> Self {
+        Self {
+            max_upload_bytes: defaults::max_upload_bytes(),
+            thumbnail_size: defaults::thumbnail_size(),
+            blob_store: BlobStoreConfig::default(),
+        }
+    }
+}
+
+/// Backend for [crate::blob_store::BlobStore], selected by `kind` (`local` or `s3`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum BlobStoreConfig {
+    /// Blobs stored as files under `root`.
+    Local { root: PathBuf },
+    /// Blobs stored in an S3-compatible bucket (AWS S3, MinIO, ...).
+    S3(S3Config),
+}
+
+impl Default for BlobStoreConfig {
+    fn default() -> Self {
+        BlobStoreConfig::Local {
+            root: PathBuf::from("data/media"),
+        }
+    }
+}
+
+/// Connection settings for an S3-compatible object store.
+#[derive(Debug, Clone, Deserialize)]
+pub struct S3Config {
+    pub bucket: String,
+    pub region: String,
+    /// Custom endpoint (e.g. `http://localhost:9000` for MinIO). Uses AWS when unset.
+    pub endpoint: Option<String>,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Address buckets as `endpoint/bucket` rather than `bucket.endpoint` (default: true).
+    #[serde(default = "defaults::force_path_style")]
+    pub force_path_style: bool,
+}
+
+/// Bearer token (JWT) authentication of API requests.
+/// HS256 tokens are checked against `hs256_secret`, RS256 tokens against the keys of a JWKS.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuthConfig {
+    /// Require a valid token on API routes (default: true); startup fails unless `hs256_secret`
+    /// or a JWKS is configured. When false, every request acts as an admin: only for tests and
+    /// local development.
+    #[serde(default = "defaults::auth_enabled")]
+    pub enabled: bool,
+    /// Shared secret for HS256-signed tokens. Startup fails on placeholders containing `change-me`.
+    pub hs256_secret: Option<String>,
+    /// JWKS file with the public keys for RS256-signed tokens.
+    pub jwks_file: Option<PathBuf>,
+    /// JWKS URL with the public keys for RS256-signed tokens (re-fetched when a token uses an unknown key id).
+    pub jwks_url: Option<String>,
+    /// Required `iss` claim, if set.
+    pub issuer: Option<String>,
+    /// Required `aud` claim, if set.
+    pub audience: Option<String>,
+    /// Claim holding the list of role names (default: `roles`).
+    #[serde(default = "defaults::roles_claim")]
+    pub roles_claim: String,
+    /// Allowed clock skew when checking `exp` / `nbf`, in seconds (default: 30).
+    #[serde(default = "defaults::leeway_secs")]
+    pub leeway_secs: u64,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: defaults::auth_enabled(),
+            hs256_secret: None,
+            jwks_file: None,
+            jwks_url: None,
+            issuer: None,
+            audience: None,
+            roles_claim: defaults::roles_claim(),
+            leeway_secs: defaults::leeway_secs(),
+        }
+    }
+}
+
+/// Per-client token-bucket rate limiting of API routes, keyed by the authenticated principal
+/// (token subject or API key) or, when authentication is disabled, the client IP. A looser limit
+/// per client IP applies before authentication, so guessing credentials is throttled too.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitConfig {
+    /// Rate limit API requests (default: true).
+    #[serde(default = "defaults::rate_limit_enabled")]
+    pub enabled: bool,
+    /// Sustained requests per second per client (default: 50).
+    #[serde(default = "defaults::requests_per_second")]
+    pub requests_per_second: f64,
+    /// Requests a client may send at once after being idle (default: 100).
+    #[serde(default = "defaults::burst")]
+    pub burst: u32,
+    /// Sustained requests per second per client IP, authenticated or not (default: 100).
+    #[serde(default = "defaults::ip_requests_per_second")]
+    pub ip_requests_per_second: f64,
+    /// Requests a client IP may send at once after being idle (default: 200).
+    #[serde(default = "defaults::ip_burst")]
+    pub ip_burst: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: defaults::rate_limit_enabled(),
+            requests_per_second: defaults::requests_per_second(),
+            burst: defaults::burst(),
+            ip_requests_per_second: defaults::ip_requests_per_second(),
+            ip_burst: defaults::ip_burst(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Reject rates that are not positive numbers (buckets would never refill) and bursts below
+    /// one request, when rate limiting is enabled.
+    pub fn validate(&self) -> Result<(), config::ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        for (name, rate) in [
+            ("requests_per_second", self.requests_per_second),
+            ("ip_requests_per_second", self.ip_requests_per_second),
+        ] {
+            if !(rate.is_finite() && rate > 0.0) {
+                return Err(config::ConfigError::Message(format!(
+                    "rate_limit.{name} must be a positive number, got {rate}"
+                )));
+            }
+        }
+        for (name, burst) in [("burst", self.burst), ("ip_burst", self.ip_burst)] {
+            if burst < 1 {
+                return Err(config::ConfigError::Message(format!(
+                    "rate_limit.{name} must be at least 1"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The limit to apply, or None when rate limiting is disabled.
+    pub fn limit(&self) -> Option<RateLimit> {
+        self.enabled.then_some(RateLimit {
+            requests_per_second: self.requests_per_second,
+            burst: self.burst,
+        })
+    }
+
+    /// The per-IP limit applied before authentication, or None when rate limiting is disabled.
+    pub fn ip_limit(&self) -> Option<RateLimit> {
+        self.enabled.then_some(RateLimit {
+            requests_per_second: self.ip_requests_per_second,
+            burst: self.ip_burst,
+        })
+    }
+}
+
+/// In-process cache of catalog items read by id.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemCacheConfig {
+    /// Cache item reads (default: true).
+    #[serde(default = "defaults::item_cache_enabled")]
+    pub enabled: bool,
+    /// Most items kept; least recently used ones are evicted (default: 10000).
+    #[serde(default = "defaults::item_cache_capacity")]
+    pub capacity: usize,
+    /// Seconds an item is served from the cache before being reloaded (default: 60).
+    #[serde(default = "defaults::item_cache_ttl_secs")]
+    pub ttl_secs: u64,
+    /// Exchange invalidations with other instances over Postgres `LISTEN/NOTIFY` (default: false).
+    #[serde(default)]
+    pub notify: bool,
+}
+
+impl Default for ItemCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: defaults::item_cache_enabled(),
+            capacity: defaults::item_cache_capacity(),
+            ttl_secs: defaults::item_cache_ttl_secs(),
+            notify: false,
+        }
+    }
+}
+
+/// Publication of catalog change events from the transactional outbox.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventsConfig {
+    /// Kafka bootstrap servers (comma-separated `host:port`). Events are neither recorded in the
+    /// outbox nor relayed when unset.
+    pub brokers: Option<String>,
+    /// Topic the events are published to (default: `catalog.items`).
+    #[serde(default = "defaults::events_topic")]
+    pub topic: String,
+    /// Tenant stamped on every event envelope (default: `default`).
+    #[serde(default = "defaults::events_tenant")]
+    pub tenant: String,
+    /// Milliseconds between outbox polls when idle (default: 1000).
+    #[serde(default = "defaults::events_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Most events published per relay transaction (default: 100).
+    #[serde(default = "defaults::events_batch_size")]
+    pub batch_size: u32,
+    /// Seconds published events are kept in the outbox (default: 7 days).
+    #[serde(default = "defaults::events_retention_secs")]
+    pub retention_secs: u64,
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            brokers: None,
+            topic: defaults::events_topic(),
+            tenant: defaults::events_tenant(),
+            poll_interval_ms: defaults::events_poll_interval_ms(),
+            batch_size: defaults::events_batch_size(),
+            retention_secs: defaults::events_retention_secs(),
+        }
+    }
+}
+
+/// Consumption of supplier price and stock updates from Kafka.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SupplierFeedConfig {
+    /// Kafka bootstrap servers (comma-separated `host:port`). The feed is not consumed when unset.
+    pub brokers: Option<String>,
+    /// Topic of the supplier updates (default: `supplier.prices`).
+    #[serde(default = "defaults::supplier_feed_topic")]
+    pub topic: String,
+    /// Topic receiving messages that cannot be applied (default: `supplier.prices.dlt`).
+    #[serde(default = "defaults::supplier_feed_dead_letter_topic")]
+    pub dead_letter_topic: String,
+    /// Consumer group (default: `catalog-svc-supplier-feed`).
+    #[serde(default = "defaults::supplier_feed_group_id")]
+    pub group_id: String,
+    /// Seconds processed message ids are remembered for deduplication (default: 7 days).
+    #[serde(default = "defaults::supplier_feed_dedup_retention_secs")]
+    pub dedup_retention_secs: u64,
+}
+
+impl Default for SupplierFeedConfig {
+    fn default() -> Self {
+        Self {
+            brokers: None,
+            topic: defaults::supplier_feed_topic(),
+            dead_letter_topic: defaults::supplier_feed_dead_letter_topic(),
+            group_id: defaults::supplier_feed_group_id(),
+            dedup_retention_secs: defaults::supplier_feed_dedup_retention_secs(),
+        }
+    }
+}
+
+/// The item change log streamed by `GET /catalog/items/changes`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemChangesConfig {
+    /// Milliseconds between change log polls of an idle stream; notifications wake streams
+    /// sooner (default: 1000).
+    #[serde(default = "defaults::item_changes_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Most changes read from the log at once (default: 100).
+    #[serde(default = "defaults::item_changes_batch_size")]
+    pub batch_size: u32,
+    /// Seconds changes are kept for resuming streams (default: 7 days).
+    #[serde(default = "defaults::item_changes_retention_secs")]
+    pub retention_secs: u64,
+}
+
+impl Default for ItemChangesConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_ms: defaults::item_changes_poll_interval_ms(),
+            batch_size: defaults::item_changes_batch_size(),
+            retention_secs: defaults::item_changes_retention_secs(),
+        }
+    }
+}
+
+/// Incremental sync (`GET /catalog/items/sync`). Deleted item ids are read from the item change
+/// log, so watermarks older than `item_changes.retention_secs` are rejected.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemSyncConfig {
+    /// Milliseconds a change is held back before it is returned, covering clock differences
+    /// between service instances and the database (default: 5000).
+    #[serde(default = "defaults::item_sync_max_clock_skew_ms")]
+    pub max_clock_skew_ms: u64,
+}
+
+impl Default for ItemSyncConfig {
+    fn default() -> Self {
+        Self {
+            max_clock_skew_ms: defaults::item_sync_max_clock_skew_ms(),
+        }
+    }
+}
+
+/// Delivery of catalog events to webhook subscriptions.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhooksConfig {
+    /// Milliseconds between polls for due deliveries when idle (default: 1000).
+    #[serde(default = "defaults::webhooks_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Most deliveries sent at once (default: 20).
+    #[serde(default = "defaults::webhooks_batch_size")]
+    pub batch_size: u32,
+    /// Milliseconds to wait for a receiver's response (default: 10000).
+    #[serde(default = "defaults::webhooks_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+    /// Attempts per delivery before it is given up (default: 8).
+    #[serde(default = "defaults::webhooks_max_attempts")]
+    pub max_attempts: u32,
+    /// Milliseconds before the first retry; doubles with every further attempt (default: 30000).
+    #[serde(default = "defaults::webhooks_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// Longest wait between attempts, in seconds (default: 3600).
+    #[serde(default = "defaults::webhooks_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+    /// Failed attempts in a row after which a subscription is disabled (default: 20).
+    #[serde(default = "defaults::webhooks_disable_after_failures")]
+    pub disable_after_failures: u32,
+    /// Seconds completed deliveries are kept in the delivery log (default: 7 days).
+    #[serde(default = "defaults::webhooks_retention_secs")]
+    pub retention_secs: u64,
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_ms: defaults::webhooks_poll_interval_ms(),
+            batch_size: defaults::webhooks_batch_size(),
+            request_timeout_ms: defaults::webhooks_request_timeout_ms(),
+            max_attempts: defaults::webhooks_max_attempts(),
+            retry_backoff_ms: defaults::webhooks_retry_backoff_ms(),
+            max_backoff_secs: defaults::webhooks_max_backoff_secs(),
+            disable_after_failures: defaults::webhooks_disable_after_failures(),
+            retention_secs: defaults::webhooks_retention_secs(),
+        }
+    }
+}
+
+/// Log output.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LoggingConfig {
+    /// `compact` (human-readable, default) or `json` (one object per line, with the request id of
+    /// the enclosing request).
+    #[serde(default)]
+    pub format: LogFormat,
+}
+
+/// OpenTelemetry trace export. Spans are exported over OTLP/HTTP only when `otlp_endpoint` is set;
+/// W3C `traceparent` headers are propagated either way.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelemetryConfig {
+    /// OTLP/HTTP traces endpoint, e.g. `http://localhost:4318/v1/traces` (default: no export).
+    pub otlp_endpoint: Option<String>,
+    /// `service.name` of the exported spans (default: catalog-svc).
+    #[serde(default = "defaults::service_name")]
+    pub service_name: String,
+    /// Fraction of new traces to sample, 0.0 to 1.0 (default: 1.0).
+    #[serde(default = "defaults::sampling_ratio")]
+    pub sampling_ratio: f64,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            otlp_endpoint: None,
+            service_name: defaults::service_name(),
+            sampling_ratio: defaults::sampling_ratio(),
+        }
+    }
+}
+
+impl TelemetryConfig {
+    /// The exporter settings, or None when no endpoint is configured.
+    pub fn otlp(&self) -> Option<OtlpSettings> {
+        self.otlp_endpoint.as_ref().map(|endpoint| OtlpSettings {
+            endpoint: endpoint.clone(),
+            service_name: self.service_name.clone(),
+            sampling_ratio: self.sampling_ratio,
+        })
+    }
+}
+
 mod defaults {
+    pub(super) fn grpc_enabled() -> bool {
+        true
+    }
+    pub(super) fn grpc_port() -> u16 {
+        50051
+    }
+    pub(super) fn smithy_port() -> u16 {
+        3031
+    }
+    pub(super) fn graphql_max_depth() -> usize {
+        8
+    }
+    pub(super) fn graphql_max_complexity() -> usize {
+        1000
+    }
     pub(super) fn max_connections() -> u32 {
         10
     }
     pub(super) fn connect_timeout_secs() -> u64 {
         5
     }
+    pub(super) fn max_upload_bytes() -> usize {
+        10 * 1024 * 1024
+    }
+    pub(super) fn thumbnail_size() -> u32 {
+        256
+    }
+    pub(super) fn force_path_style() -> bool {
+        true
+    }
+    pub(super) fn auth_enabled() -> bool {
+        true
+    }
+    pub(super) fn roles_claim() -> String {
+        "roles".to_string()
+    }
+    pub(super) fn leeway_secs() -> u64 {
+        30
+    }
+    pub(super) fn rate_limit_enabled() -> bool {
+        true
+    }
+    pub(super) fn requests_per_second() -> f64 {
+        50.0
+    }
+    pub(super) fn burst() -> u32 {
+        100
+    }
+    pub(super) fn ip_requests_per_second() -> f64 {
+        100.0
+    }
+    pub(super) fn ip_burst() -> u32 {
+        200
+    }
+    pub(super) fn item_cache_enabled() -> bool {
+        true
+    }
+    pub(super) fn item_cache_capacity() -> usize {
+        10_000
+    }
+    pub(super) fn item_cache_ttl_secs() -> u64 {
+        60
+    }
+    pub(super) fn events_topic() -> String {
+        "catalog.items".to_string()
+    }
+    pub(super) fn events_tenant() -> String {
+        "default".to_string()
+    }
+    pub(super) fn events_poll_interval_ms() -> u64 {
+        1000
+    }
+    pub(super) fn events_batch_size() -> u32 {
+        100
+    }
+    pub(super) fn events_retention_secs() -> u64 {
+        7 * 24 * 3600
+    }
+    pub(super) fn supplier_feed_topic() -> String {
+        "supplier.prices".to_string()
+    }
+    pub(super) fn supplier_feed_dead_letter_topic() -> String {
+        "supplier.prices.dlt".to_string()
+    }
+    pub(super) fn supplier_feed_group_id() -> String {
+        "catalog-svc-supplier-feed".to_string()
+    }
+    pub(super) fn supplier_feed_dedup_retention_secs() -> u64 {
+        7 * 24 * 3600
+    }
+    pub(super) fn item_changes_poll_interval_ms() -> u64 {
+        1000
+    }
+    pub(super) fn item_changes_batch_size() -> u32 {
+        100
+    }
+    pub(super) fn item_changes_retention_secs() -> u64 {
+        7 * 24 * 3600
+    }
+    pub(super) fn item_sync_max_clock_skew_ms() -> u64 {
+        5000
+    }
+    pub(super) fn webhooks_poll_interval_ms() -> u64 {
+        1000
+    }
+    pub(super) fn webhooks_batch_size() -> u32 {
+        20
+    }
+    pub(super) fn webhooks_request_timeout_ms() -> u64 {
+        10_000
+    }
+    pub(super) fn webhooks_max_attempts() -> u32 {
+        8
+    }
+    pub(super) fn webhooks_retry_backoff_ms() -> u64 {
+        30_000
+    }
+    pub(super) fn webhooks_max_backoff_secs() -> u64 {
+        3600
+    }
+    pub(super) fn webhooks_disable_after_failures() -> u32 {
+        20
+    }
+    pub(super) fn webhooks_retention_secs() -> u64 {
+        7 * 24 * 3600
+    }
+    pub(super) fn service_name() -> String {
+        "catalog-svc".to_string()
+    }
+    pub(super) fn sampling_ratio() -> f64 {
+        1.0
+    }
 }
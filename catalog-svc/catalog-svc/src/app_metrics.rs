@@ -0,0 +1,110 @@
+//! Application metrics: catalog domain counters and database gauges, exposed with the HTTP
+//! metrics of [rust_demo_commons::util::metrics] on `/metrics`.
+
+use sqlx::PgPool;
+
+/// Counter of created catalog items.
+pub const ITEMS_CREATED_TOTAL: &str = "catalog_items_created_total";
+/// Counter of updated catalog items (excluding repricing).
+pub const ITEMS_UPDATED_TOTAL: &str = "catalog_items_updated_total";
+/// Counter of deleted catalog items.
+pub const ITEMS_DELETED_TOTAL: &str = "catalog_items_deleted_total";
+/// Counter of completed [crate::catalog::service::CatalogService::increase_prices] runs.
+pub const REPRICE_RUNS_TOTAL: &str = "catalog_reprice_runs_total";
+/// Counter of items repriced by those runs.
+pub const ITEMS_REPRICED_TOTAL: &str = "catalog_items_repriced_total";
+
+/// Counter of [crate::catalog::cache::ItemCache] lookups that found the item.
+pub const ITEM_CACHE_HITS_TOTAL: &str = "catalog_item_cache_hits_total";
+/// Counter of [crate::catalog::cache::ItemCache] lookups that fell through to the database.
+pub const ITEM_CACHE_MISSES_TOTAL: &str = "catalog_item_cache_misses_total";
+
+/// Counter of outbox events published by [crate::events::relay::OutboxRelay].
+pub const EVENTS_PUBLISHED_TOTAL: &str = "catalog_events_published_total";
+/// Counter of outbox event publish attempts that failed (retried later).
+pub const EVENT_PUBLISH_FAILURES_TOTAL: &str = "catalog_event_publish_failures_total";
+
+/// Counter of handled supplier feed messages, labelled `outcome` (`applied`, `duplicate` or
+/// `dead_lettered`).
+pub const SUPPLIER_FEED_MESSAGES_TOTAL: &str = "catalog_supplier_feed_messages_total";
+
+/// Counter of webhook delivery attempts, labelled `outcome` (`succeeded`, `retrying` or
+/// `failed`).
+pub const WEBHOOK_DELIVERIES_TOTAL: &str = "catalog_webhook_deliveries_total";
+/// Counter of webhook subscriptions disabled after failing repeatedly.
+pub const WEBHOOK_SUBSCRIPTIONS_DISABLED_TOTAL: &str =
+    "catalog_webhook_subscriptions_disabled_total";
+
+/// Gauge of open `/catalog/items/changes` streams.
+pub const ITEM_CHANGE_STREAMS: &str = "catalog_item_change_streams";
+
+/// Gauge of pool connections, labelled `state` (`idle` or `in_use`).
+pub const DB_POOL_CONNECTIONS: &str = "db_pool_connections";
+/// Gauge of the pool's `max_connections`.
+pub const DB_POOL_MAX_CONNECTIONS: &str = "db_pool_max_connections";
+/// Counter of connection acquires that timed out waiting for a free connection.
+pub const DB_POOL_ACQUIRE_TIMEOUTS_TOTAL: &str = "db_pool_acquire_timeouts_total";
+/// Gauge of the latest applied database migration version.
+pub const DB_MIGRATION_VERSION: &str = "db_migration_version";
+
+/// Register help texts; call once at startup, after the recorder is installed.
+pub fn describe() {
+    metrics::describe_counter!(ITEMS_CREATED_TOTAL, "Created catalog items");
+    metrics::describe_counter!(ITEMS_UPDATED_TOTAL, "Updated catalog items");
+    metrics::describe_counter!(ITEMS_DELETED_TOTAL, "Deleted catalog items");
+    metrics::describe_counter!(REPRICE_RUNS_TOTAL, "Completed repricing runs");
+    metrics::describe_counter!(ITEMS_REPRICED_TOTAL, "Items repriced by repricing runs");
+    metrics::describe_counter!(ITEM_CACHE_HITS_TOTAL, "Item cache hits");
+    metrics::describe_counter!(ITEM_CACHE_MISSES_TOTAL, "Item cache misses");
+    metrics::describe_counter!(
+        EVENTS_PUBLISHED_TOTAL,
+        "Catalog events published from the outbox"
+    );
+    metrics::describe_counter!(
+        EVENT_PUBLISH_FAILURES_TOTAL,
+        "Failed catalog event publish attempts (retried)"
+    );
+    metrics::describe_counter!(
+        SUPPLIER_FEED_MESSAGES_TOTAL,
+        "Handled supplier feed messages by outcome"
+    );
+    metrics::describe_counter!(
+        WEBHOOK_DELIVERIES_TOTAL,
+        "Webhook delivery attempts by outcome"
+    );
+    metrics::describe_counter!(
+        WEBHOOK_SUBSCRIPTIONS_DISABLED_TOTAL,
+        "Webhook subscriptions disabled after repeated failures"
+    );
+    metrics::describe_gauge!(ITEM_CHANGE_STREAMS, "Open item change streams");
+    metrics::describe_gauge!(DB_POOL_CONNECTIONS, "PostgreSQL pool connections by state");
+    metrics::describe_gauge!(DB_POOL_MAX_CONNECTIONS, "PostgreSQL pool size limit");
+    metrics::describe_counter!(
+        DB_POOL_ACQUIRE_TIMEOUTS_TOTAL,
+        "PostgreSQL connection acquires that timed out waiting for a free connection"
+    );
+    metrics::describe_gauge!(DB_MIGRATION_VERSION, "Latest applied database migration");
+}
+
+/// Refresh the pool gauges; called on every scrape.
+pub fn record_pool_stats(pool: &PgPool) {
+    let size = pool.size();
+    let idle = u32::try_from(pool.num_idle()).unwrap_or(u32::MAX);
+    metrics::gauge!(DB_POOL_CONNECTIONS, "state" => "idle").set(f64::from(idle));
+    metrics::gauge!(DB_POOL_CONNECTIONS, "state" => "in_use")
+        .set(f64::from(size.saturating_sub(idle)));
+    metrics::gauge!(DB_POOL_MAX_CONNECTIONS).set(f64::from(pool.options().get_max_connections()));
+}
+
+/// Count `err` if it is a pool acquire timeout, i.e. every connection was busy for the whole
+/// `connect_timeout_secs`.
+pub fn record_db_error(err: &sqlx::Error) {
+    if matches!(err, sqlx::Error::PoolTimedOut) {
+        metrics::counter!(DB_POOL_ACQUIRE_TIMEOUTS_TOTAL).increment(1);
+    }
+}
+
+/// Versions are timestamps (YYYYMMDDhhmmss), exactly representable as f64.
+pub fn record_migration_version(version: i64) {
+    metrics::gauge!(DB_MIGRATION_VERSION).set(version as f64);
+}
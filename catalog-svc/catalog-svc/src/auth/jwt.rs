@@ -0,0 +1,323 @@
+//! JWT verification: HS256 with a shared secret, RS256 with keys from a JWKS file or URL.
+
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rust_demo_commons::util::tracing::trace_context_headers;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::app_config::AuthConfig;
+use crate::auth::{AuthError, Principal, Role};
+
+/// HS256 secrets containing this are example values, refused so that no deployment runs with a
+/// secret published in the repository.
+const PLACEHOLDER_SECRET_MARKER: &str = "change-me";
+
+/// A JWKS URL is fetched again for an unknown key id at most this often, whether or not the
+/// previous attempt succeeded.
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+/// Limits on fetching a JWKS URL, so a slow identity provider cannot hold up authentication.
+const JWKS_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+const JWKS_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Errors building a [JwtVerifier] from [AuthConfig].
+#[derive(Error, Debug)]
+pub enum JwtSetupError {
+    #[error(
+        "authentication is enabled but neither auth.hs256_secret nor auth.jwks_file / auth.jwks_url \
+         is configured (set auth.enabled = false only for local development)"
+    )]
+    NoKeys,
+
+    #[error("auth.hs256_secret is a placeholder; configure a random secret")]
+    PlaceholderSecret,
+
+    #[error("failed to read JWKS file {path}: {source}")]
+    JwksFile {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to fetch JWKS: {0}")]
+    JwksFetch(#[from] reqwest::Error),
+
+    #[error("invalid JWKS: {0}")]
+    JwksParse(#[from] serde_json::Error),
+
+    #[error("unsupported key in JWKS: {0}")]
+    JwksKey(#[from] jsonwebtoken::errors::Error),
+}
+
+/// Registered claims we rely on, plus everything else (roles live under a configurable name).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    /// Expiry, seconds since the Unix epoch.
+    pub exp: u64,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+impl Claims {
+    /// Claims for `subject` with `roles` (under the default `roles` claim), valid for `ttl`.
+    pub fn new(subject: &str, roles: &[Role], ttl: Duration) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let mut extra = serde_json::Map::new();
+        extra.insert(
+            "roles".to_string(),
+            roles
+                .iter()
+                .map(|role| Value::from(role.to_string()))
+                .collect(),
+        );
+        Self {
+            sub: subject.to_string(),
+            exp: (now + ttl).as_secs(),
+            extra,
+        }
+    }
+}
+
+/// Sign `claims` (for local development and tests; production tokens come from the identity provider).
+pub fn encode_token(
+    header: &Header,
+    claims: &Claims,
+    key: &EncodingKey,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    jsonwebtoken::encode(header, claims, key)
+}
+
+/// Verifies bearer tokens against the keys configured in [AuthConfig].
+pub struct JwtVerifier {
+    hs256: Option<DecodingKey>,
+    jwks: Option<Jwks>,
+    issuer: Option<String>,
+    audience: Option<String>,
+    roles_claim: String,
+    leeway_secs: u64,
+}
+
+impl JwtVerifier {
+    pub async fn from_config(config: &AuthConfig) -> Result<Self, JwtSetupError> {
+        if config
+            .hs256_secret
+            .as_ref()
+            .is_some_and(|secret| secret.contains(PLACEHOLDER_SECRET_MARKER))
+        {
+            return Err(JwtSetupError::PlaceholderSecret);
+        }
+        let hs256 = config
+            .hs256_secret
+            .as_ref()
+            .map(|secret| DecodingKey::from_secret(secret.as_bytes()));
+        let jwks_source = match (&config.jwks_file, &config.jwks_url) {
+            (Some(path), _) => Some(JwksSource::File(path.clone())),
+            (None, Some(url)) => Some(JwksSource::Url(url.clone())),
+            (None, None) => None,
+        };
+        let jwks = match jwks_source {
+            Some(source) => Some(Jwks::load(source).await?),
+            None => None,
+        };
+        if hs256.is_none() && jwks.is_none() {
+            return Err(JwtSetupError::NoKeys);
+        }
+        Ok(Self {
+            hs256,
+            jwks,
+            issuer: config.issuer.clone(),
+            audience: config.audience.clone(),
+            roles_claim: config.roles_claim.clone(),
+            leeway_secs: config.leeway_secs,
+        })
+    }
+
+    /// Check signature, expiry and (if configured) issuer and audience; map the claims to a [Principal].
+    pub async fn verify(&self, token: &str) -> Result<Principal, AuthError> {
+        let header = jsonwebtoken::decode_header(token).map_err(invalid_token)?;
+        let key = match header.alg {
+            Algorithm::HS256 => self
+                .hs256
+                .clone()
+                .ok_or_else(|| AuthError::InvalidToken("HS256 tokens are not accepted".into()))?,
+            Algorithm::RS256 => match &self.jwks {
+                Some(jwks) => jwks.key(header.kid.as_deref()).await?,
+                None => {
+                    return Err(AuthError::InvalidToken(
+                        "RS256 tokens are not accepted".into(),
+                    ));
+                }
+            },
+            other => {
+                return Err(AuthError::InvalidToken(format!(
+                    "unsupported algorithm {other:?}"
+                )));
+            }
+        };
+
+        let mut validation = Validation::new(header.alg);
+        validation.leeway = self.leeway_secs;
+        validation.set_required_spec_claims(&["exp", "sub"]);
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+
+        let claims = jsonwebtoken::decode::<Claims>(token, &key, &validation)
+            .map_err(invalid_token)?
+            .claims;
+        Ok(Principal {
+            roles: roles_from_claim(claims.extra.get(&self.roles_claim)),
+            subject: claims.sub,
+        })
+    }
+}
+
+fn invalid_token(err: jsonwebtoken::errors::Error) -> AuthError {
+    AuthError::InvalidToken(err.to_string())
+}
+
+/// Roles from a JSON array of names or a space-separated string; unknown names are ignored.
+fn roles_from_claim(value: Option<&Value>) -> Vec<Role> {
+    let names: Vec<&str> = match value {
+        Some(Value::Array(values)) => values.iter().filter_map(Value::as_str).collect(),
+        Some(Value::String(names)) => names.split_whitespace().collect(),
+        _ => Vec::new(),
+    };
+    let mut roles: Vec<Role> = names.into_iter().filter_map(|n| n.parse().ok()).collect();
+    roles.sort_unstable();
+    roles.dedup();
+    roles
+}
+
+enum JwksSource {
+    File(PathBuf),
+    Url(String),
+}
+
+/// RS256 public keys, by key id.
+struct Jwks {
+    source: JwksSource,
+    http: reqwest::Client,
+    keys: RwLock<JwksKeys>,
+    /// Start of the last fetch attempt; held while refreshing, so concurrent requests with an
+    /// unknown key id wait for one fetch instead of each starting their own.
+    refreshed_at: tokio::sync::Mutex<Instant>,
+}
+
+struct JwksKeys {
+    keys: Vec<(Option<String>, DecodingKey)>,
+}
+
+impl Jwks {
+    async fn load(source: JwksSource) -> Result<Self, JwtSetupError> {
+        let http = reqwest::Client::builder()
+            .connect_timeout(JWKS_CONNECT_TIMEOUT)
+            .timeout(JWKS_REQUEST_TIMEOUT)
+            .build()?;
+        let refreshed_at = Instant::now();
+        let keys = fetch(&source, &http).await?;
+        Ok(Self {
+            source,
+            http,
+            keys: RwLock::new(keys),
+            refreshed_at: tokio::sync::Mutex::new(refreshed_at),
+        })
+    }
+
+    /// Key for `kid` (or the only key, for tokens without `kid`). Unknown key ids trigger
+    /// a single re-fetch of a JWKS URL, at most once per [JWKS_REFRESH_INTERVAL] (failed attempts
+    /// included), to pick up key rotation.
+    async fn key(&self, kid: Option<&str>) -> Result<DecodingKey, AuthError> {
+        if let Some(key) = self.read_keys().find(kid) {
+            return Ok(key);
+        }
+        if matches!(self.source, JwksSource::Url(_)) {
+            let mut refreshed_at = self.refreshed_at.lock().await;
+            // Another request may have refreshed the keys while this one waited
+            if let Some(key) = self.read_keys().find(kid) {
+                return Ok(key);
+            }
+            if refreshed_at.elapsed() >= JWKS_REFRESH_INTERVAL {
+                *refreshed_at = Instant::now();
+                match fetch(&self.source, &self.http).await {
+                    Ok(fresh) => {
+                        let key = fresh.find(kid);
+                        *self.keys.write().unwrap_or_else(|e| e.into_inner()) = fresh;
+                        if let Some(key) = key {
+                            return Ok(key);
+                        }
+                    }
+                    Err(err) => tracing::warn!(error = %err, "failed to refresh JWKS"),
+                }
+            }
+        }
+        Err(AuthError::InvalidToken(format!(
+            "unknown signing key {}",
+            kid.unwrap_or("(no kid)")
+        )))
+    }
+
+    fn read_keys(&self) -> std::sync::RwLockReadGuard<'_, JwksKeys> {
+        self.keys.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl JwksKeys {
+    fn find(&self, kid: Option<&str>) -> Option<DecodingKey> {
+        match kid {
+            Some(kid) => self
+                .keys
+                .iter()
+                .find(|(key_id, _)| key_id.as_deref() == Some(kid))
+                .map(|(_, key)| key.clone()),
+            None => match self.keys.as_slice() {
+                [(_, key)] => Some(key.clone()),
+                _ => None,
+            },
+        }
+    }
+}
+
+async fn fetch(source: &JwksSource, http: &reqwest::Client) -> Result<JwksKeys, JwtSetupError> {
+    let body = match source {
+        JwksSource::File(path) => {
+            tokio::fs::read(path)
+                .await
+                .map_err(|source| JwtSetupError::JwksFile {
+                    path: path.clone(),
+                    source,
+                })?
+        }
+        JwksSource::Url(url) => trace_context_headers()
+            .into_iter()
+            .fold(http.get(url), |request, (name, value)| {
+                request.header(name, value)
+            })
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?
+            .to_vec(),
+    };
+    let set: JwkSet = serde_json::from_slice(&body)?;
+    let keys = set
+        .keys
+        .iter()
+        .map(|jwk| Ok((jwk.common.key_id.clone(), DecodingKey::from_jwk(jwk)?)))
+        .collect::<Result<Vec<_>, JwtSetupError>>()?;
+    tracing::info!(keys = keys.len(), "JWKS loaded");
+    Ok(JwksKeys { keys })
+}
@@ -0,0 +1,157 @@
+//! Authentication (bearer JWTs) and role-based authorization shared by the axum and Smithy front-ends.
+
+pub mod jwt;
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::app_config::AuthConfig;
+use crate::auth::jwt::{JwtSetupError, JwtVerifier};
+
+/// Header (or gRPC metadata key) carrying an API key minted via `/admin/api-keys`, as an
+/// alternative to a bearer token.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Role granted by a token (`roles` claim) or API key scope. Each role includes the permissions
+/// of the ones before it.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    Display,
+    EnumString,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Role {
+    Reader,
+    Writer,
+    Admin,
+}
+
+/// What an operation requires of the caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum Permission {
+    /// Read catalog data.
+    Read,
+    /// Create, change or delete catalog data.
+    Write,
+    /// Moderation and other administrative operations.
+    Admin,
+}
+
+impl Role {
+    pub fn grants(self, permission: Permission) -> bool {
+        match self {
+            Role::Reader => permission == Permission::Read,
+            Role::Writer => permission <= Permission::Write,
+            Role::Admin => true,
+        }
+    }
+}
+
+/// Subject of [Principal::anonymous_admin].
+const ANONYMOUS_SUBJECT: &str = "anonymous";
+
+/// Authenticated caller, available to handlers as a request extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    /// Token subject (`sub` claim), or `api-key:<key_id>` for API keys.
+    pub subject: String,
+    pub roles: Vec<Role>,
+}
+
+impl Principal {
+    /// Caller used for every request when authentication is disabled.
+    pub fn anonymous_admin() -> Self {
+        Self {
+            subject: ANONYMOUS_SUBJECT.to_string(),
+            roles: vec![Role::Admin],
+        }
+    }
+
+    /// True for [Principal::anonymous_admin], i.e. when authentication is disabled.
+    pub fn is_anonymous(&self) -> bool {
+        self.subject == ANONYMOUS_SUBJECT
+    }
+
+    pub fn can(&self, permission: Permission) -> bool {
+        self.roles.iter().any(|role| role.grants(permission))
+    }
+
+    pub fn authorize(&self, permission: Permission) -> Result<(), AuthError> {
+        if self.can(permission) {
+            Ok(())
+        } else {
+            Err(AuthError::Forbidden(permission))
+        }
+    }
+}
+
+/// Why a request was rejected: 401 for [AuthError::MissingToken] / [AuthError::InvalidToken],
+/// 403 for [AuthError::Forbidden].
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("missing bearer token")]
+    MissingToken,
+
+    #[error("invalid bearer token: {0}")]
+    InvalidToken(String),
+
+    #[error("{0} permission required")]
+    Forbidden(Permission),
+}
+
+/// Authenticates requests from their `Authorization` header. Cheap to clone.
+#[derive(Clone)]
+pub struct Authenticator {
+    verifier: Option<Arc<JwtVerifier>>,
+}
+
+impl Authenticator {
+    /// Build from config; loads the JWKS (file or URL) if one is configured.
+    pub async fn from_config(config: &AuthConfig) -> Result<Self, JwtSetupError> {
+        if !config.enabled {
+            tracing::warn!("Authentication is disabled; all requests are treated as admin");
+            return Ok(Self::disabled());
+        }
+        let verifier = JwtVerifier::from_config(config).await?;
+        Ok(Self {
+            verifier: Some(Arc::new(verifier)),
+        })
+    }
+
+    /// Accept every request as [Principal::anonymous_admin].
+    pub fn disabled() -> Self {
+        Self { verifier: None }
+    }
+
+    /// Resolve the caller from an `Authorization: Bearer <jwt>` header value.
+    pub async fn authenticate(&self, authorization: Option<&str>) -> Result<Principal, AuthError> {
+        let Some(verifier) = &self.verifier else {
+            return Ok(Principal::anonymous_admin());
+        };
+        let token = authorization
+            .and_then(|value| {
+                value
+                    .strip_prefix("Bearer ")
+                    .or_else(|| value.strip_prefix("bearer "))
+            })
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .ok_or(AuthError::MissingToken)?;
+        verifier.verify(token).await
+    }
+}
@@ -0,0 +1,77 @@
+//! Writes the JSON Schema (`<Type>.v<N>.schema.json`) and Avro (`<Type>.v<N>.avsc`) contracts of
+//! the catalog events to a directory, refusing changes that would break consumers of a committed
+//! schema of the same version. With `--check`, only verifies that the committed files are current.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use catalog_svc::events::schema::{breaking_changes, event_schemas};
+use clap::Parser;
+use serde_json::Value;
+
+#[derive(Parser)]
+#[command(about = "Generate catalog event JSON Schema and Avro contracts")]
+struct Args {
+    /// Directory of the schema files.
+    #[arg(long, default_value = "catalog-svc/catalog-svc/schemas/events")]
+    out_dir: PathBuf,
+    /// Fail instead of writing when a file is missing or out of date.
+    #[arg(long)]
+    check: bool,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let schemas = event_schemas().expect("generate event schemas");
+    if !args.check {
+        std::fs::create_dir_all(&args.out_dir).expect("create output directory");
+    }
+
+    let mut failed = false;
+    for schema in &schemas {
+        let stem = schema.file_stem();
+        let json_path = args.out_dir.join(format!("{stem}.schema.json"));
+        let committed = std::fs::read_to_string(&json_path)
+            .ok()
+            .map(|text| serde_json::from_str::<Value>(&text).expect("parse committed schema"));
+        if let Some(committed) = &committed {
+            let problems = breaking_changes(committed, &schema.json_schema);
+            if !problems.is_empty() {
+                eprintln!("{stem}: breaking changes; bump the event version instead:");
+                for problem in problems {
+                    eprintln!("  {problem}");
+                }
+                failed = true;
+                continue;
+            }
+        }
+
+        let files = [
+            (json_path, &schema.json_schema),
+            (
+                args.out_dir.join(format!("{stem}.avsc")),
+                &schema.avro_schema,
+            ),
+        ];
+        for (path, contents) in files {
+            let text = serde_json::to_string_pretty(contents).expect("serialize schema") + "\n";
+            let current = std::fs::read_to_string(&path).ok();
+            if current.as_deref() == Some(text.as_str()) {
+                continue;
+            }
+            if args.check {
+                eprintln!("{}: missing or out of date; regenerate", path.display());
+                failed = true;
+            } else {
+                std::fs::write(&path, text).expect("write schema");
+                println!("Wrote {}", path.display());
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
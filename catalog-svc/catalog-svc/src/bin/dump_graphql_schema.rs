@@ -0,0 +1,5 @@
+//! Prints the GraphQL schema (SDL) served at `/graphql` to stdout, for client generation or CI.
+
+fn main() {
+    print!("{}", catalog_svc::graphql::sdl());
+}
@@ -0,0 +1,45 @@
+//! Mints an HS256 bearer token signed with `auth.hs256_secret` from the app config, for local use.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::auth::Role;
+use catalog_svc::auth::jwt::{Claims, encode_token};
+use clap::Parser;
+use jsonwebtoken::{EncodingKey, Header};
+
+#[derive(Parser)]
+#[command(about = "Mint a bearer token for the catalog API")]
+struct Args {
+    /// Token subject (`sub` claim).
+    #[arg(long, default_value = "local-dev")]
+    subject: String,
+    /// Granted role (reader, writer or admin); repeatable.
+    #[arg(long = "role", default_value = "reader")]
+    roles: Vec<Role>,
+    /// Validity in seconds.
+    #[arg(long, default_value_t = 3600)]
+    ttl_secs: u64,
+}
+
+fn main() {
+    let args = Args::parse();
+    let app_config = AppConfig::load().expect("failed to load app config");
+    let secret = app_config
+        .auth
+        .hs256_secret
+        .expect("auth.hs256_secret is not configured");
+
+    let claims = Claims::new(
+        &args.subject,
+        &args.roles,
+        Duration::from_secs(args.ttl_secs),
+    );
+    let token = encode_token(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .expect("sign token");
+    println!("{token}");
+}
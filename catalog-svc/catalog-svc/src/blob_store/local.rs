@@ -0,0 +1,84 @@
+//! [BlobStore] backed by the local filesystem.
+
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use uuid::Uuid;
+
+use crate::blob_store::{BlobStore, BlobStoreError};
+
+/// Stores each blob as a file at `root/<key>`.
+#[derive(Debug, Clone)]
+pub struct LocalFsBlobStore {
+    root: PathBuf,
+}
+
+impl LocalFsBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Resolve `key` below the root, rejecting absolute paths and `..` traversal.
+    fn path_for(&self, key: &str) -> Result<PathBuf, BlobStoreError> {
+        let relative = Path::new(key.trim_end_matches('/'));
+        let is_plain = relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        if key.is_empty() || !is_plain {
+            return Err(BlobStoreError::InvalidKey(key.to_string()));
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalFsBlobStore {
+    async fn put(
+        &self,
+        key: &str,
+        _content_type: &str,
+        bytes: Bytes,
+    ) -> Result<(), BlobStoreError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // Write to a sibling temp file first so readers never observe a partial blob.
+        let tmp_path = path.with_extension(format!("{}.tmp", Uuid::new_v4()));
+        tokio::fs::write(&tmp_path, &bytes).await?;
+        if let Err(err) = tokio::fs::rename(&tmp_path, &path).await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Bytes, BlobStoreError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(Bytes::from(data)),
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                Err(BlobStoreError::NotFound(key.to_string()))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), BlobStoreError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    async fn delete_prefix(&self, prefix: &str) -> Result<(), BlobStoreError> {
+        let path = self.path_for(prefix)?;
+        match tokio::fs::remove_dir_all(&path).await {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Pluggable storage for binary objects (e.g. item media), addressed by `/`-separated keys.
+
+mod local;
+mod s3;
+
+pub use local::LocalFsBlobStore;
+pub use s3::S3BlobStore;
+
+use std::error::Error as StdError;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use thiserror::Error;
+
+use crate::app_config::BlobStoreConfig;
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Errors from [BlobStore] operations.
+#[derive(Error, Debug)]
+pub enum BlobStoreError {
+    #[error("blob not found: {0}")]
+    NotFound(String),
+
+    #[error("invalid blob key: {0}")]
+    InvalidKey(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("object storage error: {0}")]
+    Backend(#[source] BoxError),
+}
+
+/// Key/value store for opaque binary objects.
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Store `bytes` under `key`, replacing any existing blob.
+    async fn put(&self, key: &str, content_type: &str, bytes: Bytes) -> Result<(), BlobStoreError>;
+
+    /// Read the blob stored under `key`, or [BlobStoreError::NotFound].
+    async fn get(&self, key: &str) -> Result<Bytes, BlobStoreError>;
+
+    /// Remove the blob stored under `key`. Removing a missing blob is not an error.
+    async fn delete(&self, key: &str) -> Result<(), BlobStoreError>;
+
+    /// Remove every blob whose key starts with `prefix` (which should end with `/`).
+    async fn delete_prefix(&self, prefix: &str) -> Result<(), BlobStoreError>;
+}
+
+/// Create the [BlobStore] selected by the given config.
+pub fn create_blob_store(cfg: &BlobStoreConfig) -> Arc<dyn BlobStore> {
+    match cfg {
+        BlobStoreConfig::Local { root } => Arc::new(LocalFsBlobStore::new(root.clone())),
+        BlobStoreConfig::S3(s3_config) => Arc::new(S3BlobStore::new(s3_config)),
+    }
+}
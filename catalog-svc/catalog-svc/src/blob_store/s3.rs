@@ -0,0 +1,119 @@
+//! [BlobStore] backed by an S3-compatible object store (AWS S3, MinIO, ...).
+
+use async_trait::async_trait;
+use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::primitives::ByteStream;
+use bytes::Bytes;
+
+use crate::app_config::S3Config;
+use crate::blob_store::{BlobStore, BlobStoreError};
+
+/// Stores each blob as an object named `<key>` in a single bucket.
+#[derive(Debug, Clone)]
+pub struct S3BlobStore {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+}
+
+impl S3BlobStore {
+    /// Build a client from static credentials and an optional custom endpoint.
+    pub fn new(cfg: &S3Config) -> Self {
+        let credentials = Credentials::new(
+            &cfg.access_key_id,
+            &cfg.secret_access_key,
+            None,
+            None,
+            "catalog-svc-config",
+        );
+        let mut builder = aws_sdk_s3::Config::builder()
+            .region(Region::new(cfg.region.clone()))
+            .credentials_provider(credentials)
+            .force_path_style(cfg.force_path_style);
+        if let Some(endpoint) = &cfg.endpoint {
+            builder = builder.endpoint_url(endpoint);
+        }
+        Self::from_client(aws_sdk_s3::Client::from_conf(builder.build()), &cfg.bucket)
+    }
+
+    pub fn from_client(client: aws_sdk_s3::Client, bucket: &str) -> Self {
+        Self {
+            client,
+            bucket: bucket.to_string(),
+        }
+    }
+
+    pub fn client(&self) -> &aws_sdk_s3::Client {
+        &self.client
+    }
+
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+}
+
+fn backend_error(err: impl std::error::Error + Send + Sync + 'static) -> BlobStoreError {
+    BlobStoreError::Backend(Box::new(err))
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    async fn put(&self, key: &str, content_type: &str, bytes: Bytes) -> Result<(), BlobStoreError> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(bytes))
+            .send()
+            .await
+            .map_err(backend_error)?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Bytes, BlobStoreError> {
+        let output = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|err| {
+                if err.as_service_error().is_some_and(|e| e.is_no_such_key()) {
+                    BlobStoreError::NotFound(key.to_string())
+                } else {
+                    backend_error(err)
+                }
+            })?;
+        let data = output.body.collect().await.map_err(backend_error)?;
+        Ok(data.into_bytes())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), BlobStoreError> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(backend_error)?;
+        Ok(())
+    }
+
+    async fn delete_prefix(&self, prefix: &str) -> Result<(), BlobStoreError> {
+        let mut pages = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(prefix)
+            .into_paginator()
+            .send();
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(backend_error)?;
+            for key in page.contents().iter().filter_map(|object| object.key()) {
+                self.delete(key).await?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,13 @@
+use std::collections::HashMap;
 use std::error::Error as StdError;
+use std::fmt;
+use std::str::FromStr;
 
+use async_graphql::{Enum, InputObject, SimpleObject};
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString};
 use thiserror::Error;
@@ -10,6 +15,7 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::common::pagination::{PaginatedSearchResponse, Pagination};
+use crate::common::validation::ValidationErrors;
 
 type BoxError = Box<dyn StdError + Send + Sync>;
 
@@ -17,7 +23,7 @@ type BoxError = Box<dyn StdError + Send + Sync>;
 #[derive(Error, Debug)]
 pub enum CatalogServiceError {
     #[error("validation error: {0}")]
-    ValidationError(#[source] BoxError),
+    ValidationError(#[source] ValidationErrors),
 
     #[error("internal error: {0}")]
     InternalError(#[source] BoxError),
@@ -31,11 +37,24 @@ pub trait CatalogServiceApi: Send + Sync {
 
     async fn get(&self, item_id: Uuid) -> Result<Option<CatalogItem>, CatalogServiceError>;
 
+    /// The existing items among `item_ids`, keyed by id.
+    async fn get_many(
+        &self,
+        item_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, CatalogItem>, CatalogServiceError>;
+
     async fn list(
         &self,
         req: ListCatalogItemsRequest,
     ) -> Result<ListCatalogItemsResponse, CatalogServiceError>;
 
+    /// Like [CatalogServiceApi::list], restricted to the items matching `filter`.
+    async fn search(
+        &self,
+        req: ListCatalogItemsRequest,
+        filter: CatalogItemFilter,
+    ) -> Result<ListCatalogItemsResponse, CatalogServiceError>;
+
     async fn update(
         &self,
         item_id: Uuid,
@@ -47,7 +66,18 @@ pub trait CatalogServiceApi: Send + Sync {
 
 /// Catalog item category.
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, Display, EnumString,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    JsonSchema,
+    Enum,
+    Display,
+    EnumString,
 )]
 #[serde(rename_all = "PascalCase")]
 #[strum(serialize_all = "PascalCase")]
@@ -58,11 +88,17 @@ pub enum Category {
 
 /// Catalog item: product with id, metadata, and server-set UTC timestamps.
 /// exposed in responses only.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema, JsonSchema, SimpleObject,
+)]
 #[serde(rename_all = "camelCase")]
+#[graphql(complex)]
 pub struct CatalogItem {
     pub item_id: Uuid,
     pub name: String,
+    /// Unique URL-safe slug derived from the name (server-maintained). Previous slugs redirect to it.
+    #[schema(example = "creme-brulee-torch")]
+    pub slug: String,
     pub description: String,
     pub category: Category,
     /// Date with day resolution only (YYYY-MM-DD).
@@ -73,37 +109,227 @@ pub struct CatalogItem {
     pub price: Decimal,
     pub created_at: DateTime<Utc>,
     pub modified_at: DateTime<Utc>,
+    /// Aggregate of approved customer reviews (server-maintained).
+    pub rating: RatingSummary,
+    /// Units in stock as last reported by the supplier feed; absent when never reported.
+    pub stock: Option<u32>,
+}
+
+/// Aggregate of an item's approved reviews.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema, JsonSchema, SimpleObject,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct RatingSummary {
+    /// Mean rating rounded to two decimals (e.g. 4.25); absent when there are no reviews.
+    /// Serializes in JSON as string.
+    #[schema(value_type = Option<String>, example = "4.25")]
+    pub average: Option<Decimal>,
+    pub count: u32,
+    /// Number of reviews per rating: index 0 holds 1-star reviews, index 4 holds 5-star reviews.
+    pub histogram: Vec<u32>,
+}
+
+impl RatingSummary {
+    /// Summary of an item with no reviews.
+    pub fn empty() -> Self {
+        Self {
+            average: None,
+            count: 0,
+            histogram: vec![0; 5],
+        }
+    }
+}
+
+/// Price and/or stock of an item as reported by a supplier; unset fields are left unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupplyUpdate {
+    pub item_id: Uuid,
+    pub price: Option<Decimal>,
+    pub stock: Option<u32>,
+}
+
+/// Result of [crate::catalog::service::CatalogService::apply_supply_update].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SupplyUpdateOutcome {
+    /// The update was applied (or changed nothing); holds the resulting item.
+    Applied(Box<CatalogItem>),
+    /// The message was applied before; nothing was changed.
+    Duplicate,
+    /// No item has the update's id; nothing was recorded.
+    NotFound,
+}
+
+/// Kind of change recorded in the item change log.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, Display, EnumString,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ItemChangeType {
+    Created,
+    /// Any change to the item's representation, including its rating.
+    Updated,
+    Deleted,
+}
+
+/// Position of an entry in the item change log: the id of the writing transaction, then the
+/// entry's sequence number (see [crate::catalog::changes]). Formats as `<tx_id>-<seq>`, the SSE
+/// event id clients resume from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChangePosition {
+    pub tx_id: i64,
+    pub seq: i64,
+}
+
+impl fmt::Display for ChangePosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.tx_id, self.seq)
+    }
+}
+
+/// A string that is not a [ChangePosition].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid change position: {0}")]
+pub struct InvalidChangePosition(pub String);
+
+impl FromStr for ChangePosition {
+    type Err = InvalidChangePosition;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidChangePosition(s.to_string());
+        let (tx_id, seq) = s.split_once('-').ok_or_else(invalid)?;
+        Ok(Self {
+            tx_id: tx_id.parse().map_err(|_| invalid())?,
+            seq: seq.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// A create, update or delete of a catalog item, as streamed by `GET /catalog/items/changes`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemChange {
+    /// Position in the change log; sent as the SSE event id rather than in the data.
+    #[serde(skip)]
+    pub position: ChangePosition,
+    pub item_id: Uuid,
+    #[serde(rename = "type")]
+    pub change_type: ItemChangeType,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Position in the incremental sync of catalog items: the `modified_at` of the last change
+/// returned, then its item id to order changes with the same timestamp (see
+/// [crate::catalog::sync]). Formats as `<microseconds since epoch>_<item_id>`, the watermark token
+/// clients pass back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SyncWatermark {
+    pub modified_at: DateTime<Utc>,
+    pub item_id: Uuid,
+}
+
+impl SyncWatermark {
+    /// Before every change: where a full sync starts.
+    pub const START: Self = Self {
+        modified_at: DateTime::<Utc>::UNIX_EPOCH,
+        item_id: Uuid::nil(),
+    };
+}
+
+impl fmt::Display for SyncWatermark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}_{}",
+            self.modified_at.timestamp_micros(),
+            self.item_id
+        )
+    }
+}
+
+/// A string that is not a [SyncWatermark].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid sync watermark: {0}")]
+pub struct InvalidSyncWatermark(pub String);
+
+impl FromStr for SyncWatermark {
+    type Err = InvalidSyncWatermark;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidSyncWatermark(s.to_string());
+        let (micros, item_id) = s.split_once('_').ok_or_else(invalid)?;
+        let micros = micros.parse().map_err(|_| invalid())?;
+        Ok(Self {
+            modified_at: DateTime::from_timestamp_micros(micros).ok_or_else(invalid)?,
+            item_id: item_id.parse().map_err(|_| invalid())?,
+        })
+    }
 }
 
 // Request/response types for the REST API (created_at, modified_at not in requests)
 
 /// Body for creating a catalog item (server assigns item_id).
-#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Deserialize, ToSchema, InputObject)]
 #[serde(rename_all = "camelCase")]
+#[graphql(name = "CreateCatalogItemInput")]
 pub struct CreateCatalogItemBody {
+    /// Non-blank, at most 255 characters.
     pub name: String,
     pub description: String,
     pub category: Category,
     /// Date with day resolution only (YYYY-MM-DD).
     pub date: String,
+    /// At most 255 characters.
     pub brand: Option<String>,
+    /// Non-negative, at most two decimal places and below 100,000,000.
     #[schema(value_type = String, example = "19.99")]
     pub price: Decimal,
 }
 
 /// Body for updating a catalog item (same fields as create, except item_id).
-#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Deserialize, ToSchema, InputObject)]
 #[serde(rename_all = "camelCase")]
+#[graphql(name = "UpdateCatalogItemInput")]
 pub struct UpdateCatalogItemBody {
+    /// Non-blank, at most 255 characters.
     pub name: String,
     pub description: String,
     pub category: Category,
+    /// Date with day resolution only (YYYY-MM-DD).
     pub date: String,
+    /// At most 255 characters.
     pub brand: Option<String>,
+    /// Non-negative, at most two decimal places and below 100,000,000.
     #[schema(value_type = String, example = "19.99")]
     pub price: Decimal,
 }
 
+/// Sort order for listing catalog items.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    Enum,
+    Display,
+    EnumString,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum CatalogItemSort {
+    /// Oldest first (default).
+    #[default]
+    CreatedAt,
+    /// Highest average rating first, then most reviewed; unrated items last.
+    Rating,
+}
+
 /// Query parameters for the list catalog items endpoint.
 #[derive(Debug, Default, Deserialize, ToSchema, utoipa::IntoParams)]
 #[into_params(parameter_in = Query)]
@@ -113,11 +339,28 @@ pub struct ListCatalogItemsRequest {
     pub limit: Option<u32>,
     /// Zero-based offset into the result set. Defaults to 0.
     pub offset: Option<u32>,
+    /// Sort order. Defaults to `createdAt`.
+    #[schema(nullable = false)]
+    #[param(nullable = false)]
+    pub sort: Option<CatalogItemSort>,
+}
+
+/// Restricts a catalog item search; unset fields match every item.
+#[derive(Debug, Clone, Default, PartialEq, Eq, InputObject)]
+pub struct CatalogItemFilter {
+    pub category: Option<Category>,
+    /// Exact brand.
+    pub brand: Option<String>,
+    /// Lowest price, inclusive.
+    pub min_price: Option<Decimal>,
+    /// Highest price, inclusive.
+    pub max_price: Option<Decimal>,
 }
 
 /// Response for the list catalog items endpoint.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema, SimpleObject)]
 #[serde(rename_all = "camelCase")]
+#[graphql(name = "CatalogItemPage")]
 pub struct ListCatalogItemsResponse {
     pub items: Vec<CatalogItem>,
     pub has_more: bool,
@@ -144,3 +387,31 @@ impl ListCatalogItemsResponse {
         }
     }
 }
+
+/// Query parameters for the incremental sync endpoint.
+#[derive(Debug, Default, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemSyncRequest {
+    /// Watermark from the previous response; omit to start a full sync.
+    #[schema(nullable = false)]
+    #[param(nullable = false)]
+    pub since: Option<String>,
+    /// Maximum number of changes (items plus deleted ids) to return. Defaults to 100; clamped to
+    /// 1000 server-side.
+    pub limit: Option<u32>,
+}
+
+/// Items changed and ids of items deleted after a watermark, oldest change first.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemSyncResponse {
+    /// Items created or updated since the watermark, in their current state.
+    pub items: Vec<CatalogItem>,
+    /// Items deleted since the watermark (always empty on a full sync).
+    pub deleted_item_ids: Vec<Uuid>,
+    /// Pass as `since` to get the changes after this response.
+    pub watermark: String,
+    /// Whether more changes are already available; if false, poll again later.
+    pub has_more: bool,
+}
@@ -0,0 +1,208 @@
+//! In-process cache of [CatalogItem]s by id, bounded in size (least recently used entries are
+//! evicted) and age, in front of [crate::catalog::service::CatalogService] reads.
+//!
+//! Writers invalidate the entries they change. With `notify` enabled, invalidations are also sent
+//! on the [INVALIDATION_CHANNEL] (Postgres `NOTIFY`, delivered on commit) and applied by every
+//! instance's [ItemCache::spawn_listener], so replicas do not serve stale items until the TTL.
+
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use lru::LruCache;
+use sqlx::postgres::PgListener;
+use sqlx::{Executor, PgPool, Postgres};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::app_config::ItemCacheConfig;
+use crate::app_metrics;
+use crate::catalog::api::CatalogItem;
+
+/// Postgres channel carrying invalidated item ids, or [INVALIDATE_ALL].
+pub const INVALIDATION_CHANNEL: &str = "catalog_item_invalidation";
+/// Payload invalidating every cached item (e.g. after repricing).
+const INVALIDATE_ALL: &str = "*";
+
+/// Shared handle to the cache; cheap to clone. A disabled cache misses every lookup.
+#[derive(Clone)]
+pub struct ItemCache {
+    inner: Option<Arc<Inner>>,
+}
+
+struct Inner {
+    state: Mutex<State>,
+    ttl: Duration,
+    notify: bool,
+}
+
+struct State {
+    items: LruCache<Uuid, Entry>,
+    /// Bumped by every invalidation, so a read that started before a write cannot cache the
+    /// value it loaded after the write invalidated it.
+    generation: u64,
+}
+
+struct Entry {
+    item: CatalogItem,
+    expires_at: Instant,
+}
+
+impl ItemCache {
+    pub fn from_config(cfg: &ItemCacheConfig) -> Self {
+        match NonZeroUsize::new(cfg.capacity).filter(|_| cfg.enabled) {
+            Some(capacity) => Self::new(capacity, Duration::from_secs(cfg.ttl_secs), cfg.notify),
+            None => Self::disabled(),
+        }
+    }
+
+    pub fn new(capacity: NonZeroUsize, ttl: Duration, notify: bool) -> Self {
+        Self {
+            inner: Some(Arc::new(Inner {
+                state: Mutex::new(State {
+                    items: LruCache::new(capacity),
+                    generation: 0,
+                }),
+                ttl,
+                notify,
+            })),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self { inner: None }
+    }
+
+    /// The cached item, if present and not expired. Counts a hit or a miss.
+    pub fn get(&self, item_id: Uuid) -> Option<CatalogItem> {
+        let inner = self.inner.as_ref()?;
+        let mut state = inner.lock();
+        let cached = match state.items.get(&item_id) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.item.clone()),
+            Some(_) => {
+                state.items.pop(&item_id);
+                None
+            }
+            None => None,
+        };
+        drop(state);
+        let counter = if cached.is_some() {
+            app_metrics::ITEM_CACHE_HITS_TOTAL
+        } else {
+            app_metrics::ITEM_CACHE_MISSES_TOTAL
+        };
+        metrics::counter!(counter).increment(1);
+        cached
+    }
+
+    /// Current invalidation generation; take it before loading items to pass to [Self::insert].
+    pub fn generation(&self) -> u64 {
+        self.inner
+            .as_ref()
+            .map_or(0, |inner| inner.lock().generation)
+    }
+
+    /// Cache items loaded from the database, unless an invalidation happened since `generation`.
+    pub fn insert(&self, items: impl IntoIterator<Item = CatalogItem>, generation: u64) {
+        let Some(inner) = &self.inner else {
+            return;
+        };
+        let mut state = inner.lock();
+        if state.generation != generation {
+            return;
+        }
+        let expires_at = Instant::now() + inner.ttl;
+        for item in items {
+            state.items.put(item.item_id, Entry { item, expires_at });
+        }
+    }
+
+    /// Drop one item from this instance's cache.
+    pub fn invalidate(&self, item_id: Uuid) {
+        if let Some(inner) = &self.inner {
+            let mut state = inner.lock();
+            state.generation += 1;
+            state.items.pop(&item_id);
+        }
+    }
+
+    /// Drop every item from this instance's cache.
+    pub fn invalidate_all(&self) {
+        if let Some(inner) = &self.inner {
+            let mut state = inner.lock();
+            state.generation += 1;
+            state.items.clear();
+        }
+    }
+
+    /// Announce that `item_id` (or every item, for None) changed to the other instances, when
+    /// `notify` is enabled. Run it in the writing transaction: Postgres delivers it on commit.
+    pub async fn publish_invalidation(
+        &self,
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        if !self.inner.as_ref().is_some_and(|inner| inner.notify) {
+            return Ok(());
+        }
+        let payload = item_id.map_or_else(|| INVALIDATE_ALL.to_string(), |id| id.to_string());
+        sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(INVALIDATION_CHANNEL)
+            .bind(payload)
+            .execute(executor)
+            .await?;
+        Ok(())
+    }
+
+    /// Apply invalidations from [INVALIDATION_CHANNEL] until `shutdown` is cancelled, when
+    /// `notify` is enabled. Everything is invalidated after the connection drops, since
+    /// notifications may have been missed meanwhile.
+    pub fn spawn_listener(
+        &self,
+        pg_pool: &PgPool,
+        shutdown: CancellationToken,
+    ) -> Option<JoinHandle<()>> {
+        if !self.inner.as_ref().is_some_and(|inner| inner.notify) {
+            return None;
+        }
+        let cache = self.clone();
+        let pg_pool = pg_pool.clone();
+        Some(tokio::spawn(async move {
+            tokio::select! {
+                _ = shutdown.cancelled() => {}
+                result = cache.listen(&pg_pool) => {
+                    if let Err(err) = result {
+                        tracing::error!("Item cache invalidation listener stopped: {err}");
+                        cache.invalidate_all();
+                    }
+                }
+            }
+        }))
+    }
+
+    async fn listen(&self, pg_pool: &PgPool) -> Result<(), sqlx::Error> {
+        let mut listener = PgListener::connect_with(pg_pool).await?;
+        listener.listen(INVALIDATION_CHANNEL).await?;
+        tracing::info!("Listening for item cache invalidations on {INVALIDATION_CHANNEL}");
+        loop {
+            // None: the connection was lost and re-established by the listener
+            match listener.try_recv().await? {
+                Some(notification) => match notification.payload().parse::<Uuid>() {
+                    Ok(item_id) => self.invalidate(item_id),
+                    Err(_) => self.invalidate_all(),
+                },
+                None => self.invalidate_all(),
+            }
+        }
+    }
+}
+
+impl Inner {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        // The state stays consistent even if a holder panicked
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
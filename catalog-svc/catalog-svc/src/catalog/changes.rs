@@ -0,0 +1,211 @@
+//! Live feed of the item change log, streamed by `GET /catalog/items/changes`.
+//!
+//! Writers append to the log and notify [CHANGES_CHANNEL] in their transaction (see
+//! [ItemChangeRepository::append]). A listener turns the notifications into wake-ups of the open
+//! subscriptions, which then read the log after their own position. Subscriptions also poll, since
+//! a notification may be missed, and a change only becomes readable once every older transaction
+//! has ended.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use chrono::Utc;
+use futures::Stream;
+use sqlx::PgPool;
+use sqlx::postgres::PgListener;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::app_config::ItemChangesConfig;
+use crate::app_metrics;
+use crate::catalog::api::{ChangePosition, ItemChange};
+use crate::catalog::persistence::RepositoryError;
+use crate::catalog::persistence::changes::{CHANGES_CHANNEL, ItemChangeRepository};
+
+/// How often changes older than the retention are forgotten.
+const PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// What a subscription yields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeFeedEvent {
+    /// Changes after the requested position were forgotten: the subscriber must resync. The
+    /// subscription continues after the given position (the newest change, if any).
+    Reset(Option<ChangePosition>),
+    Change(ItemChange),
+}
+
+/// Subscriptions to the item change log.
+#[derive(Clone)]
+pub struct ItemChangeFeed {
+    pg_pool: PgPool,
+    /// Bumped on every notification; subscriptions wait for it to change.
+    wakeups: watch::Sender<u64>,
+    poll_interval: Duration,
+    batch_size: u32,
+    retention: Duration,
+}
+
+impl ItemChangeFeed {
+    pub fn new(pg_pool: PgPool, cfg: &ItemChangesConfig) -> Self {
+        let (wakeups, _) = watch::channel(0);
+        Self {
+            pg_pool,
+            wakeups,
+            poll_interval: Duration::from_millis(cfg.poll_interval_ms),
+            batch_size: cfg.batch_size.max(1),
+            retention: Duration::from_secs(cfg.retention_secs),
+        }
+    }
+
+    /// Wake subscriptions on [CHANGES_CHANNEL] notifications, and forget changes older than the
+    /// retention, until `shutdown` is cancelled. Subscriptions fall back to polling if the
+    /// listener fails.
+    pub fn spawn(&self, shutdown: CancellationToken) -> Vec<JoinHandle<()>> {
+        let feed = self.clone();
+        let listener_shutdown = shutdown.clone();
+        let listener = tokio::spawn(async move {
+            tokio::select! {
+                _ = listener_shutdown.cancelled() => {}
+                result = feed.listen() => {
+                    if let Err(err) = result {
+                        tracing::error!("Item change listener stopped: {err}");
+                    }
+                }
+            }
+        });
+        let feed = self.clone();
+        let purger = tokio::spawn(async move {
+            let mut purge = tokio::time::interval(PURGE_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = purge.tick() => feed.purge().await,
+                }
+            }
+        });
+        vec![listener, purger]
+    }
+
+    async fn listen(&self) -> Result<(), sqlx::Error> {
+        let mut listener = PgListener::connect_with(&self.pg_pool).await?;
+        listener.listen(CHANGES_CHANNEL).await?;
+        tracing::info!("Listening for item changes on {CHANGES_CHANNEL}");
+        loop {
+            // None: the connection was re-established; wake up in case something was missed
+            listener.try_recv().await?;
+            self.wakeups
+                .send_modify(|count| *count = count.wrapping_add(1));
+        }
+    }
+
+    async fn purge(&self) {
+        let retention = chrono::Duration::from_std(self.retention).unwrap_or(chrono::Duration::MAX);
+        let cutoff = Utc::now()
+            .naive_utc()
+            .checked_sub_signed(retention)
+            .unwrap_or(chrono::NaiveDateTime::MIN);
+        match ItemChangeRepository::delete_before(&self.pg_pool, cutoff).await {
+            Ok(0) => {}
+            Ok(deleted) => tracing::info!("Purged {deleted} item changes"),
+            Err(err) => tracing::warn!("Purging item changes failed: {err}"),
+        }
+    }
+
+    /// Changes after `after` (the position of a change received before), or from now on for
+    /// None, until `shutdown` is cancelled or reading the log fails. Starts with a
+    /// [ChangeFeedEvent::Reset] if `after` is no longer in the log.
+    pub async fn subscribe(
+        &self,
+        after: Option<ChangePosition>,
+        shutdown: CancellationToken,
+    ) -> Result<impl Stream<Item = ChangeFeedEvent> + Send + use<>, RepositoryError> {
+        let mut pending = VecDeque::new();
+        let position = match after {
+            Some(position) if ItemChangeRepository::contains(&self.pg_pool, position).await? => {
+                position
+            }
+            Some(_) => {
+                let last = ItemChangeRepository::last(&self.pg_pool).await?;
+                pending.push_back(ChangeFeedEvent::Reset(last));
+                match last {
+                    Some(last) => last,
+                    None => ItemChangeRepository::head(&self.pg_pool).await?,
+                }
+            }
+            None => ItemChangeRepository::head(&self.pg_pool).await?,
+        };
+        metrics::gauge!(app_metrics::ITEM_CHANGE_STREAMS).increment(1);
+        let subscription = Subscription {
+            feed: self.clone(),
+            position,
+            pending,
+            wakeups: self.wakeups.subscribe(),
+            shutdown,
+        };
+        Ok(futures::stream::unfold(
+            subscription,
+            |mut subscription| async move {
+                let event = subscription.next().await?;
+                Some((event, subscription))
+            },
+        ))
+    }
+}
+
+struct Subscription {
+    feed: ItemChangeFeed,
+    /// Position of the last change read.
+    position: ChangePosition,
+    /// Read but not yet yielded.
+    pending: VecDeque<ChangeFeedEvent>,
+    wakeups: watch::Receiver<u64>,
+    shutdown: CancellationToken,
+}
+
+impl Subscription {
+    async fn next(&mut self) -> Option<ChangeFeedEvent> {
+        loop {
+            if self.shutdown.is_cancelled() {
+                return None;
+            }
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+
+            // Notifications from here on wake the wait below
+            self.wakeups.borrow_and_update();
+            let changes = match ItemChangeRepository::list_after(
+                &self.feed.pg_pool,
+                self.position,
+                self.feed.batch_size,
+            )
+            .await
+            {
+                Ok(changes) => changes,
+                Err(err) => {
+                    tracing::warn!("Reading item changes failed: {err}");
+                    return None;
+                }
+            };
+            match changes.last() {
+                Some(last) => self.position = last.position,
+                None => {
+                    tokio::select! {
+                        _ = self.shutdown.cancelled() => return None,
+                        _ = self.wakeups.changed() => {}
+                        _ = tokio::time::sleep(self.feed.poll_interval) => {}
+                    }
+                }
+            }
+            self.pending
+                .extend(changes.into_iter().map(ChangeFeedEvent::Change));
+        }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        metrics::gauge!(app_metrics::ITEM_CHANGE_STREAMS).decrement(1);
+    }
+}
@@ -1,3 +1,6 @@
 pub mod api;
+pub mod cache;
+pub mod changes;
 pub mod persistence;
 pub mod service;
+pub mod sync;
@@ -0,0 +1,204 @@
+//! SQL repository for the `catalog_item_changes` change log.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{Executor, FromRow, Postgres};
+use uuid::Uuid;
+
+use crate::catalog::api::{ChangePosition, ItemChange, ItemChangeType, SyncWatermark};
+use crate::catalog::persistence::RepositoryError;
+
+/// Postgres notification channel told (on commit) that changes were appended.
+pub const CHANGES_CHANNEL: &str = "catalog_item_changes";
+
+/// Id of the oldest transaction still running as of the statement's snapshot: every entry of an
+/// older transaction is committed (or rolled back) and readable.
+const READABLE_BEFORE_TX: &str = "pg_snapshot_xmin(pg_current_snapshot())::text::bigint";
+
+#[derive(FromRow)]
+struct ItemChangeRow {
+    tx_id: i64,
+    seq: i64,
+    item_id: Uuid,
+    change_type: String,
+    changed_at: NaiveDateTime,
+}
+
+impl ItemChangeRow {
+    fn into_item_change(self) -> Result<ItemChange, RepositoryError> {
+        let change_type = self
+            .change_type
+            .parse::<ItemChangeType>()
+            .map_err(|_| RepositoryError::InvalidChangeType(self.change_type.clone()))?;
+        Ok(ItemChange {
+            position: ChangePosition {
+                tx_id: self.tx_id,
+                seq: self.seq,
+            },
+            item_id: self.item_id,
+            change_type,
+            changed_at: DateTime::<Utc>::from_naive_utc_and_offset(self.changed_at, Utc),
+        })
+    }
+}
+
+/// Repository for the item change log (stateless; all methods take an executor).
+pub struct ItemChangeRepository;
+
+impl ItemChangeRepository {
+    /// Append a change of `item_id` and notify [CHANGES_CHANNEL]. Call it in the transaction
+    /// making the change: the entry and the notification take effect on commit.
+    #[tracing::instrument(
+        name = "ItemChangeRepository::append",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "INSERT", db.sql.table = "catalog_item_changes")
+    )]
+    pub async fn append(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+        change_type: ItemChangeType,
+        changed_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError> {
+        // Notifications with the same payload are delivered once per transaction
+        sqlx::query(
+            r#"
+            WITH change AS (
+                INSERT INTO catalog_item_changes (item_id, change_type, changed_at)
+                VALUES ($1, $2, $3)
+                RETURNING seq
+            )
+            SELECT pg_notify($4, '') FROM change
+            "#,
+        )
+        .bind(item_id)
+        .bind(change_type.to_string())
+        .bind(changed_at.naive_utc())
+        .bind(CHANGES_CHANNEL)
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Up to `limit` readable changes after `after`, in log order.
+    #[tracing::instrument(
+        name = "ItemChangeRepository::list_after",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT", db.sql.table = "catalog_item_changes")
+    )]
+    pub async fn list_after(
+        executor: impl Executor<'_, Database = Postgres>,
+        after: ChangePosition,
+        limit: u32,
+    ) -> Result<Vec<ItemChange>, RepositoryError> {
+        let rows = sqlx::query_as::<_, ItemChangeRow>(&format!(
+            r#"
+            SELECT tx_id, seq, item_id, change_type, changed_at
+            FROM catalog_item_changes
+            WHERE (tx_id, seq) > ($1, $2) AND tx_id < {READABLE_BEFORE_TX}
+            ORDER BY tx_id, seq
+            LIMIT $3
+            "#
+        ))
+        .bind(after.tx_id)
+        .bind(after.seq)
+        .bind(i64::from(limit))
+        .fetch_all(executor)
+        .await?;
+        rows.into_iter()
+            .map(ItemChangeRow::into_item_change)
+            .collect()
+    }
+
+    /// Position every change not yet readable will come after: reading from here skips exactly
+    /// the changes committed so far (and possibly repeats a few).
+    pub async fn head(
+        executor: impl Executor<'_, Database = Postgres>,
+    ) -> Result<ChangePosition, RepositoryError> {
+        let tx_id: i64 = sqlx::query_scalar(&format!("SELECT {READABLE_BEFORE_TX}"))
+            .fetch_one(executor)
+            .await?;
+        Ok(ChangePosition { tx_id, seq: 0 })
+    }
+
+    /// Position of the newest readable change, if the log holds any.
+    pub async fn last(
+        executor: impl Executor<'_, Database = Postgres>,
+    ) -> Result<Option<ChangePosition>, RepositoryError> {
+        let row = sqlx::query_as::<_, (i64, i64)>(&format!(
+            r#"
+            SELECT tx_id, seq
+            FROM catalog_item_changes
+            WHERE tx_id < {READABLE_BEFORE_TX}
+            ORDER BY tx_id DESC, seq DESC
+            LIMIT 1
+            "#
+        ))
+        .fetch_optional(executor)
+        .await?;
+        Ok(row.map(|(tx_id, seq)| ChangePosition { tx_id, seq }))
+    }
+
+    /// Whether the log still holds the change at `position`.
+    pub async fn contains(
+        executor: impl Executor<'_, Database = Postgres>,
+        position: ChangePosition,
+    ) -> Result<bool, RepositoryError> {
+        let found: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM catalog_item_changes WHERE seq = $1 AND tx_id = $2)",
+        )
+        .bind(position.seq)
+        .bind(position.tx_id)
+        .fetch_one(executor)
+        .await?;
+        Ok(found)
+    }
+
+    /// Up to `limit` deletions made after `after` and before `before`, in (changed_at, item_id)
+    /// order, as watermarks of the deleted items.
+    #[tracing::instrument(
+        name = "ItemChangeRepository::list_deleted_after",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT", db.sql.table = "catalog_item_changes")
+    )]
+    pub async fn list_deleted_after(
+        executor: impl Executor<'_, Database = Postgres>,
+        after: SyncWatermark,
+        before: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<SyncWatermark>, RepositoryError> {
+        // The type is inlined so the partial index on deletions applies to every plan
+        let rows = sqlx::query_as::<_, (NaiveDateTime, Uuid)>(
+            r#"
+            SELECT changed_at, item_id
+            FROM catalog_item_changes
+            WHERE change_type = 'deleted' AND (changed_at, item_id) > ($1, $2) AND changed_at < $3
+            ORDER BY changed_at, item_id
+            LIMIT $4
+            "#,
+        )
+        .bind(after.modified_at.naive_utc())
+        .bind(after.item_id)
+        .bind(before.naive_utc())
+        .bind(i64::from(limit))
+        .fetch_all(executor)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(changed_at, item_id)| SyncWatermark {
+                modified_at: DateTime::<Utc>::from_naive_utc_and_offset(changed_at, Utc),
+                item_id,
+            })
+            .collect())
+    }
+
+    /// Forget changes made before `cutoff`. Returns how many were removed.
+    pub async fn delete_before(
+        executor: impl Executor<'_, Database = Postgres>,
+        cutoff: NaiveDateTime,
+    ) -> Result<u64, RepositoryError> {
+        let result = sqlx::query("DELETE FROM catalog_item_changes WHERE changed_at < $1")
+            .bind(cutoff)
+            .execute(executor)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -1,19 +1,25 @@
 //! SQL repository for [CatalogItem] CRUD operations.
 
+pub mod changes;
+
 use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use rust_decimal::Decimal;
 use sqlx::{Executor, FromRow, Postgres};
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::catalog::api::{CatalogItem, Category};
+use crate::app_metrics;
+use crate::catalog::api::{
+    CatalogItem, CatalogItemFilter, CatalogItemSort, Category, RatingSummary, SyncWatermark,
+};
 use crate::common::pagination::{PaginatedSearchResponse, Pagination};
 
 /// Row type for mapping SELECT results from `catalog_items` into [CatalogItem].
 #[derive(FromRow)]
-struct CatalogItemRow {
+pub(crate) struct CatalogItemRow {
     item_id: Uuid,
     name: String,
+    slug: String,
     description: String,
     category: String,
     date: NaiveDate,
@@ -21,10 +27,14 @@ struct CatalogItemRow {
     price: Decimal,
     created_at: NaiveDateTime,
     modified_at: NaiveDateTime,
+    rating_count: i32,
+    rating_average: Option<Decimal>,
+    rating_histogram: Vec<i32>,
+    stock: Option<i32>,
 }
 
 impl CatalogItemRow {
-    fn into_catalog_item(self) -> Result<CatalogItem, RepositoryError> {
+    pub(crate) fn into_catalog_item(self) -> Result<CatalogItem, RepositoryError> {
         let category = self
             .category
             .parse::<Category>()
@@ -32,6 +42,7 @@ impl CatalogItemRow {
         Ok(CatalogItem {
             item_id: self.item_id,
             name: self.name,
+            slug: self.slug,
             description: self.description,
             category,
             date: self.date,
@@ -39,12 +50,27 @@ impl CatalogItemRow {
             price: self.price,
             created_at: DateTime::<Utc>::from_naive_utc_and_offset(self.created_at, Utc),
             modified_at: DateTime::<Utc>::from_naive_utc_and_offset(self.modified_at, Utc),
+            rating: RatingSummary {
+                average: self.rating_average,
+                count: self.rating_count.try_into().unwrap_or_default(),
+                histogram: self
+                    .rating_histogram
+                    .into_iter()
+                    .map(|n| n.try_into().unwrap_or_default())
+                    .collect(),
+            },
+            stock: self.stock.map(|n| n.try_into().unwrap_or_default()),
         })
     }
 }
 
 pub type CatalogItemSearchResponse = PaginatedSearchResponse<CatalogItem>;
 
+/// `stock` as stored in the `INTEGER` column; the service rejects larger values.
+fn stock_column(stock: Option<u32>) -> Option<i32> {
+    stock.map(|n| i32::try_from(n).unwrap_or(i32::MAX))
+}
+
 /// PostgreSQL catalog persistence. Each method runs on the given [Executor] (`&PgPool`, `&mut Transaction`, …).
 pub struct CatalogItemRepository;
 
@@ -52,13 +78,43 @@ pub struct CatalogItemRepository;
 #[derive(Error, Debug)]
 pub enum RepositoryError {
     #[error("database error: {0}")]
-    Db(#[from] sqlx::Error),
+    Db(#[source] sqlx::Error),
 
     #[error("invalid category in row: {0}")]
     InvalidCategory(String),
+
+    #[error("invalid link kind in row: {0}")]
+    InvalidLinkKind(String),
+
+    #[error("invalid moderation state in row: {0}")]
+    InvalidModerationState(String),
+
+    #[error("invalid role in row: {0}")]
+    InvalidRole(String),
+
+    #[error("invalid change type in row: {0}")]
+    InvalidChangeType(String),
+
+    #[error("invalid delivery status in row: {0}")]
+    InvalidDeliveryStatus(String),
+
+    #[error("failed to serialize event: {0}")]
+    Serialization(#[source] serde_json::Error),
+}
+
+impl From<sqlx::Error> for RepositoryError {
+    fn from(err: sqlx::Error) -> Self {
+        app_metrics::record_db_error(&err);
+        RepositoryError::Db(err)
+    }
 }
 
 impl CatalogItemRepository {
+    #[tracing::instrument(
+        name = "CatalogItemRepository::create",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "INSERT", db.sql.table = "catalog_items")
+    )]
     pub async fn create(
         executor: impl Executor<'_, Database = Postgres>,
         item: &CatalogItem,
@@ -68,19 +124,22 @@ impl CatalogItemRepository {
             INSERT INTO catalog_items (
                 item_id,
                 name,
+                slug,
                 description,
                 category,
                 date,
                 brand,
                 price,
                 created_at,
-                modified_at
+                modified_at,
+                stock
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             "#,
         )
         .bind(item.item_id)
         .bind(&item.name)
+        .bind(&item.slug)
         .bind(&item.description)
         .bind(item.category.to_string())
         .bind(item.date)
@@ -88,11 +147,17 @@ impl CatalogItemRepository {
         .bind(item.price)
         .bind(item.created_at.naive_utc())
         .bind(item.modified_at.naive_utc())
+        .bind(stock_column(item.stock))
         .execute(executor)
         .await?;
         Ok(())
     }
 
+    #[tracing::instrument(
+        name = "CatalogItemRepository::get",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT", db.sql.table = "catalog_items")
+    )]
     pub async fn get(
         executor: impl Executor<'_, Database = Postgres>,
         item_id: Uuid,
@@ -102,15 +167,60 @@ impl CatalogItemRepository {
             SELECT
                 item_id,
                 name,
+                slug,
+                description,
+                category,
+                date,
+                brand,
+                price,
+                created_at,
+                modified_at,
+                rating_count,
+                rating_average,
+                rating_histogram,
+                stock
+            FROM catalog_items
+            WHERE item_id = $1
+            "#,
+        )
+        .bind(item_id)
+        .fetch_optional(executor)
+        .await?;
+
+        row.map(CatalogItemRow::into_catalog_item).transpose()
+    }
+
+    /// Like [CatalogItemRepository::get], but locks the row (`FOR UPDATE`) until the transaction
+    /// ends, so concurrent read-modify-write cycles on the item run one after the other.
+    #[tracing::instrument(
+        name = "CatalogItemRepository::get_for_update",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT", db.sql.table = "catalog_items")
+    )]
+    pub async fn get_for_update(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+    ) -> Result<Option<CatalogItem>, RepositoryError> {
+        let row = sqlx::query_as::<_, CatalogItemRow>(
+            r#"
+            SELECT
+                item_id,
+                name,
+                slug,
                 description,
                 category,
                 date,
                 brand,
                 price,
                 created_at,
-                modified_at
+                modified_at,
+                rating_count,
+                rating_average,
+                rating_histogram,
+                stock
             FROM catalog_items
             WHERE item_id = $1
+            FOR UPDATE
             "#,
         )
         .bind(item_id)
@@ -120,6 +230,51 @@ impl CatalogItemRepository {
         row.map(CatalogItemRow::into_catalog_item).transpose()
     }
 
+    /// The items among `item_ids` that exist, in no particular order.
+    #[tracing::instrument(
+        name = "CatalogItemRepository::get_many",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT", db.sql.table = "catalog_items")
+    )]
+    pub async fn get_many(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_ids: &[Uuid],
+    ) -> Result<Vec<CatalogItem>, RepositoryError> {
+        let rows = sqlx::query_as::<_, CatalogItemRow>(
+            r#"
+            SELECT
+                item_id,
+                name,
+                slug,
+                description,
+                category,
+                date,
+                brand,
+                price,
+                created_at,
+                modified_at,
+                rating_count,
+                rating_average,
+                rating_histogram,
+                stock
+            FROM catalog_items
+            WHERE item_id = ANY($1)
+            "#,
+        )
+        .bind(item_ids)
+        .fetch_all(executor)
+        .await?;
+
+        rows.into_iter()
+            .map(CatalogItemRow::into_catalog_item)
+            .collect()
+    }
+
+    #[tracing::instrument(
+        name = "CatalogItemRepository::update",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "UPDATE", db.sql.table = "catalog_items")
+    )]
     pub async fn update(
         executor: impl Executor<'_, Database = Postgres>,
         item: &CatalogItem,
@@ -129,28 +284,37 @@ impl CatalogItemRepository {
             UPDATE catalog_items
             SET
                 name = $2,
-                description = $3,
-                category = $4,
-                date = $5,
-                brand = $6,
-                price = $7,
-                modified_at = $8
+                slug = $3,
+                description = $4,
+                category = $5,
+                date = $6,
+                brand = $7,
+                price = $8,
+                modified_at = $9,
+                stock = $10
             WHERE item_id = $1
             "#,
         )
         .bind(item.item_id)
         .bind(&item.name)
+        .bind(&item.slug)
         .bind(&item.description)
         .bind(item.category.to_string())
         .bind(item.date)
         .bind(&item.brand)
         .bind(item.price)
         .bind(item.modified_at.naive_utc())
+        .bind(stock_column(item.stock))
         .execute(executor)
         .await?;
         Ok(result.rows_affected() > 0)
     }
 
+    #[tracing::instrument(
+        name = "CatalogItemRepository::delete",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "DELETE", db.sql.table = "catalog_items")
+    )]
     pub async fn delete(
         executor: impl Executor<'_, Database = Postgres>,
         item_id: Uuid,
@@ -167,32 +331,59 @@ impl CatalogItemRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    /// A page of the items matching `filter`, in `sort` order.
+    #[tracing::instrument(
+        name = "CatalogItemRepository::search",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT", db.sql.table = "catalog_items")
+    )]
     pub async fn search(
         executor: impl Executor<'_, Database = Postgres>,
         page: Pagination,
+        sort: CatalogItemSort,
+        filter: &CatalogItemFilter,
     ) -> Result<CatalogItemSearchResponse, RepositoryError> {
         let limit = page.limit;
         let offset = page.offset;
+        let order_by = match sort {
+            CatalogItemSort::CreatedAt => "created_at, item_id",
+            CatalogItemSort::Rating => {
+                "rating_average DESC NULLS LAST, rating_count DESC, created_at, item_id"
+            }
+        };
 
-        let rows = sqlx::query_as::<_, CatalogItemRow>(
+        let rows = sqlx::query_as::<_, CatalogItemRow>(&format!(
             r#"
             SELECT
                 item_id,
                 name,
+                slug,
                 description,
                 category,
                 date,
                 brand,
                 price,
                 created_at,
-                modified_at
+                modified_at,
+                rating_count,
+                rating_average,
+                rating_histogram,
+                stock
             FROM catalog_items
-            ORDER BY created_at, item_id
+            WHERE ($3::VARCHAR IS NULL OR category = $3)
+                AND ($4::VARCHAR IS NULL OR brand = $4)
+                AND ($5::NUMERIC IS NULL OR price >= $5)
+                AND ($6::NUMERIC IS NULL OR price <= $6)
+            ORDER BY {order_by}
             LIMIT $1 OFFSET $2
-            "#,
-        )
+            "#
+        ))
         .bind((limit + 1) as i64)
         .bind(offset as i64)
+        .bind(filter.category.map(|category| category.to_string()))
+        .bind(filter.brand.as_deref())
+        .bind(filter.min_price)
+        .bind(filter.max_price)
         .fetch_all(executor)
         .await?;
 
@@ -207,4 +398,189 @@ impl CatalogItemRepository {
 
         Ok(CatalogItemSearchResponse::new(items, has_more))
     }
+
+    /// Up to `limit` items modified after `after` and before `before`, in (modified_at, item_id)
+    /// order.
+    #[tracing::instrument(
+        name = "CatalogItemRepository::list_modified_after",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT", db.sql.table = "catalog_items")
+    )]
+    pub async fn list_modified_after(
+        executor: impl Executor<'_, Database = Postgres>,
+        after: SyncWatermark,
+        before: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<CatalogItem>, RepositoryError> {
+        let rows = sqlx::query_as::<_, CatalogItemRow>(
+            r#"
+            SELECT
+                item_id,
+                name,
+                slug,
+                description,
+                category,
+                date,
+                brand,
+                price,
+                created_at,
+                modified_at,
+                rating_count,
+                rating_average,
+                rating_histogram,
+                stock
+            FROM catalog_items
+            WHERE (modified_at, item_id) > ($1, $2) AND modified_at < $3
+            ORDER BY modified_at, item_id
+            LIMIT $4
+            "#,
+        )
+        .bind(after.modified_at.naive_utc())
+        .bind(after.item_id)
+        .bind(before.naive_utc())
+        .bind(i64::from(limit))
+        .fetch_all(executor)
+        .await?;
+
+        rows.into_iter()
+            .map(CatalogItemRow::into_catalog_item)
+            .collect()
+    }
+
+    /// Database time before which every item change is committed: the current time, or the start
+    /// of the oldest transaction that has written and is still running. Only sessions of the
+    /// same role are seen, as with the service's own connections.
+    #[tracing::instrument(
+        name = "CatalogItemRepository::committed_before",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT", db.sql.table = "pg_stat_activity")
+    )]
+    pub async fn committed_before(
+        executor: impl Executor<'_, Database = Postgres>,
+    ) -> Result<DateTime<Utc>, RepositoryError> {
+        // LEAST ignores the NULL when no other transaction is writing
+        let horizon: NaiveDateTime = sqlx::query_scalar(
+            r#"
+            SELECT LEAST(
+                clock_timestamp(),
+                (
+                    SELECT MIN(xact_start)
+                    FROM pg_stat_activity
+                    WHERE datname = current_database()
+                        AND backend_xid IS NOT NULL
+                        AND pid <> pg_backend_pid()
+                )
+            ) AT TIME ZONE 'UTC'
+            "#,
+        )
+        .fetch_one(executor)
+        .await?;
+        Ok(DateTime::<Utc>::from_naive_utc_and_offset(horizon, Utc))
+    }
+
+    /// Look up an item by any slug it has had (canonical or previous).
+    #[tracing::instrument(
+        name = "CatalogItemRepository::get_by_slug",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT", db.sql.table = "catalog_item_slugs")
+    )]
+    pub async fn get_by_slug(
+        executor: impl Executor<'_, Database = Postgres>,
+        slug: &str,
+    ) -> Result<Option<CatalogItem>, RepositoryError> {
+        let row = sqlx::query_as::<_, CatalogItemRow>(
+            r#"
+            SELECT
+                i.item_id,
+                i.name,
+                i.slug,
+                i.description,
+                i.category,
+                i.date,
+                i.brand,
+                i.price,
+                i.created_at,
+                i.modified_at,
+                i.rating_count,
+                i.rating_average,
+                i.rating_histogram,
+                i.stock
+            FROM catalog_item_slugs s
+            JOIN catalog_items i ON i.item_id = s.item_id
+            WHERE s.slug = $1
+            "#,
+        )
+        .bind(slug)
+        .fetch_optional(executor)
+        .await?;
+
+        row.map(CatalogItemRow::into_catalog_item).transpose()
+    }
+
+    /// Serialize slug allocation for `base` until the end of the current transaction.
+    #[tracing::instrument(
+        name = "CatalogItemRepository::lock_slug_base",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT", db.sql.table = "catalog_item_slugs")
+    )]
+    pub async fn lock_slug_base(
+        executor: impl Executor<'_, Database = Postgres>,
+        base: &str,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(base)
+            .execute(executor)
+            .await?;
+        Ok(())
+    }
+
+    /// Slugs (current or previous, of any item) that are `base` or start with `base-`, with their owners.
+    #[tracing::instrument(
+        name = "CatalogItemRepository::slugs_with_prefix",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT", db.sql.table = "catalog_item_slugs")
+    )]
+    pub async fn slugs_with_prefix(
+        executor: impl Executor<'_, Database = Postgres>,
+        base: &str,
+    ) -> Result<Vec<(String, Uuid)>, RepositoryError> {
+        let rows = sqlx::query_as::<_, (String, Uuid)>(
+            r#"
+            SELECT slug, item_id
+            FROM catalog_item_slugs
+            WHERE slug = $1 OR slug LIKE $1 || '-%'
+            "#,
+        )
+        .bind(base)
+        .fetch_all(executor)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Record `slug` as belonging to `item_id`. No-op if it already does.
+    #[tracing::instrument(
+        name = "CatalogItemRepository::add_slug",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "INSERT", db.sql.table = "catalog_item_slugs")
+    )]
+    pub async fn add_slug(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+        slug: &str,
+        created_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO catalog_item_slugs (slug, item_id, created_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (slug) DO NOTHING
+            "#,
+        )
+        .bind(slug)
+        .bind(item_id)
+        .bind(created_at.naive_utc())
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
 }
@@ -1,15 +1,31 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use chrono::{NaiveDate, Utc};
-use rust_decimal::Decimal;
-use sqlx::PgPool;
+use rust_decimal::{Decimal, RoundingStrategy};
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
+use crate::app_metrics;
 use crate::catalog::api::{
-    CatalogItem, CatalogServiceApi, CatalogServiceError, CreateCatalogItemBody,
-    ListCatalogItemsRequest, ListCatalogItemsResponse, UpdateCatalogItemBody,
+    CatalogItem, CatalogItemFilter, CatalogItemSort, CatalogServiceApi, CatalogServiceError,
+    CreateCatalogItemBody, ItemChangeType, ListCatalogItemsRequest, ListCatalogItemsResponse,
+    RatingSummary, SupplyUpdate, SupplyUpdateOutcome, UpdateCatalogItemBody,
 };
+use crate::catalog::cache::ItemCache;
+use crate::catalog::persistence::changes::ItemChangeRepository;
 use crate::catalog::persistence::{CatalogItemRepository, RepositoryError};
 use crate::common::pagination::Pagination;
+use crate::common::slug;
+use crate::common::validation::ValidationErrors;
+use crate::events::inbox::InboxRepository;
+use crate::events::outbox::OutboxRepository;
+use crate::events::{CatalogEvent, EventEnvelope, ItemCreated, ItemDeleted, ItemUpdated};
+use crate::media::service::MediaService;
+use crate::webhooks::persistence::WebhookDeliveryRepository;
+
+/// Maximum length (in characters) of an item name or brand; matches the `VARCHAR(255)` columns.
+const MAX_TEXT_LEN: usize = 255;
 
 impl From<RepositoryError> for CatalogServiceError {
     fn from(err: RepositoryError) -> Self {
@@ -18,14 +34,44 @@ impl From<RepositoryError> for CatalogServiceError {
 }
 
 /// CRUD service for catalog items, using [CatalogItemRepository] against [PgPool].
+///
+/// Reads by id go through the [ItemCache]; writes invalidate what they change, and record
+/// [CatalogEvent]s in the outbox (unless disabled with [CatalogService::with_outbox]), webhook
+/// deliveries and entries in the item change log within their transaction.
 #[derive(Clone)]
 pub struct CatalogService {
     pg_pool: PgPool,
+    cache: ItemCache,
+    /// Tenant stamped on published events.
+    tenant: String,
+    /// Whether writes append their events to the outbox.
+    outbox: bool,
+    /// Media of deleted items, whose blobs are purged after the delete.
+    media: Option<MediaService>,
 }
 
 impl CatalogService {
-    pub fn new(pg_pool: PgPool) -> Self {
-        Self { pg_pool }
+    pub fn new(pg_pool: PgPool, cache: ItemCache, tenant: impl Into<String>) -> Self {
+        Self {
+            pg_pool,
+            cache,
+            tenant: tenant.into(),
+            outbox: true,
+            media: None,
+        }
+    }
+
+    /// Record events in the outbox or not. Turn it off when no relay publishes (and purges)
+    /// them, so the outbox does not grow without bound.
+    pub fn with_outbox(mut self, enabled: bool) -> Self {
+        self.outbox = enabled;
+        self
+    }
+
+    /// Purge the media blobs of deleted items from `media`'s blob store.
+    pub fn with_media(mut self, media: MediaService) -> Self {
+        self.media = Some(media);
+        self
     }
 
     pub fn pg_pool(&self) -> &PgPool {
@@ -38,12 +84,17 @@ impl CatalogService {
         body: CreateCatalogItemBody,
     ) -> Result<CatalogItem, CatalogServiceError> {
         let item_id = Uuid::new_v4();
+        let date = validate_item_fields(&body.name, body.brand.as_deref(), body.price, &body.date)
+            .map_err(CatalogServiceError::ValidationError)?;
+        let mut tx = self.pg_pool.begin().await.map_err(RepositoryError::from)?;
+        let slug = allocate_slug(&mut tx, item_id, &body.name, None).await?;
+        // After waiting for the slug lock: until its first write the transaction is not seen by
+        // sync's commit horizon, so an earlier timestamp could fall behind a sync watermark
         let now = Utc::now();
-        let date = NaiveDate::parse_from_str(&body.date, "%Y-%m-%d")
-            .map_err(|e| CatalogServiceError::ValidationError(Box::new(e)))?;
         let item = CatalogItem {
             item_id,
             name: body.name,
+            slug,
             description: body.description,
             category: body.category,
             date,
@@ -51,27 +102,95 @@ impl CatalogService {
             price: body.price,
             created_at: now,
             modified_at: now,
+            rating: RatingSummary::empty(),
+            stock: None,
         };
 
-        CatalogItemRepository::create(&self.pg_pool, &item).await?;
+        CatalogItemRepository::create(&mut *tx, &item).await?;
+        CatalogItemRepository::add_slug(&mut *tx, item_id, &item.slug, now).await?;
+        ItemChangeRepository::append(&mut *tx, item_id, ItemChangeType::Created, now).await?;
+        self.record_event(
+            &mut tx,
+            CatalogEvent::ItemCreated(ItemCreated { item: item.clone() }),
+        )
+        .await?;
+        tx.commit().await.map_err(RepositoryError::from)?;
+        metrics::counter!(app_metrics::ITEMS_CREATED_TOTAL).increment(1);
         Ok(item)
     }
 
     /// Get a catalog item by id, if it exists.
     pub async fn get(&self, item_id: Uuid) -> Result<Option<CatalogItem>, CatalogServiceError> {
-        Ok(CatalogItemRepository::get(&self.pg_pool, item_id).await?)
+        if let Some(item) = self.cache.get(item_id) {
+            return Ok(Some(item));
+        }
+        let generation = self.cache.generation();
+        let item = CatalogItemRepository::get(&self.pg_pool, item_id).await?;
+        self.cache.insert(item.clone(), generation);
+        Ok(item)
+    }
+
+    /// Get the existing items among `item_ids`, keyed by id, loading cache misses in one query.
+    pub async fn get_many(
+        &self,
+        item_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, CatalogItem>, CatalogServiceError> {
+        let mut items = HashMap::with_capacity(item_ids.len());
+        let mut missing = Vec::new();
+        for &item_id in item_ids {
+            if items.contains_key(&item_id) || missing.contains(&item_id) {
+                continue;
+            }
+            match self.cache.get(item_id) {
+                Some(item) => {
+                    items.insert(item_id, item);
+                }
+                None => missing.push(item_id),
+            }
+        }
+        if !missing.is_empty() {
+            let generation = self.cache.generation();
+            let loaded = CatalogItemRepository::get_many(&self.pg_pool, &missing).await?;
+            self.cache.insert(loaded.iter().cloned(), generation);
+            items.extend(loaded.into_iter().map(|item| (item.item_id, item)));
+        }
+        Ok(items)
+    }
+
+    /// Get a catalog item by its current or a previous slug, if it exists.
+    /// Callers compare against [CatalogItem::slug] to detect an outdated slug.
+    pub async fn get_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<CatalogItem>, CatalogServiceError> {
+        Ok(CatalogItemRepository::get_by_slug(&self.pg_pool, slug).await?)
     }
 
     /// List catalog items with optional offset-based pagination.
     pub async fn list(
         &self,
         req: ListCatalogItemsRequest,
+    ) -> Result<ListCatalogItemsResponse, CatalogServiceError> {
+        self.search(req, CatalogItemFilter::default()).await
+    }
+
+    /// List the catalog items matching `filter`, like [CatalogService::list].
+    pub async fn search(
+        &self,
+        req: ListCatalogItemsRequest,
+        filter: CatalogItemFilter,
     ) -> Result<ListCatalogItemsResponse, CatalogServiceError> {
         let limit = req.limit.unwrap_or(100).clamp(1, 100);
         let offset = req.offset.unwrap_or(0);
+        let sort = req.sort.unwrap_or_default();
 
-        let search =
-            CatalogItemRepository::search(&self.pg_pool, Pagination { limit, offset }).await?;
+        let search = CatalogItemRepository::search(
+            &self.pg_pool,
+            Pagination { limit, offset },
+            sort,
+            &filter,
+        )
+        .await?;
         Ok(ListCatalogItemsResponse::from_paginated(
             search,
             Pagination { limit, offset },
@@ -84,13 +203,17 @@ impl CatalogService {
         item_id: Uuid,
         body: UpdateCatalogItemBody,
     ) -> Result<Option<CatalogItem>, CatalogServiceError> {
-        let date = NaiveDate::parse_from_str(&body.date, "%Y-%m-%d")
-            .map_err(|e| CatalogServiceError::ValidationError(Box::new(e)))?;
+        let date = validate_item_fields(&body.name, body.brand.as_deref(), body.price, &body.date)
+            .map_err(CatalogServiceError::ValidationError)?;
 
-        let existing = CatalogItemRepository::get(&self.pg_pool, item_id).await?;
+        let mut tx = self.pg_pool.begin().await.map_err(RepositoryError::from)?;
+        // Locked, so a concurrent supplier update is applied before or after this one, not lost
+        let existing = CatalogItemRepository::get_for_update(&mut *tx, item_id).await?;
         let Some(mut item) = existing else {
             return Ok(None);
         };
+        let old_price = item.price;
+        item.slug = allocate_slug(&mut tx, item_id, &body.name, Some(&item.slug)).await?;
         item.name = body.name;
         item.description = body.description;
         item.category = body.category;
@@ -98,34 +221,136 @@ impl CatalogService {
         item.brand = body.brand;
         item.price = body.price;
         item.modified_at = Utc::now();
-        let updated = CatalogItemRepository::update(&self.pg_pool, &item).await?;
-        if updated { Ok(Some(item)) } else { Ok(None) }
+        let updated = CatalogItemRepository::update(&mut *tx, &item).await?;
+        if !updated {
+            return Ok(None);
+        }
+        CatalogItemRepository::add_slug(&mut *tx, item_id, &item.slug, item.modified_at).await?;
+        ItemChangeRepository::append(&mut *tx, item_id, ItemChangeType::Updated, item.modified_at)
+            .await?;
+        self.record_event(
+            &mut tx,
+            CatalogEvent::ItemUpdated(ItemUpdated { item: item.clone() }),
+        )
+        .await?;
+        if let Some(event) = CatalogEvent::price_change(item_id, old_price, item.price) {
+            self.record_event(&mut tx, event).await?;
+        }
+        self.cache
+            .publish_invalidation(&mut *tx, Some(item_id))
+            .await
+            .map_err(RepositoryError::from)?;
+        tx.commit().await.map_err(RepositoryError::from)?;
+        self.cache.invalidate(item_id);
+        metrics::counter!(app_metrics::ITEMS_UPDATED_TOTAL).increment(1);
+        Ok(Some(item))
     }
 
-    /// Delete a catalog item. Returns true if it existed and was removed.
+    /// Delete a catalog item with its media: the rows go with the database cascade, the blobs are
+    /// purged afterwards (best effort, see [CatalogService::with_media]). Returns true if it
+    /// existed and was removed.
     pub async fn delete(&self, item_id: Uuid) -> Result<bool, CatalogServiceError> {
-        Ok(CatalogItemRepository::delete(&self.pg_pool, item_id).await?)
+        let mut tx = self.pg_pool.begin().await.map_err(RepositoryError::from)?;
+        let deleted = CatalogItemRepository::delete(&mut *tx, item_id).await?;
+        if !deleted {
+            return Ok(false);
+        }
+        ItemChangeRepository::append(&mut *tx, item_id, ItemChangeType::Deleted, Utc::now())
+            .await?;
+        self.record_event(&mut tx, CatalogEvent::ItemDeleted(ItemDeleted { item_id }))
+            .await?;
+        self.cache
+            .publish_invalidation(&mut *tx, Some(item_id))
+            .await
+            .map_err(RepositoryError::from)?;
+        tx.commit().await.map_err(RepositoryError::from)?;
+        self.cache.invalidate(item_id);
+        metrics::counter!(app_metrics::ITEMS_DELETED_TOTAL).increment(1);
+        if let Some(media) = &self.media
+            && let Err(err) = media.purge_item(item_id).await
+        {
+            // Only leaks storage; the item is gone either way
+            tracing::warn!("failed to purge media blobs of item {item_id}: {err}");
+        }
+        Ok(true)
     }
 
-    /// Multiply every stored item's price by `multiplier` (e.g. `1.1` for a 10% increase).
-    /// Runs inside a single SQL transaction.
-    pub async fn increase_prices(&self, multiplier: Decimal) -> Result<u32, CatalogServiceError> {
+    /// Apply a supplier-reported price and/or stock once per `message_id` from `source`: the
+    /// message is recorded in the inbox in the same transaction, so redeliveries are skipped. The
+    /// item stays locked from read to write, so concurrent API edits are kept.
+    pub async fn apply_supply_update(
+        &self,
+        source: &str,
+        message_id: &str,
+        update: SupplyUpdate,
+    ) -> Result<SupplyUpdateOutcome, CatalogServiceError> {
+        validate_supply_update(&update).map_err(CatalogServiceError::ValidationError)?;
+
+        let mut tx = self.pg_pool.begin().await.map_err(RepositoryError::from)?;
+        if !InboxRepository::record(&mut *tx, source, message_id).await? {
+            return Ok(SupplyUpdateOutcome::Duplicate);
+        }
+        let Some(mut item) =
+            CatalogItemRepository::get_for_update(&mut *tx, update.item_id).await?
+        else {
+            return Ok(SupplyUpdateOutcome::NotFound);
+        };
+        let old_price = item.price;
+        let price = update.price.unwrap_or(item.price);
+        let stock = update.stock.or(item.stock);
+        if price == item.price && stock == item.stock {
+            tx.commit().await.map_err(RepositoryError::from)?;
+            return Ok(SupplyUpdateOutcome::Applied(Box::new(item)));
+        }
+        item.price = price;
+        item.stock = stock;
+        item.modified_at = Utc::now();
+        CatalogItemRepository::update(&mut *tx, &item).await?;
+        ItemChangeRepository::append(
+            &mut *tx,
+            item.item_id,
+            ItemChangeType::Updated,
+            item.modified_at,
+        )
+        .await?;
+        self.record_event(
+            &mut tx,
+            CatalogEvent::ItemUpdated(ItemUpdated { item: item.clone() }),
+        )
+        .await?;
+        if let Some(event) = CatalogEvent::price_change(item.item_id, old_price, item.price) {
+            self.record_event(&mut tx, event).await?;
+        }
+        self.cache
+            .publish_invalidation(&mut *tx, Some(item.item_id))
+            .await
+            .map_err(RepositoryError::from)?;
+        tx.commit().await.map_err(RepositoryError::from)?;
+        self.cache.invalidate(item.item_id);
+        metrics::counter!(app_metrics::ITEMS_UPDATED_TOTAL).increment(1);
+        Ok(SupplyUpdateOutcome::Applied(Box::new(item)))
+    }
+
+    /// Multiply the price of every item matching `filter` by `multiplier` (e.g. `1.1` for a 10%
+    /// increase), rounded to cents. Runs inside a single SQL transaction, each item locked from
+    /// read to write; items whose price does not change are skipped. Returns the repriced items.
+    pub async fn increase_prices(
+        &self,
+        multiplier: Decimal,
+        filter: &CatalogItemFilter,
+    ) -> Result<Vec<CatalogItem>, CatalogServiceError> {
         if multiplier <= Decimal::ZERO {
-            return Err(CatalogServiceError::ValidationError(Box::new(
-                std::io::Error::new(
-                    std::io::ErrorKind::InvalidInput,
-                    "multiplier must be greater than zero",
-                ),
-            )));
+            return Err(CatalogServiceError::ValidationError(
+                ValidationErrors::single("multiplier", "must be greater than zero"),
+            ));
         }
 
-        let mult = multiplier;
         let mut tx = self.pg_pool.begin().await.map_err(RepositoryError::from)?;
 
+        // Collect the matches first: repricing may move items in or out of a price filter
+        let mut item_ids = Vec::new();
         let mut offset: u32 = 0;
-        let mut updated: u32 = 0;
         const PAGE: u32 = 100;
-
         loop {
             let page = CatalogItemRepository::search(
                 &mut *tx,
@@ -133,33 +358,176 @@ impl CatalogService {
                     limit: PAGE,
                     offset,
                 },
+                CatalogItemSort::CreatedAt,
+                filter,
             )
             .await?;
-
-            if page.items.is_empty() {
+            item_ids.extend(page.items.iter().map(|item| item.item_id));
+            if !page.has_more {
                 break;
             }
+            offset += PAGE;
+        }
 
-            let batch_modified_at = Utc::now();
-            for mut item in page.items {
-                let new_price = item.price * mult;
-                item.price = new_price;
-                item.modified_at = batch_modified_at;
-                CatalogItemRepository::update(&mut *tx, &item).await?;
-                updated += 1;
+        let modified_at = Utc::now();
+        let mut repriced = Vec::new();
+        for item_id in item_ids {
+            let Some(mut item) = CatalogItemRepository::get_for_update(&mut *tx, item_id).await?
+            else {
+                // Deleted since listed
+                continue;
+            };
+            let old_price = item.price;
+            // As NUMERIC(10, 2) stores it, so the event carries the stored price
+            let new_price = (item.price * multiplier)
+                .round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero);
+            if new_price == old_price {
+                continue;
             }
-
-            if !page.has_more {
-                break;
+            item.price = new_price;
+            item.modified_at = modified_at;
+            CatalogItemRepository::update(&mut *tx, &item).await?;
+            ItemChangeRepository::append(&mut *tx, item_id, ItemChangeType::Updated, modified_at)
+                .await?;
+            self.record_event(
+                &mut tx,
+                CatalogEvent::ItemUpdated(ItemUpdated { item: item.clone() }),
+            )
+            .await?;
+            if let Some(event) = CatalogEvent::price_change(item_id, old_price, new_price) {
+                self.record_event(&mut tx, event).await?;
             }
-            offset += PAGE;
+            repriced.push(item);
         }
 
+        self.cache
+            .publish_invalidation(&mut *tx, None)
+            .await
+            .map_err(RepositoryError::from)?;
         tx.commit().await.map_err(RepositoryError::from)?;
-        Ok(updated)
+        self.cache.invalidate_all();
+        metrics::counter!(app_metrics::REPRICE_RUNS_TOTAL).increment(1);
+        metrics::counter!(app_metrics::ITEMS_REPRICED_TOTAL).increment(repriced.len() as u64);
+        Ok(repriced)
+    }
+
+    /// Write `event` to the outbox (if enabled) and queue it for matching webhook subscriptions
+    /// on `conn`, inside the transaction making the change.
+    async fn record_event(
+        &self,
+        conn: &mut PgConnection,
+        event: CatalogEvent,
+    ) -> Result<(), RepositoryError> {
+        let envelope = EventEnvelope::new(self.tenant.clone(), event);
+        if self.outbox {
+            OutboxRepository::append(&mut *conn, &envelope).await?;
+        }
+        WebhookDeliveryRepository::enqueue(conn, &envelope).await
     }
 }
 
+/// Check the user-editable fields shared by create and update, collecting every violation.
+/// Returns the parsed `date` when all fields are valid.
+fn validate_item_fields(
+    name: &str,
+    brand: Option<&str>,
+    price: Decimal,
+    date: &str,
+) -> Result<NaiveDate, ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+
+    if name.trim().is_empty() {
+        errors.add("name", "must not be empty");
+    } else if name.chars().count() > MAX_TEXT_LEN {
+        errors.add("name", format!("must be at most {MAX_TEXT_LEN} characters"));
+    }
+    if brand.is_some_and(|brand| brand.chars().count() > MAX_TEXT_LEN) {
+        errors.add(
+            "brand",
+            format!("must be at most {MAX_TEXT_LEN} characters"),
+        );
+    }
+
+    validate_price(price, &mut errors);
+
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+    if date.is_none() {
+        errors.add("date", "must be a valid date in YYYY-MM-DD format");
+    }
+
+    match date {
+        Some(date) if errors.is_empty() => Ok(date),
+        _ => Err(errors),
+    }
+}
+
+/// Check a supplier update: at least one field, a storable price and an `INTEGER` stock.
+fn validate_supply_update(update: &SupplyUpdate) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    if update.price.is_none() && update.stock.is_none() {
+        errors.add("price", "price or stock is required");
+    }
+    if let Some(price) = update.price {
+        validate_price(price, &mut errors);
+    }
+    if update
+        .stock
+        .is_some_and(|stock| i32::try_from(stock).is_err())
+    {
+        errors.add("stock", format!("must be at most {}", i32::MAX));
+    }
+    errors.into_result(())
+}
+
+fn validate_price(price: Decimal, errors: &mut ValidationErrors) {
+    // price is stored as NUMERIC(10, 2)
+    if price < Decimal::ZERO {
+        errors.add("price", "must not be negative");
+    } else if price.normalize().scale() > 2 {
+        errors.add("price", "must have at most 2 decimal places");
+    } else if price >= Decimal::new(100_000_000, 0) {
+        errors.add("price", "must be less than 100000000");
+    }
+}
+
+/// Pick the slug for item `item_id` named `name`. Keeps `current` while it still matches the name,
+/// reuses a previous slug of the same item, and otherwise takes the first free of `base`, `base-2`, ….
+/// Slugs stay reserved after renames so old links keep redirecting to the right item.
+async fn allocate_slug(
+    conn: &mut PgConnection,
+    item_id: Uuid,
+    name: &str,
+    current: Option<&str>,
+) -> Result<String, RepositoryError> {
+    let base = slug::slugify(name);
+    if let Some(current) = current.filter(|current| slug::suffix_of(current, &base).is_some()) {
+        return Ok(current.to_string());
+    }
+
+    CatalogItemRepository::lock_slug_base(&mut *conn, &base).await?;
+    let mut taken = Vec::new();
+    for (existing, owner) in CatalogItemRepository::slugs_with_prefix(&mut *conn, &base).await? {
+        let Some(n) = slug::suffix_of(&existing, &base) else {
+            continue;
+        };
+        if owner == item_id {
+            return Ok(existing);
+        }
+        taken.push(n);
+    }
+    taken.sort_unstable();
+
+    let mut n = 1;
+    for used in taken {
+        if used == n {
+            n += 1;
+        } else if used > n {
+            break;
+        }
+    }
+    Ok(slug::with_suffix(&base, n))
+}
+
 #[async_trait]
 impl CatalogServiceApi for CatalogService {
     async fn create(
@@ -173,6 +541,13 @@ impl CatalogServiceApi for CatalogService {
         CatalogService::get(self, item_id).await
     }
 
+    async fn get_many(
+        &self,
+        item_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, CatalogItem>, CatalogServiceError> {
+        CatalogService::get_many(self, item_ids).await
+    }
+
     async fn list(
         &self,
         req: ListCatalogItemsRequest,
@@ -180,6 +555,14 @@ impl CatalogServiceApi for CatalogService {
         CatalogService::list(self, req).await
     }
 
+    async fn search(
+        &self,
+        req: ListCatalogItemsRequest,
+        filter: CatalogItemFilter,
+    ) -> Result<ListCatalogItemsResponse, CatalogServiceError> {
+        CatalogService::search(self, req, filter).await
+    }
+
     async fn update(
         &self,
         item_id: Uuid,
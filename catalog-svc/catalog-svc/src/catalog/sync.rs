@@ -0,0 +1,154 @@
+//! Incremental sync of catalog items, served by `GET /catalog/items/sync`.
+//!
+//! Clients keep a [SyncWatermark]: the (modified_at, item_id) of the last change they got. Items
+//! are read from `catalog_items` and deletions from the item change log in that order, so changes
+//! with the same timestamp are neither skipped nor repeated across pages.
+//!
+//! `modified_at` is taken from the writing instance's clock before its transaction commits, so a
+//! change may become visible after newer ones were returned. Changes are therefore only returned
+//! once they are older than [CatalogItemRepository::committed_before] minus the maximum clock skew:
+//! nothing can still commit before that point.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::app_config::{ItemChangesConfig, ItemSyncConfig};
+use crate::catalog::api::{CatalogItem, ItemSyncResponse, SyncWatermark};
+use crate::catalog::persistence::changes::ItemChangeRepository;
+use crate::catalog::persistence::{CatalogItemRepository, RepositoryError};
+
+/// Page size when the request has none.
+const DEFAULT_LIMIT: u32 = 100;
+/// Largest page size.
+const MAX_LIMIT: u32 = 1000;
+
+/// Errors from [ItemSync::sync].
+#[derive(Error, Debug)]
+pub enum SyncError {
+    /// Deletions after the watermark may have been purged from the change log.
+    #[error("sync watermark is older than the change log retention")]
+    WatermarkExpired,
+
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+}
+
+/// Reads item changes after a [SyncWatermark].
+#[derive(Clone)]
+pub struct ItemSync {
+    pg_pool: PgPool,
+    max_clock_skew: Duration,
+    /// How long deletions stay in the change log.
+    retention: Duration,
+}
+
+impl ItemSync {
+    pub fn new(pg_pool: PgPool, cfg: &ItemSyncConfig, changes: &ItemChangesConfig) -> Self {
+        Self {
+            pg_pool,
+            max_clock_skew: Duration::from_millis(cfg.max_clock_skew_ms),
+            retention: Duration::from_secs(changes.retention_secs),
+        }
+    }
+
+    /// Up to `limit` changes after `since`, oldest first: items in their current state, and ids of
+    /// deleted items. Without `since`, every item (and no deletion) is returned.
+    pub async fn sync(
+        &self,
+        since: Option<SyncWatermark>,
+        limit: Option<u32>,
+    ) -> Result<ItemSyncResponse, SyncError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let retained_since = earlier(Utc::now(), self.retention);
+        if since.is_some_and(|since| since.modified_at < retained_since) {
+            return Err(SyncError::WatermarkExpired);
+        }
+        let after = since.unwrap_or(SyncWatermark::START);
+        let before = earlier(
+            CatalogItemRepository::committed_before(&self.pg_pool).await?,
+            self.max_clock_skew,
+        );
+
+        // One more of each than fits, to tell whether changes remain
+        let items =
+            CatalogItemRepository::list_modified_after(&self.pg_pool, after, before, limit + 1)
+                .await?;
+        let deletions = match since {
+            Some(_) => {
+                ItemChangeRepository::list_deleted_after(&self.pg_pool, after, before, limit + 1)
+                    .await?
+            }
+            None => Vec::new(),
+        };
+        Ok(merge(after, before, limit, items, deletions))
+    }
+}
+
+/// The first `limit` of `items` and `deletions` (each in watermark order) and the watermark after
+/// them: the last one returned, or `before` once nothing remains.
+fn merge(
+    after: SyncWatermark,
+    before: DateTime<Utc>,
+    limit: u32,
+    items: Vec<CatalogItem>,
+    deletions: Vec<SyncWatermark>,
+) -> ItemSyncResponse {
+    let mut items = items.into_iter().peekable();
+    let mut deletions = deletions.into_iter().peekable();
+    let mut response = ItemSyncResponse {
+        items: Vec::new(),
+        deleted_item_ids: Vec::new(),
+        watermark: String::new(),
+        has_more: false,
+    };
+    let mut watermark = after;
+    for _ in 0..limit {
+        let next_item = items.peek().map(item_watermark);
+        match (next_item, deletions.peek().copied()) {
+            (Some(item), Some(deleted)) if deleted < item => {
+                watermark = deleted;
+                response.deleted_item_ids.push(deleted.item_id);
+                deletions.next();
+            }
+            (Some(item), _) => {
+                watermark = item;
+                response.items.extend(items.next());
+            }
+            (None, Some(deleted)) => {
+                watermark = deleted;
+                response.deleted_item_ids.push(deleted.item_id);
+                deletions.next();
+            }
+            (None, None) => break,
+        }
+    }
+    response.has_more = items.peek().is_some() || deletions.peek().is_some();
+    if !response.has_more {
+        // Every change before `before` was returned; later ones all sort after this
+        watermark = watermark.max(SyncWatermark {
+            modified_at: before,
+            item_id: Uuid::nil(),
+        });
+    }
+    response.watermark = watermark.to_string();
+    response
+}
+
+fn item_watermark(item: &CatalogItem) -> SyncWatermark {
+    SyncWatermark {
+        modified_at: item.modified_at,
+        item_id: item.item_id,
+    }
+}
+
+/// `time` minus `duration`, saturating at the earliest representable time.
+fn earlier(time: DateTime<Utc>, duration: Duration) -> DateTime<Utc> {
+    chrono::Duration::from_std(duration)
+        .ok()
+        .and_then(|duration| time.checked_sub_signed(duration))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
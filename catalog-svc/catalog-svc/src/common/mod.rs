@@ -1 +1,3 @@
 pub mod pagination;
+pub mod slug;
+pub mod validation;
@@ -1,10 +1,11 @@
 //! Offset-based pagination shared across HTTP and persistence layers in this service.
 
+use async_graphql::SimpleObject;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 /// Page window: maximum rows and zero-based starting offset (request body/query or echoed in list responses).
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
 #[serde(rename_all = "camelCase")]
 pub struct Pagination {
     pub limit: u32,
@@ -0,0 +1,51 @@
+//! URL-safe slugs derived from human-readable names.
+
+use deunicode::deunicode;
+
+/// Maximum length of a slug before any collision suffix is appended.
+pub const MAX_SLUG_LEN: usize = 80;
+
+/// Slug used when a name has no transliterable alphanumeric characters.
+const FALLBACK_SLUG: &str = "item";
+
+/// Turn `name` into a lowercase ASCII slug: Unicode is transliterated (`Crème Brûlée` → `creme-brulee`),
+/// runs of other characters become single dashes, and the result is capped at [MAX_SLUG_LEN].
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for ch in deunicode(name).chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(MAX_SLUG_LEN);
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        FALLBACK_SLUG.to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Collision suffix of `slug` relative to `base`: 1 for `base` itself, N for `base-N` (N ≥ 2).
+pub fn suffix_of(slug: &str, base: &str) -> Option<u32> {
+    let rest = slug.strip_prefix(base)?;
+    if rest.is_empty() {
+        return Some(1);
+    }
+    let n = rest.strip_prefix('-')?;
+    if n.starts_with('0') {
+        return None;
+    }
+    n.parse::<u32>().ok().filter(|n| *n >= 2)
+}
+
+/// `base` with collision suffix `n` (see [suffix_of]).
+pub fn with_suffix(base: &str, n: u32) -> String {
+    if n <= 1 {
+        base.to_string()
+    } else {
+        format!("{base}-{n}")
+    }
+}
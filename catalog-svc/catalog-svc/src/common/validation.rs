@@ -0,0 +1,89 @@
+//! Field-level validation errors shared by domain services and the HTTP / Smithy front-ends.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A single violated constraint on a request field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldViolation {
+    /// JSON Pointer to the offending field in the request body, e.g. `/name`.
+    #[schema(example = "/name")]
+    pub path: String,
+    /// Human-readable description of the violated constraint.
+    #[schema(example = "must not be empty")]
+    pub message: String,
+}
+
+/// All field violations found in a request. Validators collect every violation instead of
+/// stopping at the first one, so clients can fix a form in one round trip.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    violations: Vec<FieldViolation>,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shorthand for a single violation of the top-level `field`.
+    pub fn single(field: &str, message: impl Into<String>) -> Self {
+        let mut errors = Self::new();
+        errors.add(field, message);
+        errors
+    }
+
+    /// Record a violation of the top-level body `field` (given by name, e.g. `"name"`).
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.violations.push(FieldViolation {
+            path: format!("/{field}"),
+            message: message.into(),
+        });
+    }
+
+    /// Record a violation at a JSON Pointer, e.g. `/items/0/name`, or `""` for the whole body.
+    pub fn add_at(&mut self, pointer: impl Into<String>, message: impl Into<String>) {
+        self.violations.push(FieldViolation {
+            path: pointer.into(),
+            message: message.into(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn violations(&self) -> &[FieldViolation] {
+        &self.violations
+    }
+
+    pub fn into_violations(self) -> Vec<FieldViolation> {
+        self.violations
+    }
+
+    /// `Ok(value)` if nothing was recorded, otherwise `Err(self)`.
+    pub fn into_result<T>(self, value: T) -> Result<T, Self> {
+        if self.is_empty() {
+            Ok(value)
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}: {}", violation.path, violation.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
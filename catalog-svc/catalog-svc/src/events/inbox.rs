@@ -0,0 +1,53 @@
+//! SQL repository for the `inbox_messages` table: ids of inbound messages already applied.
+
+use chrono::{NaiveDateTime, Utc};
+use sqlx::{Executor, Postgres};
+
+use crate::catalog::persistence::RepositoryError;
+
+/// Repository for processed inbound messages (stateless; all methods take an executor).
+pub struct InboxRepository;
+
+impl InboxRepository {
+    /// Record `message_id` from `source` as processed. Returns false if it already was; call it
+    /// in the transaction applying the message, so the record and the effect commit together.
+    #[tracing::instrument(
+        name = "InboxRepository::record",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "INSERT", db.sql.table = "inbox_messages")
+    )]
+    pub async fn record(
+        executor: impl Executor<'_, Database = Postgres>,
+        source: &str,
+        message_id: &str,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO inbox_messages (source, message_id, processed_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (source, message_id) DO NOTHING
+            "#,
+        )
+        .bind(source)
+        .bind(message_id)
+        .bind(Utc::now().naive_utc())
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Forget messages from `source` processed before `cutoff`. Returns how many were removed.
+    pub async fn delete_processed_before(
+        executor: impl Executor<'_, Database = Postgres>,
+        source: &str,
+        cutoff: NaiveDateTime,
+    ) -> Result<u64, RepositoryError> {
+        let result =
+            sqlx::query("DELETE FROM inbox_messages WHERE source = $1 AND processed_at < $2")
+                .bind(source)
+                .bind(cutoff)
+                .execute(executor)
+                .await?;
+        Ok(result.rows_affected())
+    }
+}
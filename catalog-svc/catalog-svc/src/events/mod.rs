@@ -0,0 +1,247 @@
+//! Catalog change events, written to a transactional outbox with the change itself and published
+//! by the [relay::OutboxRelay] to an [sink::EventSink] (Kafka, or in memory for tests).
+//! Inbound messages are deduplicated against the [inbox].
+//!
+//! Events are published as an [EventEnvelope] around one of the payload types below. Each payload
+//! type has a schema version ([EventPayload::VERSION]) and generated JSON Schema and Avro
+//! contracts (see [schema]); the version is bumped when a change would break existing consumers.
+
+pub mod inbox;
+pub mod outbox;
+pub mod relay;
+pub mod schema;
+pub mod sink;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::catalog::api::CatalogItem;
+
+/// Payload of one event type.
+pub trait EventPayload: Serialize + DeserializeOwned + JsonSchema {
+    /// Event type name, carried as the envelope's `type`.
+    const TYPE: &'static str;
+    /// Schema version of the payload, carried as the envelope's `version`.
+    const VERSION: u32;
+}
+
+/// An item was created.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemCreated {
+    pub item: CatalogItem,
+}
+
+/// An item was changed; holds its new state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemUpdated {
+    pub item: CatalogItem,
+}
+
+/// An item was deleted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemDeleted {
+    pub item_id: Uuid,
+}
+
+/// An item's price changed (along with an [ItemUpdated] event).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceChanged {
+    pub item_id: Uuid,
+    /// Serializes in JSON as string.
+    pub old_price: Decimal,
+    /// Serializes in JSON as string.
+    pub new_price: Decimal,
+}
+
+impl EventPayload for ItemCreated {
+    const TYPE: &'static str = "ItemCreated";
+    const VERSION: u32 = 1;
+}
+
+impl EventPayload for ItemUpdated {
+    const TYPE: &'static str = "ItemUpdated";
+    const VERSION: u32 = 1;
+}
+
+impl EventPayload for ItemDeleted {
+    const TYPE: &'static str = "ItemDeleted";
+    const VERSION: u32 = 1;
+}
+
+impl EventPayload for PriceChanged {
+    const TYPE: &'static str = "PriceChanged";
+    const VERSION: u32 = 1;
+}
+
+/// A change to a catalog item. Serializes as its payload alone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum CatalogEvent {
+    ItemCreated(ItemCreated),
+    ItemUpdated(ItemUpdated),
+    ItemDeleted(ItemDeleted),
+    PriceChanged(PriceChanged),
+}
+
+/// Errors decoding an [EventEnvelope] payload.
+#[derive(Error, Debug)]
+pub enum EventDecodeError {
+    #[error("unknown event type: {0}")]
+    UnknownType(String),
+
+    #[error("unsupported version {version} of event type {event_type}")]
+    UnsupportedVersion { event_type: String, version: u32 },
+
+    #[error("invalid event payload: {0}")]
+    Payload(#[from] serde_json::Error),
+}
+
+impl CatalogEvent {
+    /// Every event type name.
+    pub const TYPES: [&'static str; 4] = [
+        ItemCreated::TYPE,
+        ItemUpdated::TYPE,
+        ItemDeleted::TYPE,
+        PriceChanged::TYPE,
+    ];
+
+    /// Id of the changed item; the message key, so events of one item stay ordered.
+    pub fn item_id(&self) -> Uuid {
+        match self {
+            CatalogEvent::ItemCreated(ItemCreated { item })
+            | CatalogEvent::ItemUpdated(ItemUpdated { item }) => item.item_id,
+            CatalogEvent::ItemDeleted(ItemDeleted { item_id })
+            | CatalogEvent::PriceChanged(PriceChanged { item_id, .. }) => *item_id,
+        }
+    }
+
+    /// Event type name, e.g. `ItemCreated`.
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            CatalogEvent::ItemCreated(_) => ItemCreated::TYPE,
+            CatalogEvent::ItemUpdated(_) => ItemUpdated::TYPE,
+            CatalogEvent::ItemDeleted(_) => ItemDeleted::TYPE,
+            CatalogEvent::PriceChanged(_) => PriceChanged::TYPE,
+        }
+    }
+
+    /// Schema version of the payload.
+    pub fn version(&self) -> u32 {
+        match self {
+            CatalogEvent::ItemCreated(_) => ItemCreated::VERSION,
+            CatalogEvent::ItemUpdated(_) => ItemUpdated::VERSION,
+            CatalogEvent::ItemDeleted(_) => ItemDeleted::VERSION,
+            CatalogEvent::PriceChanged(_) => PriceChanged::VERSION,
+        }
+    }
+
+    /// The [PriceChanged] event for an item whose price went from `old_price` to `new_price`,
+    /// if it changed.
+    pub fn price_change(item_id: Uuid, old_price: Decimal, new_price: Decimal) -> Option<Self> {
+        (old_price != new_price).then_some(CatalogEvent::PriceChanged(PriceChanged {
+            item_id,
+            old_price,
+            new_price,
+        }))
+    }
+
+    /// Decode the payload of an event of type `event_type` and schema `version`.
+    pub fn from_payload(
+        event_type: &str,
+        version: u32,
+        payload: serde_json::Value,
+    ) -> Result<Self, EventDecodeError> {
+        match event_type {
+            ItemCreated::TYPE => decode(version, payload).map(CatalogEvent::ItemCreated),
+            ItemUpdated::TYPE => decode(version, payload).map(CatalogEvent::ItemUpdated),
+            ItemDeleted::TYPE => decode(version, payload).map(CatalogEvent::ItemDeleted),
+            PriceChanged::TYPE => decode(version, payload).map(CatalogEvent::PriceChanged),
+            _ => Err(EventDecodeError::UnknownType(event_type.to_string())),
+        }
+    }
+}
+
+fn decode<P: EventPayload>(
+    version: u32,
+    payload: serde_json::Value,
+) -> Result<P, EventDecodeError> {
+    if version != P::VERSION {
+        return Err(EventDecodeError::UnsupportedVersion {
+            event_type: P::TYPE.to_string(),
+            version,
+        });
+    }
+    Ok(serde_json::from_value(payload)?)
+}
+
+/// Published form of an event: metadata around the payload of its `type` and `version`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EventEnvelope<P = serde_json::Value> {
+    /// Unique event id; the same on redelivery, for consumer-side deduplication.
+    pub id: Uuid,
+    /// Event type name, e.g. `ItemCreated`.
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// Schema version of the payload.
+    pub version: u32,
+    /// When the change was made.
+    pub occurred_at: DateTime<Utc>,
+    /// Tenant (catalog deployment) the item belongs to.
+    pub tenant: String,
+    pub payload: P,
+}
+
+impl EventEnvelope<CatalogEvent> {
+    /// Envelope for `event`, happening now.
+    pub fn new(tenant: impl Into<String>, event: CatalogEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event_type: event.event_type().to_string(),
+            version: event.version(),
+            occurred_at: Utc::now(),
+            tenant: tenant.into(),
+            payload: event,
+        }
+    }
+}
+
+impl EventEnvelope {
+    /// Decode the payload according to `type` and `version`.
+    pub fn into_event(self) -> Result<EventEnvelope<CatalogEvent>, EventDecodeError> {
+        let payload = CatalogEvent::from_payload(&self.event_type, self.version, self.payload)?;
+        Ok(EventEnvelope {
+            id: self.id,
+            event_type: self.event_type,
+            version: self.version,
+            occurred_at: self.occurred_at,
+            tenant: self.tenant,
+            payload,
+        })
+    }
+}
+
+/// An event as stored in the outbox and handed to the [sink::EventSink].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxMessage {
+    /// Position in the outbox; publication order.
+    pub seq: i64,
+    /// [EventEnvelope::id], for consumer-side deduplication of redeliveries.
+    pub event_id: Uuid,
+    pub item_id: Uuid,
+    pub event_type: String,
+    /// The serialized [EventEnvelope].
+    pub payload: serde_json::Value,
+    /// Message headers captured when the event was written (W3C trace context).
+    pub headers: Vec<(String, String)>,
+    pub created_at: DateTime<Utc>,
+}
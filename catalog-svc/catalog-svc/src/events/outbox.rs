@@ -0,0 +1,134 @@
+//! SQL repository for the `catalog_outbox` table.
+
+use std::collections::HashMap;
+
+use chrono::{NaiveDateTime, Utc};
+use rust_demo_commons::util::tracing::trace_context_headers;
+use sqlx::types::Json;
+use sqlx::{Executor, FromRow, Postgres};
+use uuid::Uuid;
+
+use crate::catalog::persistence::RepositoryError;
+use crate::events::{CatalogEvent, EventEnvelope, OutboxMessage};
+
+/// Advisory lock key held by the active relay ("outbox" in ASCII).
+const RELAY_LOCK_KEY: i64 = 0x6f75_7462_6f78;
+
+#[derive(FromRow)]
+struct OutboxRow {
+    seq: i64,
+    event_id: Uuid,
+    item_id: Uuid,
+    event_type: String,
+    payload: Json<serde_json::Value>,
+    headers: Json<HashMap<String, String>>,
+    created_at: NaiveDateTime,
+}
+
+impl OutboxRow {
+    fn into_message(self) -> OutboxMessage {
+        let mut headers: Vec<_> = self.headers.0.into_iter().collect();
+        headers.sort();
+        OutboxMessage {
+            seq: self.seq,
+            event_id: self.event_id,
+            item_id: self.item_id,
+            event_type: self.event_type,
+            payload: self.payload.0,
+            headers,
+            created_at: self.created_at.and_utc(),
+        }
+    }
+}
+
+/// Repository for outbox events (stateless; all methods take an executor).
+pub struct OutboxRepository;
+
+impl OutboxRepository {
+    /// Record `envelope`; call it in the transaction making the change so both commit together.
+    /// Captures the current trace context so the published message continues the trace.
+    #[tracing::instrument(
+        name = "OutboxRepository::append",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "INSERT", db.sql.table = "catalog_outbox")
+    )]
+    pub async fn append(
+        executor: impl Executor<'_, Database = Postgres>,
+        envelope: &EventEnvelope<CatalogEvent>,
+    ) -> Result<(), RepositoryError> {
+        let payload = serde_json::to_value(envelope).map_err(RepositoryError::Serialization)?;
+        sqlx::query(
+            r#"
+            INSERT INTO catalog_outbox (event_id, item_id, event_type, payload, headers, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(envelope.id)
+        .bind(envelope.payload.item_id())
+        .bind(&envelope.event_type)
+        .bind(Json(payload))
+        .bind(Json(trace_context_headers()))
+        .bind(envelope.occurred_at.naive_utc())
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Take the relay lock for the rest of the transaction. Returns false while another instance
+    /// holds it: a single relay at a time keeps the events of each item in order.
+    pub async fn try_lock_relay(
+        executor: impl Executor<'_, Database = Postgres>,
+    ) -> Result<bool, RepositoryError> {
+        Ok(sqlx::query_scalar("SELECT pg_try_advisory_xact_lock($1)")
+            .bind(RELAY_LOCK_KEY)
+            .fetch_one(executor)
+            .await?)
+    }
+
+    /// The oldest unpublished events, in publication order. Takes no row lock; callers hold the
+    /// relay lock ([OutboxRepository::try_lock_relay]) instead.
+    pub async fn fetch_unpublished(
+        executor: impl Executor<'_, Database = Postgres>,
+        limit: u32,
+    ) -> Result<Vec<OutboxMessage>, RepositoryError> {
+        let rows = sqlx::query_as::<_, OutboxRow>(
+            r#"
+            SELECT seq, event_id, item_id, event_type, payload, headers, created_at
+            FROM catalog_outbox
+            WHERE published_at IS NULL
+            ORDER BY seq
+            LIMIT $1
+            "#,
+        )
+        .bind(i64::from(limit))
+        .fetch_all(executor)
+        .await?;
+        Ok(rows.into_iter().map(OutboxRow::into_message).collect())
+    }
+
+    pub async fn mark_published(
+        executor: impl Executor<'_, Database = Postgres>,
+        seqs: &[i64],
+    ) -> Result<(), RepositoryError> {
+        sqlx::query("UPDATE catalog_outbox SET published_at = $2 WHERE seq = ANY($1)")
+            .bind(seqs)
+            .bind(Utc::now().naive_utc())
+            .execute(executor)
+            .await?;
+        Ok(())
+    }
+
+    /// Delete events published before `cutoff`. Returns how many were removed.
+    pub async fn delete_published_before(
+        executor: impl Executor<'_, Database = Postgres>,
+        cutoff: NaiveDateTime,
+    ) -> Result<u64, RepositoryError> {
+        let result = sqlx::query(
+            "DELETE FROM catalog_outbox WHERE published_at IS NOT NULL AND published_at < $1",
+        )
+        .bind(cutoff)
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
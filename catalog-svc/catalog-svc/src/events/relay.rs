@@ -0,0 +1,159 @@
+//! Background task moving events from the outbox to an [EventSink].
+//!
+//! Delivery is at least once: an event is marked published only after the sink accepted it, so
+//! a crash in between publishes it again. A batch is sent concurrently, so events after a failed
+//! one may have been delivered too and are published again on the next attempt. Consumers
+//! deduplicate by the `event_id` header.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::app_config::EventsConfig;
+use crate::app_metrics;
+use crate::catalog::persistence::RepositoryError;
+use crate::events::outbox::OutboxRepository;
+use crate::events::sink::{EventSink, EventSinkError};
+
+/// Longest wait between retries after failures.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// How often published events older than the retention are deleted.
+const PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Errors from one [OutboxRelay::relay_batch] run.
+#[derive(thiserror::Error, Debug)]
+pub enum RelayError {
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+
+    #[error("failed to publish event {event_id}: {source}")]
+    Publish {
+        event_id: uuid::Uuid,
+        #[source]
+        source: EventSinkError,
+    },
+}
+
+impl From<sqlx::Error> for RelayError {
+    fn from(err: sqlx::Error) -> Self {
+        RelayError::Repository(RepositoryError::from(err))
+    }
+}
+
+/// Publishes outbox events in order; one instance is active at a time across replicas.
+pub struct OutboxRelay {
+    pg_pool: PgPool,
+    sink: Arc<dyn EventSink>,
+    poll_interval: Duration,
+    batch_size: u32,
+    retention: Duration,
+}
+
+impl OutboxRelay {
+    pub fn new(pg_pool: PgPool, sink: Arc<dyn EventSink>, cfg: &EventsConfig) -> Self {
+        Self {
+            pg_pool,
+            sink,
+            poll_interval: Duration::from_millis(cfg.poll_interval_ms),
+            batch_size: cfg.batch_size.max(1),
+            retention: Duration::from_secs(cfg.retention_secs),
+        }
+    }
+
+    /// Publish one batch of the oldest unpublished events. The batch is handed to the sink in
+    /// order and sent concurrently, so the transaction holding the relay lock stays open for
+    /// about one send timeout at most. Only the events before the first failure are marked
+    /// published. Returns how many were published; 0 when there was nothing to do or another
+    /// instance is relaying.
+    pub async fn relay_batch(&self) -> Result<usize, RelayError> {
+        let mut tx = self.pg_pool.begin().await?;
+        if !OutboxRepository::try_lock_relay(&mut *tx).await? {
+            return Ok(0);
+        }
+        let messages = OutboxRepository::fetch_unpublished(&mut *tx, self.batch_size).await?;
+        let results =
+            futures::future::join_all(messages.iter().map(|message| self.sink.publish(message)))
+                .await;
+        let mut published = Vec::with_capacity(messages.len());
+        let mut failure = None;
+        for (message, result) in messages.iter().zip(results) {
+            match result {
+                Ok(()) => published.push(message.seq),
+                Err(source) => {
+                    failure = Some(RelayError::Publish {
+                        event_id: message.event_id,
+                        source,
+                    });
+                    break;
+                }
+            }
+        }
+        if !published.is_empty() {
+            OutboxRepository::mark_published(&mut *tx, &published).await?;
+        }
+        tx.commit().await?;
+        let count = published.len();
+        metrics::counter!(app_metrics::EVENTS_PUBLISHED_TOTAL).increment(count as u64);
+        match failure {
+            Some(err) => {
+                metrics::counter!(app_metrics::EVENT_PUBLISH_FAILURES_TOTAL).increment(1);
+                Err(err)
+            }
+            None => Ok(count),
+        }
+    }
+
+    /// Drop events published longer than the retention ago.
+    async fn purge(&self) -> Result<u64, RelayError> {
+        let retention = chrono::Duration::from_std(self.retention).unwrap_or(chrono::Duration::MAX);
+        let cutoff = Utc::now()
+            .naive_utc()
+            .checked_sub_signed(retention)
+            .unwrap_or(chrono::NaiveDateTime::MIN);
+        Ok(OutboxRepository::delete_published_before(&self.pg_pool, cutoff).await?)
+    }
+
+    /// Relay until `shutdown` is cancelled: batches back to back while events are pending,
+    /// polling every `poll_interval` when idle, with exponential backoff after failures.
+    /// Published events are kept for the retention period, then deleted.
+    pub fn spawn(self, shutdown: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Outbox relay started");
+            let mut backoff = self.poll_interval;
+            let mut last_purge: Option<Instant> = None;
+            loop {
+                let delay = match self.relay_batch().await {
+                    Ok(count) if count > 0 => {
+                        backoff = self.poll_interval;
+                        Duration::ZERO
+                    }
+                    Ok(_) => {
+                        backoff = self.poll_interval;
+                        if last_purge.is_none_or(|at| at.elapsed() >= PURGE_INTERVAL) {
+                            last_purge = Some(Instant::now());
+                            if let Err(err) = self.purge().await {
+                                tracing::warn!("Outbox purge failed: {err}");
+                            }
+                        }
+                        self.poll_interval
+                    }
+                    Err(err) => {
+                        tracing::warn!("Outbox relay failed, retrying in {backoff:?}: {err}");
+                        let delay = backoff;
+                        backoff = (backoff * 2).min(MAX_BACKOFF);
+                        delay
+                    }
+                };
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(delay) => {}
+                }
+            }
+            tracing::info!("Outbox relay stopped");
+        })
+    }
+}
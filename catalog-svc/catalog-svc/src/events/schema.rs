@@ -0,0 +1,518 @@
+//! Contracts of the published events: a JSON Schema and an Avro schema of the [EventEnvelope] of
+//! each event type and version, generated from the Rust types (see the `dump-event-schemas` bin),
+//! and a check that a changed schema still accepts what consumers of the old one rely on.
+//!
+//! Avro schemas mirror the JSON encoding: timestamps, dates and decimals are strings.
+
+use std::collections::{BTreeSet, HashSet};
+
+use schemars::r#gen::SchemaSettings;
+use serde_json::{Map, Value, json};
+use thiserror::Error;
+
+use crate::events::{
+    EventEnvelope, EventPayload, ItemCreated, ItemDeleted, ItemUpdated, PriceChanged,
+};
+
+/// Namespace of the Avro named types.
+pub const AVRO_NAMESPACE: &str = "catalog.events";
+
+/// Deepest schema nesting followed; guards against recursive definitions.
+const MAX_DEPTH: usize = 32;
+
+/// Errors generating event schemas.
+#[derive(Error, Debug)]
+pub enum SchemaError {
+    #[error("failed to serialize schema: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("no Avro equivalent at {path}: {reason}")]
+    Unsupported { path: String, reason: String },
+}
+
+/// The contracts of one event type and version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventSchema {
+    pub event_type: &'static str,
+    pub version: u32,
+    pub json_schema: Value,
+    pub avro_schema: Value,
+}
+
+impl EventSchema {
+    /// File name without extension, e.g. `ItemCreated.v1`.
+    pub fn file_stem(&self) -> String {
+        format!("{}.v{}", self.event_type, self.version)
+    }
+}
+
+/// Schemas of every event type, at its current version.
+pub fn event_schemas() -> Result<Vec<EventSchema>, SchemaError> {
+    Ok(vec![
+        event_schema::<ItemCreated>()?,
+        event_schema::<ItemUpdated>()?,
+        event_schema::<ItemDeleted>()?,
+        event_schema::<PriceChanged>()?,
+    ])
+}
+
+/// Schemas of the envelope of `P`, with `type` and `version` pinned to its own.
+pub fn event_schema<P: EventPayload>() -> Result<EventSchema, SchemaError> {
+    let generator = SchemaSettings::draft07().into_generator();
+    let mut json_schema =
+        serde_json::to_value(generator.into_root_schema_for::<EventEnvelope<P>>())?;
+    if let Some(root) = json_schema.as_object_mut() {
+        root.insert(
+            "$id".to_string(),
+            json!(format!(
+                "urn:catalog-svc:events:{}:v{}",
+                P::TYPE,
+                P::VERSION
+            )),
+        );
+        root.insert("title".to_string(), json!(format!("{}Envelope", P::TYPE)));
+        if let Some(properties) = root.get_mut("properties").and_then(Value::as_object_mut) {
+            properties.insert(
+                "type".to_string(),
+                json!({"description": "Event type name.", "type": "string", "const": P::TYPE}),
+            );
+            properties.insert(
+                "version".to_string(),
+                json!({"description": "Schema version of the payload.", "type": "integer", "const": P::VERSION}),
+            );
+        }
+    }
+    let avro_schema = to_avro(&json_schema, &format!("{}Envelope", P::TYPE))?;
+    Ok(EventSchema {
+        event_type: P::TYPE,
+        version: P::VERSION,
+        json_schema,
+        avro_schema,
+    })
+}
+
+/// Definitions (`#/definitions/...`) of a generated root schema.
+fn definitions(root: &Value) -> Option<&Map<String, Value>> {
+    root.get("definitions").and_then(Value::as_object)
+}
+
+/// Follow `$ref`s and single-element `allOf` wrappers to the schema they stand for.
+fn resolve<'a>(definitions: Option<&'a Map<String, Value>>, schema: &'a Value) -> &'a Value {
+    let mut schema = schema;
+    for _ in 0..MAX_DEPTH {
+        if let Some(name) = ref_name(schema) {
+            match definitions.and_then(|defs| defs.get(name)) {
+                Some(target) => schema = target,
+                None => break,
+            }
+        } else if let Some([inner]) = schema
+            .get("allOf")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+        {
+            schema = inner;
+        } else {
+            break;
+        }
+    }
+    schema
+}
+
+fn ref_name(schema: &Value) -> Option<&str> {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix("#/definitions/"))
+}
+
+/// Instance types a schema accepts: `type`, or the types of its `anyOf` / `oneOf` variants.
+fn types(definitions: Option<&Map<String, Value>>, schema: &Value) -> BTreeSet<String> {
+    let schema = resolve(definitions, schema);
+    match schema.get("type") {
+        Some(Value::String(name)) => BTreeSet::from([name.clone()]),
+        Some(Value::Array(names)) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => variants(schema)
+            .iter()
+            .flat_map(|variant| types(definitions, variant))
+            .collect(),
+    }
+}
+
+fn variants(schema: &Value) -> &[Value] {
+    schema
+        .get("anyOf")
+        .or_else(|| schema.get("oneOf"))
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+fn required(schema: &Value) -> BTreeSet<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn properties(schema: &Value) -> Option<&Map<String, Value>> {
+    schema.get("properties").and_then(Value::as_object)
+}
+
+// --- Avro ---
+
+/// Convert a generated JSON Schema to an Avro record schema named `name`.
+pub fn to_avro(json_schema: &Value, name: &str) -> Result<Value, SchemaError> {
+    let mut converter = AvroConverter {
+        definitions: definitions(json_schema),
+        defined: HashSet::new(),
+    };
+    let mut record = converter.convert(json_schema, name, name, 0)?;
+    if let Some(record) = record.as_object_mut() {
+        record.insert("namespace".to_string(), json!(AVRO_NAMESPACE));
+    }
+    Ok(record)
+}
+
+struct AvroConverter<'a> {
+    definitions: Option<&'a Map<String, Value>>,
+    /// Named types already emitted; later uses refer to them by name.
+    defined: HashSet<String>,
+}
+
+impl AvroConverter<'_> {
+    fn convert(
+        &mut self,
+        schema: &Value,
+        name: &str,
+        path: &str,
+        depth: usize,
+    ) -> Result<Value, SchemaError> {
+        if depth > MAX_DEPTH {
+            return Err(unsupported(path, "nested too deeply"));
+        }
+        if let Some(reference) = ref_name(schema) {
+            if self.defined.contains(reference) {
+                return Ok(json!(reference));
+            }
+            let target = self
+                .definitions
+                .and_then(|defs| defs.get(reference))
+                .ok_or_else(|| unsupported(path, &format!("unknown definition {reference}")))?;
+            return self.convert(target, reference, path, depth + 1);
+        }
+        if let Some([inner]) = schema
+            .get("allOf")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+        {
+            return self.convert(inner, name, path, depth + 1);
+        }
+
+        let variants = variants(schema);
+        if !variants.is_empty() {
+            let mut union = Vec::new();
+            for (index, variant) in variants.iter().enumerate() {
+                let converted =
+                    self.convert(variant, name, &format!("{path}[{index}]"), depth + 1)?;
+                push_union(&mut union, converted);
+            }
+            return Ok(Value::Array(union));
+        }
+
+        match schema.get("type") {
+            Some(Value::Array(names)) => {
+                let mut union = Vec::new();
+                for type_name in names.iter().filter_map(Value::as_str) {
+                    let mut single = schema.clone();
+                    if let Some(single) = single.as_object_mut() {
+                        single.insert("type".to_string(), json!(type_name));
+                    }
+                    push_union(&mut union, self.convert(&single, name, path, depth + 1)?);
+                }
+                Ok(Value::Array(union))
+            }
+            Some(Value::String(type_name)) => {
+                self.convert_type(type_name, schema, name, path, depth)
+            }
+            _ => Err(unsupported(path, "schema without a type")),
+        }
+    }
+
+    fn convert_type(
+        &mut self,
+        type_name: &str,
+        schema: &Value,
+        name: &str,
+        path: &str,
+        depth: usize,
+    ) -> Result<Value, SchemaError> {
+        let format = schema.get("format").and_then(Value::as_str);
+        match type_name {
+            "null" => Ok(json!("null")),
+            "boolean" => Ok(json!("boolean")),
+            "number" => Ok(json!("double")),
+            "integer" => Ok(match format {
+                Some("int8" | "int16" | "int32" | "uint8" | "uint16") => json!("int"),
+                _ => json!("long"),
+            }),
+            "string" => {
+                if let Some(symbols) = schema.get("enum").and_then(Value::as_array) {
+                    return Ok(self.named(
+                        name,
+                        |name| json!({"type": "enum", "name": name, "symbols": symbols}),
+                    ));
+                }
+                Ok(match format {
+                    Some("uuid") => json!({"type": "string", "logicalType": "uuid"}),
+                    _ => json!("string"),
+                })
+            }
+            "array" => {
+                let items = schema
+                    .get("items")
+                    .ok_or_else(|| unsupported(path, "array without items"))?;
+                let items = self.convert(
+                    items,
+                    &format!("{name}Item"),
+                    &format!("{path}[]"),
+                    depth + 1,
+                )?;
+                Ok(json!({"type": "array", "items": items}))
+            }
+            "object" => self.convert_record(schema, name, path, depth),
+            other => Err(unsupported(path, &format!("type {other}"))),
+        }
+    }
+
+    fn convert_record(
+        &mut self,
+        schema: &Value,
+        name: &str,
+        path: &str,
+        depth: usize,
+    ) -> Result<Value, SchemaError> {
+        if self.defined.contains(name) {
+            return Ok(json!(name));
+        }
+        self.defined.insert(name.to_string());
+        let required = required(schema);
+        let mut fields = Vec::new();
+        for (field_name, field_schema) in properties(schema).into_iter().flatten() {
+            let mut field_type = self.convert(
+                field_schema,
+                &pascal_case(field_name),
+                &format!("{path}.{field_name}"),
+                depth + 1,
+            )?;
+            if !required.contains(field_name.as_str()) && !is_nullable(&field_type) {
+                let mut union = vec![json!("null")];
+                push_union(&mut union, field_type);
+                field_type = Value::Array(union);
+            }
+            let mut field = Map::new();
+            field.insert("name".to_string(), json!(field_name));
+            field.insert("type".to_string(), field_type.clone());
+            // A field's own doc only; that of its type goes on the named type
+            if let Some(doc) = field_schema.get("description") {
+                field.insert("doc".to_string(), doc.clone());
+            }
+            if is_nullable(&field_type) {
+                field.insert("default".to_string(), Value::Null);
+            }
+            fields.push(Value::Object(field));
+        }
+        let mut record = Map::new();
+        record.insert("type".to_string(), json!("record"));
+        record.insert("name".to_string(), json!(name));
+        if let Some(doc) = schema.get("description") {
+            record.insert("doc".to_string(), doc.clone());
+        }
+        record.insert("fields".to_string(), Value::Array(fields));
+        Ok(Value::Object(record))
+    }
+
+    /// Emit a named type the first time, and its name afterwards.
+    fn named(&mut self, name: &str, define: impl FnOnce(&str) -> Value) -> Value {
+        if self.defined.insert(name.to_string()) {
+            define(name)
+        } else {
+            json!(name)
+        }
+    }
+}
+
+/// Add a branch to an Avro union, flattening nested unions and keeping `null` first so that
+/// `null` defaults are valid.
+fn push_union(union: &mut Vec<Value>, branch: Value) {
+    let branches = match branch {
+        Value::Array(branches) => branches,
+        branch => vec![branch],
+    };
+    for branch in branches {
+        if union.contains(&branch) {
+            continue;
+        }
+        if branch == json!("null") {
+            union.insert(0, branch);
+        } else {
+            union.push(branch);
+        }
+    }
+}
+
+fn is_nullable(avro_type: &Value) -> bool {
+    avro_type
+        .as_array()
+        .is_some_and(|branches| branches.first() == Some(&json!("null")))
+}
+
+fn pascal_case(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+fn unsupported(path: &str, reason: &str) -> SchemaError {
+    SchemaError::Unsupported {
+        path: path.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+// --- Compatibility ---
+
+/// Ways in which documents valid against `new` could break a consumer written against `old`
+/// (both generated JSON Schemas of the same event type and version). Empty when compatible.
+///
+/// Adding optional or required properties is compatible; removing a required property, making
+/// one optional or nullable, changing a type, format, pattern or constant, and adding enum values are not.
+pub fn breaking_changes(old: &Value, new: &Value) -> Vec<String> {
+    let mut check = CompatibilityCheck {
+        old_definitions: definitions(old),
+        new_definitions: definitions(new),
+        problems: Vec::new(),
+    };
+    check.compare("$", old, new, 0);
+    check.problems
+}
+
+struct CompatibilityCheck<'a> {
+    old_definitions: Option<&'a Map<String, Value>>,
+    new_definitions: Option<&'a Map<String, Value>>,
+    problems: Vec<String>,
+}
+
+impl CompatibilityCheck<'_> {
+    fn compare(&mut self, path: &str, old: &Value, new: &Value, depth: usize) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        let old_types = types(self.old_definitions, old);
+        let new_types = types(self.new_definitions, new);
+        let old = resolve(self.old_definitions, old);
+        let new = resolve(self.new_definitions, new);
+
+        let widened: Vec<_> = new_types
+            .iter()
+            .filter(|name| {
+                // Integers are numbers
+                !(old_types.contains(*name) || *name == "integer" && old_types.contains("number"))
+            })
+            .collect();
+        if !old_types.is_empty() && !widened.is_empty() {
+            self.problems.push(format!(
+                "{path}: type changed from {old_types:?} to {new_types:?}"
+            ));
+            return;
+        }
+
+        if let Some(old_const) = old.get("const")
+            && new.get("const") != Some(old_const)
+        {
+            self.problems
+                .push(format!("{path}: constant {old_const} changed"));
+        }
+        for keyword in ["format", "pattern"] {
+            if let Some(old_value) = old.get(keyword)
+                && new.get(keyword) != Some(old_value)
+            {
+                self.problems
+                    .push(format!("{path}: {keyword} {old_value} changed"));
+            }
+        }
+        if let Some(old_values) = old.get("enum").and_then(Value::as_array) {
+            match new.get("enum").and_then(Value::as_array) {
+                Some(new_values) => {
+                    let added: Vec<_> = new_values
+                        .iter()
+                        .filter(|value| !old_values.contains(value))
+                        .collect();
+                    if !added.is_empty() {
+                        self.problems
+                            .push(format!("{path}: enum values added: {added:?}"));
+                    }
+                }
+                None => self
+                    .problems
+                    .push(format!("{path}: no longer restricted to {old_values:?}")),
+            }
+        }
+
+        if let (Some(old_properties), Some(new_properties)) = (properties(old), properties(new)) {
+            let new_required = required(new);
+            for name in required(old) {
+                match new_properties.get(name) {
+                    None => self.problems.push(format!("{path}.{name}: removed")),
+                    Some(_) if !new_required.contains(name) => {
+                        self.problems
+                            .push(format!("{path}.{name}: no longer required"));
+                    }
+                    Some(_) => {}
+                }
+            }
+            for (name, old_property) in old_properties {
+                if let Some(new_property) = new_properties.get(name) {
+                    self.compare(
+                        &format!("{path}.{name}"),
+                        old_property,
+                        new_property,
+                        depth + 1,
+                    );
+                }
+            }
+            if old.get("additionalProperties") == Some(&Value::Bool(false)) {
+                for name in new_properties
+                    .keys()
+                    .filter(|name| !old_properties.contains_key(*name))
+                {
+                    self.problems.push(format!(
+                        "{path}.{name}: added where no other properties are allowed"
+                    ));
+                }
+            }
+        }
+
+        if let (Some(old_items), Some(new_items)) = (old.get("items"), new.get("items")) {
+            self.compare(&format!("{path}[]"), old_items, new_items, depth + 1);
+        }
+
+        // Nullable references (`anyOf: [{$ref}, {type: null}]`): compare the non-null variants
+        let non_null = |schema: &Value| -> Vec<Value> {
+            variants(schema)
+                .iter()
+                .filter(|variant| variant.get("type") != Some(&json!("null")))
+                .cloned()
+                .collect()
+        };
+        if let ([old_variant], [new_variant]) = (non_null(old).as_slice(), non_null(new).as_slice())
+        {
+            self.compare(path, old_variant, new_variant, depth + 1);
+        }
+    }
+}
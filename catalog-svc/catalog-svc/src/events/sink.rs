@@ -0,0 +1,127 @@
+//! Destinations of published events: Kafka, or memory for tests without a broker.
+
+use std::error::Error as StdError;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rdkafka::ClientConfig;
+use rdkafka::error::KafkaError;
+use rdkafka::message::{Header, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use thiserror::Error;
+
+use crate::events::OutboxMessage;
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Message header carrying [OutboxMessage::event_id].
+pub const EVENT_ID_HEADER: &str = "event_id";
+/// Message header carrying [OutboxMessage::event_type].
+pub const EVENT_TYPE_HEADER: &str = "event_type";
+
+/// Errors from [EventSink] operations.
+#[derive(Error, Debug)]
+pub enum EventSinkError {
+    #[error("failed to serialize event: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("event broker error: {0}")]
+    Backend(#[source] BoxError),
+}
+
+/// Publishes outbox events. A message counts as delivered once `publish` returns `Ok`.
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), EventSinkError>;
+}
+
+/// Publishes events to a Kafka topic, keyed by item id so each item's events stay in one
+/// partition, in order.
+pub struct KafkaEventSink {
+    producer: FutureProducer,
+    topic: String,
+}
+
+impl KafkaEventSink {
+    /// Producer for `brokers` (comma-separated `host:port`); waits for all in-sync replicas and
+    /// lets the producer retry without duplicating or reordering messages.
+    pub fn new(brokers: &str, topic: impl Into<String>) -> Result<Self, KafkaError> {
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("enable.idempotence", "true")
+            .set("acks", "all")
+            .set("message.timeout.ms", "30000")
+            .create()?;
+        Ok(Self {
+            producer,
+            topic: topic.into(),
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaEventSink {
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), EventSinkError> {
+        let payload = serde_json::to_vec(&message.payload)?;
+        let key = message.item_id.to_string();
+        let event_id = message.event_id.to_string();
+        let headers = message.headers.iter().fold(
+            OwnedHeaders::new()
+                .insert(Header {
+                    key: EVENT_ID_HEADER,
+                    value: Some(&event_id),
+                })
+                .insert(Header {
+                    key: EVENT_TYPE_HEADER,
+                    value: Some(&message.event_type),
+                }),
+            |headers, (key, value)| {
+                headers.insert(Header {
+                    key,
+                    value: Some(value),
+                })
+            },
+        );
+        let record = FutureRecord::to(&self.topic)
+            .key(&key)
+            .payload(&payload)
+            .headers(headers);
+        self.producer
+            .send(record, Duration::from_secs(0))
+            .await
+            .map_err(|(err, _)| EventSinkError::Backend(Box::new(err)))?;
+        Ok(())
+    }
+}
+
+/// Keeps published messages in memory, in publication order.
+#[derive(Default)]
+pub struct InMemoryEventSink {
+    messages: Mutex<Vec<OutboxMessage>>,
+}
+
+impl InMemoryEventSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Messages published so far.
+    pub fn messages(&self) -> Vec<OutboxMessage> {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<OutboxMessage>> {
+        self.messages
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl EventSink for InMemoryEventSink {
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), EventSinkError> {
+        self.lock().push(message.clone());
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+//! [Loader]s batching the lookups of one GraphQL request: every item or link fetched while
+//! resolving the same level of a query is loaded by a single query.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_graphql::dataloader::Loader;
+use uuid::Uuid;
+
+use crate::catalog::api::{CatalogItem, CatalogServiceApi, CatalogServiceError};
+use crate::related_items::api::{ItemLink, RelatedItemsServiceError};
+use crate::related_items::service::RelatedItemsService;
+
+/// Loads catalog items by id with [CatalogServiceApi::get_many].
+pub struct ItemLoader {
+    catalog: Arc<dyn CatalogServiceApi>,
+}
+
+impl ItemLoader {
+    pub fn new(catalog: Arc<dyn CatalogServiceApi>) -> Self {
+        Self { catalog }
+    }
+}
+
+impl Loader<Uuid> for ItemLoader {
+    type Value = CatalogItem;
+    type Error = Arc<CatalogServiceError>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, CatalogItem>, Self::Error> {
+        self.catalog.get_many(keys).await.map_err(Arc::new)
+    }
+}
+
+/// Loads the links from catalog items, by item id, with [RelatedItemsService::links_from].
+pub struct LinkLoader {
+    related_items: RelatedItemsService,
+}
+
+impl LinkLoader {
+    pub fn new(related_items: RelatedItemsService) -> Self {
+        Self { related_items }
+    }
+}
+
+impl Loader<Uuid> for LinkLoader {
+    type Value = Vec<ItemLink>;
+    type Error = Arc<RelatedItemsServiceError>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Vec<ItemLink>>, Self::Error> {
+        self.related_items.links_from(keys).await.map_err(Arc::new)
+    }
+}
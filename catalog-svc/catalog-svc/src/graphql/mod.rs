@@ -0,0 +1,122 @@
+//! GraphQL API over the catalog, served at `/graphql` by the axum server.
+//!
+//! Items are resolved through [CatalogServiceApi]; item lookups by id and the links of related
+//! items go through the batching [loaders], so nested selections don't issue one query per
+//! item. Queries are bounded by [GraphqlConfig] (depth and complexity).
+
+pub mod loaders;
+mod schema;
+
+use std::sync::Arc;
+
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{EmptySubscription, ErrorExtensions, Schema, SchemaBuilder};
+
+use crate::app_config::GraphqlConfig;
+use crate::auth::{AuthError, Permission, Principal};
+use crate::catalog::api::{CatalogServiceApi, CatalogServiceError};
+use crate::graphql::loaders::{ItemLoader, LinkLoader};
+use crate::related_items::api::RelatedItemsServiceError;
+use crate::related_items::service::RelatedItemsService;
+
+pub use schema::{MutationRoot, QueryRoot};
+
+pub type CatalogSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+/// The schema without its data, enough to export the SDL (see the `dump-graphql-schema` bin).
+pub fn schema_builder(
+    config: &GraphqlConfig,
+) -> SchemaBuilder<QueryRoot, MutationRoot, EmptySubscription> {
+    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .limit_depth(config.max_depth)
+        .limit_complexity(config.max_complexity)
+}
+
+/// The schema served at `/graphql`. Requests must carry the caller's [Principal] as data.
+pub fn schema(
+    config: &GraphqlConfig,
+    catalog: Arc<dyn CatalogServiceApi>,
+    related_items: RelatedItemsService,
+) -> CatalogSchema {
+    schema_builder(config)
+        .data(DataLoader::new(
+            ItemLoader::new(Arc::clone(&catalog)),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            LinkLoader::new(related_items),
+            tokio::spawn,
+        ))
+        .data(catalog)
+        .finish()
+}
+
+/// The SDL of the schema, as committed in `schemas/catalog.graphql`.
+pub fn sdl() -> String {
+    schema_builder(&GraphqlConfig::default()).finish().sdl()
+}
+
+/// Error `code` extension, as commonly used by GraphQL clients.
+fn with_code(message: impl Into<String>, code: &'static str) -> async_graphql::Error {
+    async_graphql::Error::new(message).extend_with(|_, extensions| extensions.set("code", code))
+}
+
+/// Log `err` and hide it from the caller, like [crate::http_server::problem::ProblemDetails::internal].
+fn internal(err: &dyn std::error::Error) -> async_graphql::Error {
+    tracing::error!(error = %err, "GraphQL resolver failed with internal error");
+    with_code("internal error", "INTERNAL_SERVER_ERROR")
+}
+
+impl ErrorExtensions for CatalogServiceError {
+    fn extend(&self) -> async_graphql::Error {
+        match self {
+            CatalogServiceError::ValidationError(errors) => {
+                let violations = errors
+                    .violations()
+                    .iter()
+                    .map(|violation| {
+                        async_graphql::Value::from_json(serde_json::json!({
+                            "path": violation.path,
+                            "message": violation.message,
+                        }))
+                        .unwrap_or_default()
+                    })
+                    .collect::<Vec<_>>();
+                with_code(errors.to_string(), "BAD_USER_INPUT")
+                    .extend_with(|_, extensions| extensions.set("violations", violations.clone()))
+            }
+            CatalogServiceError::InternalError(_) => internal(self),
+        }
+    }
+}
+
+impl ErrorExtensions for RelatedItemsServiceError {
+    fn extend(&self) -> async_graphql::Error {
+        match self {
+            RelatedItemsServiceError::ValidationError(_) => {
+                with_code(self.to_string(), "BAD_USER_INPUT")
+            }
+            RelatedItemsServiceError::Conflict => with_code(self.to_string(), "CONFLICT"),
+            RelatedItemsServiceError::InternalError(_) => internal(self),
+        }
+    }
+}
+
+impl ErrorExtensions for AuthError {
+    fn extend(&self) -> async_graphql::Error {
+        let code = match self {
+            AuthError::MissingToken | AuthError::InvalidToken(_) => "UNAUTHENTICATED",
+            AuthError::Forbidden(_) => "FORBIDDEN",
+        };
+        with_code(self.to_string(), code)
+    }
+}
+
+/// Check the caller has `permission`. `/graphql` itself only requires [Permission::Read].
+fn authorize(
+    ctx: &async_graphql::Context<'_>,
+    permission: Permission,
+) -> async_graphql::Result<()> {
+    let principal = ctx.data::<Principal>()?;
+    principal.authorize(permission).map_err(|err| err.extend())
+}
@@ -0,0 +1,138 @@
+//! Query and mutation roots, and the fields of [CatalogItem] resolved beyond its own data.
+
+use std::sync::Arc;
+
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{ComplexObject, Context, ErrorExtensions, Object, Result};
+use uuid::Uuid;
+
+use crate::auth::Permission;
+use crate::catalog::api::{
+    CatalogItem, CatalogItemFilter, CatalogItemSort, CatalogServiceApi, CreateCatalogItemBody,
+    ListCatalogItemsRequest, ListCatalogItemsResponse, UpdateCatalogItemBody,
+};
+use crate::graphql::authorize;
+use crate::graphql::loaders::{ItemLoader, LinkLoader};
+use crate::related_items::api::{LinkKind, RelatedCatalogItem};
+
+/// Page size the catalog service uses for `limit` (default 100, clamped to 1..=100), which
+/// multiplies the complexity of the selection on each item.
+fn page_size(limit: Option<u32>) -> usize {
+    usize::try_from(limit.unwrap_or(100).clamp(1, 100)).unwrap_or(100)
+}
+
+fn catalog<'a>(ctx: &Context<'a>) -> Result<&'a Arc<dyn CatalogServiceApi>> {
+    ctx.data::<Arc<dyn CatalogServiceApi>>()
+}
+
+pub struct QueryRoot;
+
+#[Object(name = "Query")]
+impl QueryRoot {
+    /// The catalog item with this id, if it exists.
+    async fn item(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<CatalogItem>> {
+        ctx.data::<DataLoader<ItemLoader>>()?
+            .load_one(id)
+            .await
+            .map_err(|err| err.extend())
+    }
+
+    /// A page of the catalog items matching `filter`. `limit` defaults to 100 and is clamped
+    /// to 1..=100.
+    #[graphql(complexity = "page_size(limit) * child_complexity")]
+    async fn items(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<CatalogItemFilter>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        sort: Option<CatalogItemSort>,
+    ) -> Result<ListCatalogItemsResponse> {
+        let req = ListCatalogItemsRequest {
+            limit,
+            offset,
+            sort,
+        };
+        catalog(ctx)?
+            .search(req, filter.unwrap_or_default())
+            .await
+            .map_err(|err| err.extend())
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object(name = "Mutation")]
+impl MutationRoot {
+    /// Create a catalog item. Requires the writer role.
+    async fn create_item(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateCatalogItemBody,
+    ) -> Result<CatalogItem> {
+        authorize(ctx, Permission::Write)?;
+        catalog(ctx)?
+            .create(input)
+            .await
+            .map_err(|err| err.extend())
+    }
+
+    /// Update a catalog item; null if it does not exist. Requires the writer role.
+    async fn update_item(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: UpdateCatalogItemBody,
+    ) -> Result<Option<CatalogItem>> {
+        authorize(ctx, Permission::Write)?;
+        catalog(ctx)?
+            .update(id, input)
+            .await
+            .map_err(|err| err.extend())
+    }
+
+    /// Delete a catalog item with its media; false if it does not exist. Requires the writer
+    /// role.
+    async fn delete_item(&self, ctx: &Context<'_>, id: Uuid) -> Result<bool> {
+        authorize(ctx, Permission::Write)?;
+        catalog(ctx)?.delete(id).await.map_err(|err| err.extend())
+    }
+}
+
+#[ComplexObject]
+impl CatalogItem {
+    /// Items linked from this one, optionally of one kind, oldest link first.
+    async fn related(
+        &self,
+        ctx: &Context<'_>,
+        kind: Option<LinkKind>,
+    ) -> Result<Vec<RelatedCatalogItem>> {
+        let links = ctx
+            .data::<DataLoader<LinkLoader>>()?
+            .load_one(self.item_id)
+            .await
+            .map_err(|err| err.extend())?
+            .unwrap_or_default();
+        let links: Vec<_> = links
+            .into_iter()
+            .filter(|link| kind.is_none_or(|kind| link.kind == kind))
+            .collect();
+        let items = ctx
+            .data::<DataLoader<ItemLoader>>()?
+            .load_many(links.iter().map(|link| link.related_item_id))
+            .await
+            .map_err(|err| err.extend())?;
+        // An item deleted since its links were read has no links anymore
+        Ok(links
+            .into_iter()
+            .filter_map(|link| {
+                let item = items.get(&link.related_item_id)?.clone();
+                Some(RelatedCatalogItem {
+                    kind: link.kind,
+                    linked_at: link.created_at,
+                    item,
+                })
+            })
+            .collect())
+    }
+}
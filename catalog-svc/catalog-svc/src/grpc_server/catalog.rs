@@ -0,0 +1,214 @@
+//! `catalog.v1.CatalogService` on top of [CatalogServiceApi].
+
+use std::pin::Pin;
+use std::sync::Arc;
+
+use futures::{Stream, TryStreamExt, stream};
+use tonic::{Request, Response, Status};
+
+use crate::api_keys::api::ApiKeysServiceError;
+use crate::api_keys::service::ApiKeysService;
+use crate::auth::{API_KEY_HEADER, AuthError, Authenticator, Permission};
+use crate::catalog::api::{CatalogServiceApi, CatalogServiceError, ListCatalogItemsRequest};
+use crate::common::validation::ValidationErrors;
+use crate::grpc_server::convert;
+use crate::grpc_server::proto;
+use crate::grpc_server::proto::catalog_service_server::CatalogService;
+use crate::http_server::CatalogApp;
+
+impl From<CatalogServiceError> for Status {
+    fn from(err: CatalogServiceError) -> Status {
+        match err {
+            CatalogServiceError::ValidationError(errors) => invalid_argument(errors),
+            CatalogServiceError::InternalError(_) => internal(&err),
+        }
+    }
+}
+
+impl From<AuthError> for Status {
+    fn from(err: AuthError) -> Status {
+        match err {
+            AuthError::MissingToken | AuthError::InvalidToken(_) => {
+                Status::unauthenticated(err.to_string())
+            }
+            AuthError::Forbidden(_) => Status::permission_denied(err.to_string()),
+        }
+    }
+}
+
+impl From<ApiKeysServiceError> for Status {
+    fn from(err: ApiKeysServiceError) -> Status {
+        match err {
+            ApiKeysServiceError::ValidationError(errors) => invalid_argument(errors),
+            ApiKeysServiceError::InvalidKey(_) => Status::unauthenticated(err.to_string()),
+            ApiKeysServiceError::InternalError(_) => internal(&err),
+        }
+    }
+}
+
+fn invalid_argument(errors: ValidationErrors) -> Status {
+    Status::invalid_argument(errors.to_string())
+}
+
+/// Log `err` and hide it from the caller, like [crate::http_server::problem::ProblemDetails::internal].
+fn internal(err: &dyn std::error::Error) -> Status {
+    tracing::error!(error = %err, "gRPC call failed with internal error");
+    Status::internal("internal error")
+}
+
+/// Server-side stream of [proto::CatalogItem]s.
+type ItemStream = Pin<Box<dyn Stream<Item = Result<proto::CatalogItem, Status>> + Send>>;
+
+/// gRPC catalog service. Callers authenticate like on the REST API: reads need
+/// [Permission::Read], writes [Permission::Write].
+#[derive(Clone)]
+pub struct CatalogGrpc {
+    catalog: Arc<dyn CatalogServiceApi>,
+    auth: Authenticator,
+    api_keys: ApiKeysService,
+}
+
+impl CatalogGrpc {
+    pub fn new(state: &CatalogApp) -> Self {
+        Self {
+            catalog: Arc::new(state.catalog.clone()),
+            auth: state.auth.clone(),
+            api_keys: state.api_keys.clone(),
+        }
+    }
+
+    /// Authenticate the caller (by `x-api-key` metadata if present, `authorization` otherwise)
+    /// and check it has `permission`.
+    async fn authorize<T>(
+        &self,
+        request: &Request<T>,
+        permission: Permission,
+    ) -> Result<(), Status> {
+        let metadata = request.metadata();
+        let principal = match metadata.get(API_KEY_HEADER) {
+            Some(api_key) => {
+                let api_key = api_key.to_str().unwrap_or_default();
+                self.api_keys.authenticate(api_key).await?
+            }
+            None => {
+                let authorization = metadata
+                    .get("authorization")
+                    .and_then(|value| value.to_str().ok());
+                self.auth.authenticate(authorization).await?
+            }
+        };
+        Ok(principal.authorize(permission)?)
+    }
+}
+
+#[tonic::async_trait]
+impl CatalogService for CatalogGrpc {
+    type StreamItemsStream = ItemStream;
+
+    async fn create_item(
+        &self,
+        request: Request<proto::CreateItemRequest>,
+    ) -> Result<Response<proto::CatalogItem>, Status> {
+        self.authorize(&request, Permission::Write).await?;
+        let body = convert::create_body(request.into_inner()).map_err(invalid_argument)?;
+        let item = self.catalog.create(body).await?;
+        Ok(Response::new(item.into()))
+    }
+
+    async fn get_item(
+        &self,
+        request: Request<proto::GetItemRequest>,
+    ) -> Result<Response<proto::CatalogItem>, Status> {
+        self.authorize(&request, Permission::Read).await?;
+        let item_id = convert::item_id(&request.get_ref().item_id).map_err(invalid_argument)?;
+        match self.catalog.get(item_id).await? {
+            Some(item) => Ok(Response::new(item.into())),
+            None => Err(Status::not_found(format!("item {item_id} not found"))),
+        }
+    }
+
+    async fn list_items(
+        &self,
+        request: Request<proto::ListItemsRequest>,
+    ) -> Result<Response<proto::ListItemsResponse>, Status> {
+        self.authorize(&request, Permission::Read).await?;
+        let req = request.into_inner();
+        let sort = convert::item_sort(req.sort).map_err(invalid_argument)?;
+        let page = self
+            .catalog
+            .list(ListCatalogItemsRequest {
+                limit: req.limit,
+                offset: req.offset,
+                sort: Some(sort),
+            })
+            .await?;
+        Ok(Response::new(proto::ListItemsResponse {
+            items: page.items.into_iter().map(Into::into).collect(),
+            has_more: page.has_more,
+            limit: page.pagination.limit,
+            offset: page.pagination.offset,
+            total_count: page.total_count,
+        }))
+    }
+
+    async fn update_item(
+        &self,
+        request: Request<proto::UpdateItemRequest>,
+    ) -> Result<Response<proto::CatalogItem>, Status> {
+        self.authorize(&request, Permission::Write).await?;
+        let (item_id, body) =
+            convert::update_body(request.into_inner()).map_err(invalid_argument)?;
+        match self.catalog.update(item_id, body).await? {
+            Some(item) => Ok(Response::new(item.into())),
+            None => Err(Status::not_found(format!("item {item_id} not found"))),
+        }
+    }
+
+    async fn delete_item(
+        &self,
+        request: Request<proto::DeleteItemRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.authorize(&request, Permission::Write).await?;
+        let item_id = convert::item_id(&request.get_ref().item_id).map_err(invalid_argument)?;
+        if self.catalog.delete(item_id).await? {
+            Ok(Response::new(()))
+        } else {
+            Err(Status::not_found(format!("item {item_id} not found")))
+        }
+    }
+
+    async fn stream_items(
+        &self,
+        request: Request<proto::StreamItemsRequest>,
+    ) -> Result<Response<Self::StreamItemsStream>, Status> {
+        self.authorize(&request, Permission::Read).await?;
+        let req = request.into_inner();
+        let sort = convert::item_sort(req.sort).map_err(invalid_argument)?;
+        let catalog = Arc::clone(&self.catalog);
+        // The next page is only fetched once the client has read the previous one
+        let pages = stream::try_unfold(Some(0u32), move |offset| {
+            let catalog = Arc::clone(&catalog);
+            async move {
+                let Some(offset) = offset else {
+                    return Ok(None);
+                };
+                let page = catalog
+                    .list(ListCatalogItemsRequest {
+                        limit: req.page_size,
+                        offset: Some(offset),
+                        sort: Some(sort),
+                    })
+                    .await?;
+                let read = u32::try_from(page.items.len()).unwrap_or(u32::MAX);
+                let next = (page.has_more && read > 0).then(|| offset.saturating_add(read));
+                let items: Vec<proto::CatalogItem> =
+                    page.items.into_iter().map(Into::into).collect();
+                Ok::<_, Status>(Some((items, next)))
+            }
+        });
+        let items = pages
+            .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+            .try_flatten();
+        Ok(Response::new(Box::pin(items)))
+    }
+}
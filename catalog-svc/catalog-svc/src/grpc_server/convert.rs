@@ -0,0 +1,145 @@
+//! Conversions between the generated protobuf messages and the catalog API types.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::catalog::api::{
+    CatalogItem, CatalogItemSort, Category, CreateCatalogItemBody, RatingSummary,
+    UpdateCatalogItemBody,
+};
+use crate::common::validation::ValidationErrors;
+use crate::grpc_server::proto;
+
+impl From<CatalogItem> for proto::CatalogItem {
+    fn from(item: CatalogItem) -> Self {
+        Self {
+            item_id: item.item_id.to_string(),
+            name: item.name,
+            slug: item.slug,
+            description: item.description,
+            category: proto::Category::from(item.category).into(),
+            date: item.date.to_string(),
+            brand: item.brand,
+            price: item.price.to_string(),
+            created_at: Some(timestamp(item.created_at)),
+            modified_at: Some(timestamp(item.modified_at)),
+            rating: Some(item.rating.into()),
+            stock: item.stock,
+        }
+    }
+}
+
+impl From<RatingSummary> for proto::RatingSummary {
+    fn from(rating: RatingSummary) -> Self {
+        Self {
+            average: rating.average.map(|average| average.to_string()),
+            count: rating.count,
+            histogram: rating.histogram,
+        }
+    }
+}
+
+impl From<Category> for proto::Category {
+    fn from(category: Category) -> Self {
+        match category {
+            Category::Books => proto::Category::Books,
+            Category::Electronics => proto::Category::Electronics,
+        }
+    }
+}
+
+fn timestamp(at: DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: at.timestamp(),
+        nanos: i32::try_from(at.timestamp_subsec_nanos()).unwrap_or_default(),
+    }
+}
+
+/// Parse an item id field.
+pub(super) fn item_id(item_id: &str) -> Result<Uuid, ValidationErrors> {
+    Uuid::parse_str(item_id).map_err(|_| ValidationErrors::single("item_id", "must be a UUID"))
+}
+
+/// The sort order of a `sort` field; unspecified means the default.
+pub(super) fn item_sort(sort: i32) -> Result<CatalogItemSort, ValidationErrors> {
+    match proto::ItemSort::try_from(sort) {
+        Ok(proto::ItemSort::Unspecified | proto::ItemSort::CreatedAt) => {
+            Ok(CatalogItemSort::CreatedAt)
+        }
+        Ok(proto::ItemSort::Rating) => Ok(CatalogItemSort::Rating),
+        Err(_) => Err(ValidationErrors::single("sort", "unknown sort order")),
+    }
+}
+
+fn category(category: i32, errors: &mut ValidationErrors) -> Option<Category> {
+    match proto::Category::try_from(category) {
+        Ok(proto::Category::Books) => Some(Category::Books),
+        Ok(proto::Category::Electronics) => Some(Category::Electronics),
+        Ok(proto::Category::Unspecified) => {
+            errors.add("category", "must be specified");
+            None
+        }
+        Err(_) => {
+            errors.add("category", "unknown category");
+            None
+        }
+    }
+}
+
+fn price(price: &str, errors: &mut ValidationErrors) -> Option<Decimal> {
+    let parsed = Decimal::from_str(price.trim()).ok();
+    if parsed.is_none() {
+        errors.add("price", "must be a decimal number, e.g. 19.99");
+    }
+    parsed
+}
+
+/// The create body for `req`; the catalog service validates the remaining fields.
+pub(super) fn create_body(
+    req: proto::CreateItemRequest,
+) -> Result<CreateCatalogItemBody, ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    let category = category(req.category, &mut errors);
+    let price = price(&req.price, &mut errors);
+    match (category, price) {
+        (Some(category), Some(price)) => Ok(CreateCatalogItemBody {
+            name: req.name,
+            description: req.description,
+            category,
+            date: req.date,
+            brand: req.brand,
+            price,
+        }),
+        _ => Err(errors),
+    }
+}
+
+/// The item id and update body for `req`; the catalog service validates the remaining fields.
+pub(super) fn update_body(
+    req: proto::UpdateItemRequest,
+) -> Result<(Uuid, UpdateCatalogItemBody), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    let item_id = Uuid::parse_str(&req.item_id).ok();
+    if item_id.is_none() {
+        errors.add("item_id", "must be a UUID");
+    }
+    let category = category(req.category, &mut errors);
+    let price = price(&req.price, &mut errors);
+    match (item_id, category, price) {
+        (Some(item_id), Some(category), Some(price)) => Ok((
+            item_id,
+            UpdateCatalogItemBody {
+                name: req.name,
+                description: req.description,
+                category,
+                date: req.date,
+                brand: req.brand,
+                price,
+            },
+        )),
+        _ => Err(errors),
+    }
+}
@@ -0,0 +1,73 @@
+//! gRPC API for catalog items (`proto/catalog.proto`), served on its own port next to the REST
+//! API from the same [CatalogApp], with gRPC health checking and server reflection.
+
+mod catalog;
+mod convert;
+
+use std::time::Duration;
+
+use thiserror::Error;
+use tokio::net::TcpListener;
+use tonic::transport::Server;
+use tonic::transport::server::TcpIncoming;
+use tonic_health::ServingStatus;
+
+pub use crate::grpc_server::catalog::CatalogGrpc;
+use crate::grpc_server::proto::catalog_service_server::CatalogServiceServer;
+use crate::http_server::CatalogApp;
+
+/// Types and client/server stubs generated from `proto/catalog.proto`.
+pub mod proto {
+    tonic::include_proto!("catalog.v1");
+
+    /// Encoded descriptors of `catalog.proto`, served by reflection.
+    pub const FILE_DESCRIPTOR_SET: &[u8] =
+        tonic::include_file_descriptor_set!("catalog_descriptor");
+}
+
+/// Errors setting up or running the gRPC server.
+#[derive(Error, Debug)]
+pub enum GrpcServerError {
+    #[error("failed to build reflection service: {0}")]
+    Reflection(#[from] tonic_reflection::server::Error),
+
+    #[error("gRPC transport error: {0}")]
+    Transport(#[from] tonic::transport::Error),
+}
+
+/// Serve the catalog, health and reflection services on `listener` until the app's shutdown
+/// token is cancelled. Health checks then report `NOT_SERVING` for `shutdown_delay` before the
+/// listener closes, like `/health/ready` on the REST API.
+pub async fn serve(
+    state: CatalogApp,
+    listener: TcpListener,
+    shutdown_delay: Duration,
+) -> Result<(), GrpcServerError> {
+    let (health, health_service) = tonic_health::server::health_reporter();
+    health
+        .set_serving::<CatalogServiceServer<CatalogGrpc>>()
+        .await;
+    let reflection = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(proto::FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+        .build_v1()?;
+
+    let shutdown = state.server_shutdown.clone();
+    let shutdown = async move {
+        shutdown.cancelled().await;
+        health
+            .set_not_serving::<CatalogServiceServer<CatalogGrpc>>()
+            .await;
+        health
+            .set_service_status("", ServingStatus::NotServing)
+            .await;
+        tokio::time::sleep(shutdown_delay).await;
+    };
+    Server::builder()
+        .add_service(health_service)
+        .add_service(reflection)
+        .add_service(CatalogServiceServer::new(CatalogGrpc::new(&state)))
+        .serve_with_incoming_shutdown(TcpIncoming::from(listener), shutdown)
+        .await?;
+    Ok(())
+}
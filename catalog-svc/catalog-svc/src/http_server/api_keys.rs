@@ -0,0 +1,76 @@
+//! HTTP handlers for API key administration (`/admin/api-keys`).
+
+use axum::{Json, extract::State, http::StatusCode};
+use uuid::Uuid;
+
+use crate::api_keys::api::{
+    ApiKeysServiceError, CreateApiKeyBody, CreatedApiKey, ListApiKeysRequest, ListApiKeysResponse,
+};
+use crate::http_server::CatalogApp;
+use crate::http_server::extract::{ApiJson, ApiPath, ApiQuery};
+use crate::http_server::problem::ProblemDetails;
+
+impl From<ApiKeysServiceError> for ProblemDetails {
+    fn from(err: ApiKeysServiceError) -> ProblemDetails {
+        match err {
+            ApiKeysServiceError::ValidationError(errors) => ProblemDetails::validation(errors),
+            ApiKeysServiceError::InvalidKey(_) => {
+                ProblemDetails::new(StatusCode::UNAUTHORIZED).with_detail(err.to_string())
+            }
+            ApiKeysServiceError::InternalError(_) => ProblemDetails::internal(&err),
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys",
+    request_body = CreateApiKeyBody,
+    responses(
+        (status = 201, description = "API key minted; the key is only shown in this response",
+            body = CreatedApiKey),
+        (status = 400, description = "Invalid name, scopes or expiry",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn create_api_key(
+    State(state): State<CatalogApp>,
+    ApiJson(body): ApiJson<CreateApiKeyBody>,
+) -> Result<(StatusCode, Json<CreatedApiKey>), ProblemDetails> {
+    let created = state.api_keys.create(body).await?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/api-keys",
+    params(ListApiKeysRequest),
+    responses((status = 200, description = "API keys, oldest first", body = ListApiKeysResponse)),
+)]
+pub(super) async fn list_api_keys(
+    State(state): State<CatalogApp>,
+    ApiQuery(req): ApiQuery<ListApiKeysRequest>,
+) -> Result<Json<ListApiKeysResponse>, ProblemDetails> {
+    Ok(Json(state.api_keys.list(req).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/api-keys/{key_id}",
+    params(("key_id" = Uuid, Path, description = "API key ID")),
+    responses(
+        (status = 204, description = "API key revoked"),
+        (status = 404, description = "API key not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn revoke_api_key(
+    State(state): State<CatalogApp>,
+    ApiPath(key_id): ApiPath<Uuid>,
+) -> Result<StatusCode, ProblemDetails> {
+    if state.api_keys.revoke(key_id).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ProblemDetails::not_found())
+    }
+}
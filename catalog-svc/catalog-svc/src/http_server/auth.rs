@@ -0,0 +1,72 @@
+//! Bearer token / API key authentication and per-route permissions for the API router.
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{Method, StatusCode, header},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::auth::{API_KEY_HEADER, AuthError, Permission};
+use crate::http_server::CatalogApp;
+use crate::http_server::problem::ProblemDetails;
+
+impl From<AuthError> for ProblemDetails {
+    fn from(err: AuthError) -> ProblemDetails {
+        let status = match err {
+            AuthError::MissingToken | AuthError::InvalidToken(_) => StatusCode::UNAUTHORIZED,
+            AuthError::Forbidden(_) => StatusCode::FORBIDDEN,
+        };
+        ProblemDetails::new(status).with_detail(err.to_string())
+    }
+}
+
+/// Permission needed for `method` on the route template `route` (as registered in the router):
+/// reads need [Permission::Read], writes [Permission::Write], review moderation and `/admin`
+/// routes [Permission::Admin]. `/graphql` needs [Permission::Read]; its mutations check for
+/// more.
+pub(super) fn required_permission(method: &Method, route: &str) -> Permission {
+    match route {
+        _ if route.starts_with("/admin/") => Permission::Admin,
+        "/graphql" => Permission::Read,
+        "/catalog/items/{item_id}/reviews/{review_id}/moderation" => Permission::Admin,
+        "/catalog/items/{item_id}/reviews/{review_id}" if method == Method::DELETE => {
+            Permission::Admin
+        }
+        _ if method == Method::GET || method == Method::HEAD => Permission::Read,
+        _ => Permission::Write,
+    }
+}
+
+/// Route middleware: authenticate the caller (by [API_KEY_HEADER] if present, bearer token
+/// otherwise), check the route's [required_permission] and make the [crate::auth::Principal]
+/// available to handlers as a request extension.
+pub(super) async fn authorize(
+    State(state): State<CatalogApp>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ProblemDetails> {
+    let headers = request.headers();
+    let principal = match headers.get(API_KEY_HEADER) {
+        Some(api_key) => {
+            let api_key = api_key.to_str().unwrap_or_default();
+            state.api_keys.authenticate(api_key).await?
+        }
+        None => {
+            let authorization = headers
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok());
+            state.auth.authenticate(authorization).await?
+        }
+    };
+
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(MatchedPath::as_str)
+        .unwrap_or_default();
+    principal.authorize(required_permission(request.method(), route))?;
+
+    request.extensions_mut().insert(principal);
+    Ok(next.run(request).await)
+}
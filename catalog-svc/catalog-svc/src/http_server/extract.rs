@@ -0,0 +1,150 @@
+//! Extractors rejecting malformed requests with [ProblemDetails] instead of axum's plain-text
+//! rejections. Bodies and query strings that fail to deserialize are validation problems naming
+//! the offending field.
+
+use axum::body::Bytes;
+use axum::extract::{FromRequest, FromRequestParts, Path, Request};
+use axum::http::{HeaderMap, StatusCode, header, request::Parts};
+use serde::de::DeserializeOwned;
+use serde_path_to_error::Segment;
+
+use crate::common::validation::ValidationErrors;
+use crate::http_server::problem::ProblemDetails;
+
+/// JSON request body, like [axum::Json].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApiJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ProblemDetails;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !is_json(req.headers()) {
+            return Err(ProblemDetails::new(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+                .with_detail("Expected a request body with `Content-Type: application/json`."));
+        }
+        let bytes = Bytes::from_request(req, state).await.map_err(|rejection| {
+            ProblemDetails::new(rejection.status()).with_detail(rejection.body_text())
+        })?;
+        let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+        let value = serde_path_to_error::deserialize(&mut deserializer).map_err(|err| {
+            let pointer = match err.inner().classify() {
+                serde_json::error::Category::Data => json_pointer(err.path()),
+                // Syntax errors concern the body as a whole
+                _ => String::new(),
+            };
+            invalid(pointer, &err.into_inner().to_string())
+        })?;
+        deserializer
+            .end()
+            .map_err(|err| invalid(String::new(), &err.to_string()))?;
+        Ok(ApiJson(value))
+    }
+}
+
+/// Query string parameters, like [axum::extract::Query].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApiQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ProblemDetails;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let query = parts.uri.query().unwrap_or_default();
+        let deserializer =
+            serde_urlencoded::Deserializer::new(form_urlencoded::parse(query.as_bytes()));
+        serde_path_to_error::deserialize(deserializer)
+            .map(ApiQuery)
+            .map_err(|err| invalid(json_pointer(err.path()), &err.into_inner().to_string()))
+    }
+}
+
+/// Path parameters, like [axum::extract::Path].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApiPath<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ApiPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = ProblemDetails;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection| {
+                ProblemDetails::new(rejection.status()).with_detail(rejection.body_text())
+            })?;
+        Ok(ApiPath(value))
+    }
+}
+
+/// `application/json` or any `application/*+json` media type.
+fn is_json(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence == "application/json"
+        || (essence.starts_with("application/") && essence.ends_with("+json"))
+}
+
+/// Validation problem for a value that failed to deserialize at `pointer`. Serde reports a missing
+/// field at the enclosing object; the violation names the field itself.
+fn invalid(mut pointer: String, message: &str) -> ProblemDetails {
+    // serde_json appends the position, which means nothing to API clients
+    let message = message
+        .rsplit_once(" at line ")
+        .map_or(message, |(message, _)| message);
+    let message = match message
+        .strip_prefix("missing field `")
+        .and_then(|field| field.strip_suffix('`'))
+    {
+        Some(field) => {
+            pointer.push('/');
+            pointer.push_str(&escape(field));
+            "is required".to_string()
+        }
+        None => message.to_string(),
+    };
+    let mut errors = ValidationErrors::new();
+    errors.add_at(pointer, message);
+    ProblemDetails::validation(errors)
+}
+
+/// JSON Pointer (RFC 6901) of a deserialization path, e.g. `/items/0/name`.
+fn json_pointer(path: &serde_path_to_error::Path) -> String {
+    path.iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(index.to_string()),
+            Segment::Map { key } => Some(escape(key)),
+            Segment::Enum { variant } => Some(escape(variant)),
+            Segment::Unknown => None,
+        })
+        .fold(String::new(), |mut pointer, segment| {
+            pointer.push('/');
+            pointer.push_str(&segment);
+            pointer
+        })
+}
+
+fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
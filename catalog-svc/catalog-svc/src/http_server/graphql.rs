@@ -0,0 +1,20 @@
+//! `POST /graphql`: the GraphQL API (see [crate::graphql]), executed as the authenticated caller.
+
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::extract::{Extension, State};
+
+use crate::auth::Principal;
+use crate::http_server::CatalogApp;
+
+/// Route permission is [crate::auth::Permission::Read]; mutations check for write access.
+pub(super) async fn graphql(
+    State(state): State<CatalogApp>,
+    Extension(principal): Extension<Principal>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    state
+        .graphql
+        .execute(request.into_inner().data(principal))
+        .await
+        .into()
+}
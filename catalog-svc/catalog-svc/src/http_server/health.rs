@@ -0,0 +1,137 @@
+//! Health endpoints for orchestrators and load balancers: `/health/live` (the process serves
+//! requests) and `/health/ready` (dependencies are usable and the server is not shutting down).
+
+use std::collections::{BTreeMap, HashSet};
+use std::time::{Duration, Instant};
+
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use serde::Serialize;
+
+use crate::http_server::CatalogApp;
+use crate::server::MIGRATOR;
+
+/// Longest a single dependency check may take before it is reported down.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Up,
+    Down,
+}
+
+/// Outcome of one readiness check.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Check {
+    status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latency_ms: Option<u128>,
+}
+
+impl Check {
+    fn up() -> Self {
+        Self {
+            status: Status::Up,
+            detail: None,
+            latency_ms: None,
+        }
+    }
+
+    fn down(detail: impl Into<String>) -> Self {
+        Self {
+            status: Status::Down,
+            detail: Some(detail.into()),
+            latency_ms: None,
+        }
+    }
+
+    fn timed(mut self, started: Instant) -> Self {
+        self.latency_ms = Some(started.elapsed().as_millis());
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Health {
+    status: Status,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    checks: BTreeMap<&'static str, Check>,
+}
+
+impl IntoResponse for Health {
+    fn into_response(self) -> axum::response::Response {
+        let status = match self.status {
+            Status::Up => StatusCode::OK,
+            Status::Down => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        (status, Json(self)).into_response()
+    }
+}
+
+/// Liveness: always up while the server answers.
+pub(super) async fn live() -> impl IntoResponse {
+    Health {
+        status: Status::Up,
+        checks: BTreeMap::new(),
+    }
+}
+
+/// Readiness: `503` when PostgreSQL is unreachable, migrations are pending or `server_shutdown`
+/// has been cancelled (so load balancers stop routing here while in-flight requests drain).
+pub(super) async fn ready(State(state): State<CatalogApp>) -> impl IntoResponse {
+    let mut checks = BTreeMap::new();
+    checks.insert(
+        "shutdown",
+        if state.server_shutdown.is_cancelled() {
+            Check::down("shutting down")
+        } else {
+            Check::up()
+        },
+    );
+    checks.insert("postgres", check_postgres(&state.pg_pool).await);
+    checks.insert("migrations", check_migrations(&state.pg_pool).await);
+
+    let status = if checks.values().all(|check| check.status == Status::Up) {
+        Status::Up
+    } else {
+        Status::Down
+    };
+    Health { status, checks }
+}
+
+async fn check_postgres(pool: &sqlx::PgPool) -> Check {
+    let started = Instant::now();
+    let ping = sqlx::query_scalar::<_, i32>("SELECT 1").fetch_one(pool);
+    match tokio::time::timeout(CHECK_TIMEOUT, ping).await {
+        Ok(Ok(_)) => Check::up(),
+        Ok(Err(err)) => Check::down(err.to_string()),
+        Err(_) => Check::down("timed out"),
+    }
+    .timed(started)
+}
+
+/// Down while any migration embedded in this build has not been applied successfully.
+async fn check_migrations(pool: &sqlx::PgPool) -> Check {
+    let applied =
+        sqlx::query_scalar::<_, i64>("SELECT version FROM _sqlx_migrations WHERE success")
+            .fetch_all(pool);
+    let applied: HashSet<i64> = match tokio::time::timeout(CHECK_TIMEOUT, applied).await {
+        Ok(Ok(versions)) => versions.into_iter().collect(),
+        Ok(Err(err)) => return Check::down(err.to_string()),
+        Err(_) => return Check::down("timed out"),
+    };
+    let pending: Vec<String> = MIGRATOR
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .filter(|migration| !applied.contains(&migration.version))
+        .map(|migration| migration.version.to_string())
+        .collect();
+    if pending.is_empty() {
+        Check::up()
+    } else {
+        Check::down(format!("pending migrations: {}", pending.join(", ")))
+    }
+}
@@ -0,0 +1,85 @@
+//! HTTP handler for the item change stream (`/catalog/items/changes`, Server-Sent Events).
+
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::{Stream, StreamExt};
+
+use crate::catalog::api::{ChangePosition, ItemChange};
+use crate::catalog::changes::ChangeFeedEvent;
+use crate::common::validation::ValidationErrors;
+use crate::http_server::CatalogApp;
+use crate::http_server::problem::ProblemDetails;
+
+/// Header with the id of the last event a reconnecting `EventSource` received.
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+/// Event telling the client that changes were missed and it must reload what it holds.
+const RESET_EVENT: &str = "reset";
+
+#[utoipa::path(
+    get,
+    path = "/catalog/items/changes",
+    params(
+        ("Last-Event-ID" = Option<String>, Header, nullable = false,
+            description = "Id of the last event received; the stream resumes after it"),
+    ),
+    responses(
+        (status = 200, description = "Server-Sent Events stream of item changes. Events are named \
+            after the change type (`created`, `updated`, `deleted`), carry an ItemChange as data \
+            and their log position as id. A `reset` event means changes after `Last-Event-ID` \
+            are no longer available: reload, then keep consuming the stream.",
+            body = ItemChange, content_type = "text/event-stream"),
+        (status = 400, description = "Malformed Last-Event-ID",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn stream_item_changes(
+    State(state): State<CatalogApp>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ProblemDetails> {
+    let after = last_event_id(&headers).map_err(ProblemDetails::validation)?;
+    // Ends when the server shuts down, so graceful shutdown is not held up by open streams
+    let changes = state
+        .item_changes
+        .subscribe(after, state.server_shutdown.clone())
+        .await
+        .map_err(|err| ProblemDetails::internal(&err))?;
+    Ok(Sse::new(changes.map(sse_event)).keep_alive(KeepAlive::default()))
+}
+
+/// Position in the `Last-Event-ID` header, if sent.
+fn last_event_id(headers: &HeaderMap) -> Result<Option<ChangePosition>, ValidationErrors> {
+    let Some(value) = headers.get(LAST_EVENT_ID_HEADER) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .map(Some)
+        .ok_or_else(|| {
+            ValidationErrors::single(
+                "Last-Event-ID",
+                "must be the id of an event from this stream",
+            )
+        })
+}
+
+fn sse_event(event: ChangeFeedEvent) -> Result<Event, axum::Error> {
+    match event {
+        ChangeFeedEvent::Change(change) => Event::default()
+            .id(change.position.to_string())
+            .event(change.change_type.to_string())
+            .json_data(&change),
+        ChangeFeedEvent::Reset(position) => {
+            let event = Event::default().event(RESET_EVENT).data("{}");
+            Ok(match position {
+                Some(position) => event.id(position.to_string()),
+                None => event,
+            })
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! HTTP handler for the incremental sync of catalog items (`/catalog/items/sync`).
+
+use axum::{Json, extract::State, http::StatusCode};
+
+use crate::catalog::api::{ItemSyncRequest, ItemSyncResponse, SyncWatermark};
+use crate::catalog::sync::SyncError;
+use crate::common::validation::ValidationErrors;
+use crate::http_server::CatalogApp;
+use crate::http_server::extract::ApiQuery;
+use crate::http_server::problem::ProblemDetails;
+
+impl From<SyncError> for ProblemDetails {
+    fn from(err: SyncError) -> ProblemDetails {
+        match err {
+            SyncError::WatermarkExpired => ProblemDetails::new(StatusCode::GONE).with_detail(
+                "Changes since the watermark are no longer available; sync again without `since`.",
+            ),
+            SyncError::Repository(_) => ProblemDetails::internal(&err),
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/catalog/items/sync",
+    params(ItemSyncRequest),
+    responses(
+        (status = 200, description = "Items modified and ids of items deleted after `since`, \
+            oldest change first. Changes are returned once they are a few seconds old, so none is \
+            missed when writers' clocks differ. Rating changes do not move `modifiedAt`.",
+            body = ItemSyncResponse),
+        (status = 400, description = "Malformed watermark",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 410, description = "Watermark older than the change log retention: sync again \
+            without `since`",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn sync_catalog_items(
+    State(state): State<CatalogApp>,
+    ApiQuery(req): ApiQuery<ItemSyncRequest>,
+) -> Result<Json<ItemSyncResponse>, ProblemDetails> {
+    let since = parse_since(req.since.as_deref()).map_err(ProblemDetails::validation)?;
+    let response = state.item_sync.sync(since, req.limit).await?;
+    Ok(Json(response))
+}
+
+fn parse_since(since: Option<&str>) -> Result<Option<SyncWatermark>, ValidationErrors> {
+    since
+        .map(|since| {
+            since.parse().map_err(|_| {
+                ValidationErrors::single("since", "must be a watermark from a sync response")
+            })
+        })
+        .transpose()
+}
@@ -0,0 +1,193 @@
+//! HTTP handlers for the item media subresource (`/catalog/items/{item_id}/media`).
+
+use axum::{
+    Json,
+    body::Bytes,
+    extract::State,
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::http_server::CatalogApp;
+use crate::http_server::extract::{ApiJson, ApiPath};
+use crate::http_server::problem::ProblemDetails;
+use crate::media::api::{
+    ImageBytes, MediaAttachment, MediaContent, MediaServiceError, ReorderMediaBody,
+};
+
+impl From<MediaServiceError> for ProblemDetails {
+    fn from(err: MediaServiceError) -> ProblemDetails {
+        let status = match err {
+            MediaServiceError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            MediaServiceError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            MediaServiceError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            MediaServiceError::InternalError(_) => return ProblemDetails::internal(&err),
+        };
+        ProblemDetails::new(status).with_detail(err.to_string())
+    }
+}
+
+fn content_response(content: MediaContent) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, content.content_type)],
+        content.bytes,
+    )
+}
+
+#[utoipa::path(
+    post,
+    path = "/catalog/items/{item_id}/media",
+    params(("item_id" = Uuid, Path, description = "Catalog item ID")),
+    request_body(
+        content = inline(ImageBytes),
+        content_type = "application/octet-stream",
+        description = "Image bytes (JPEG, PNG, GIF or WebP; the type is detected from the content)",
+    ),
+    responses(
+        (status = 201, description = "Media attached to the item", body = MediaAttachment),
+        (status = 400, description = "Image could not be decoded",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Catalog item not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 413, description = "Upload exceeds the configured size limit",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 415, description = "Content is not a supported image format",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn upload_item_media(
+    State(state): State<CatalogApp>,
+    ApiPath(item_id): ApiPath<Uuid>,
+    body: Bytes,
+) -> Result<(StatusCode, Json<MediaAttachment>), ProblemDetails> {
+    let media = state
+        .media
+        .upload(item_id, body)
+        .await?
+        .ok_or_else(ProblemDetails::not_found)?;
+    Ok((StatusCode::CREATED, Json(media)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/catalog/items/{item_id}/media",
+    params(("item_id" = Uuid, Path, description = "Catalog item ID")),
+    responses(
+        (status = 200, description = "Item media in display order", body = Vec<MediaAttachment>),
+        (status = 404, description = "Catalog item not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn list_item_media(
+    State(state): State<CatalogApp>,
+    ApiPath(item_id): ApiPath<Uuid>,
+) -> Result<Json<Vec<MediaAttachment>>, ProblemDetails> {
+    state
+        .media
+        .list(item_id)
+        .await?
+        .map(Json)
+        .ok_or_else(ProblemDetails::not_found)
+}
+
+#[utoipa::path(
+    put,
+    path = "/catalog/items/{item_id}/media/order",
+    params(("item_id" = Uuid, Path, description = "Catalog item ID")),
+    request_body = ReorderMediaBody,
+    responses(
+        (status = 200, description = "Item media in the new display order", body = Vec<MediaAttachment>),
+        (status = 400, description = "mediaIds is not a permutation of the item's media",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Catalog item not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn reorder_item_media(
+    State(state): State<CatalogApp>,
+    ApiPath(item_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<ReorderMediaBody>,
+) -> Result<Json<Vec<MediaAttachment>>, ProblemDetails> {
+    state
+        .media
+        .reorder(item_id, body.media_ids)
+        .await?
+        .map(Json)
+        .ok_or_else(ProblemDetails::not_found)
+}
+
+#[utoipa::path(
+    get,
+    path = "/catalog/items/{item_id}/media/{media_id}/content",
+    params(
+        ("item_id" = Uuid, Path, description = "Catalog item ID"),
+        ("media_id" = Uuid, Path, description = "Media attachment ID"),
+    ),
+    responses(
+        (status = 200, description = "Original image bytes", content_type = "image/*", body = inline(ImageBytes)),
+        (status = 404, description = "Media not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn get_item_media_content(
+    State(state): State<CatalogApp>,
+    ApiPath((item_id, media_id)): ApiPath<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, ProblemDetails> {
+    state
+        .media
+        .content(item_id, media_id)
+        .await?
+        .map(content_response)
+        .ok_or_else(ProblemDetails::not_found)
+}
+
+#[utoipa::path(
+    get,
+    path = "/catalog/items/{item_id}/media/{media_id}/thumbnail",
+    params(
+        ("item_id" = Uuid, Path, description = "Catalog item ID"),
+        ("media_id" = Uuid, Path, description = "Media attachment ID"),
+    ),
+    responses(
+        (status = 200, description = "PNG thumbnail", content_type = "image/png", body = inline(ImageBytes)),
+        (status = 404, description = "Media not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn get_item_media_thumbnail(
+    State(state): State<CatalogApp>,
+    ApiPath((item_id, media_id)): ApiPath<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, ProblemDetails> {
+    state
+        .media
+        .thumbnail(item_id, media_id)
+        .await?
+        .map(content_response)
+        .ok_or_else(ProblemDetails::not_found)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/catalog/items/{item_id}/media/{media_id}",
+    params(
+        ("item_id" = Uuid, Path, description = "Catalog item ID"),
+        ("media_id" = Uuid, Path, description = "Media attachment ID"),
+    ),
+    responses(
+        (status = 204, description = "Media deleted"),
+        (status = 404, description = "Media not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn delete_item_media(
+    State(state): State<CatalogApp>,
+    ApiPath((item_id, media_id)): ApiPath<(Uuid, Uuid)>,
+) -> Result<StatusCode, ProblemDetails> {
+    let deleted = state.media.delete(item_id, media_id).await?;
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ProblemDetails::not_found())
+    }
+}
@@ -0,0 +1,28 @@
+//! Prometheus scrape endpoint (`/metrics`) and route labels for the HTTP request metrics.
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::header,
+    response::IntoResponse,
+};
+
+use crate::app_metrics;
+use crate::http_server::CatalogApp;
+
+/// Route template of a request (e.g. `/catalog/items/{item_id}`), used as the `route` label.
+pub(super) fn route_label(request: &Request) -> String {
+    request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("unmatched", MatchedPath::as_str)
+        .to_string()
+}
+
+/// All metrics in the Prometheus text format; refreshes the database pool gauges first.
+pub(super) async fn metrics(State(state): State<CatalogApp>) -> impl IntoResponse {
+    app_metrics::record_pool_stats(&state.pg_pool);
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+}
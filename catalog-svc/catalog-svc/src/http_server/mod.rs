@@ -1,29 +1,74 @@
+mod api_keys;
+mod auth;
+mod extract;
+mod graphql;
+mod health;
+mod item_changes;
+mod item_sync;
+mod media;
+mod metrics;
+pub mod problem;
+mod rate_limit;
+mod related_items;
+mod reviews;
+mod webhooks;
+
 use axum::{
     Json, Router,
-    extract::{Path, Query, State},
-    http::StatusCode,
-    routing::{get, post},
+    extract::{DefaultBodyLimit, State},
+    http::{StatusCode, header},
+    middleware,
+    response::{IntoResponse, Response},
+    routing::{delete, get, post, put},
 };
-use rust_demo_commons::util::server;
+use rust_demo_commons::util::metrics::{HttpMetricsLayer, Metrics};
+use rust_demo_commons::util::server::{self, RateLimit, RateLimitLayer};
 use sqlx::Postgres;
 use tower_http::services::{ServeDir, ServeFile};
-use utoipa::OpenApi;
+use utoipa::openapi::security::{
+    ApiKey as ApiKeyScheme, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme,
+};
+use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::SwaggerUi;
 use uuid::Uuid;
 
+use crate::api_keys::api::{ApiKey, CreateApiKeyBody, CreatedApiKey, ListApiKeysResponse};
+use crate::api_keys::service::ApiKeysService;
+use crate::auth::{Authenticator, Role};
 use crate::catalog::api::CatalogServiceError;
 use crate::catalog::api::{
-    CatalogItem, CreateCatalogItemBody, ListCatalogItemsRequest, ListCatalogItemsResponse,
-    UpdateCatalogItemBody,
+    CatalogItem, CatalogItemSort, CreateCatalogItemBody, ItemChange, ItemChangeType,
+    ItemSyncRequest, ItemSyncResponse, ListCatalogItemsRequest, ListCatalogItemsResponse,
+    RatingSummary, UpdateCatalogItemBody,
 };
+use crate::catalog::changes::ItemChangeFeed;
 use crate::catalog::service::CatalogService;
+use crate::catalog::sync::ItemSync;
 use crate::common::pagination::Pagination;
+use crate::common::validation::FieldViolation;
+use crate::graphql::CatalogSchema;
+use crate::http_server::extract::{ApiJson, ApiPath, ApiQuery};
+use crate::http_server::problem::ProblemDetails;
+use crate::media::api::{MediaAttachment, ReorderMediaBody};
+use crate::media::service::MediaService;
+use crate::related_items::api::{CreateItemLinkBody, ItemLink, LinkKind, RelatedCatalogItem};
+use crate::related_items::service::RelatedItemsService;
+use crate::reviews::api::{
+    CreateReviewBody, ListReviewsResponse, ModerateReviewBody, ModerationState, Review,
+};
+use crate::reviews::service::ReviewsService;
+use crate::webhooks::api::{
+    CreateWebhookSubscriptionBody, CreatedWebhookSubscription, DeliveryStatus,
+    ListWebhookDeliveriesResponse, ListWebhookSubscriptionsResponse, UpdateWebhookSubscriptionBody,
+    WebhookDelivery, WebhookSubscription,
+};
+use crate::webhooks::service::WebhooksService;
 
-impl From<CatalogServiceError> for StatusCode {
-    fn from(err: CatalogServiceError) -> StatusCode {
+impl From<CatalogServiceError> for ProblemDetails {
+    fn from(err: CatalogServiceError) -> ProblemDetails {
         match err {
-            CatalogServiceError::ValidationError(_) => StatusCode::BAD_REQUEST,
-            CatalogServiceError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            CatalogServiceError::ValidationError(errors) => ProblemDetails::validation(errors),
+            CatalogServiceError::InternalError(_) => ProblemDetails::internal(&err),
         }
     }
 }
@@ -35,25 +80,129 @@ impl From<CatalogServiceError> for StatusCode {
         create_catalog_item,
         list_catalog_items,
         get_catalog_item,
+        get_catalog_item_by_slug,
         update_catalog_item,
         delete_catalog_item,
+        item_changes::stream_item_changes,
+        item_sync::sync_catalog_items,
+        media::upload_item_media,
+        media::list_item_media,
+        media::reorder_item_media,
+        media::get_item_media_content,
+        media::get_item_media_thumbnail,
+        media::delete_item_media,
+        related_items::create_item_link,
+        related_items::list_related_items,
+        related_items::delete_item_link,
+        reviews::create_review,
+        reviews::list_reviews,
+        reviews::get_review,
+        reviews::moderate_review,
+        reviews::delete_review,
+        api_keys::create_api_key,
+        api_keys::list_api_keys,
+        api_keys::revoke_api_key,
+        webhooks::create_webhook_subscription,
+        webhooks::list_webhook_subscriptions,
+        webhooks::get_webhook_subscription,
+        webhooks::update_webhook_subscription,
+        webhooks::delete_webhook_subscription,
+        webhooks::list_webhook_deliveries,
     ),
     components(schemas(
         CatalogItem,
+        RatingSummary,
+        CatalogItemSort,
         CreateCatalogItemBody,
         UpdateCatalogItemBody,
         ListCatalogItemsRequest,
         ListCatalogItemsResponse,
+        ItemChangeType,
+        ItemChange,
+        ItemSyncRequest,
+        ItemSyncResponse,
         Pagination,
-    ))
+        ProblemDetails,
+        FieldViolation,
+        MediaAttachment,
+        ReorderMediaBody,
+        LinkKind,
+        ItemLink,
+        CreateItemLinkBody,
+        RelatedCatalogItem,
+        ModerationState,
+        Review,
+        CreateReviewBody,
+        ModerateReviewBody,
+        ListReviewsResponse,
+        Role,
+        ApiKey,
+        CreateApiKeyBody,
+        CreatedApiKey,
+        ListApiKeysResponse,
+        WebhookSubscription,
+        CreateWebhookSubscriptionBody,
+        UpdateWebhookSubscriptionBody,
+        CreatedWebhookSubscription,
+        ListWebhookSubscriptionsResponse,
+        DeliveryStatus,
+        WebhookDelivery,
+        ListWebhookDeliveriesResponse,
+    )),
+    modifiers(&BearerAuth),
+    security(("bearer" = []), ("apiKey" = []))
 )]
 pub struct ApiDoc;
 
+/// Declares the `bearer` (JWT) and `apiKey` (`X-Api-Key`) security schemes; every API operation
+/// requires one of them.
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .description(Some(
+                        "Roles (claim `roles`): reader (GET), writer (create, update, delete), \
+                         admin (review moderation)",
+                    ))
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            "apiKey",
+            SecurityScheme::ApiKey(ApiKeyScheme::Header(ApiKeyValue::with_description(
+                "X-Api-Key",
+                "Key minted via /admin/api-keys; its scopes are the granted roles",
+            ))),
+        );
+    }
+}
+
 #[derive(Clone)]
 pub struct CatalogApp {
     pub server_shutdown: tokio_util::sync::CancellationToken,
     pub pg_pool: sqlx::Pool<Postgres>,
+    pub auth: Authenticator,
+    pub api_keys: ApiKeysService,
+    pub metrics: Metrics,
+    /// Per-client limit on API requests; None when rate limiting is disabled.
+    pub rate_limit: Option<RateLimit>,
+    /// Per-IP limit on API requests, checked before authentication; None when disabled.
+    pub ip_rate_limit: Option<RateLimit>,
     pub catalog: CatalogService,
+    pub graphql: CatalogSchema,
+    pub item_changes: ItemChangeFeed,
+    pub item_sync: ItemSync,
+    pub media: MediaService,
+    pub related_items: RelatedItemsService,
+    pub reviews: ReviewsService,
+    pub webhooks: WebhooksService,
 }
 
 /// Build the API router with the given shared state. Use this when you need to keep a copy of [CatalogApp].
@@ -61,7 +210,8 @@ pub fn router_with_state(state: CatalogApp) -> Router {
     let static_files =
         ServeDir::new("/app/public").not_found_service(ServeFile::new("/app/public/index.html"));
 
-    let api = Router::new()
+    let max_upload_bytes = state.media.max_upload_bytes();
+    let mut api = Router::new()
         .route(
             "/catalog/items",
             post(create_catalog_item).get(list_catalog_items),
@@ -72,11 +222,102 @@ pub fn router_with_state(state: CatalogApp) -> Router {
                 .post(update_catalog_item)
                 .delete(delete_catalog_item),
         )
+        .route(
+            "/catalog/items/changes",
+            get(item_changes::stream_item_changes),
+        )
+        .route("/catalog/items/sync", get(item_sync::sync_catalog_items))
+        .route("/graphql", post(graphql::graphql))
+        .route(
+            "/catalog/items/by-slug/{slug}",
+            get(get_catalog_item_by_slug),
+        )
+        .route(
+            "/catalog/items/{item_id}/media",
+            post(media::upload_item_media)
+                .layer(DefaultBodyLimit::max(max_upload_bytes))
+                .get(media::list_item_media),
+        )
+        .route(
+            "/catalog/items/{item_id}/media/order",
+            put(media::reorder_item_media),
+        )
+        .route(
+            "/catalog/items/{item_id}/media/{media_id}",
+            delete(media::delete_item_media),
+        )
+        .route(
+            "/catalog/items/{item_id}/media/{media_id}/content",
+            get(media::get_item_media_content),
+        )
+        .route(
+            "/catalog/items/{item_id}/media/{media_id}/thumbnail",
+            get(media::get_item_media_thumbnail),
+        )
+        .route(
+            "/catalog/items/{item_id}/related",
+            post(related_items::create_item_link).get(related_items::list_related_items),
+        )
+        .route(
+            "/catalog/items/{item_id}/related/{related_item_id}",
+            delete(related_items::delete_item_link),
+        )
+        .route(
+            "/catalog/items/{item_id}/reviews",
+            post(reviews::create_review).get(reviews::list_reviews),
+        )
+        .route(
+            "/catalog/items/{item_id}/reviews/{review_id}",
+            get(reviews::get_review).delete(reviews::delete_review),
+        )
+        .route(
+            "/catalog/items/{item_id}/reviews/{review_id}/moderation",
+            post(reviews::moderate_review),
+        )
+        .route(
+            "/admin/api-keys",
+            post(api_keys::create_api_key).get(api_keys::list_api_keys),
+        )
+        .route("/admin/api-keys/{key_id}", delete(api_keys::revoke_api_key))
+        .route(
+            "/admin/webhooks",
+            post(webhooks::create_webhook_subscription).get(webhooks::list_webhook_subscriptions),
+        )
+        .route(
+            "/admin/webhooks/{subscription_id}",
+            get(webhooks::get_webhook_subscription)
+                .post(webhooks::update_webhook_subscription)
+                .delete(webhooks::delete_webhook_subscription),
+        )
+        .route(
+            "/admin/webhooks/{subscription_id}/deliveries",
+            get(webhooks::list_webhook_deliveries),
+        );
+    // Route layers run outermost-last: `authorize` resolves the principal the limiter keys on,
+    // and the per-IP limit also throttles requests `authorize` rejects
+    if let Some(limit) = state.rate_limit {
+        api = api.route_layer(RateLimitLayer::new(limit, rate_limit::client_key));
+    }
+    api = api.route_layer(middleware::from_fn_with_state(
+        state.clone(),
+        auth::authorize,
+    ));
+    if let Some(limit) = state.ip_rate_limit {
+        api = api.route_layer(RateLimitLayer::new(limit, rate_limit::ip_key));
+    }
+    let api = api
+        .layer(HttpMetricsLayer::new(metrics::route_label))
+        .with_state(state.clone());
+    let operational = Router::new()
+        .route("/metrics", get(metrics::metrics))
+        .route("/health/live", get(health::live))
+        .route("/health/ready", get(health::ready))
         .with_state(state);
 
     Router::new()
         .layer(server::http_trace_layer())
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .merge(operational)
         .merge(api)
         .fallback_service(static_files)
 }
@@ -87,13 +328,14 @@ pub fn router_with_state(state: CatalogApp) -> Router {
     request_body = CreateCatalogItemBody,
     responses(
         (status = 201, description = "Catalog item created", body = CatalogItem),
-        (status = 400, description = "Validation error"),
+        (status = 400, description = "Validation error",
+            body = ProblemDetails, content_type = "application/problem+json"),
     )
 )]
 async fn create_catalog_item(
     State(state): State<CatalogApp>,
-    Json(body): Json<CreateCatalogItemBody>,
-) -> Result<(StatusCode, Json<CatalogItem>), StatusCode> {
+    ApiJson(body): ApiJson<CreateCatalogItemBody>,
+) -> Result<(StatusCode, Json<CatalogItem>), ProblemDetails> {
     let item = state.catalog.create(body).await?;
     Ok((StatusCode::CREATED, Json(item)))
 }
@@ -106,8 +348,8 @@ async fn create_catalog_item(
 )]
 async fn list_catalog_items(
     State(state): State<CatalogApp>,
-    Query(req): Query<ListCatalogItemsRequest>,
-) -> Result<Json<ListCatalogItemsResponse>, StatusCode> {
+    ApiQuery(req): ApiQuery<ListCatalogItemsRequest>,
+) -> Result<Json<ListCatalogItemsResponse>, ProblemDetails> {
     let response = state.catalog.list(req).await?;
     Ok(Json(response))
 }
@@ -118,19 +360,52 @@ async fn list_catalog_items(
     params(("item_id" = Uuid, Path, description = "Catalog item ID")),
     responses(
         (status = 200, description = "Catalog item found", body = CatalogItem),
-        (status = 404, description = "Catalog item not found"),
+        (status = 404, description = "Catalog item not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
     )
 )]
 async fn get_catalog_item(
     State(state): State<CatalogApp>,
-    Path(item_id): Path<Uuid>,
-) -> Result<Json<CatalogItem>, StatusCode> {
+    ApiPath(item_id): ApiPath<Uuid>,
+) -> Result<Json<CatalogItem>, ProblemDetails> {
     state
         .catalog
         .get(item_id)
         .await?
         .map(Json)
-        .ok_or(StatusCode::NOT_FOUND)
+        .ok_or_else(ProblemDetails::not_found)
+}
+
+#[utoipa::path(
+    get,
+    path = "/catalog/items/by-slug/{slug}",
+    params(("slug" = String, Path, description = "Current or previous slug of the catalog item")),
+    responses(
+        (status = 200, description = "Catalog item found by its current slug", body = CatalogItem),
+        (status = 301, description = "Previous slug; redirects to the current one",
+            headers(("Location" = String, description = "URL of the item under its current slug"))),
+        (status = 404, description = "Catalog item not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn get_catalog_item_by_slug(
+    State(state): State<CatalogApp>,
+    ApiPath(slug): ApiPath<String>,
+) -> Result<Response, ProblemDetails> {
+    let item = state
+        .catalog
+        .get_by_slug(&slug)
+        .await?
+        .ok_or_else(ProblemDetails::not_found)?;
+    if item.slug == slug {
+        return Ok(Json(item).into_response());
+    }
+    let location = format!("/catalog/items/by-slug/{}", item.slug);
+    Ok((
+        StatusCode::MOVED_PERMANENTLY,
+        [(header::LOCATION, location)],
+    )
+        .into_response())
 }
 
 #[utoipa::path(
@@ -140,20 +415,23 @@ async fn get_catalog_item(
     request_body = UpdateCatalogItemBody,
     responses(
         (status = 200, description = "Catalog item updated", body = CatalogItem),
-        (status = 404, description = "Catalog item not found"),
+        (status = 400, description = "Validation error",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Catalog item not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
     )
 )]
 async fn update_catalog_item(
     State(state): State<CatalogApp>,
-    Path(item_id): Path<Uuid>,
-    Json(body): Json<UpdateCatalogItemBody>,
-) -> Result<Json<CatalogItem>, StatusCode> {
+    ApiPath(item_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<UpdateCatalogItemBody>,
+) -> Result<Json<CatalogItem>, ProblemDetails> {
     state
         .catalog
         .update(item_id, body)
         .await?
         .map(Json)
-        .ok_or(StatusCode::NOT_FOUND)
+        .ok_or_else(ProblemDetails::not_found)
 }
 
 #[utoipa::path(
@@ -162,17 +440,18 @@ async fn update_catalog_item(
     params(("item_id" = Uuid, Path, description = "Catalog item ID")),
     responses(
         (status = 204, description = "Catalog item deleted"),
-        (status = 404, description = "Catalog item not found"),
+        (status = 404, description = "Catalog item not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
     )
 )]
 async fn delete_catalog_item(
     State(state): State<CatalogApp>,
-    Path(item_id): Path<Uuid>,
-) -> Result<StatusCode, StatusCode> {
+    ApiPath(item_id): ApiPath<Uuid>,
+) -> Result<StatusCode, ProblemDetails> {
     let deleted = state.catalog.delete(item_id).await?;
     if deleted {
         Ok(StatusCode::NO_CONTENT)
     } else {
-        Err(StatusCode::NOT_FOUND)
+        Err(ProblemDetails::not_found())
     }
 }
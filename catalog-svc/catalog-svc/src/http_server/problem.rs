@@ -0,0 +1,114 @@
+//! RFC 9457 Problem Details (`application/problem+json`) error responses.
+
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use rust_demo_commons::util::server::current_request_id;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::common::validation::{FieldViolation, ValidationErrors};
+
+/// Media type of [ProblemDetails] bodies.
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Problem type of validation failures; `errors` lists every offending field.
+pub const VALIDATION_PROBLEM_TYPE: &str = "urn:catalog-svc:problem:validation";
+
+/// Error body returned by every API endpoint (RFC 9457).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProblemDetails {
+    /// URI reference identifying the problem type; `about:blank` when the status code says it all.
+    #[serde(rename = "type")]
+    #[schema(example = "urn:catalog-svc:problem:validation")]
+    pub problem_type: String,
+    /// Short summary of the problem type.
+    #[schema(example = "Validation failed")]
+    pub title: String,
+    /// HTTP status code of this occurrence.
+    #[schema(example = 400)]
+    pub status: u16,
+    /// Explanation specific to this occurrence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Field-level violations (validation problems only).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldViolation>,
+    /// `X-Request-Id` of the failed request, to correlate with server logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "5f0c6a8e-3b9f-4d2c-9a51-7f2e8c1d4b60")]
+    pub request_id: Option<String>,
+}
+
+impl ProblemDetails {
+    /// Generic problem for `status` (`about:blank` type, title from the status reason phrase).
+    pub fn new(status: StatusCode) -> Self {
+        Self {
+            problem_type: "about:blank".to_string(),
+            title: status
+                .canonical_reason()
+                .unwrap_or("Unknown error")
+                .to_string(),
+            status: status.as_u16(),
+            detail: None,
+            errors: Vec::new(),
+            request_id: None,
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn not_found() -> Self {
+        Self::new(StatusCode::NOT_FOUND)
+    }
+
+    /// 400 listing every field violation.
+    pub fn validation(errors: ValidationErrors) -> Self {
+        Self {
+            problem_type: VALIDATION_PROBLEM_TYPE.to_string(),
+            title: "Validation failed".to_string(),
+            status: StatusCode::BAD_REQUEST.as_u16(),
+            detail: Some("One or more fields are invalid.".to_string()),
+            errors: errors.into_violations(),
+            request_id: None,
+        }
+    }
+
+    /// 500 without internal details; the cause is logged instead of being sent to the client.
+    pub fn internal(err: &dyn std::error::Error) -> Self {
+        tracing::error!(error = %err, "request failed with internal error");
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+impl From<StatusCode> for ProblemDetails {
+    fn from(status: StatusCode) -> Self {
+        ProblemDetails::new(status)
+    }
+}
+
+impl IntoResponse for ProblemDetails {
+    fn into_response(mut self) -> Response {
+        let status = self.status_code();
+        if self.request_id.is_none() {
+            self.request_id = current_request_id();
+        }
+        let mut response = (status, Json(self)).into_response();
+        let headers = response.headers_mut();
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+        if status == StatusCode::UNAUTHORIZED {
+            headers.insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        response
+    }
+}
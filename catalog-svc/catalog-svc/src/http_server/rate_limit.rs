@@ -0,0 +1,25 @@
+//! Client keys for the per-client and per-IP rate limiters ([rust_demo_commons::util::server::RateLimitLayer]).
+
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Request};
+
+use crate::auth::Principal;
+
+/// Rate limit key of a request: the authenticated principal (set by the `authorize` middleware),
+/// or the peer IP address when authentication is disabled.
+pub(super) fn client_key(request: &Request) -> String {
+    match request.extensions().get::<Principal>() {
+        Some(principal) if !principal.is_anonymous() => format!("principal:{}", principal.subject),
+        _ => ip_key(request),
+    }
+}
+
+/// Rate limit key of a request by peer IP address, whoever it authenticates as.
+pub(super) fn ip_key(request: &Request) -> String {
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| format!("ip:{}", addr.ip()))
+        .unwrap_or_else(|| "ip:unknown".to_string())
+}
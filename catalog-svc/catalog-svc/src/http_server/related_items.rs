@@ -0,0 +1,107 @@
+//! HTTP handlers for related items (`/catalog/items/{item_id}/related`).
+
+use axum::{Json, extract::State, http::StatusCode};
+use uuid::Uuid;
+
+use crate::http_server::CatalogApp;
+use crate::http_server::extract::{ApiJson, ApiPath, ApiQuery};
+use crate::http_server::problem::ProblemDetails;
+use crate::related_items::api::{
+    CreateItemLinkBody, DeleteItemLinkRequest, ItemLink, ListRelatedItemsRequest,
+    RelatedCatalogItem, RelatedItemsServiceError,
+};
+
+impl From<RelatedItemsServiceError> for ProblemDetails {
+    fn from(err: RelatedItemsServiceError) -> ProblemDetails {
+        let status = match err {
+            RelatedItemsServiceError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            RelatedItemsServiceError::Conflict => StatusCode::CONFLICT,
+            RelatedItemsServiceError::InternalError(_) => return ProblemDetails::internal(&err),
+        };
+        ProblemDetails::new(status).with_detail(err.to_string())
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/catalog/items/{item_id}/related",
+    params(("item_id" = Uuid, Path, description = "Catalog item ID")),
+    request_body = CreateItemLinkBody,
+    responses(
+        (status = 201, description = "Link created", body = ItemLink),
+        (status = 400, description = "Self-link or unknown related item",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Catalog item not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Link already exists",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn create_item_link(
+    State(state): State<CatalogApp>,
+    ApiPath(item_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<CreateItemLinkBody>,
+) -> Result<(StatusCode, Json<ItemLink>), ProblemDetails> {
+    let link = state
+        .related_items
+        .link(item_id, body)
+        .await?
+        .ok_or_else(ProblemDetails::not_found)?;
+    Ok((StatusCode::CREATED, Json(link)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/catalog/items/{item_id}/related",
+    params(
+        ("item_id" = Uuid, Path, description = "Catalog item ID"),
+        ListRelatedItemsRequest,
+    ),
+    responses(
+        (status = 200, description = "Items linked from this item", body = Vec<RelatedCatalogItem>),
+        (status = 404, description = "Catalog item not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn list_related_items(
+    State(state): State<CatalogApp>,
+    ApiPath(item_id): ApiPath<Uuid>,
+    ApiQuery(req): ApiQuery<ListRelatedItemsRequest>,
+) -> Result<Json<Vec<RelatedCatalogItem>>, ProblemDetails> {
+    state
+        .related_items
+        .related(item_id, req.kind)
+        .await?
+        .map(Json)
+        .ok_or_else(ProblemDetails::not_found)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/catalog/items/{item_id}/related/{related_item_id}",
+    params(
+        ("item_id" = Uuid, Path, description = "Catalog item ID"),
+        ("related_item_id" = Uuid, Path, description = "Linked catalog item ID"),
+        DeleteItemLinkRequest,
+    ),
+    responses(
+        (status = 204, description = "Link(s) removed"),
+        (status = 404, description = "Link not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn delete_item_link(
+    State(state): State<CatalogApp>,
+    ApiPath((item_id, related_item_id)): ApiPath<(Uuid, Uuid)>,
+    ApiQuery(req): ApiQuery<DeleteItemLinkRequest>,
+) -> Result<StatusCode, ProblemDetails> {
+    let removed = state
+        .related_items
+        .unlink(item_id, related_item_id, req.kind)
+        .await?;
+    if removed {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ProblemDetails::not_found())
+    }
+}
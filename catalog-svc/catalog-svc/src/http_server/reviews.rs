@@ -0,0 +1,157 @@
+//! HTTP handlers for item reviews (`/catalog/items/{item_id}/reviews`).
+
+use axum::{Extension, Json, extract::State, http::StatusCode};
+use uuid::Uuid;
+
+use crate::auth::{Permission, Principal};
+use crate::http_server::CatalogApp;
+use crate::http_server::extract::{ApiJson, ApiPath, ApiQuery};
+use crate::http_server::problem::ProblemDetails;
+use crate::reviews::api::{
+    CreateReviewBody, ListReviewsRequest, ListReviewsResponse, ModerateReviewBody, ModerationState,
+    Review, ReviewsServiceError,
+};
+
+impl From<ReviewsServiceError> for ProblemDetails {
+    fn from(err: ReviewsServiceError) -> ProblemDetails {
+        match err {
+            ReviewsServiceError::ValidationError(errors) => ProblemDetails::validation(errors),
+            ReviewsServiceError::InternalError(_) => ProblemDetails::internal(&err),
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/catalog/items/{item_id}/reviews",
+    params(("item_id" = Uuid, Path, description = "Catalog item ID")),
+    request_body = CreateReviewBody,
+    responses(
+        (status = 201, description = "Review submitted for moderation", body = Review),
+        (status = 400, description = "Invalid rating, title or author",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Catalog item not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn create_review(
+    State(state): State<CatalogApp>,
+    ApiPath(item_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<CreateReviewBody>,
+) -> Result<(StatusCode, Json<Review>), ProblemDetails> {
+    let review = state
+        .reviews
+        .create(item_id, body)
+        .await?
+        .ok_or_else(ProblemDetails::not_found)?;
+    Ok((StatusCode::CREATED, Json(review)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/catalog/items/{item_id}/reviews",
+    params(
+        ("item_id" = Uuid, Path, description = "Catalog item ID"),
+        ListReviewsRequest,
+    ),
+    responses(
+        (status = 200, description = "Reviews of this item, newest first", body = ListReviewsResponse),
+        (status = 403, description = "Listing unapproved reviews requires the admin role",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn list_reviews(
+    State(state): State<CatalogApp>,
+    Extension(principal): Extension<Principal>,
+    ApiPath(item_id): ApiPath<Uuid>,
+    ApiQuery(req): ApiQuery<ListReviewsRequest>,
+) -> Result<Json<ListReviewsResponse>, ProblemDetails> {
+    if req
+        .moderation_state
+        .is_some_and(|s| s != ModerationState::Approved)
+    {
+        principal.authorize(Permission::Admin)?;
+    }
+    Ok(Json(state.reviews.list(item_id, req).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/catalog/items/{item_id}/reviews/{review_id}",
+    params(
+        ("item_id" = Uuid, Path, description = "Catalog item ID"),
+        ("review_id" = Uuid, Path, description = "Review ID"),
+    ),
+    responses(
+        (status = 200, description = "Review found", body = Review),
+        (status = 404, description = "Review not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn get_review(
+    State(state): State<CatalogApp>,
+    Extension(principal): Extension<Principal>,
+    ApiPath((item_id, review_id)): ApiPath<(Uuid, Uuid)>,
+) -> Result<Json<Review>, ProblemDetails> {
+    // Unapproved reviews are only visible to moderators
+    state
+        .reviews
+        .get(item_id, review_id)
+        .await?
+        .filter(|review| {
+            review.moderation_state == ModerationState::Approved || principal.can(Permission::Admin)
+        })
+        .map(Json)
+        .ok_or_else(ProblemDetails::not_found)
+}
+
+#[utoipa::path(
+    post,
+    path = "/catalog/items/{item_id}/reviews/{review_id}/moderation",
+    params(
+        ("item_id" = Uuid, Path, description = "Catalog item ID"),
+        ("review_id" = Uuid, Path, description = "Review ID"),
+    ),
+    request_body = ModerateReviewBody,
+    responses(
+        (status = 200, description = "Review moderated", body = Review),
+        (status = 404, description = "Review not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn moderate_review(
+    State(state): State<CatalogApp>,
+    ApiPath((item_id, review_id)): ApiPath<(Uuid, Uuid)>,
+    ApiJson(body): ApiJson<ModerateReviewBody>,
+) -> Result<Json<Review>, ProblemDetails> {
+    state
+        .reviews
+        .moderate(item_id, review_id, body.moderation_state)
+        .await?
+        .map(Json)
+        .ok_or_else(ProblemDetails::not_found)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/catalog/items/{item_id}/reviews/{review_id}",
+    params(
+        ("item_id" = Uuid, Path, description = "Catalog item ID"),
+        ("review_id" = Uuid, Path, description = "Review ID"),
+    ),
+    responses(
+        (status = 204, description = "Review deleted"),
+        (status = 404, description = "Review not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn delete_review(
+    State(state): State<CatalogApp>,
+    ApiPath((item_id, review_id)): ApiPath<(Uuid, Uuid)>,
+) -> Result<StatusCode, ProblemDetails> {
+    if state.reviews.delete(item_id, review_id).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ProblemDetails::not_found())
+    }
+}
@@ -0,0 +1,149 @@
+//! HTTP handlers for webhook subscription administration (`/admin/webhooks`).
+
+use axum::{Json, extract::State, http::StatusCode};
+use uuid::Uuid;
+
+use crate::http_server::CatalogApp;
+use crate::http_server::extract::{ApiJson, ApiPath, ApiQuery};
+use crate::http_server::problem::ProblemDetails;
+use crate::webhooks::api::{
+    CreateWebhookSubscriptionBody, CreatedWebhookSubscription, ListWebhookDeliveriesRequest,
+    ListWebhookDeliveriesResponse, ListWebhookSubscriptionsResponse, UpdateWebhookSubscriptionBody,
+    WebhookSubscription, WebhooksServiceError,
+};
+
+impl From<WebhooksServiceError> for ProblemDetails {
+    fn from(err: WebhooksServiceError) -> ProblemDetails {
+        match err {
+            WebhooksServiceError::ValidationError(errors) => ProblemDetails::validation(errors),
+            WebhooksServiceError::InternalError(_) => ProblemDetails::internal(&err),
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/webhooks",
+    request_body = CreateWebhookSubscriptionBody,
+    responses(
+        (status = 201, description = "Subscription created; the secret is only shown in this \
+            response", body = CreatedWebhookSubscription),
+        (status = 400, description = "Invalid URL, event types or secret",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn create_webhook_subscription(
+    State(state): State<CatalogApp>,
+    ApiJson(body): ApiJson<CreateWebhookSubscriptionBody>,
+) -> Result<(StatusCode, Json<CreatedWebhookSubscription>), ProblemDetails> {
+    let created = state.webhooks.create(body).await?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/webhooks",
+    responses((status = 200, description = "Webhook subscriptions, oldest first",
+        body = ListWebhookSubscriptionsResponse)),
+)]
+pub(super) async fn list_webhook_subscriptions(
+    State(state): State<CatalogApp>,
+) -> Result<Json<ListWebhookSubscriptionsResponse>, ProblemDetails> {
+    Ok(Json(state.webhooks.list().await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/webhooks/{subscription_id}",
+    params(("subscription_id" = Uuid, Path, description = "Webhook subscription ID")),
+    responses(
+        (status = 200, description = "Webhook subscription", body = WebhookSubscription),
+        (status = 404, description = "Webhook subscription not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn get_webhook_subscription(
+    State(state): State<CatalogApp>,
+    ApiPath(subscription_id): ApiPath<Uuid>,
+) -> Result<Json<WebhookSubscription>, ProblemDetails> {
+    state
+        .webhooks
+        .get(subscription_id)
+        .await?
+        .map(Json)
+        .ok_or_else(ProblemDetails::not_found)
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/webhooks/{subscription_id}",
+    params(("subscription_id" = Uuid, Path, description = "Webhook subscription ID")),
+    request_body = UpdateWebhookSubscriptionBody,
+    responses(
+        (status = 200, description = "Webhook subscription updated", body = WebhookSubscription),
+        (status = 400, description = "Invalid URL, event types or secret",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Webhook subscription not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn update_webhook_subscription(
+    State(state): State<CatalogApp>,
+    ApiPath(subscription_id): ApiPath<Uuid>,
+    ApiJson(body): ApiJson<UpdateWebhookSubscriptionBody>,
+) -> Result<Json<WebhookSubscription>, ProblemDetails> {
+    state
+        .webhooks
+        .update(subscription_id, body)
+        .await?
+        .map(Json)
+        .ok_or_else(ProblemDetails::not_found)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/webhooks/{subscription_id}",
+    params(("subscription_id" = Uuid, Path, description = "Webhook subscription ID")),
+    responses(
+        (status = 204, description = "Webhook subscription and its deliveries deleted"),
+        (status = 404, description = "Webhook subscription not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn delete_webhook_subscription(
+    State(state): State<CatalogApp>,
+    ApiPath(subscription_id): ApiPath<Uuid>,
+) -> Result<StatusCode, ProblemDetails> {
+    if state.webhooks.delete(subscription_id).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ProblemDetails::not_found())
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/webhooks/{subscription_id}/deliveries",
+    params(
+        ("subscription_id" = Uuid, Path, description = "Webhook subscription ID"),
+        ListWebhookDeliveriesRequest,
+    ),
+    responses(
+        (status = 200, description = "Deliveries to the subscription, newest first",
+            body = ListWebhookDeliveriesResponse),
+        (status = 404, description = "Webhook subscription not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+pub(super) async fn list_webhook_deliveries(
+    State(state): State<CatalogApp>,
+    ApiPath(subscription_id): ApiPath<Uuid>,
+    ApiQuery(req): ApiQuery<ListWebhookDeliveriesRequest>,
+) -> Result<Json<ListWebhookDeliveriesResponse>, ProblemDetails> {
+    state
+        .webhooks
+        .list_deliveries(subscription_id, req)
+        .await?
+        .map(Json)
+        .ok_or_else(ProblemDetails::not_found)
+}
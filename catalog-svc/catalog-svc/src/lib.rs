@@ -1,5 +1,17 @@
+pub mod api_keys;
 pub mod app_config;
+pub mod app_metrics;
+pub mod auth;
+pub mod blob_store;
 pub mod catalog;
 pub mod common;
+pub mod events;
+pub mod graphql;
+pub mod grpc_server;
 pub mod http_server;
+pub mod media;
+pub mod related_items;
+pub mod reviews;
 pub mod server;
+pub mod supplier_feed;
+pub mod webhooks;
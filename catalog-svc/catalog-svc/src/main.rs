@@ -8,10 +8,17 @@ const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    util::tracing::init_tracing();
-
     let app_config = AppConfig::load().expect("failed to load app config");
-    let app_state = server::build_app(&app_config).await;
+    let _tracing = util::tracing::init_tracing_with(&app_config.tracing_settings())?;
+
+    let (app_state, background) = server::build_app(&app_config).await;
+    let mut handles = Vec::new();
+    if app_config.grpc.enabled {
+        let (grpc_handle, _addr) = server::start_grpc_and_serve(&app_state, &app_config).await?;
+        handles.push(grpc_handle);
+    }
     let (state, handle, _addr) = server::start_service_and_serve(app_state, app_config).await?;
-    util::server::graceful_shutdown(state.server_shutdown, handle, DRAIN_TIMEOUT).await
+    handles.push(handle);
+    let handle = server::join_servers(handles, state.server_shutdown.clone());
+    util::server::graceful_shutdown(state.server_shutdown, handle, background, DRAIN_TIMEOUT).await
 }
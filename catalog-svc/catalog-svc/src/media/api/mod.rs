@@ -0,0 +1,61 @@
+use std::error::Error as StdError;
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Errors that can occur when using [crate::media::service::MediaService].
+#[derive(Error, Debug)]
+pub enum MediaServiceError {
+    #[error("validation error: {0}")]
+    ValidationError(#[source] BoxError),
+
+    #[error("unsupported media type: {0}")]
+    UnsupportedMediaType(String),
+
+    #[error("upload of {size} bytes exceeds the {limit} byte limit")]
+    PayloadTooLarge { size: usize, limit: usize },
+
+    #[error("internal error: {0}")]
+    InternalError(#[source] BoxError),
+}
+
+/// Image attached to a catalog item. The bytes live in the blob store; this is the metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaAttachment {
+    pub media_id: Uuid,
+    pub item_id: Uuid,
+    /// Zero-based display position within the item's media list.
+    pub position: u32,
+    /// Content type sniffed from the uploaded bytes (e.g. `image/png`).
+    pub content_type: String,
+    pub size_bytes: u64,
+    pub width: u32,
+    pub height: u32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body for reordering an item's media: every attachment id, in the new display order.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderMediaBody {
+    pub media_ids: Vec<Uuid>,
+}
+
+/// OpenAPI schema of raw image bytes (upload body and content/thumbnail responses).
+#[derive(ToSchema)]
+#[schema(value_type = String, format = Binary)]
+pub struct ImageBytes(pub Vec<u8>);
+
+/// Raw media bytes with their content type (original upload or generated thumbnail).
+#[derive(Debug, Clone)]
+pub struct MediaContent {
+    pub content_type: String,
+    pub bytes: Bytes,
+}
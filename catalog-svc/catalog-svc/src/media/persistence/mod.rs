@@ -0,0 +1,224 @@
+//! SQL repository for [MediaAttachment] metadata.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{Executor, FromRow, Postgres};
+use uuid::Uuid;
+
+use crate::catalog::persistence::RepositoryError;
+use crate::media::api::MediaAttachment;
+
+/// Stored attachment: API-facing metadata plus the blob store keys of its content.
+#[derive(Debug, Clone)]
+pub struct MediaRecord {
+    pub attachment: MediaAttachment,
+    pub blob_key: String,
+    pub thumbnail_key: String,
+}
+
+/// Row type for mapping SELECT results from `catalog_item_media` into [MediaRecord].
+#[derive(FromRow)]
+struct MediaRow {
+    media_id: Uuid,
+    item_id: Uuid,
+    position: i32,
+    content_type: String,
+    size_bytes: i64,
+    width: i32,
+    height: i32,
+    blob_key: String,
+    thumbnail_key: String,
+    created_at: NaiveDateTime,
+}
+
+impl MediaRow {
+    fn into_record(self) -> MediaRecord {
+        MediaRecord {
+            attachment: MediaAttachment {
+                media_id: self.media_id,
+                item_id: self.item_id,
+                position: self.position.try_into().unwrap_or_default(),
+                content_type: self.content_type,
+                size_bytes: self.size_bytes.try_into().unwrap_or_default(),
+                width: self.width.try_into().unwrap_or_default(),
+                height: self.height.try_into().unwrap_or_default(),
+                created_at: DateTime::<Utc>::from_naive_utc_and_offset(self.created_at, Utc),
+            },
+            blob_key: self.blob_key,
+            thumbnail_key: self.thumbnail_key,
+        }
+    }
+}
+
+/// PostgreSQL media persistence. Each method runs on the given [Executor] (`&PgPool`, `&mut Transaction`, …).
+pub struct MediaRepository;
+
+impl MediaRepository {
+    /// Lock the item's row until the end of the current transaction, serializing changes to its
+    /// media list (item updates are not blocked). Returns false if the item does not exist.
+    pub async fn lock_item(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+    ) -> Result<bool, RepositoryError> {
+        let locked: Option<Uuid> = sqlx::query_scalar(
+            "SELECT item_id FROM catalog_items WHERE item_id = $1 FOR NO KEY UPDATE",
+        )
+        .bind(item_id)
+        .fetch_optional(executor)
+        .await?;
+        Ok(locked.is_some())
+    }
+
+    /// Insert a new attachment at the end of the item's media list; returns the assigned position.
+    /// Call it with the item locked ([MediaRepository::lock_item]), so concurrent uploads do not
+    /// take the same position.
+    pub async fn create(
+        executor: impl Executor<'_, Database = Postgres>,
+        record: &MediaRecord,
+    ) -> Result<u32, RepositoryError> {
+        let media = &record.attachment;
+        let position: i32 = sqlx::query_scalar(
+            r#"
+            INSERT INTO catalog_item_media (
+                media_id,
+                item_id,
+                position,
+                content_type,
+                size_bytes,
+                width,
+                height,
+                blob_key,
+                thumbnail_key,
+                created_at
+            )
+            VALUES (
+                $1,
+                $2,
+                (SELECT COALESCE(MAX(position) + 1, 0) FROM catalog_item_media WHERE item_id = $2),
+                $3, $4, $5, $6, $7, $8, $9
+            )
+            RETURNING position
+            "#,
+        )
+        .bind(media.media_id)
+        .bind(media.item_id)
+        .bind(&media.content_type)
+        .bind(media.size_bytes as i64)
+        .bind(media.width as i32)
+        .bind(media.height as i32)
+        .bind(&record.blob_key)
+        .bind(&record.thumbnail_key)
+        .bind(media.created_at.naive_utc())
+        .fetch_one(executor)
+        .await?;
+        Ok(position.try_into().unwrap_or_default())
+    }
+
+    pub async fn get(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+        media_id: Uuid,
+    ) -> Result<Option<MediaRecord>, RepositoryError> {
+        let row = sqlx::query_as::<_, MediaRow>(
+            r#"
+            SELECT
+                media_id,
+                item_id,
+                position,
+                content_type,
+                size_bytes,
+                width,
+                height,
+                blob_key,
+                thumbnail_key,
+                created_at
+            FROM catalog_item_media
+            WHERE item_id = $1 AND media_id = $2
+            "#,
+        )
+        .bind(item_id)
+        .bind(media_id)
+        .fetch_optional(executor)
+        .await?;
+        Ok(row.map(MediaRow::into_record))
+    }
+
+    /// All attachments of an item, in display order.
+    pub async fn list_for_item(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+    ) -> Result<Vec<MediaRecord>, RepositoryError> {
+        let rows = sqlx::query_as::<_, MediaRow>(
+            r#"
+            SELECT
+                media_id,
+                item_id,
+                position,
+                content_type,
+                size_bytes,
+                width,
+                height,
+                blob_key,
+                thumbnail_key,
+                created_at
+            FROM catalog_item_media
+            WHERE item_id = $1
+            ORDER BY position, media_id
+            "#,
+        )
+        .bind(item_id)
+        .fetch_all(executor)
+        .await?;
+        Ok(rows.into_iter().map(MediaRow::into_record).collect())
+    }
+
+    pub async fn set_position(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+        media_id: Uuid,
+        position: u32,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE catalog_item_media
+            SET position = $3
+            WHERE item_id = $1 AND media_id = $2
+            "#,
+        )
+        .bind(item_id)
+        .bind(media_id)
+        .bind(position as i32)
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete an attachment, returning the removed record (so its blobs can be cleaned up).
+    pub async fn delete(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+        media_id: Uuid,
+    ) -> Result<Option<MediaRecord>, RepositoryError> {
+        let row = sqlx::query_as::<_, MediaRow>(
+            r#"
+            DELETE FROM catalog_item_media
+            WHERE item_id = $1 AND media_id = $2
+            RETURNING
+                media_id,
+                item_id,
+                position,
+                content_type,
+                size_bytes,
+                width,
+                height,
+                blob_key,
+                thumbnail_key,
+                created_at
+            "#,
+        )
+        .bind(item_id)
+        .bind(media_id)
+        .fetch_optional(executor)
+        .await?;
+        Ok(row.map(MediaRow::into_record))
+    }
+}
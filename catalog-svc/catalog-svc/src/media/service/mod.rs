@@ -0,0 +1,305 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use chrono::Utc;
+use image::ImageFormat;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::app_config::MediaConfig;
+use crate::blob_store::{BlobStore, BlobStoreError};
+use crate::catalog::persistence::{CatalogItemRepository, RepositoryError};
+use crate::media::api::{MediaAttachment, MediaContent, MediaServiceError};
+use crate::media::persistence::{MediaRecord, MediaRepository};
+
+/// Content type of generated thumbnails.
+const THUMBNAIL_CONTENT_TYPE: &str = "image/png";
+
+impl From<RepositoryError> for MediaServiceError {
+    fn from(err: RepositoryError) -> Self {
+        MediaServiceError::InternalError(Box::new(err))
+    }
+}
+
+impl From<BlobStoreError> for MediaServiceError {
+    fn from(err: BlobStoreError) -> Self {
+        MediaServiceError::InternalError(Box::new(err))
+    }
+}
+
+/// Media attachments of catalog items: metadata in [PgPool] via [MediaRepository], bytes in a [BlobStore].
+#[derive(Clone)]
+pub struct MediaService {
+    pg_pool: PgPool,
+    blob_store: Arc<dyn BlobStore>,
+    max_upload_bytes: usize,
+    thumbnail_size: u32,
+}
+
+impl MediaService {
+    pub fn new(pg_pool: PgPool, blob_store: Arc<dyn BlobStore>, config: &MediaConfig) -> Self {
+        Self {
+            pg_pool,
+            blob_store,
+            max_upload_bytes: config.max_upload_bytes,
+            thumbnail_size: config.thumbnail_size,
+        }
+    }
+
+    pub fn max_upload_bytes(&self) -> usize {
+        self.max_upload_bytes
+    }
+
+    /// Attach an image to an item, appending it to the item's media list.
+    /// The content type is sniffed from the bytes; only JPEG, PNG, GIF and WebP are accepted.
+    /// Returns None if the item does not exist.
+    pub async fn upload(
+        &self,
+        item_id: Uuid,
+        bytes: Bytes,
+    ) -> Result<Option<MediaAttachment>, MediaServiceError> {
+        if bytes.len() > self.max_upload_bytes {
+            return Err(MediaServiceError::PayloadTooLarge {
+                size: bytes.len(),
+                limit: self.max_upload_bytes,
+            });
+        }
+        let (content_type, format) = sniff_image_format(&bytes)?;
+        if CatalogItemRepository::get(&self.pg_pool, item_id)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+
+        let thumbnail_size = self.thumbnail_size;
+        let source = bytes.clone();
+        let processed =
+            tokio::task::spawn_blocking(move || process_image(&source, format, thumbnail_size))
+                .await
+                .map_err(|e| MediaServiceError::InternalError(Box::new(e)))??;
+
+        let media_id = Uuid::new_v4();
+        let blob_key = format!("{}{media_id}/original", item_blob_prefix(item_id));
+        let thumbnail_key = format!("{}{media_id}/thumbnail", item_blob_prefix(item_id));
+        let mut record = MediaRecord {
+            attachment: MediaAttachment {
+                media_id,
+                item_id,
+                position: 0,
+                content_type: content_type.to_string(),
+                size_bytes: bytes.len() as u64,
+                width: processed.width,
+                height: processed.height,
+                created_at: Utc::now(),
+            },
+            blob_key,
+            thumbnail_key,
+        };
+
+        self.blob_store
+            .put(&record.blob_key, content_type, bytes)
+            .await?;
+        self.blob_store
+            .put(
+                &record.thumbnail_key,
+                THUMBNAIL_CONTENT_TYPE,
+                processed.thumbnail,
+            )
+            .await?;
+
+        match self.append(&record).await {
+            Ok(Some(position)) => {
+                record.attachment.position = position;
+                Ok(Some(record.attachment))
+            }
+            // The item was deleted concurrently; don't leave orphaned blobs behind.
+            Ok(None) => {
+                self.delete_blobs(&record).await;
+                Ok(None)
+            }
+            Err(err) => {
+                self.delete_blobs(&record).await;
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Insert `record` at the end of its item's media list. Returns the assigned position, or
+    /// None if the item does not exist.
+    async fn append(&self, record: &MediaRecord) -> Result<Option<u32>, RepositoryError> {
+        let mut tx = self.pg_pool.begin().await?;
+        if !MediaRepository::lock_item(&mut *tx, record.attachment.item_id).await? {
+            return Ok(None);
+        }
+        let position = MediaRepository::create(&mut *tx, record).await?;
+        tx.commit().await?;
+        Ok(Some(position))
+    }
+
+    /// List an item's attachments in display order. Returns None if the item does not exist.
+    pub async fn list(
+        &self,
+        item_id: Uuid,
+    ) -> Result<Option<Vec<MediaAttachment>>, MediaServiceError> {
+        let records = MediaRepository::list_for_item(&self.pg_pool, item_id).await?;
+        if records.is_empty()
+            && CatalogItemRepository::get(&self.pg_pool, item_id)
+                .await?
+                .is_none()
+        {
+            return Ok(None);
+        }
+        Ok(Some(
+            records
+                .into_iter()
+                .map(|record| record.attachment)
+                .collect(),
+        ))
+    }
+
+    /// Original bytes of an attachment, if it exists.
+    pub async fn content(
+        &self,
+        item_id: Uuid,
+        media_id: Uuid,
+    ) -> Result<Option<MediaContent>, MediaServiceError> {
+        let Some(record) = MediaRepository::get(&self.pg_pool, item_id, media_id).await? else {
+            return Ok(None);
+        };
+        let bytes = self.blob_store.get(&record.blob_key).await?;
+        Ok(Some(MediaContent {
+            content_type: record.attachment.content_type,
+            bytes,
+        }))
+    }
+
+    /// Generated thumbnail of an attachment, if it exists.
+    pub async fn thumbnail(
+        &self,
+        item_id: Uuid,
+        media_id: Uuid,
+    ) -> Result<Option<MediaContent>, MediaServiceError> {
+        let Some(record) = MediaRepository::get(&self.pg_pool, item_id, media_id).await? else {
+            return Ok(None);
+        };
+        let bytes = self.blob_store.get(&record.thumbnail_key).await?;
+        Ok(Some(MediaContent {
+            content_type: THUMBNAIL_CONTENT_TYPE.to_string(),
+            bytes,
+        }))
+    }
+
+    /// Set the display order of an item's attachments. `media_ids` must list every attachment of
+    /// the item exactly once. Returns the reordered list, or None if the item does not exist.
+    pub async fn reorder(
+        &self,
+        item_id: Uuid,
+        media_ids: Vec<Uuid>,
+    ) -> Result<Option<Vec<MediaAttachment>>, MediaServiceError> {
+        let mut tx = self.pg_pool.begin().await.map_err(RepositoryError::from)?;
+
+        if !MediaRepository::lock_item(&mut *tx, item_id).await? {
+            return Ok(None);
+        }
+        let existing = MediaRepository::list_for_item(&mut *tx, item_id).await?;
+        let mut existing_ids: Vec<Uuid> = existing.iter().map(|r| r.attachment.media_id).collect();
+        let mut requested_ids = media_ids.clone();
+        existing_ids.sort();
+        requested_ids.sort();
+        if existing_ids != requested_ids {
+            return Err(MediaServiceError::ValidationError(
+                "mediaIds must list every attachment of the item exactly once".into(),
+            ));
+        }
+
+        for (position, media_id) in (0u32..).zip(&media_ids) {
+            MediaRepository::set_position(&mut *tx, item_id, *media_id, position).await?;
+        }
+        let reordered = MediaRepository::list_for_item(&mut *tx, item_id).await?;
+        tx.commit().await.map_err(RepositoryError::from)?;
+
+        Ok(Some(
+            reordered
+                .into_iter()
+                .map(|record| record.attachment)
+                .collect(),
+        ))
+    }
+
+    /// Delete an attachment and its blobs. Returns true if it existed and was removed.
+    pub async fn delete(&self, item_id: Uuid, media_id: Uuid) -> Result<bool, MediaServiceError> {
+        let Some(record) = MediaRepository::delete(&self.pg_pool, item_id, media_id).await? else {
+            return Ok(false);
+        };
+        self.delete_blobs(&record).await;
+        Ok(true)
+    }
+
+    /// Remove all stored blobs of an item. Call after the item is deleted (its media rows are
+    /// removed by the database cascade).
+    pub async fn purge_item(&self, item_id: Uuid) -> Result<(), MediaServiceError> {
+        Ok(self
+            .blob_store
+            .delete_prefix(&item_blob_prefix(item_id))
+            .await?)
+    }
+
+    /// Best-effort blob cleanup: the metadata row is gone, so failures only leak storage.
+    async fn delete_blobs(&self, record: &MediaRecord) {
+        for key in [&record.blob_key, &record.thumbnail_key] {
+            if let Err(err) = self.blob_store.delete(key).await {
+                tracing::warn!("failed to delete media blob {key}: {err}");
+            }
+        }
+    }
+}
+
+fn item_blob_prefix(item_id: Uuid) -> String {
+    format!("items/{item_id}/")
+}
+
+/// Detect the image format from magic bytes (the client-declared content type is not trusted).
+fn sniff_image_format(bytes: &[u8]) -> Result<(&'static str, ImageFormat), MediaServiceError> {
+    let Some(kind) = infer::get(bytes) else {
+        return Err(MediaServiceError::UnsupportedMediaType(
+            "unrecognized content".to_string(),
+        ));
+    };
+    let format = match kind.mime_type() {
+        "image/jpeg" => ImageFormat::Jpeg,
+        "image/png" => ImageFormat::Png,
+        "image/gif" => ImageFormat::Gif,
+        "image/webp" => ImageFormat::WebP,
+        other => return Err(MediaServiceError::UnsupportedMediaType(other.to_string())),
+    };
+    Ok((kind.mime_type(), format))
+}
+
+struct ProcessedImage {
+    width: u32,
+    height: u32,
+    /// PNG-encoded thumbnail fitting in a `thumbnail_size` square, preserving aspect ratio.
+    thumbnail: Bytes,
+}
+
+/// Decode the image (validating it) and render its thumbnail. CPU-bound: run off the async runtime.
+fn process_image(
+    bytes: &[u8],
+    format: ImageFormat,
+    thumbnail_size: u32,
+) -> Result<ProcessedImage, MediaServiceError> {
+    let image = image::load_from_memory_with_format(bytes, format)
+        .map_err(|e| MediaServiceError::ValidationError(Box::new(e)))?;
+    let mut thumbnail = Cursor::new(Vec::new());
+    image
+        .thumbnail(thumbnail_size, thumbnail_size)
+        .write_to(&mut thumbnail, ImageFormat::Png)
+        .map_err(|e| MediaServiceError::InternalError(Box::new(e)))?;
+    Ok(ProcessedImage {
+        width: image.width(),
+        height: image.height(),
+        thumbnail: Bytes::from(thumbnail.into_inner()),
+    })
+}
@@ -0,0 +1,88 @@
+use std::error::Error as StdError;
+
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::catalog::api::CatalogItem;
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Errors that can occur when using [crate::related_items::service::RelatedItemsService].
+#[derive(Error, Debug)]
+pub enum RelatedItemsServiceError {
+    #[error("validation error: {0}")]
+    ValidationError(#[source] BoxError),
+
+    #[error("link already exists")]
+    Conflict,
+
+    #[error("internal error: {0}")]
+    InternalError(#[source] BoxError),
+}
+
+/// Kind of a directed link from one catalog item to another.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, Enum, Display, EnumString,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum LinkKind {
+    /// The related item complements this one (e.g. a case for a phone).
+    Accessory,
+    /// The related item supersedes or substitutes this one.
+    Replacement,
+    /// The related item is part of this bundle.
+    BundleMember,
+    /// The related item is an alternative to this one.
+    Similar,
+}
+
+/// Directed link `item_id -> related_item_id` of a given kind.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemLink {
+    pub item_id: Uuid,
+    pub related_item_id: Uuid,
+    pub kind: LinkKind,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body for linking a catalog item to another one.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateItemLinkBody {
+    pub related_item_id: Uuid,
+    pub kind: LinkKind,
+}
+
+/// Query parameters for listing related items.
+#[derive(Debug, Default, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRelatedItemsRequest {
+    /// Only return links of this kind. Returns all kinds when unset.
+    pub kind: Option<LinkKind>,
+}
+
+/// Query parameters for removing links between two items.
+#[derive(Debug, Default, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteItemLinkRequest {
+    /// Only remove the link of this kind. Removes links of every kind when unset.
+    pub kind: Option<LinkKind>,
+}
+
+/// A related item with the kind of link and when it was created.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedCatalogItem {
+    pub kind: LinkKind,
+    pub linked_at: DateTime<Utc>,
+    pub item: CatalogItem,
+}
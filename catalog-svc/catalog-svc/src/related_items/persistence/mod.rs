@@ -0,0 +1,167 @@
+//! SQL repository for [ItemLink]s between catalog items.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{Executor, FromRow, Postgres};
+use uuid::Uuid;
+
+use crate::catalog::persistence::{CatalogItemRow, RepositoryError};
+use crate::related_items::api::{ItemLink, LinkKind, RelatedCatalogItem};
+
+/// Row type for related-item queries: link columns joined with the target `catalog_items` row.
+#[derive(FromRow)]
+struct RelatedItemRow {
+    kind: String,
+    linked_at: NaiveDateTime,
+    #[sqlx(flatten)]
+    item: CatalogItemRow,
+}
+
+impl RelatedItemRow {
+    fn into_related_item(self) -> Result<RelatedCatalogItem, RepositoryError> {
+        let kind = parse_kind(&self.kind)?;
+        Ok(RelatedCatalogItem {
+            kind,
+            linked_at: DateTime::<Utc>::from_naive_utc_and_offset(self.linked_at, Utc),
+            item: self.item.into_catalog_item()?,
+        })
+    }
+}
+
+/// Row type for `catalog_item_links` queries.
+#[derive(FromRow)]
+struct ItemLinkRow {
+    item_id: Uuid,
+    related_item_id: Uuid,
+    kind: String,
+    created_at: NaiveDateTime,
+}
+
+impl ItemLinkRow {
+    fn into_item_link(self) -> Result<ItemLink, RepositoryError> {
+        Ok(ItemLink {
+            item_id: self.item_id,
+            related_item_id: self.related_item_id,
+            kind: parse_kind(&self.kind)?,
+            created_at: DateTime::<Utc>::from_naive_utc_and_offset(self.created_at, Utc),
+        })
+    }
+}
+
+fn parse_kind(value: &str) -> Result<LinkKind, RepositoryError> {
+    value
+        .parse::<LinkKind>()
+        .map_err(|_| RepositoryError::InvalidLinkKind(value.to_string()))
+}
+
+/// PostgreSQL item link persistence. Each method runs on the given [Executor] (`&PgPool`, `&mut Transaction`, …).
+pub struct ItemLinkRepository;
+
+impl ItemLinkRepository {
+    /// Insert a link. Returns false if an identical link (same items and kind) already exists.
+    pub async fn create(
+        executor: impl Executor<'_, Database = Postgres>,
+        link: &ItemLink,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO catalog_item_links (
+                item_id,
+                related_item_id,
+                kind,
+                created_at
+            )
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(link.item_id)
+        .bind(link.related_item_id)
+        .bind(link.kind.to_string())
+        .bind(link.created_at.naive_utc())
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Items linked from `item_id`, optionally restricted to one kind, oldest link first.
+    pub async fn list_related(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+        kind: Option<LinkKind>,
+    ) -> Result<Vec<RelatedCatalogItem>, RepositoryError> {
+        let rows = sqlx::query_as::<_, RelatedItemRow>(
+            r#"
+            SELECT
+                l.kind,
+                l.created_at AS linked_at,
+                i.item_id,
+                i.name,
+                i.slug,
+                i.description,
+                i.category,
+                i.date,
+                i.brand,
+                i.price,
+                i.created_at,
+                i.modified_at,
+                i.rating_count,
+                i.rating_average,
+                i.rating_histogram,
+                i.stock
+            FROM catalog_item_links l
+            JOIN catalog_items i ON i.item_id = l.related_item_id
+            WHERE l.item_id = $1 AND ($2::VARCHAR IS NULL OR l.kind = $2)
+            ORDER BY l.created_at, l.related_item_id, l.kind
+            "#,
+        )
+        .bind(item_id)
+        .bind(kind.map(|k| k.to_string()))
+        .fetch_all(executor)
+        .await?;
+
+        rows.into_iter()
+            .map(RelatedItemRow::into_related_item)
+            .collect()
+    }
+
+    /// Links from any of `item_ids`, oldest link first.
+    pub async fn list_from(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_ids: &[Uuid],
+    ) -> Result<Vec<ItemLink>, RepositoryError> {
+        let rows = sqlx::query_as::<_, ItemLinkRow>(
+            r#"
+            SELECT item_id, related_item_id, kind, created_at
+            FROM catalog_item_links
+            WHERE item_id = ANY($1)
+            ORDER BY created_at, related_item_id, kind
+            "#,
+        )
+        .bind(item_ids)
+        .fetch_all(executor)
+        .await?;
+
+        rows.into_iter().map(ItemLinkRow::into_item_link).collect()
+    }
+
+    /// Remove links `item_id -> related_item_id` (of one kind, or all kinds). Returns the number removed.
+    pub async fn delete(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+        related_item_id: Uuid,
+        kind: Option<LinkKind>,
+    ) -> Result<u64, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM catalog_item_links
+            WHERE item_id = $1 AND related_item_id = $2 AND ($3::VARCHAR IS NULL OR kind = $3)
+            "#,
+        )
+        .bind(item_id)
+        .bind(related_item_id)
+        .bind(kind.map(|k| k.to_string()))
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::catalog::persistence::{CatalogItemRepository, RepositoryError};
+use crate::related_items::api::{
+    CreateItemLinkBody, ItemLink, LinkKind, RelatedCatalogItem, RelatedItemsServiceError,
+};
+use crate::related_items::persistence::ItemLinkRepository;
+
+impl From<RepositoryError> for RelatedItemsServiceError {
+    fn from(err: RepositoryError) -> Self {
+        RelatedItemsServiceError::InternalError(Box::new(err))
+    }
+}
+
+/// Foreign key of `catalog_item_links.item_id`; other violations concern `related_item_id`.
+const ITEM_ID_FOREIGN_KEY: &str = "catalog_item_links_item_id_fkey";
+
+fn related_item_not_found(related_item_id: Uuid) -> RelatedItemsServiceError {
+    RelatedItemsServiceError::ValidationError(
+        format!("related item {related_item_id} does not exist").into(),
+    )
+}
+
+/// Related items / cross-sell links between catalog items, using [ItemLinkRepository] against [PgPool].
+#[derive(Clone)]
+pub struct RelatedItemsService {
+    pg_pool: PgPool,
+}
+
+impl RelatedItemsService {
+    pub fn new(pg_pool: PgPool) -> Self {
+        Self { pg_pool }
+    }
+
+    /// Link `item_id` to another item. Returns None if `item_id` does not exist.
+    /// Self-links and links to unknown items are validation errors; duplicates are conflicts.
+    pub async fn link(
+        &self,
+        item_id: Uuid,
+        body: CreateItemLinkBody,
+    ) -> Result<Option<ItemLink>, RelatedItemsServiceError> {
+        if body.related_item_id == item_id {
+            return Err(RelatedItemsServiceError::ValidationError(
+                "an item cannot be linked to itself".into(),
+            ));
+        }
+        if CatalogItemRepository::get(&self.pg_pool, item_id)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+        if CatalogItemRepository::get(&self.pg_pool, body.related_item_id)
+            .await?
+            .is_none()
+        {
+            return Err(related_item_not_found(body.related_item_id));
+        }
+
+        let link = ItemLink {
+            item_id,
+            related_item_id: body.related_item_id,
+            kind: body.kind,
+            created_at: Utc::now(),
+        };
+        match ItemLinkRepository::create(&self.pg_pool, &link).await {
+            Ok(true) => Ok(Some(link)),
+            Ok(false) => Err(RelatedItemsServiceError::Conflict),
+            // Either item was deleted since the checks above
+            Err(RepositoryError::Db(sqlx::Error::Database(err)))
+                if err.is_foreign_key_violation() =>
+            {
+                if err.constraint() == Some(ITEM_ID_FOREIGN_KEY) {
+                    Ok(None)
+                } else {
+                    Err(related_item_not_found(body.related_item_id))
+                }
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Items linked from `item_id`, optionally of one kind. Returns None if `item_id` does not exist.
+    pub async fn related(
+        &self,
+        item_id: Uuid,
+        kind: Option<LinkKind>,
+    ) -> Result<Option<Vec<RelatedCatalogItem>>, RelatedItemsServiceError> {
+        let related = ItemLinkRepository::list_related(&self.pg_pool, item_id, kind).await?;
+        if related.is_empty()
+            && CatalogItemRepository::get(&self.pg_pool, item_id)
+                .await?
+                .is_none()
+        {
+            return Ok(None);
+        }
+        Ok(Some(related))
+    }
+
+    /// Links from each of `item_ids` (oldest first), loaded in one query; items without links
+    /// are missing from the map.
+    pub async fn links_from(
+        &self,
+        item_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<ItemLink>>, RelatedItemsServiceError> {
+        let mut links: HashMap<Uuid, Vec<ItemLink>> = HashMap::new();
+        for link in ItemLinkRepository::list_from(&self.pg_pool, item_ids).await? {
+            links.entry(link.item_id).or_default().push(link);
+        }
+        Ok(links)
+    }
+
+    /// Remove links `item_id -> related_item_id` (of one kind, or all kinds).
+    /// Returns true if any link was removed.
+    pub async fn unlink(
+        &self,
+        item_id: Uuid,
+        related_item_id: Uuid,
+        kind: Option<LinkKind>,
+    ) -> Result<bool, RelatedItemsServiceError> {
+        let removed =
+            ItemLinkRepository::delete(&self.pg_pool, item_id, related_item_id, kind).await?;
+        Ok(removed > 0)
+    }
+}
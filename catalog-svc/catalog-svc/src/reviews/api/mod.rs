@@ -0,0 +1,117 @@
+use std::error::Error as StdError;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::common::pagination::{PaginatedSearchResponse, Pagination};
+use crate::common::validation::ValidationErrors;
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Errors that can occur when using [crate::reviews::service::ReviewsService].
+#[derive(Error, Debug)]
+pub enum ReviewsServiceError {
+    #[error("validation error: {0}")]
+    ValidationError(#[source] ValidationErrors),
+
+    #[error("internal error: {0}")]
+    InternalError(#[source] BoxError),
+}
+
+/// Moderation state of a review. Only approved reviews are public and count towards ratings.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    Display,
+    EnumString,
+)]
+#[serde(rename_all = "PascalCase")]
+#[strum(serialize_all = "PascalCase")]
+pub enum ModerationState {
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// Customer review of a catalog item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Review {
+    pub review_id: Uuid,
+    pub item_id: Uuid,
+    /// Star rating, 1 to 5.
+    pub rating: u8,
+    pub title: String,
+    pub body: String,
+    pub author: String,
+    pub moderation_state: ModerationState,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+}
+
+/// Body for submitting a review (starts in [ModerationState::Pending]).
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReviewBody {
+    /// Star rating, 1 to 5.
+    pub rating: u8,
+    pub title: String,
+    pub body: String,
+    pub author: String,
+}
+
+/// Body for moving a review to another moderation state.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ModerateReviewBody {
+    pub moderation_state: ModerationState,
+}
+
+/// Query parameters for the list reviews endpoint.
+#[derive(Debug, Default, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(rename_all = "camelCase")]
+pub struct ListReviewsRequest {
+    /// Only return reviews in this state. Defaults to `Approved`.
+    #[schema(nullable = false)]
+    #[param(nullable = false)]
+    pub moderation_state: Option<ModerationState>,
+    /// Maximum number of reviews to return (page size). Defaults to 100; clamped server-side.
+    pub limit: Option<u32>,
+    /// Zero-based offset into the result set. Defaults to 0.
+    pub offset: Option<u32>,
+}
+
+/// Response for the list reviews endpoint (newest first).
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListReviewsResponse {
+    pub reviews: Vec<Review>,
+    pub has_more: bool,
+    pub pagination: Pagination,
+}
+
+impl ListReviewsResponse {
+    pub(crate) fn from_paginated(
+        page: PaginatedSearchResponse<Review>,
+        pagination: Pagination,
+    ) -> Self {
+        Self {
+            reviews: page.items,
+            has_more: page.has_more,
+            pagination,
+        }
+    }
+}
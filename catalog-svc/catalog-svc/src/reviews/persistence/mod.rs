@@ -0,0 +1,250 @@
+//! SQL repository for [Review]s and the rating aggregate they maintain on `catalog_items`.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{Executor, FromRow, Postgres};
+use uuid::Uuid;
+
+use crate::catalog::persistence::RepositoryError;
+use crate::common::pagination::{PaginatedSearchResponse, Pagination};
+use crate::reviews::api::{ModerationState, Review};
+
+/// Row type for mapping SELECT results from `catalog_item_reviews` into [Review].
+#[derive(FromRow)]
+struct ReviewRow {
+    review_id: Uuid,
+    item_id: Uuid,
+    rating: i16,
+    title: String,
+    body: String,
+    author: String,
+    moderation_state: String,
+    created_at: NaiveDateTime,
+    modified_at: NaiveDateTime,
+}
+
+impl ReviewRow {
+    fn into_review(self) -> Result<Review, RepositoryError> {
+        let moderation_state = self
+            .moderation_state
+            .parse::<ModerationState>()
+            .map_err(|_| RepositoryError::InvalidModerationState(self.moderation_state.clone()))?;
+        Ok(Review {
+            review_id: self.review_id,
+            item_id: self.item_id,
+            rating: self.rating.try_into().unwrap_or_default(),
+            title: self.title,
+            body: self.body,
+            author: self.author,
+            moderation_state,
+            created_at: DateTime::<Utc>::from_naive_utc_and_offset(self.created_at, Utc),
+            modified_at: DateTime::<Utc>::from_naive_utc_and_offset(self.modified_at, Utc),
+        })
+    }
+}
+
+/// PostgreSQL review persistence. Each method runs on the given [Executor] (`&PgPool`, `&mut Transaction`, …).
+pub struct ReviewRepository;
+
+impl ReviewRepository {
+    /// Lock the item's row for the rest of the transaction, serializing rating aggregate updates.
+    /// Returns false if the item does not exist.
+    pub async fn lock_item(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+    ) -> Result<bool, RepositoryError> {
+        let row = sqlx::query("SELECT 1 FROM catalog_items WHERE item_id = $1 FOR UPDATE")
+            .bind(item_id)
+            .fetch_optional(executor)
+            .await?;
+        Ok(row.is_some())
+    }
+
+    pub async fn create(
+        executor: impl Executor<'_, Database = Postgres>,
+        review: &Review,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO catalog_item_reviews (
+                review_id,
+                item_id,
+                rating,
+                title,
+                body,
+                author,
+                moderation_state,
+                created_at,
+                modified_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(review.review_id)
+        .bind(review.item_id)
+        .bind(i16::from(review.rating))
+        .bind(&review.title)
+        .bind(&review.body)
+        .bind(&review.author)
+        .bind(review.moderation_state.to_string())
+        .bind(review.created_at.naive_utc())
+        .bind(review.modified_at.naive_utc())
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+        review_id: Uuid,
+    ) -> Result<Option<Review>, RepositoryError> {
+        let row = sqlx::query_as::<_, ReviewRow>(
+            r#"
+            SELECT
+                review_id,
+                item_id,
+                rating,
+                title,
+                body,
+                author,
+                moderation_state,
+                created_at,
+                modified_at
+            FROM catalog_item_reviews
+            WHERE item_id = $1 AND review_id = $2
+            "#,
+        )
+        .bind(item_id)
+        .bind(review_id)
+        .fetch_optional(executor)
+        .await?;
+
+        row.map(ReviewRow::into_review).transpose()
+    }
+
+    /// Reviews of an item in the given state, newest first.
+    pub async fn search(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+        moderation_state: ModerationState,
+        page: Pagination,
+    ) -> Result<PaginatedSearchResponse<Review>, RepositoryError> {
+        let limit = page.limit;
+        let offset = page.offset;
+
+        let rows = sqlx::query_as::<_, ReviewRow>(
+            r#"
+            SELECT
+                review_id,
+                item_id,
+                rating,
+                title,
+                body,
+                author,
+                moderation_state,
+                created_at,
+                modified_at
+            FROM catalog_item_reviews
+            WHERE item_id = $1 AND moderation_state = $2
+            ORDER BY created_at DESC, review_id
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(item_id)
+        .bind(moderation_state.to_string())
+        .bind((limit + 1) as i64)
+        .bind(offset as i64)
+        .fetch_all(executor)
+        .await?;
+
+        let has_more = rows.len() as u32 > limit;
+        let take = if has_more { limit as usize } else { rows.len() };
+        let reviews: Result<Vec<_>, _> = rows
+            .into_iter()
+            .take(take)
+            .map(ReviewRow::into_review)
+            .collect();
+
+        Ok(PaginatedSearchResponse::new(reviews?, has_more))
+    }
+
+    pub async fn set_moderation_state(
+        executor: impl Executor<'_, Database = Postgres>,
+        review: &Review,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE catalog_item_reviews
+            SET
+                moderation_state = $3,
+                modified_at = $4
+            WHERE item_id = $1 AND review_id = $2
+            "#,
+        )
+        .bind(review.item_id)
+        .bind(review.review_id)
+        .bind(review.moderation_state.to_string())
+        .bind(review.modified_at.naive_utc())
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+        review_id: Uuid,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM catalog_item_reviews
+            WHERE item_id = $1 AND review_id = $2
+            "#,
+        )
+        .bind(item_id)
+        .bind(review_id)
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Recompute the item's rating aggregate (count, average, histogram) from its approved reviews,
+    /// and set its `modified_at` so incremental sync returns the new rating.
+    pub async fn refresh_item_rating(
+        executor: impl Executor<'_, Database = Postgres>,
+        item_id: Uuid,
+        modified_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            UPDATE catalog_items
+            SET
+                rating_count = agg.rating_count,
+                rating_average = agg.rating_average,
+                rating_histogram = agg.rating_histogram,
+                modified_at = $3
+            FROM (
+                SELECT
+                    COUNT(*)::INTEGER AS rating_count,
+                    ROUND(AVG(rating), 2) AS rating_average,
+                    ARRAY[
+                        COUNT(*) FILTER (WHERE rating = 1),
+                        COUNT(*) FILTER (WHERE rating = 2),
+                        COUNT(*) FILTER (WHERE rating = 3),
+                        COUNT(*) FILTER (WHERE rating = 4),
+                        COUNT(*) FILTER (WHERE rating = 5)
+                    ]::INTEGER[] AS rating_histogram
+                FROM catalog_item_reviews
+                WHERE item_id = $1 AND moderation_state = $2
+            ) AS agg
+            WHERE item_id = $1
+            "#,
+        )
+        .bind(item_id)
+        .bind(ModerationState::Approved.to_string())
+        .bind(modified_at.naive_utc())
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::app_metrics;
+use crate::catalog::api::ItemChangeType;
+use crate::catalog::cache::ItemCache;
+use crate::catalog::persistence::RepositoryError;
+use crate::catalog::persistence::changes::ItemChangeRepository;
+use crate::common::pagination::Pagination;
+use crate::common::validation::ValidationErrors;
+use crate::reviews::api::{
+    CreateReviewBody, ListReviewsRequest, ListReviewsResponse, ModerationState, Review,
+    ReviewsServiceError,
+};
+use crate::reviews::persistence::ReviewRepository;
+
+impl From<RepositoryError> for ReviewsServiceError {
+    fn from(err: RepositoryError) -> Self {
+        ReviewsServiceError::InternalError(Box::new(err))
+    }
+}
+
+impl From<sqlx::Error> for ReviewsServiceError {
+    fn from(err: sqlx::Error) -> Self {
+        app_metrics::record_db_error(&err);
+        ReviewsServiceError::InternalError(Box::new(err))
+    }
+}
+
+/// Customer reviews of catalog items, using [ReviewRepository] against [PgPool].
+///
+/// Every write runs in a transaction that locks the item row and recomputes the item's
+/// rating aggregate, so `CatalogItem::rating` always reflects the approved reviews (the item is
+/// then invalidated in the [ItemCache] and logged as updated in the item change log).
+#[derive(Clone)]
+pub struct ReviewsService {
+    pg_pool: PgPool,
+    item_cache: ItemCache,
+}
+
+impl ReviewsService {
+    pub fn new(pg_pool: PgPool, item_cache: ItemCache) -> Self {
+        Self {
+            pg_pool,
+            item_cache,
+        }
+    }
+
+    /// Submit a review for moderation. Returns None if the item does not exist.
+    pub async fn create(
+        &self,
+        item_id: Uuid,
+        body: CreateReviewBody,
+    ) -> Result<Option<Review>, ReviewsServiceError> {
+        let mut errors = ValidationErrors::new();
+        if !(1..=5).contains(&body.rating) {
+            errors.add("rating", "must be between 1 and 5");
+        }
+        for (field, value) in [("title", &body.title), ("author", &body.author)] {
+            if value.trim().is_empty() {
+                errors.add(field, "must not be empty");
+            } else if value.chars().count() > 255 {
+                errors.add(field, "must be at most 255 characters");
+            }
+        }
+        errors
+            .into_result(())
+            .map_err(ReviewsServiceError::ValidationError)?;
+
+        let now = Utc::now();
+        let review = Review {
+            review_id: Uuid::new_v4(),
+            item_id,
+            rating: body.rating,
+            title: body.title,
+            body: body.body,
+            author: body.author,
+            moderation_state: ModerationState::Pending,
+            created_at: now,
+            modified_at: now,
+        };
+
+        let mut tx = self.pg_pool.begin().await?;
+        if !ReviewRepository::lock_item(&mut *tx, item_id).await? {
+            return Ok(None);
+        }
+        ReviewRepository::create(&mut *tx, &review).await?;
+        tx.commit().await?;
+        Ok(Some(review))
+    }
+
+    /// Get a review of an item, in any moderation state.
+    pub async fn get(
+        &self,
+        item_id: Uuid,
+        review_id: Uuid,
+    ) -> Result<Option<Review>, ReviewsServiceError> {
+        Ok(ReviewRepository::get(&self.pg_pool, item_id, review_id).await?)
+    }
+
+    /// List reviews of an item in one moderation state (approved by default), newest first.
+    pub async fn list(
+        &self,
+        item_id: Uuid,
+        req: ListReviewsRequest,
+    ) -> Result<ListReviewsResponse, ReviewsServiceError> {
+        let limit = req.limit.unwrap_or(100).clamp(1, 100);
+        let offset = req.offset.unwrap_or(0);
+        let moderation_state = req.moderation_state.unwrap_or(ModerationState::Approved);
+
+        let search = ReviewRepository::search(
+            &self.pg_pool,
+            item_id,
+            moderation_state,
+            Pagination { limit, offset },
+        )
+        .await?;
+        Ok(ListReviewsResponse::from_paginated(
+            search,
+            Pagination { limit, offset },
+        ))
+    }
+
+    /// Move a review to another moderation state and refresh the item's rating (which counts as a
+    /// modification of the item).
+    /// Returns the updated review or None if not found.
+    pub async fn moderate(
+        &self,
+        item_id: Uuid,
+        review_id: Uuid,
+        moderation_state: ModerationState,
+    ) -> Result<Option<Review>, ReviewsServiceError> {
+        let mut tx = self.pg_pool.begin().await?;
+        if !ReviewRepository::lock_item(&mut *tx, item_id).await? {
+            return Ok(None);
+        }
+        let Some(mut review) = ReviewRepository::get(&mut *tx, item_id, review_id).await? else {
+            return Ok(None);
+        };
+        review.moderation_state = moderation_state;
+        review.modified_at = Utc::now();
+        ReviewRepository::set_moderation_state(&mut *tx, &review).await?;
+        ReviewRepository::refresh_item_rating(&mut *tx, item_id, review.modified_at).await?;
+        ItemChangeRepository::append(
+            &mut *tx,
+            item_id,
+            ItemChangeType::Updated,
+            review.modified_at,
+        )
+        .await?;
+        self.item_cache
+            .publish_invalidation(&mut *tx, Some(item_id))
+            .await?;
+        tx.commit().await?;
+        self.item_cache.invalidate(item_id);
+        Ok(Some(review))
+    }
+
+    /// Delete a review and refresh the item's rating (which counts as a modification of the item).
+    /// Returns true if it existed.
+    pub async fn delete(
+        &self,
+        item_id: Uuid,
+        review_id: Uuid,
+    ) -> Result<bool, ReviewsServiceError> {
+        let mut tx = self.pg_pool.begin().await?;
+        if !ReviewRepository::lock_item(&mut *tx, item_id).await? {
+            return Ok(false);
+        }
+        let deleted = ReviewRepository::delete(&mut *tx, item_id, review_id).await?;
+        if deleted {
+            let now = Utc::now();
+            ReviewRepository::refresh_item_rating(&mut *tx, item_id, now).await?;
+            ItemChangeRepository::append(&mut *tx, item_id, ItemChangeType::Updated, now).await?;
+            self.item_cache
+                .publish_invalidation(&mut *tx, Some(item_id))
+                .await?;
+        }
+        tx.commit().await?;
+        if deleted {
+            self.item_cache.invalidate(item_id);
+        }
+        Ok(deleted)
+    }
+}
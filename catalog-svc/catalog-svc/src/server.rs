@@ -1,33 +1,135 @@
 //! Server lifecycle: bind and run the catalog API.
 
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
 
+use futures::stream::{FuturesUnordered, StreamExt};
+use rust_demo_commons::util::metrics::Metrics;
+use sqlx::migrate::Migrator;
 use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
 
+use crate::api_keys::service::ApiKeysService;
 use crate::app_config::{AppConfig, create_pg_pool};
+use crate::app_metrics;
+use crate::auth::Authenticator;
+use crate::blob_store::create_blob_store;
+use crate::catalog::cache::ItemCache;
+use crate::catalog::changes::ItemChangeFeed;
 use crate::catalog::service::CatalogService;
+use crate::catalog::sync::ItemSync;
+use crate::events::relay::OutboxRelay;
+use crate::events::sink::KafkaEventSink;
+use crate::graphql;
+use crate::grpc_server;
 use crate::http_server;
 use crate::http_server::CatalogApp;
+use crate::media::service::MediaService;
+use crate::related_items::service::RelatedItemsService;
+use crate::reviews::service::ReviewsService;
+use crate::supplier_feed::consumer::SupplierFeedConsumer;
+use crate::webhooks::delivery::WebhookDispatcher;
+use crate::webhooks::service::WebhooksService;
 
-pub async fn build_app(app_config: &AppConfig) -> CatalogApp {
+/// Database migrations embedded in this build.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Tasks spawned by [build_app] (outbox relay, supplier feed consumer, change feed, webhook
+/// dispatcher, cache listener). They stop once `server_shutdown` is cancelled; await them before
+/// exiting so they finish their last unit of work (e.g. the consumer's final offset commit).
+pub type BackgroundTasks = Vec<tokio::task::JoinHandle<()>>;
+
+pub async fn build_app(app_config: &AppConfig) -> (CatalogApp, BackgroundTasks) {
     let pg_pool = create_pg_pool(&app_config.postgres)
         .await
         .expect("failed to create PostgreSQL pool");
     tracing::info!("PostgreSQL pool initialized");
 
-    sqlx::migrate!("./migrations")
+    let metrics = Metrics::install();
+    app_metrics::describe();
+
+    MIGRATOR
         .run(&pg_pool)
         .await
         .expect("failed to run database migrations");
     tracing::info!("Database migrations applied");
+    if let Some(version) = MIGRATOR.iter().map(|migration| migration.version).max() {
+        app_metrics::record_migration_version(version);
+    }
 
-    let catalog = CatalogService::new(pg_pool.clone());
-    let shutdown = tokio_util::sync::CancellationToken::new();
-    CatalogApp {
+    let auth = Authenticator::from_config(&app_config.auth)
+        .await
+        .expect("failed to set up authentication");
+
+    let api_keys = ApiKeysService::new(pg_pool.clone());
+    let shutdown = CancellationToken::new();
+    let item_cache = ItemCache::from_config(&app_config.item_cache);
+    let mut background = BackgroundTasks::new();
+    background.extend(item_cache.spawn_listener(&pg_pool, shutdown.clone()));
+    if let Some(brokers) = &app_config.events.brokers {
+        let sink = KafkaEventSink::new(brokers, app_config.events.topic.clone())
+            .expect("failed to create Kafka producer");
+        background.push(
+            OutboxRelay::new(pg_pool.clone(), Arc::new(sink), &app_config.events)
+                .spawn(shutdown.clone()),
+        );
+    }
+    let blob_store = create_blob_store(&app_config.media.blob_store);
+    let media = MediaService::new(pg_pool.clone(), blob_store, &app_config.media);
+    // Without a relay nothing would publish or purge the outbox
+    let catalog = CatalogService::new(
+        pg_pool.clone(),
+        item_cache.clone(),
+        app_config.events.tenant.clone(),
+    )
+    .with_outbox(app_config.events.brokers.is_some())
+    .with_media(media.clone());
+    if let Some(brokers) = &app_config.supplier_feed.brokers {
+        background.push(
+            SupplierFeedConsumer::new(brokers, &app_config.supplier_feed, catalog.clone())
+                .expect("failed to create supplier feed consumer")
+                .spawn(shutdown.clone()),
+        );
+    }
+    let item_changes = ItemChangeFeed::new(pg_pool.clone(), &app_config.item_changes);
+    background.extend(item_changes.spawn(shutdown.clone()));
+    let item_sync = ItemSync::new(
+        pg_pool.clone(),
+        &app_config.item_sync,
+        &app_config.item_changes,
+    );
+    let related_items = RelatedItemsService::new(pg_pool.clone());
+    let graphql = graphql::schema(
+        &app_config.graphql,
+        Arc::new(catalog.clone()),
+        related_items.clone(),
+    );
+    let reviews = ReviewsService::new(pg_pool.clone(), item_cache);
+    let webhooks = WebhooksService::new(pg_pool.clone());
+    background.push(
+        WebhookDispatcher::new(pg_pool.clone(), &app_config.webhooks)
+            .expect("failed to create webhook HTTP client")
+            .spawn(shutdown.clone()),
+    );
+    let app = CatalogApp {
         catalog,
+        graphql,
+        item_changes,
+        item_sync,
+        media,
+        related_items,
+        reviews,
+        webhooks,
         pg_pool,
+        auth,
+        api_keys,
+        metrics,
+        rate_limit: app_config.rate_limit.limit(),
+        ip_rate_limit: app_config.rate_limit.ip_limit(),
         server_shutdown: shutdown.clone(),
-    }
+    };
+    (app, background)
 }
 
 /// Start the catalog service and run the API server on a spawned task.
@@ -48,11 +150,81 @@ pub async fn start_service_and_serve(
     tracing::info!("Catalog API listening on {}", addr);
 
     let shutdown_token = app_state.server_shutdown.clone();
+    let shutdown_delay = Duration::from_secs(server_settings.shutdown_delay_secs);
     let join_handle = tokio::spawn(async move {
-        let shutdown_token = shutdown_token.cancelled_owned();
-        axum::serve(listener, app)
-            .with_graceful_shutdown(shutdown_token)
-            .await
+        // Keep accepting requests for a while after cancellation: `/health/ready` reports
+        // not-ready meanwhile, so load balancers stop routing here before connections close
+        let shutdown_token = async move {
+            shutdown_token.cancelled().await;
+            if !shutdown_delay.is_zero() {
+                tracing::info!(
+                    "Not ready; closing the listener in {}s",
+                    shutdown_delay.as_secs()
+                );
+                tokio::time::sleep(shutdown_delay).await;
+            }
+        };
+        // Connect info gives the rate limiter the client IP
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(shutdown_token)
+        .await
     });
     Ok((app_state, join_handle, addr))
 }
+
+/// Start the gRPC API (see [grpc_server]) on `grpc.port` of `server.host` on a spawned task,
+/// sharing `app_state` and its shutdown token with the REST API. A failing gRPC server cancels
+/// the token, so the whole service shuts down.
+pub async fn start_grpc_and_serve(
+    app_state: &CatalogApp,
+    app_config: &AppConfig,
+) -> std::io::Result<(tokio::task::JoinHandle<std::io::Result<()>>, SocketAddr)> {
+    let listener =
+        TcpListener::bind((app_config.server.host.as_str(), app_config.grpc.port)).await?;
+    let addr = listener.local_addr()?;
+    tracing::info!("Catalog gRPC API listening on {}", addr);
+
+    let state = app_state.clone();
+    let shutdown_delay = Duration::from_secs(app_config.server.shutdown_delay_secs);
+    let join_handle = tokio::spawn(async move {
+        let shutdown = state.server_shutdown.clone();
+        let result = grpc_server::serve(state, listener, shutdown_delay).await;
+        if let Err(err) = &result {
+            tracing::error!("gRPC server failed: {err}");
+            shutdown.cancel();
+        }
+        result.map_err(std::io::Error::other)
+    });
+    Ok((join_handle, addr))
+}
+
+/// A task awaiting all server tasks: the first error, if any, once all have stopped. The first
+/// server to fail cancels `shutdown`, so the others stop too.
+pub fn join_servers(
+    handles: Vec<tokio::task::JoinHandle<std::io::Result<()>>>,
+    shutdown: CancellationToken,
+) -> tokio::task::JoinHandle<std::io::Result<()>> {
+    tokio::spawn(async move {
+        let mut servers: FuturesUnordered<_> = handles.into_iter().collect();
+        let mut first_error = None;
+        while let Some(result) = servers.next().await {
+            let result = match result {
+                Ok(result) => result,
+                Err(err) if err.is_panic() => {
+                    shutdown.cancel();
+                    std::panic::resume_unwind(err.into_panic())
+                }
+                // Cancelled (aborted or its runtime shut down)
+                Err(err) => Err(std::io::Error::other(err)),
+            };
+            if let Err(err) = result {
+                shutdown.cancel();
+                first_error.get_or_insert(err);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    })
+}
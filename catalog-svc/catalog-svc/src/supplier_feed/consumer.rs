@@ -0,0 +1,279 @@
+//! Kafka consumer of the supplier feed.
+//!
+//! Each message is applied in its own database transaction, which also records its id in the
+//! inbox; its offset is committed only after that transaction succeeded, so a crash replays it
+//! and the inbox skips it. Messages that can never be applied go to the dead-letter topic;
+//! transient failures are retried with backoff, holding back the partition meanwhile.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::Utc;
+use rdkafka::consumer::{
+    BaseConsumer, CommitMode, Consumer, ConsumerContext, Rebalance, StreamConsumer,
+};
+use rdkafka::error::{KafkaError, KafkaResult, RDKafkaErrorCode};
+use rdkafka::message::{BorrowedMessage, Header, Headers, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::{ClientConfig, ClientContext, Message, TopicPartitionList};
+use rust_demo_commons::util::tracing::extract_message_trace_context;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::app_config::SupplierFeedConfig;
+use crate::app_metrics;
+use crate::catalog::service::CatalogService;
+use crate::events::inbox::InboxRepository;
+use crate::supplier_feed::{FeedError, Processed, SOURCE, apply_message};
+
+/// Wait before the first retry of a transient failure.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+/// Longest wait between retries.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// How often processed message ids older than the retention are forgotten.
+const PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Dead-letter header with the reason the message was rejected.
+pub const DLT_REASON_HEADER: &str = "dlt.reason";
+/// Dead-letter headers locating the original message.
+pub const DLT_TOPIC_HEADER: &str = "dlt.original_topic";
+pub const DLT_PARTITION_HEADER: &str = "dlt.original_partition";
+pub const DLT_OFFSET_HEADER: &str = "dlt.original_offset";
+
+/// Logs rebalances and failed offset commits.
+struct FeedContext;
+
+impl ClientContext for FeedContext {}
+
+impl ConsumerContext for FeedContext {
+    fn pre_rebalance(&self, _: &BaseConsumer<Self>, rebalance: &Rebalance) {
+        tracing::info!("Supplier feed pre rebalance {:?}", rebalance);
+    }
+
+    fn post_rebalance(&self, _: &BaseConsumer<Self>, rebalance: &Rebalance) {
+        tracing::info!("Supplier feed post rebalance {:?}", rebalance);
+    }
+
+    fn commit_callback(&self, result: KafkaResult<()>, _offsets: &TopicPartitionList) {
+        if let Err(err) = result {
+            tracing::warn!("Supplier feed offset commit failed: {err}");
+        }
+    }
+}
+
+type FeedStreamConsumer = StreamConsumer<FeedContext>;
+
+/// Consumes the supplier feed topic and applies each message through [CatalogService].
+pub struct SupplierFeedConsumer {
+    consumer: FeedStreamConsumer,
+    producer: FutureProducer,
+    catalog: CatalogService,
+    dead_letter_topic: String,
+    dedup_retention: Duration,
+}
+
+impl SupplierFeedConsumer {
+    /// Subscribe to `cfg.topic` with consumer group `cfg.group_id` on `brokers`.
+    pub fn new(
+        brokers: &str,
+        cfg: &SupplierFeedConfig,
+        catalog: CatalogService,
+    ) -> KafkaResult<Self> {
+        let consumer: FeedStreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("group.id", &cfg.group_id)
+            .set("auto.offset.reset", "earliest")
+            .set("session.timeout.ms", "6000")
+            // Offsets are stored and committed by hand once a message is handled
+            .set("enable.auto.commit", "false")
+            .set("enable.auto.offset.store", "false")
+            .create_with_context(FeedContext)?;
+        consumer.subscribe(&[&cfg.topic])?;
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("enable.idempotence", "true")
+            .set("acks", "all")
+            .set("message.timeout.ms", "30000")
+            .create()?;
+        Ok(Self {
+            consumer,
+            producer,
+            catalog,
+            dead_letter_topic: cfg.dead_letter_topic.clone(),
+            dedup_retention: Duration::from_secs(cfg.dedup_retention_secs),
+        })
+    }
+
+    /// Consume until `shutdown` is cancelled, then commit the handled offsets and leave the
+    /// consumer group. A message interrupted by shutdown is left uncommitted and redelivered.
+    pub fn spawn(self, shutdown: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Supplier feed consumer started");
+            self.run(&shutdown).await;
+            // The synchronous commit blocks until the broker answers
+            let consumer = self.consumer;
+            let stopped = tokio::task::spawn_blocking(move || {
+                match consumer.commit_consumer_state(CommitMode::Sync) {
+                    Ok(()) | Err(KafkaError::ConsumerCommit(RDKafkaErrorCode::NoOffset)) => {}
+                    Err(err) => tracing::warn!("Supplier feed final offset commit failed: {err}"),
+                }
+                consumer.unsubscribe();
+            })
+            .await;
+            if let Err(err) = stopped {
+                tracing::error!("Supplier feed final offset commit failed: {err}");
+            }
+            tracing::info!("Supplier feed consumer stopped");
+        })
+    }
+
+    async fn run(&self, shutdown: &CancellationToken) {
+        let mut purge = tokio::time::interval(PURGE_INTERVAL);
+        loop {
+            let received = tokio::select! {
+                _ = shutdown.cancelled() => return,
+                _ = purge.tick() => {
+                    self.purge_inbox().await;
+                    continue;
+                }
+                received = self.consumer.recv() => received,
+            };
+            let message = match received {
+                Ok(message) => message,
+                Err(err) => {
+                    tracing::warn!("Supplier feed receive failed: {err}");
+                    continue;
+                }
+            };
+
+            let mut backoff = INITIAL_BACKOFF;
+            while let Err(err) = self.handle_traced(&message).await {
+                tracing::warn!(
+                    "Supplier feed message {}/{}@{} failed, retrying in {backoff:?}: {err}",
+                    message.topic(),
+                    message.partition(),
+                    message.offset()
+                );
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(backoff) => {}
+                }
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+
+            let committed = self
+                .consumer
+                .store_offset_from_message(&message)
+                .and_then(|()| self.consumer.commit_consumer_state(CommitMode::Async));
+            if let Err(err) = committed {
+                tracing::warn!("Supplier feed offset commit failed: {err}");
+            }
+        }
+    }
+
+    /// [Self::handle] in a span continuing the trace of the producer (its `traceparent` header),
+    /// so the events the message causes carry that trace on.
+    async fn handle_traced(&self, message: &BorrowedMessage<'_>) -> Result<(), FeedError> {
+        let span = tracing::info_span!(
+            "supplier_feed_message",
+            topic = message.topic(),
+            partition = message.partition(),
+            offset = message.offset()
+        );
+        let headers: HashMap<String, String> = message
+            .headers()
+            .into_iter()
+            .flat_map(|headers| headers.iter())
+            .filter_map(|header| {
+                let value = std::str::from_utf8(header.value?).ok()?;
+                Some((header.key.to_string(), value.to_string()))
+            })
+            .collect();
+        // Only fails when the span is disabled, then there is nothing to link
+        let _ = span.set_parent(extract_message_trace_context(&headers));
+        self.handle(message).instrument(span).await
+    }
+
+    /// Apply one message, dead-lettering it if it can never be applied. An error means the
+    /// message was not handled and must be retried.
+    async fn handle(&self, message: &BorrowedMessage<'_>) -> Result<(), FeedError> {
+        let outcome = match apply_message(&self.catalog, message.payload()).await {
+            Ok(Processed::Applied(item)) => {
+                tracing::debug!("Supplier feed updated item {}", item.item_id);
+                "applied"
+            }
+            Ok(Processed::Duplicate) => "duplicate",
+            Err(FeedError::Poison(reason)) => {
+                tracing::warn!(
+                    "Supplier feed message {}/{}@{} dead-lettered: {reason}",
+                    message.topic(),
+                    message.partition(),
+                    message.offset()
+                );
+                self.dead_letter(message, &reason)
+                    .await
+                    .map_err(|err| FeedError::Transient(Box::new(err)))?;
+                "dead_lettered"
+            }
+            Err(err) => return Err(err),
+        };
+        metrics::counter!(app_metrics::SUPPLIER_FEED_MESSAGES_TOTAL, "outcome" => outcome)
+            .increment(1);
+        Ok(())
+    }
+
+    /// Copy `message` to the dead-letter topic with its key, payload and headers, adding the
+    /// rejection reason and the original position.
+    async fn dead_letter(&self, message: &BorrowedMessage<'_>, reason: &str) -> KafkaResult<()> {
+        let partition = message.partition().to_string();
+        let offset = message.offset().to_string();
+        let mut headers = OwnedHeaders::new();
+        if let Some(original) = message.headers() {
+            for header in original.iter() {
+                headers = headers.insert(header);
+            }
+        }
+        let headers = [
+            (DLT_REASON_HEADER, reason),
+            (DLT_TOPIC_HEADER, message.topic()),
+            (DLT_PARTITION_HEADER, partition.as_str()),
+            (DLT_OFFSET_HEADER, offset.as_str()),
+        ]
+        .into_iter()
+        .fold(headers, |headers, (key, value)| {
+            headers.insert(Header {
+                key,
+                value: Some(value),
+            })
+        });
+        let mut record = FutureRecord::<[u8], [u8]>::to(&self.dead_letter_topic).headers(headers);
+        if let Some(key) = message.key() {
+            record = record.key(key);
+        }
+        if let Some(payload) = message.payload() {
+            record = record.payload(payload);
+        }
+        self.producer
+            .send(record, Duration::from_secs(0))
+            .await
+            .map(|_| ())
+            .map_err(|(err, _)| err)
+    }
+
+    /// Forget processed message ids older than the dedup retention.
+    async fn purge_inbox(&self) {
+        let retention =
+            chrono::Duration::from_std(self.dedup_retention).unwrap_or(chrono::Duration::MAX);
+        let cutoff = Utc::now()
+            .naive_utc()
+            .checked_sub_signed(retention)
+            .unwrap_or(chrono::NaiveDateTime::MIN);
+        if let Err(err) =
+            InboxRepository::delete_processed_before(self.catalog.pg_pool(), SOURCE, cutoff).await
+        {
+            tracing::warn!("Purging {SOURCE} inbox failed: {err}");
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Inbound supplier price and stock feed: JSON messages consumed from Kafka by the
+//! [consumer::SupplierFeedConsumer] and applied through [CatalogService].
+//!
+//! Message payload: `{"messageId": "...", "itemId": "...", "price": "12.50", "stock": 7}`, where
+//! `price` and `stock` are optional but not both absent. `messageId` identifies redeliveries.
+
+pub mod consumer;
+
+use std::error::Error as StdError;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::catalog::api::{CatalogItem, CatalogServiceError, SupplyUpdate, SupplyUpdateOutcome};
+use crate::catalog::service::CatalogService;
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Inbox source of feed messages; message ids are unique per source.
+pub const SOURCE: &str = "supplier_feed";
+
+/// Longest accepted `messageId`; matches the `inbox_messages.message_id` column.
+const MAX_MESSAGE_ID_LEN: usize = 255;
+
+/// A supplier's report of an item's price and/or stock.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplierFeedMessage {
+    pub message_id: String,
+    pub item_id: Uuid,
+    #[serde(default)]
+    pub price: Option<Decimal>,
+    #[serde(default)]
+    pub stock: Option<u32>,
+}
+
+/// A message that was handled and whose offset may be committed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Processed {
+    Applied(Box<CatalogItem>),
+    /// Applied before (redelivery); skipped.
+    Duplicate,
+}
+
+/// Errors from [apply_message].
+#[derive(Error, Debug)]
+pub enum FeedError {
+    /// The message can never be applied (malformed, invalid or for an unknown item).
+    #[error("rejected message: {0}")]
+    Poison(String),
+
+    /// Applying may succeed when retried (e.g. the database is unavailable).
+    #[error("failed to apply message: {0}")]
+    Transient(#[source] BoxError),
+}
+
+/// Parse a feed message payload and apply it, at most once per `messageId`.
+pub async fn apply_message(
+    catalog: &CatalogService,
+    payload: Option<&[u8]>,
+) -> Result<Processed, FeedError> {
+    let payload = payload.ok_or_else(|| FeedError::Poison("empty payload".to_string()))?;
+    let message: SupplierFeedMessage = serde_json::from_slice(payload)
+        .map_err(|err| FeedError::Poison(format!("invalid payload: {err}")))?;
+    if message.message_id.is_empty() || message.message_id.chars().count() > MAX_MESSAGE_ID_LEN {
+        return Err(FeedError::Poison(format!(
+            "messageId must have 1 to {MAX_MESSAGE_ID_LEN} characters"
+        )));
+    }
+
+    let update = SupplyUpdate {
+        item_id: message.item_id,
+        price: message.price,
+        stock: message.stock,
+    };
+    match catalog
+        .apply_supply_update(SOURCE, &message.message_id, update)
+        .await
+    {
+        Ok(SupplyUpdateOutcome::Applied(item)) => Ok(Processed::Applied(item)),
+        Ok(SupplyUpdateOutcome::Duplicate) => Ok(Processed::Duplicate),
+        Ok(SupplyUpdateOutcome::NotFound) => Err(FeedError::Poison(format!(
+            "unknown item {}",
+            message.item_id
+        ))),
+        Err(CatalogServiceError::ValidationError(errors)) => {
+            Err(FeedError::Poison(format!("invalid update: {errors}")))
+        }
+        Err(CatalogServiceError::InternalError(err)) => Err(FeedError::Transient(err)),
+    }
+}
@@ -0,0 +1,146 @@
+use std::error::Error as StdError;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::common::pagination::Pagination;
+use crate::common::validation::ValidationErrors;
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Errors that can occur when using [crate::webhooks::service::WebhooksService].
+#[derive(Error, Debug)]
+pub enum WebhooksServiceError {
+    #[error("validation error: {0}")]
+    ValidationError(#[source] ValidationErrors),
+
+    #[error("internal error: {0}")]
+    InternalError(#[source] BoxError),
+}
+
+/// An endpoint receiving catalog events. The signing secret is only returned on creation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookSubscription {
+    pub subscription_id: Uuid,
+    /// `http` or `https` URL the events are POSTed to.
+    pub url: String,
+    /// Event types delivered, e.g. `ItemCreated`.
+    pub event_types: Vec<String>,
+    /// Disabled subscriptions get no new deliveries; pending ones resume when re-enabled.
+    pub enabled: bool,
+    /// Failed attempts since the last successful one.
+    pub consecutive_failures: u32,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+    /// When the subscription was disabled, by an admin or after repeated failures.
+    pub disabled_at: Option<DateTime<Utc>>,
+}
+
+/// Body for creating a webhook subscription.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookSubscriptionBody {
+    /// `http` or `https` URL, at most 2048 characters.
+    pub url: String,
+    /// Event types to deliver; at least one of `ItemCreated`, `ItemUpdated`, `ItemDeleted`,
+    /// `PriceChanged`.
+    pub event_types: Vec<String>,
+    /// Signing secret, 16 to 255 characters; generated when absent.
+    pub secret: Option<String>,
+}
+
+/// Body for updating a webhook subscription.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWebhookSubscriptionBody {
+    /// `http` or `https` URL, at most 2048 characters.
+    pub url: String,
+    /// Event types to deliver; at least one.
+    pub event_types: Vec<String>,
+    /// Enabling a disabled subscription resets its failure count.
+    pub enabled: bool,
+    /// New signing secret, 16 to 255 characters; the current one is kept when absent.
+    pub secret: Option<String>,
+}
+
+/// A new subscription: store `secret` to verify signatures, it cannot be retrieved again.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedWebhookSubscription {
+    pub secret: String,
+    pub subscription: WebhookSubscription,
+}
+
+/// Response for the list webhook subscriptions endpoint (oldest first).
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhookSubscriptionsResponse {
+    pub subscriptions: Vec<WebhookSubscription>,
+}
+
+/// State of a webhook delivery.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, Display, EnumString,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum DeliveryStatus {
+    /// Not delivered yet; attempted at `nextAttemptAt`.
+    Pending,
+    /// The receiver answered with a 2xx status.
+    Succeeded,
+    /// Every attempt failed.
+    Failed,
+}
+
+/// One event sent, or to be sent, to a subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    /// Sent as `X-Webhook-Id`; the same on every attempt.
+    pub delivery_id: Uuid,
+    pub subscription_id: Uuid,
+    /// Id of the event envelope in the request body.
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    /// HTTP status of the last response; absent when none was received.
+    pub last_status_code: Option<u16>,
+    /// Why the last attempt failed.
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// When the delivery succeeded or was given up.
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Query parameters for the delivery log endpoint.
+#[derive(Debug, Default, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhookDeliveriesRequest {
+    /// Only deliveries in this state.
+    #[schema(nullable = false)]
+    #[param(nullable = false)]
+    pub status: Option<DeliveryStatus>,
+    /// Maximum number of deliveries to return (page size). Defaults to 100; clamped server-side.
+    pub limit: Option<u32>,
+    /// Zero-based offset into the result set. Defaults to 0.
+    pub offset: Option<u32>,
+}
+
+/// Response for the delivery log endpoint (newest first).
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhookDeliveriesResponse {
+    pub deliveries: Vec<WebhookDelivery>,
+    pub has_more: bool,
+    pub pagination: Pagination,
+}
@@ -0,0 +1,269 @@
+//! Background task POSTing queued webhook deliveries to their subscriptions.
+//!
+//! Delivery is at least once: a delivery is marked succeeded only after the receiver answered
+//! with a 2xx status, so a crash in between sends it again. Receivers deduplicate by the
+//! `X-Webhook-Id` header.
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::redirect;
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::app_config::WebhooksConfig;
+use crate::app_metrics;
+use crate::catalog::persistence::RepositoryError;
+use crate::webhooks::api::DeliveryStatus;
+use crate::webhooks::persistence::{
+    ClaimedDelivery, DeliveryAttempt, WebhookDeliveryRepository, WebhookSubscriptionRepository,
+};
+use crate::webhooks::signature::{self, EVENT_HEADER, ID_HEADER, SIGNATURE_HEADER};
+
+/// Longest wait between retries after database failures.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// How often completed deliveries older than the retention are deleted.
+const PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+/// Time on top of the request timeout before a claimed delivery may be claimed again, e.g.
+/// after the instance sending it crashed.
+const LEASE_MARGIN: Duration = Duration::from_secs(60);
+/// Longest `last_error` kept.
+const MAX_ERROR_LEN: usize = 1000;
+
+/// What a receiver did with one attempt.
+struct Response {
+    status_code: Option<u16>,
+    /// Why the attempt failed; `None` on success.
+    error: Option<String>,
+}
+
+/// Sends due webhook deliveries, retrying failures with exponential backoff and disabling
+/// subscriptions that keep failing. Any number of instances may run; each claims its own batch.
+pub struct WebhookDispatcher {
+    pg_pool: PgPool,
+    client: reqwest::Client,
+    poll_interval: Duration,
+    batch_size: u32,
+    lease: Duration,
+    max_attempts: u32,
+    retry_backoff: Duration,
+    max_retry_backoff: Duration,
+    disable_after_failures: u32,
+    retention: Duration,
+}
+
+impl WebhookDispatcher {
+    pub fn new(pg_pool: PgPool, cfg: &WebhooksConfig) -> Result<Self, reqwest::Error> {
+        let request_timeout = Duration::from_millis(cfg.request_timeout_ms);
+        // Redirects are not followed: the signature is for the subscribed URL only
+        let client = reqwest::Client::builder()
+            .timeout(request_timeout)
+            .redirect(redirect::Policy::none())
+            .build()?;
+        Ok(Self {
+            pg_pool,
+            client,
+            poll_interval: Duration::from_millis(cfg.poll_interval_ms),
+            batch_size: cfg.batch_size.max(1),
+            lease: request_timeout + LEASE_MARGIN,
+            max_attempts: cfg.max_attempts.max(1),
+            retry_backoff: Duration::from_millis(cfg.retry_backoff_ms),
+            max_retry_backoff: Duration::from_secs(cfg.max_backoff_secs),
+            disable_after_failures: cfg.disable_after_failures.max(1),
+            retention: Duration::from_secs(cfg.retention_secs),
+        })
+    }
+
+    /// Send one batch of due deliveries concurrently and record the results. Returns how many
+    /// were attempted; 0 when none was due.
+    pub async fn dispatch_batch(&self) -> Result<usize, RepositoryError> {
+        let now = Utc::now();
+        let lease_until = later(now, self.lease);
+        let deliveries =
+            WebhookDeliveryRepository::claim_due(&self.pg_pool, now, lease_until, self.batch_size)
+                .await?;
+        let responses = join_all(deliveries.iter().map(|delivery| self.send(delivery))).await;
+        for (delivery, response) in deliveries.iter().zip(responses) {
+            self.record(delivery, response).await?;
+        }
+        Ok(deliveries.len())
+    }
+
+    async fn send(&self, delivery: &ClaimedDelivery) -> Response {
+        let body = match serde_json::to_vec(&delivery.payload.0) {
+            Ok(body) => body,
+            Err(err) => {
+                return Response {
+                    status_code: None,
+                    error: Some(format!("failed to serialize payload: {err}")),
+                };
+            }
+        };
+        let signature = signature::sign(&delivery.secret, Utc::now().timestamp(), &body);
+        let result = self
+            .client
+            .post(&delivery.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(ID_HEADER, delivery.delivery_id.to_string())
+            .header(EVENT_HEADER, &delivery.event_type)
+            .header(SIGNATURE_HEADER, signature)
+            .body(body)
+            .send()
+            .await;
+        match result {
+            Ok(response) if response.status().is_success() => Response {
+                status_code: Some(response.status().as_u16()),
+                error: None,
+            },
+            Ok(response) => Response {
+                status_code: Some(response.status().as_u16()),
+                error: Some(format!("receiver answered {}", response.status())),
+            },
+            Err(err) => Response {
+                status_code: None,
+                error: Some(err.to_string()),
+            },
+        }
+    }
+
+    /// Record the attempt and its effect on the subscription in one transaction.
+    async fn record(
+        &self,
+        delivery: &ClaimedDelivery,
+        response: Response,
+    ) -> Result<(), RepositoryError> {
+        let attempted_at = Utc::now();
+        let attempts = u32::try_from(delivery.attempts).unwrap_or_default() + 1;
+        let mut tx = self.pg_pool.begin().await?;
+        let (status, next_attempt_at, outcome) = match &response.error {
+            None => {
+                WebhookSubscriptionRepository::record_success(&mut *tx, delivery.subscription_id)
+                    .await?;
+                (DeliveryStatus::Succeeded, None, "succeeded")
+            }
+            Some(error) => {
+                tracing::warn!(
+                    "Webhook delivery {} to {} failed (attempt {attempts}): {error}",
+                    delivery.delivery_id,
+                    delivery.url
+                );
+                let disabled = WebhookSubscriptionRepository::record_failure(
+                    &mut *tx,
+                    delivery.subscription_id,
+                    self.disable_after_failures,
+                    attempted_at,
+                )
+                .await?;
+                if disabled {
+                    tracing::warn!(
+                        "Webhook subscription {} disabled after {} failures in a row",
+                        delivery.subscription_id,
+                        self.disable_after_failures
+                    );
+                    metrics::counter!(app_metrics::WEBHOOK_SUBSCRIPTIONS_DISABLED_TOTAL)
+                        .increment(1);
+                }
+                if attempts >= self.max_attempts {
+                    (DeliveryStatus::Failed, None, "failed")
+                } else {
+                    let next = later(attempted_at, self.retry_delay(attempts));
+                    (DeliveryStatus::Pending, Some(next), "retrying")
+                }
+            }
+        };
+        let failed = response.error.is_some();
+        let attempt = DeliveryAttempt {
+            delivery_id: delivery.delivery_id,
+            attempted_at,
+            status,
+            next_attempt_at,
+            status_code: response.status_code,
+            error: response
+                .error
+                .map(|error| error.chars().take(MAX_ERROR_LEN).collect()),
+        };
+        WebhookDeliveryRepository::record_attempt(&mut *tx, &attempt).await?;
+        if failed {
+            // This failure or an earlier one (or an admin) may have disabled the subscription
+            WebhookDeliveryRepository::fail_pending_of_disabled(
+                &mut *tx,
+                delivery.subscription_id,
+                attempted_at,
+            )
+            .await?;
+        }
+        tx.commit().await?;
+        metrics::counter!(app_metrics::WEBHOOK_DELIVERIES_TOTAL, "outcome" => outcome).increment(1);
+        Ok(())
+    }
+
+    /// Wait after the `attempts`-th failed attempt: the retry backoff, doubled for every attempt
+    /// after the first, capped at the maximum.
+    fn retry_delay(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.retry_backoff
+            .saturating_mul(factor)
+            .min(self.max_retry_backoff)
+    }
+
+    /// Drop deliveries completed longer than the retention ago.
+    async fn purge(&self) -> Result<u64, RepositoryError> {
+        let retention = chrono::Duration::from_std(self.retention).unwrap_or(chrono::Duration::MAX);
+        let cutoff = Utc::now()
+            .naive_utc()
+            .checked_sub_signed(retention)
+            .unwrap_or(chrono::NaiveDateTime::MIN);
+        WebhookDeliveryRepository::delete_completed_before(&self.pg_pool, cutoff).await
+    }
+
+    /// Dispatch until `shutdown` is cancelled: batches back to back while deliveries are due,
+    /// polling every `poll_interval` when idle, with exponential backoff after failures.
+    /// Completed deliveries are kept for the retention period, then deleted.
+    pub fn spawn(self, shutdown: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            tracing::info!("Webhook dispatcher started");
+            let mut backoff = self.poll_interval;
+            let mut last_purge: Option<Instant> = None;
+            loop {
+                let delay = match self.dispatch_batch().await {
+                    Ok(count) if count > 0 => {
+                        backoff = self.poll_interval;
+                        Duration::ZERO
+                    }
+                    Ok(_) => {
+                        backoff = self.poll_interval;
+                        if last_purge.is_none_or(|at| at.elapsed() >= PURGE_INTERVAL) {
+                            last_purge = Some(Instant::now());
+                            if let Err(err) = self.purge().await {
+                                tracing::warn!("Webhook delivery purge failed: {err}");
+                            }
+                        }
+                        self.poll_interval
+                    }
+                    Err(err) => {
+                        tracing::warn!("Webhook dispatch failed, retrying in {backoff:?}: {err}");
+                        let delay = backoff;
+                        backoff = (backoff * 2).min(MAX_BACKOFF);
+                        delay
+                    }
+                };
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(delay) => {}
+                }
+            }
+            tracing::info!("Webhook dispatcher stopped");
+        })
+    }
+}
+
+/// `at` plus `delay`, saturating at the latest representable time.
+fn later(at: DateTime<Utc>, delay: Duration) -> DateTime<Utc> {
+    chrono::Duration::from_std(delay)
+        .ok()
+        .and_then(|delay| at.checked_add_signed(delay))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
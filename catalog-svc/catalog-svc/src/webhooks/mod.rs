@@ -0,0 +1,11 @@
+//! Webhooks: catalog events POSTed to subscribed HTTP endpoints, for partners without Kafka.
+//!
+//! A delivery is queued per matching subscription in the transaction recording the event (see
+//! [persistence::WebhookDeliveryRepository::enqueue]) and sent by the [delivery::WebhookDispatcher],
+//! signed as described in [signature].
+
+pub mod api;
+pub mod delivery;
+pub mod persistence;
+pub mod service;
+pub mod signature;
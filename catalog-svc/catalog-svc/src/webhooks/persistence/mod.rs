@@ -0,0 +1,499 @@
+//! SQL repositories for [WebhookSubscription]s and their [WebhookDelivery] log.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{Executor, FromRow, Postgres};
+use uuid::Uuid;
+
+use crate::catalog::persistence::RepositoryError;
+use crate::common::pagination::{PaginatedSearchResponse, Pagination};
+use crate::events::{CatalogEvent, EventEnvelope};
+use crate::webhooks::api::{DeliveryStatus, WebhookDelivery, WebhookSubscription};
+
+fn utc(ts: NaiveDateTime) -> DateTime<Utc> {
+    DateTime::<Utc>::from_naive_utc_and_offset(ts, Utc)
+}
+
+/// Row type for mapping SELECT results from `webhook_subscriptions` into [WebhookSubscription].
+#[derive(FromRow)]
+struct WebhookSubscriptionRow {
+    subscription_id: Uuid,
+    url: String,
+    event_types: Vec<String>,
+    enabled: bool,
+    consecutive_failures: i32,
+    created_at: NaiveDateTime,
+    modified_at: NaiveDateTime,
+    disabled_at: Option<NaiveDateTime>,
+}
+
+impl WebhookSubscriptionRow {
+    fn into_subscription(self) -> WebhookSubscription {
+        WebhookSubscription {
+            subscription_id: self.subscription_id,
+            url: self.url,
+            event_types: self.event_types,
+            enabled: self.enabled,
+            consecutive_failures: self.consecutive_failures.try_into().unwrap_or_default(),
+            created_at: utc(self.created_at),
+            modified_at: utc(self.modified_at),
+            disabled_at: self.disabled_at.map(utc),
+        }
+    }
+}
+
+/// PostgreSQL webhook subscription persistence. Each method runs on the given [Executor].
+pub struct WebhookSubscriptionRepository;
+
+impl WebhookSubscriptionRepository {
+    pub async fn create(
+        executor: impl Executor<'_, Database = Postgres>,
+        subscription: &WebhookSubscription,
+        secret: &str,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_subscriptions (
+                subscription_id,
+                url,
+                event_types,
+                secret,
+                enabled,
+                created_at,
+                modified_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(subscription.subscription_id)
+        .bind(&subscription.url)
+        .bind(&subscription.event_types)
+        .bind(secret)
+        .bind(subscription.enabled)
+        .bind(subscription.created_at.naive_utc())
+        .bind(subscription.modified_at.naive_utc())
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get(
+        executor: impl Executor<'_, Database = Postgres>,
+        subscription_id: Uuid,
+    ) -> Result<Option<WebhookSubscription>, RepositoryError> {
+        let row = sqlx::query_as::<_, WebhookSubscriptionRow>(
+            r#"
+            SELECT
+                subscription_id,
+                url,
+                event_types,
+                enabled,
+                consecutive_failures,
+                created_at,
+                modified_at,
+                disabled_at
+            FROM webhook_subscriptions
+            WHERE subscription_id = $1
+            "#,
+        )
+        .bind(subscription_id)
+        .fetch_optional(executor)
+        .await?;
+        Ok(row.map(WebhookSubscriptionRow::into_subscription))
+    }
+
+    /// All subscriptions, oldest first.
+    pub async fn list(
+        executor: impl Executor<'_, Database = Postgres>,
+    ) -> Result<Vec<WebhookSubscription>, RepositoryError> {
+        let rows = sqlx::query_as::<_, WebhookSubscriptionRow>(
+            r#"
+            SELECT
+                subscription_id,
+                url,
+                event_types,
+                enabled,
+                consecutive_failures,
+                created_at,
+                modified_at,
+                disabled_at
+            FROM webhook_subscriptions
+            ORDER BY created_at, subscription_id
+            "#,
+        )
+        .fetch_all(executor)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(WebhookSubscriptionRow::into_subscription)
+            .collect())
+    }
+
+    /// Store the url, event types, enabled flag, failure count and timestamps of
+    /// `subscription`, and `secret` if given. Returns false if it does not exist.
+    pub async fn update(
+        executor: impl Executor<'_, Database = Postgres>,
+        subscription: &WebhookSubscription,
+        secret: Option<&str>,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE webhook_subscriptions
+            SET
+                url = $2,
+                event_types = $3,
+                enabled = $4,
+                consecutive_failures = $5,
+                modified_at = $6,
+                disabled_at = $7,
+                secret = COALESCE($8, secret)
+            WHERE subscription_id = $1
+            "#,
+        )
+        .bind(subscription.subscription_id)
+        .bind(&subscription.url)
+        .bind(&subscription.event_types)
+        .bind(subscription.enabled)
+        .bind(i32::try_from(subscription.consecutive_failures).unwrap_or(i32::MAX))
+        .bind(subscription.modified_at.naive_utc())
+        .bind(subscription.disabled_at.map(|ts| ts.naive_utc()))
+        .bind(secret)
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete the subscription and its delivery log. Returns false if it does not exist.
+    pub async fn delete(
+        executor: impl Executor<'_, Database = Postgres>,
+        subscription_id: Uuid,
+    ) -> Result<bool, RepositoryError> {
+        let result = sqlx::query("DELETE FROM webhook_subscriptions WHERE subscription_id = $1")
+            .bind(subscription_id)
+            .execute(executor)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record a successful attempt: the failure count starts over.
+    pub async fn record_success(
+        executor: impl Executor<'_, Database = Postgres>,
+        subscription_id: Uuid,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "UPDATE webhook_subscriptions SET consecutive_failures = 0 WHERE subscription_id = $1",
+        )
+        .bind(subscription_id)
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Record a failed attempt, disabling the subscription at `disable_after` failures in a row.
+    /// Returns true if this disabled it.
+    pub async fn record_failure(
+        executor: impl Executor<'_, Database = Postgres>,
+        subscription_id: Uuid,
+        disable_after: u32,
+        failed_at: DateTime<Utc>,
+    ) -> Result<bool, RepositoryError> {
+        // SET expressions see the row before the update, RETURNING the row after it
+        let disabled: Option<bool> = sqlx::query_scalar(
+            r#"
+            UPDATE webhook_subscriptions
+            SET
+                consecutive_failures = consecutive_failures + 1,
+                enabled = enabled AND consecutive_failures + 1 < $2,
+                disabled_at = CASE
+                    WHEN enabled AND consecutive_failures + 1 >= $2 THEN $3
+                    ELSE disabled_at
+                END
+            WHERE subscription_id = $1
+            RETURNING NOT enabled AND disabled_at = $3
+            "#,
+        )
+        .bind(subscription_id)
+        .bind(i64::from(disable_after))
+        .bind(failed_at.naive_utc())
+        .fetch_optional(executor)
+        .await?;
+        Ok(disabled.unwrap_or(false))
+    }
+}
+
+/// Row type for mapping SELECT results from `webhook_deliveries` into [WebhookDelivery].
+#[derive(FromRow)]
+struct WebhookDeliveryRow {
+    delivery_id: Uuid,
+    subscription_id: Uuid,
+    event_id: Uuid,
+    event_type: String,
+    status: String,
+    attempts: i32,
+    next_attempt_at: Option<NaiveDateTime>,
+    last_attempt_at: Option<NaiveDateTime>,
+    last_status_code: Option<i32>,
+    last_error: Option<String>,
+    created_at: NaiveDateTime,
+    completed_at: Option<NaiveDateTime>,
+}
+
+impl WebhookDeliveryRow {
+    fn into_delivery(self) -> Result<WebhookDelivery, RepositoryError> {
+        let status = self
+            .status
+            .parse::<DeliveryStatus>()
+            .map_err(|_| RepositoryError::InvalidDeliveryStatus(self.status.clone()))?;
+        Ok(WebhookDelivery {
+            delivery_id: self.delivery_id,
+            subscription_id: self.subscription_id,
+            event_id: self.event_id,
+            event_type: self.event_type,
+            status,
+            attempts: self.attempts.try_into().unwrap_or_default(),
+            next_attempt_at: self.next_attempt_at.map(utc),
+            last_attempt_at: self.last_attempt_at.map(utc),
+            last_status_code: self.last_status_code.and_then(|code| code.try_into().ok()),
+            last_error: self.last_error,
+            created_at: utc(self.created_at),
+            completed_at: self.completed_at.map(utc),
+        })
+    }
+}
+
+/// A delivery claimed for an attempt, with what is needed to send it.
+#[derive(Debug, Clone, FromRow)]
+pub struct ClaimedDelivery {
+    pub delivery_id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_type: String,
+    pub payload: Json<serde_json::Value>,
+    /// Attempts made before this one.
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+}
+
+/// Result of one delivery attempt, as recorded by [WebhookDeliveryRepository::record_attempt].
+#[derive(Debug, Clone)]
+pub struct DeliveryAttempt {
+    pub delivery_id: Uuid,
+    pub attempted_at: DateTime<Utc>,
+    /// [DeliveryStatus::Pending] to retry at `next_attempt_at`.
+    pub status: DeliveryStatus,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// PostgreSQL webhook delivery persistence. Each method runs on the given [Executor].
+pub struct WebhookDeliveryRepository;
+
+impl WebhookDeliveryRepository {
+    /// Queue `envelope` for every enabled subscription to its type, due now. Call it in the
+    /// transaction recording the event so both commit together.
+    #[tracing::instrument(
+        name = "WebhookDeliveryRepository::enqueue",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "INSERT", db.sql.table = "webhook_deliveries")
+    )]
+    pub async fn enqueue(
+        executor: impl Executor<'_, Database = Postgres>,
+        envelope: &EventEnvelope<CatalogEvent>,
+    ) -> Result<(), RepositoryError> {
+        let payload = serde_json::to_value(envelope).map_err(RepositoryError::Serialization)?;
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_deliveries (
+                delivery_id,
+                subscription_id,
+                event_id,
+                event_type,
+                payload,
+                status,
+                next_attempt_at,
+                created_at
+            )
+            SELECT gen_random_uuid(), subscription_id, $1, $2, $3, $4, $5, $5
+            FROM webhook_subscriptions
+            WHERE enabled AND $2 = ANY(event_types)
+            "#,
+        )
+        .bind(envelope.id)
+        .bind(&envelope.event_type)
+        .bind(Json(payload))
+        .bind(DeliveryStatus::Pending.to_string())
+        .bind(envelope.occurred_at.naive_utc())
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Claim up to `limit` pending deliveries due at `now` to enabled subscriptions, oldest due
+    /// first. They are not due again before `lease_until`, so other instances skip them while
+    /// the attempt is in flight.
+    #[tracing::instrument(
+        name = "WebhookDeliveryRepository::claim_due",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "UPDATE", db.sql.table = "webhook_deliveries")
+    )]
+    pub async fn claim_due(
+        executor: impl Executor<'_, Database = Postgres>,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<ClaimedDelivery>, RepositoryError> {
+        let claimed = sqlx::query_as::<_, ClaimedDelivery>(
+            r#"
+            UPDATE webhook_deliveries d
+            SET next_attempt_at = $2
+            FROM webhook_subscriptions s
+            WHERE s.subscription_id = d.subscription_id
+              AND d.delivery_id IN (
+                SELECT due.delivery_id
+                FROM webhook_deliveries due
+                JOIN webhook_subscriptions sub ON sub.subscription_id = due.subscription_id
+                WHERE due.status = $4 AND due.next_attempt_at <= $1 AND sub.enabled
+                ORDER BY due.next_attempt_at
+                LIMIT $3
+                FOR UPDATE OF due SKIP LOCKED
+              )
+            RETURNING
+                d.delivery_id,
+                d.subscription_id,
+                d.event_type,
+                d.payload,
+                d.attempts,
+                s.url,
+                s.secret
+            "#,
+        )
+        .bind(now.naive_utc())
+        .bind(lease_until.naive_utc())
+        .bind(i64::from(limit))
+        .bind(DeliveryStatus::Pending.to_string())
+        .fetch_all(executor)
+        .await?;
+        Ok(claimed)
+    }
+
+    pub async fn record_attempt(
+        executor: impl Executor<'_, Database = Postgres>,
+        attempt: &DeliveryAttempt,
+    ) -> Result<(), RepositoryError> {
+        let completed_at =
+            (attempt.status != DeliveryStatus::Pending).then(|| attempt.attempted_at.naive_utc());
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET
+                status = $2,
+                attempts = attempts + 1,
+                next_attempt_at = $3,
+                last_attempt_at = $4,
+                last_status_code = $5,
+                last_error = $6,
+                completed_at = $7
+            WHERE delivery_id = $1
+            "#,
+        )
+        .bind(attempt.delivery_id)
+        .bind(attempt.status.to_string())
+        .bind(attempt.next_attempt_at.map(|ts| ts.naive_utc()))
+        .bind(attempt.attempted_at.naive_utc())
+        .bind(attempt.status_code.map(i32::from))
+        .bind(&attempt.error)
+        .bind(completed_at)
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark the pending deliveries of `subscription_id` failed if it is disabled, so they are not
+    /// all sent at once when it is re-enabled, and get purged like other completed deliveries.
+    /// Returns how many were failed.
+    pub async fn fail_pending_of_disabled(
+        executor: impl Executor<'_, Database = Postgres>,
+        subscription_id: Uuid,
+        failed_at: DateTime<Utc>,
+    ) -> Result<u64, RepositoryError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE webhook_deliveries d
+            SET
+                status = $3,
+                next_attempt_at = NULL,
+                last_error = 'subscription disabled',
+                completed_at = $2
+            FROM webhook_subscriptions s
+            WHERE s.subscription_id = d.subscription_id
+              AND d.subscription_id = $1
+              AND d.status = $4
+              AND NOT s.enabled
+            "#,
+        )
+        .bind(subscription_id)
+        .bind(failed_at.naive_utc())
+        .bind(DeliveryStatus::Failed.to_string())
+        .bind(DeliveryStatus::Pending.to_string())
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Deliveries of `subscription_id`, newest first, optionally only those in `status`.
+    pub async fn list(
+        executor: impl Executor<'_, Database = Postgres>,
+        subscription_id: Uuid,
+        status: Option<DeliveryStatus>,
+        page: Pagination,
+    ) -> Result<PaginatedSearchResponse<WebhookDelivery>, RepositoryError> {
+        let rows = sqlx::query_as::<_, WebhookDeliveryRow>(
+            r#"
+            SELECT
+                delivery_id,
+                subscription_id,
+                event_id,
+                event_type,
+                status,
+                attempts,
+                next_attempt_at,
+                last_attempt_at,
+                last_status_code,
+                last_error,
+                created_at,
+                completed_at
+            FROM webhook_deliveries
+            WHERE subscription_id = $1 AND ($2::VARCHAR IS NULL OR status = $2)
+            ORDER BY created_at DESC, delivery_id
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(subscription_id)
+        .bind(status.map(|status| status.to_string()))
+        .bind(i64::from(page.limit) + 1)
+        .bind(i64::from(page.offset))
+        .fetch_all(executor)
+        .await?;
+
+        let has_more = rows.len() > page.limit as usize;
+        let deliveries = rows
+            .into_iter()
+            .take(page.limit as usize)
+            .map(WebhookDeliveryRow::into_delivery)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PaginatedSearchResponse::new(deliveries, has_more))
+    }
+
+    /// Delete deliveries completed before `cutoff`. Returns how many were removed.
+    pub async fn delete_completed_before(
+        executor: impl Executor<'_, Database = Postgres>,
+        cutoff: NaiveDateTime,
+    ) -> Result<u64, RepositoryError> {
+        let result = sqlx::query("DELETE FROM webhook_deliveries WHERE completed_at < $1")
+            .bind(cutoff)
+            .execute(executor)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
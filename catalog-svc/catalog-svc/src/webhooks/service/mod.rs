@@ -0,0 +1,223 @@
+use chrono::Utc;
+use rand::RngCore;
+use reqwest::Url;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::catalog::persistence::RepositoryError;
+use crate::common::pagination::Pagination;
+use crate::common::validation::ValidationErrors;
+use crate::events::CatalogEvent;
+use crate::webhooks::api::{
+    CreateWebhookSubscriptionBody, CreatedWebhookSubscription, ListWebhookDeliveriesRequest,
+    ListWebhookDeliveriesResponse, ListWebhookSubscriptionsResponse, UpdateWebhookSubscriptionBody,
+    WebhookSubscription, WebhooksServiceError,
+};
+use crate::webhooks::persistence::{WebhookDeliveryRepository, WebhookSubscriptionRepository};
+
+/// Prefix of generated signing secrets.
+const SECRET_PREFIX: &str = "whsec_";
+const MAX_URL_LEN: usize = 2048;
+const MIN_SECRET_LEN: usize = 16;
+const MAX_SECRET_LEN: usize = 255;
+const DEFAULT_DELIVERIES_LIMIT: u32 = 100;
+const MAX_DELIVERIES_LIMIT: u32 = 100;
+
+impl From<RepositoryError> for WebhooksServiceError {
+    fn from(err: RepositoryError) -> Self {
+        WebhooksServiceError::InternalError(Box::new(err))
+    }
+}
+
+/// Webhook subscriptions and their delivery log, using the webhook repositories against [PgPool].
+/// Deliveries are queued by the catalog service and sent by
+/// [crate::webhooks::delivery::WebhookDispatcher].
+#[derive(Clone)]
+pub struct WebhooksService {
+    pg_pool: PgPool,
+}
+
+impl WebhooksService {
+    pub fn new(pg_pool: PgPool) -> Self {
+        Self { pg_pool }
+    }
+
+    /// Subscribe an endpoint. The returned secret is only shown here.
+    pub async fn create(
+        &self,
+        body: CreateWebhookSubscriptionBody,
+    ) -> Result<CreatedWebhookSubscription, WebhooksServiceError> {
+        let mut errors = ValidationErrors::new();
+        validate_url(&body.url, &mut errors);
+        let event_types = validate_event_types(body.event_types, &mut errors);
+        if let Some(secret) = &body.secret {
+            validate_secret(secret, &mut errors);
+        }
+        errors
+            .into_result(())
+            .map_err(WebhooksServiceError::ValidationError)?;
+
+        let secret = body.secret.unwrap_or_else(generate_secret);
+        let now = Utc::now();
+        let subscription = WebhookSubscription {
+            subscription_id: Uuid::new_v4(),
+            url: body.url,
+            event_types,
+            enabled: true,
+            consecutive_failures: 0,
+            created_at: now,
+            modified_at: now,
+            disabled_at: None,
+        };
+        WebhookSubscriptionRepository::create(&self.pg_pool, &subscription, &secret).await?;
+        Ok(CreatedWebhookSubscription {
+            secret,
+            subscription,
+        })
+    }
+
+    /// List subscriptions, oldest first.
+    pub async fn list(&self) -> Result<ListWebhookSubscriptionsResponse, WebhooksServiceError> {
+        let subscriptions = WebhookSubscriptionRepository::list(&self.pg_pool).await?;
+        Ok(ListWebhookSubscriptionsResponse { subscriptions })
+    }
+
+    pub async fn get(
+        &self,
+        subscription_id: Uuid,
+    ) -> Result<Option<WebhookSubscription>, WebhooksServiceError> {
+        Ok(WebhookSubscriptionRepository::get(&self.pg_pool, subscription_id).await?)
+    }
+
+    /// Replace a subscription's settings. Re-enabling it resets its failure count; disabling it
+    /// fails its pending deliveries. Returns `None` if it does not exist.
+    pub async fn update(
+        &self,
+        subscription_id: Uuid,
+        body: UpdateWebhookSubscriptionBody,
+    ) -> Result<Option<WebhookSubscription>, WebhooksServiceError> {
+        let mut errors = ValidationErrors::new();
+        validate_url(&body.url, &mut errors);
+        let event_types = validate_event_types(body.event_types, &mut errors);
+        if let Some(secret) = &body.secret {
+            validate_secret(secret, &mut errors);
+        }
+        errors
+            .into_result(())
+            .map_err(WebhooksServiceError::ValidationError)?;
+
+        let Some(mut subscription) =
+            WebhookSubscriptionRepository::get(&self.pg_pool, subscription_id).await?
+        else {
+            return Ok(None);
+        };
+        let now = Utc::now();
+        match (subscription.enabled, body.enabled) {
+            (false, true) => {
+                subscription.consecutive_failures = 0;
+                subscription.disabled_at = None;
+            }
+            (true, false) => subscription.disabled_at = Some(now),
+            _ => {}
+        }
+        subscription.url = body.url;
+        subscription.event_types = event_types;
+        subscription.enabled = body.enabled;
+        subscription.modified_at = now;
+        let mut tx = self.pg_pool.begin().await.map_err(RepositoryError::from)?;
+        let updated =
+            WebhookSubscriptionRepository::update(&mut *tx, &subscription, body.secret.as_deref())
+                .await?;
+        if updated && !subscription.enabled {
+            WebhookDeliveryRepository::fail_pending_of_disabled(&mut *tx, subscription_id, now)
+                .await?;
+        }
+        tx.commit().await.map_err(RepositoryError::from)?;
+        Ok(updated.then_some(subscription))
+    }
+
+    /// Delete a subscription and its delivery log. Returns false if it does not exist.
+    pub async fn delete(&self, subscription_id: Uuid) -> Result<bool, WebhooksServiceError> {
+        Ok(WebhookSubscriptionRepository::delete(&self.pg_pool, subscription_id).await?)
+    }
+
+    /// A page of a subscription's deliveries, newest first. Returns `None` if the subscription
+    /// does not exist.
+    pub async fn list_deliveries(
+        &self,
+        subscription_id: Uuid,
+        req: ListWebhookDeliveriesRequest,
+    ) -> Result<Option<ListWebhookDeliveriesResponse>, WebhooksServiceError> {
+        if WebhookSubscriptionRepository::get(&self.pg_pool, subscription_id)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+        let pagination = Pagination {
+            limit: req
+                .limit
+                .unwrap_or(DEFAULT_DELIVERIES_LIMIT)
+                .clamp(1, MAX_DELIVERIES_LIMIT),
+            offset: req.offset.unwrap_or(0),
+        };
+        let page =
+            WebhookDeliveryRepository::list(&self.pg_pool, subscription_id, req.status, pagination)
+                .await?;
+        Ok(Some(ListWebhookDeliveriesResponse {
+            deliveries: page.items,
+            has_more: page.has_more,
+            pagination,
+        }))
+    }
+}
+
+fn validate_url(url: &str, errors: &mut ValidationErrors) {
+    if url.len() > MAX_URL_LEN {
+        errors.add("url", "must be at most 2048 characters");
+        return;
+    }
+    match Url::parse(url) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => {}
+        Ok(_) => errors.add("url", "must be an http or https URL"),
+        Err(_) => errors.add("url", "must be a valid URL"),
+    }
+}
+
+/// The known, deduplicated and sorted `event_types`.
+fn validate_event_types(
+    mut event_types: Vec<String>,
+    errors: &mut ValidationErrors,
+) -> Vec<String> {
+    if event_types.is_empty() {
+        errors.add("eventTypes", "must contain at least one event type");
+    }
+    if let Some(unknown) = event_types
+        .iter()
+        .find(|event_type| !CatalogEvent::TYPES.contains(&event_type.as_str()))
+    {
+        errors.add(
+            "eventTypes",
+            format!(
+                "unknown event type `{unknown}`, expected one of {}",
+                CatalogEvent::TYPES.join(", ")
+            ),
+        );
+    }
+    event_types.sort();
+    event_types.dedup();
+    event_types
+}
+
+fn validate_secret(secret: &str, errors: &mut ValidationErrors) {
+    let len = secret.chars().count();
+    if !(MIN_SECRET_LEN..=MAX_SECRET_LEN).contains(&len) {
+        errors.add("secret", "must be 16 to 255 characters");
+    }
+}
+
+fn generate_secret() -> String {
+    let mut secret = [0u8; 32];
+    rand::rng().fill_bytes(&mut secret);
+    format!("{SECRET_PREFIX}{}", hex::encode(secret))
+}
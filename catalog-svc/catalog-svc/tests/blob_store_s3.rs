@@ -0,0 +1,63 @@
+//! Integration test for the S3 blob store against the local MinIO stand-in (docker-compose.yml).
+
+use bytes::Bytes;
+use catalog_svc::app_config::S3Config;
+use catalog_svc::blob_store::{BlobStore, BlobStoreError, S3BlobStore};
+use rust_demo_commons::util::tests;
+use uuid::Uuid;
+
+fn minio_config() -> S3Config {
+    S3Config {
+        bucket: "catalog-media-test".to_string(),
+        region: "us-east-1".to_string(),
+        endpoint: Some("http://127.0.0.1:9000".to_string()),
+        access_key_id: "minioadmin".to_string(),
+        secret_access_key: "minioadmin".to_string(),
+        force_path_style: true,
+    }
+}
+
+#[tokio::test]
+async fn s3_blob_store_roundtrip() {
+    tests::init_logging();
+
+    let store = S3BlobStore::new(&minio_config());
+    if let Err(err) = store
+        .client()
+        .create_bucket()
+        .bucket(store.bucket())
+        .send()
+        .await
+    {
+        let already_exists = err
+            .as_service_error()
+            .is_some_and(|e| e.is_bucket_already_owned_by_you() || e.is_bucket_already_exists());
+        assert!(already_exists, "create bucket: {err}");
+    }
+
+    let prefix = format!("tests/{}/", Uuid::new_v4());
+    let key_a = format!("{prefix}a");
+    let key_b = format!("{prefix}nested/b");
+
+    store
+        .put(&key_a, "text/plain", Bytes::from_static(b"alpha"))
+        .await
+        .expect("put a");
+    store
+        .put(&key_b, "text/plain", Bytes::from_static(b"beta"))
+        .await
+        .expect("put b");
+    assert_eq!(store.get(&key_a).await.expect("get a"), "alpha");
+
+    store.delete(&key_a).await.expect("delete a");
+    assert!(matches!(
+        store.get(&key_a).await,
+        Err(BlobStoreError::NotFound(_))
+    ));
+
+    store.delete_prefix(&prefix).await.expect("delete prefix");
+    assert!(matches!(
+        store.get(&key_b).await,
+        Err(BlobStoreError::NotFound(_))
+    ));
+}
@@ -0,0 +1,190 @@
+//! Integration tests for API key minting, `X-Api-Key` authentication, expiry and revocation.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::{AppConfig, AuthConfig};
+use catalog_svc::auth::Role as AuthRole;
+use catalog_svc::auth::jwt::{Claims, encode_token};
+use catalog_svc::server;
+use catalog_svc_client::Client;
+use catalog_svc_client::types::{Category, CreateApiKeyBody, CreateCatalogItemBody, Role};
+use jsonwebtoken::{EncodingKey, Header};
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue};
+use rust_demo_commons::util::tests;
+
+const SECRET: &str = "catalog-api-keys-test-secret";
+
+fn client(base_url: &str, header: HeaderName, value: &str) -> Client {
+    let mut headers = HeaderMap::new();
+    headers.insert(header, HeaderValue::from_str(value).expect("header value"));
+    let http = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .expect("http client");
+    Client::new_with_client(base_url, http)
+}
+
+fn api_key_client(base_url: &str, key: &str) -> Client {
+    client(base_url, HeaderName::from_static("x-api-key"), key)
+}
+
+fn status<T, E>(result: Result<T, catalog_svc_client::Error<E>>) -> Option<u16> {
+    result
+        .err()
+        .and_then(|err| err.status())
+        .map(|s| s.as_u16())
+}
+
+fn key_body(name: &str, scopes: Vec<Role>) -> CreateApiKeyBody {
+    CreateApiKeyBody {
+        name: name.to_string(),
+        scopes,
+        expires_at: Some(chrono::Utc::now() + chrono::Duration::hours(1)),
+    }
+}
+
+#[tokio::test]
+async fn catalog_api_keys() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth = AuthConfig {
+        enabled: true,
+        hs256_secret: Some(SECRET.to_string()),
+        ..AuthConfig::default()
+    };
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let base_url = format!("http://{addr}");
+    let admin_token = encode_token(
+        &Header::default(),
+        &Claims::new("key-admin", &[AuthRole::Admin], Duration::from_secs(300)),
+        &EncodingKey::from_secret(SECRET.as_bytes()),
+    )
+    .expect("sign admin token");
+    let admin = client(&base_url, AUTHORIZATION, &format!("Bearer {admin_token}"));
+
+    // Minting is admin-only and validated
+    let invalid = CreateApiKeyBody {
+        name: " ".to_string(),
+        scopes: vec![],
+        expires_at: Some(chrono::Utc::now() - chrono::Duration::hours(1)),
+    };
+    let err = admin
+        .create_api_key(&invalid)
+        .await
+        .expect_err("invalid key request");
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(400));
+
+    let writer_key = admin
+        .create_api_key(&key_body("partner-writer", vec![Role::Writer]))
+        .await
+        .expect("mint writer key")
+        .into_inner();
+    let reader_key = admin
+        .create_api_key(&key_body("partner-reader", vec![Role::Reader]))
+        .await
+        .expect("mint reader key")
+        .into_inner();
+    assert!(writer_key.key.starts_with(&writer_key.api_key.key_prefix));
+    assert_ne!(writer_key.key, reader_key.key);
+
+    // Keys authenticate with their scopes as roles
+    let writer = api_key_client(&base_url, &writer_key.key);
+    let reader = api_key_client(&base_url, &reader_key.key);
+    let body = CreateCatalogItemBody {
+        name: "Partner item".to_string(),
+        description: "Created with an API key".to_string(),
+        category: Category::Books,
+        date: "2025-04-05".to_string(),
+        brand: None,
+        price: "3.50".to_string(),
+    };
+    writer
+        .create_catalog_item(&body)
+        .await
+        .expect("writer key can create");
+    reader
+        .list_catalog_items(Some(1), None, None)
+        .await
+        .expect("reader key can list");
+    assert_eq!(status(reader.create_catalog_item(&body).await), Some(403));
+    assert_eq!(status(writer.list_api_keys(None).await), Some(403));
+    assert_eq!(
+        status(
+            api_key_client(&base_url, "ck_not-a-real-key")
+                .list_catalog_items(None, None, None)
+                .await
+        ),
+        Some(401)
+    );
+
+    // Listing shows metadata and last use, never the key
+    let listed = admin
+        .list_api_keys(None)
+        .await
+        .expect("list keys")
+        .into_inner();
+    let writer_info = listed
+        .api_keys
+        .iter()
+        .find(|k| k.key_id == writer_key.api_key.key_id)
+        .expect("writer key listed");
+    assert_eq!(writer_info.scopes, vec![Role::Writer]);
+    assert!(writer_info.last_used_at.is_some());
+
+    // Expired keys are rejected
+    sqlx::query("UPDATE api_keys SET expires_at = NOW() - INTERVAL '1 second' WHERE key_id = $1")
+        .bind(reader_key.api_key.key_id)
+        .execute(&state.pg_pool)
+        .await
+        .expect("expire reader key");
+    assert_eq!(
+        status(reader.list_catalog_items(None, None, None).await),
+        Some(401)
+    );
+
+    // Revoked keys are rejected and hidden from the default listing
+    admin
+        .revoke_api_key(&writer_key.api_key.key_id)
+        .await
+        .expect("revoke writer key");
+    assert_eq!(
+        status(writer.list_catalog_items(None, None, None).await),
+        Some(401)
+    );
+    let active = admin
+        .list_api_keys(None)
+        .await
+        .expect("list active keys")
+        .into_inner();
+    assert!(
+        active
+            .api_keys
+            .iter()
+            .all(|k| k.key_id != writer_key.api_key.key_id)
+    );
+    let all = admin
+        .list_api_keys(Some(true))
+        .await
+        .expect("list all keys")
+        .into_inner();
+    let revoked = all
+        .api_keys
+        .iter()
+        .find(|k| k.key_id == writer_key.api_key.key_id)
+        .expect("revoked key listed");
+    assert!(revoked.revoked_at.is_some());
+    assert_eq!(
+        status(admin.revoke_api_key(&uuid::Uuid::new_v4()).await),
+        Some(404)
+    );
+
+    state.server_shutdown.cancel();
+}
@@ -0,0 +1,230 @@
+//! Integration tests for bearer token authentication and role-based authorization.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::{AppConfig, AuthConfig};
+use catalog_svc::auth::jwt::{Claims, JwtSetupError, encode_token};
+use catalog_svc::auth::{Authenticator, Role};
+use catalog_svc::server;
+use catalog_svc_client::Client;
+use catalog_svc_client::types::{
+    Category, CreateCatalogItemBody, CreateReviewBody, ModerateReviewBody, ModerationState,
+};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use rust_demo_commons::util::tests;
+
+const SECRET: &str = "catalog-auth-test-secret";
+/// Test-only key pair: `jwks.json` holds the public half of `rs256_private.pem` (kid `test-key`).
+const RS256_PRIVATE_KEY: &[u8] = include_bytes!("fixtures/auth/rs256_private.pem");
+
+fn hs256_token(roles: &[Role]) -> String {
+    let claims = Claims::new("hs-user", roles, Duration::from_secs(300));
+    encode_token(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(SECRET.as_bytes()),
+    )
+    .expect("sign HS256 token")
+}
+
+fn rs256_token(kid: &str, roles: &[Role]) -> String {
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some(kid.to_string());
+    let claims = Claims::new("rs-user", roles, Duration::from_secs(300));
+    let key = EncodingKey::from_rsa_pem(RS256_PRIVATE_KEY).expect("test private key");
+    encode_token(&header, &claims, &key).expect("sign RS256 token")
+}
+
+fn client(base_url: &str, token: Option<&str>) -> Client {
+    let mut headers = HeaderMap::new();
+    if let Some(token) = token {
+        let value = HeaderValue::from_str(&format!("Bearer {token}")).expect("header value");
+        headers.insert(AUTHORIZATION, value);
+    }
+    let http = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .expect("http client");
+    Client::new_with_client(base_url, http)
+}
+
+fn status<T, E>(result: Result<T, catalog_svc_client::Error<E>>) -> Option<u16> {
+    result
+        .err()
+        .and_then(|err| err.status())
+        .map(|s| s.as_u16())
+}
+
+#[tokio::test]
+async fn catalog_auth() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth = AuthConfig {
+        enabled: true,
+        hs256_secret: Some(SECRET.to_string()),
+        jwks_file: Some("tests/fixtures/auth/jwks.json".into()),
+        ..AuthConfig::default()
+    };
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let base_url = format!("http://{addr}");
+    let anonymous = client(&base_url, None);
+    let reader = client(&base_url, Some(&hs256_token(&[Role::Reader])));
+    let writer = client(&base_url, Some(&rs256_token("test-key", &[Role::Writer])));
+    let admin = client(&base_url, Some(&hs256_token(&[Role::Admin])));
+
+    // No or invalid tokens: 401
+    assert_eq!(
+        status(anonymous.list_catalog_items(None, None, None).await),
+        Some(401)
+    );
+    let mut expired = Claims::new("late", &[Role::Admin], Duration::ZERO);
+    expired.exp -= 3600;
+    let expired = encode_token(
+        &Header::default(),
+        &expired,
+        &EncodingKey::from_secret(SECRET.as_bytes()),
+    )
+    .expect("sign expired token");
+    let forged = encode_token(
+        &Header::default(),
+        &Claims::new("mallory", &[Role::Admin], Duration::from_secs(300)),
+        &EncodingKey::from_secret(b"not-the-secret"),
+    )
+    .expect("sign forged token");
+    for token in [
+        expired,
+        forged,
+        rs256_token("unknown-key", &[Role::Admin]),
+        "not-a-jwt".to_string(),
+    ] {
+        let result = client(&base_url, Some(&token))
+            .list_catalog_items(None, None, None)
+            .await;
+        assert_eq!(status(result), Some(401));
+    }
+
+    // Readers read, writers write (RS256 via the JWKS), admins moderate
+    reader
+        .list_catalog_items(Some(1), None, None)
+        .await
+        .expect("reader can list");
+    let body = CreateCatalogItemBody {
+        name: "Secured".to_string(),
+        description: "Needs a writer".to_string(),
+        category: Category::Books,
+        date: "2025-03-01".to_string(),
+        brand: None,
+        price: "1.00".to_string(),
+    };
+    assert_eq!(status(reader.create_catalog_item(&body).await), Some(403));
+    let item = writer
+        .create_catalog_item(&body)
+        .await
+        .expect("writer can create")
+        .into_inner();
+
+    let review = writer
+        .create_review(
+            &item.item_id,
+            &CreateReviewBody {
+                rating: 4,
+                title: "Fine".to_string(),
+                body: String::new(),
+                author: "rs-user".to_string(),
+            },
+        )
+        .await
+        .expect("writer can review")
+        .into_inner();
+    let approve = ModerateReviewBody {
+        moderation_state: ModerationState::Approved,
+    };
+
+    // Pending reviews and moderation are admin-only
+    assert_eq!(
+        status(reader.get_review(&item.item_id, &review.review_id).await),
+        Some(404)
+    );
+    assert_eq!(
+        status(
+            reader
+                .list_reviews(&item.item_id, None, Some(ModerationState::Pending), None)
+                .await
+        ),
+        Some(403)
+    );
+    assert_eq!(
+        status(
+            writer
+                .moderate_review(&item.item_id, &review.review_id, &approve)
+                .await
+        ),
+        Some(403)
+    );
+    let pending = admin
+        .list_reviews(&item.item_id, None, Some(ModerationState::Pending), None)
+        .await
+        .expect("admin lists pending reviews")
+        .into_inner();
+    assert_eq!(pending.reviews.len(), 1);
+    admin
+        .moderate_review(&item.item_id, &review.review_id, &approve)
+        .await
+        .expect("admin can moderate");
+    reader
+        .get_review(&item.item_id, &review.review_id)
+        .await
+        .expect("approved review is public");
+
+    state.server_shutdown.cancel();
+}
+
+#[tokio::test]
+async fn catalog_auth_refuses_placeholder_secret() {
+    let config = AuthConfig {
+        enabled: true,
+        hs256_secret: Some("local-dev-secret-change-me".to_string()),
+        ..AuthConfig::default()
+    };
+    assert!(matches!(
+        Authenticator::from_config(&config).await,
+        Err(JwtSetupError::PlaceholderSecret)
+    ));
+}
+
+#[tokio::test]
+async fn catalog_auth_required_by_default() {
+    tests::init_logging();
+
+    // Enabled without keys, startup fails rather than letting every caller in
+    assert!(matches!(
+        Authenticator::from_config(&AuthConfig::default()).await,
+        Err(JwtSetupError::NoKeys)
+    ));
+
+    // The shipped config does not opt out: admin routes need a token
+    let mut app_config = AppConfig::load_tests();
+    assert!(app_config.auth.enabled, "authentication is on by default");
+    app_config.auth.hs256_secret = Some(SECRET.to_string());
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+
+    let response = reqwest::get(format!("http://{addr}/admin/api-keys"))
+        .await
+        .expect("request");
+    assert_eq!(response.status().as_u16(), 401);
+
+    state.server_shutdown.cancel();
+}
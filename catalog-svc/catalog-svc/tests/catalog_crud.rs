@@ -13,7 +13,8 @@ async fn catalog_crud() {
     tests::init_logging();
 
     let mut app_config = AppConfig::load_tests();
-    let app_state = server::build_app(&app_config).await;
+    app_config.auth.enabled = false;
+    let (app_state, _background) = server::build_app(&app_config).await;
     app_config.server.port = 3031;
 
     let (state, _handle, _addr) = server::start_service_and_serve(app_state, app_config)
@@ -48,7 +49,7 @@ async fn catalog_crud() {
 
     // List (verify create persisted)
     let list = client
-        .list_catalog_items(None, None)
+        .list_catalog_items(None, None, None)
         .await
         .expect("list should succeed");
     let list_body = list.into_inner();
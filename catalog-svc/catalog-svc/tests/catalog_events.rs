@@ -0,0 +1,203 @@
+//! Integration tests for catalog change events: writes record events in the outbox, and the relay
+//! publishes their envelopes in order to an in-memory sink. Without brokers nothing is recorded.
+
+use std::sync::Arc;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::catalog::api::{
+    CatalogItemFilter, Category, CreateCatalogItemBody, UpdateCatalogItemBody,
+};
+use catalog_svc::events::relay::OutboxRelay;
+use catalog_svc::events::sink::InMemoryEventSink;
+use catalog_svc::events::{
+    CatalogEvent, EventEnvelope, ItemCreated, ItemDeleted, ItemUpdated, OutboxMessage, PriceChanged,
+};
+use catalog_svc::server;
+use rust_decimal::Decimal;
+use rust_demo_commons::util::tests;
+use uuid::Uuid;
+
+fn create_body(name: &str) -> CreateCatalogItemBody {
+    CreateCatalogItemBody {
+        name: name.to_string(),
+        description: "Evented".to_string(),
+        category: Category::Electronics,
+        date: "2025-04-09".to_string(),
+        brand: None,
+        price: Decimal::new(1000, 2),
+    }
+}
+
+fn events_of(messages: &[OutboxMessage], item_id: Uuid) -> Vec<CatalogEvent> {
+    messages
+        .iter()
+        .filter(|message| message.item_id == item_id)
+        .map(|message| {
+            let envelope: EventEnvelope =
+                serde_json::from_value(message.payload.clone()).expect("event envelope");
+            let envelope = envelope.into_event().expect("event payload");
+            assert_eq!(envelope.id, message.event_id);
+            assert_eq!(envelope.event_type, message.event_type);
+            assert_eq!(envelope.tenant, "default");
+            envelope.payload
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn catalog_events_outbox() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app, _background) = server::build_app(&app_config).await;
+    // The test config has no brokers, so the outbox is enabled explicitly
+    let catalog = &app.catalog.clone().with_outbox(true);
+
+    let item = catalog.create(create_body("Outbox")).await.expect("create");
+    let item_id = item.item_id;
+    let renamed = catalog
+        .update(
+            item_id,
+            UpdateCatalogItemBody {
+                name: "Outbox renamed".to_string(),
+                description: item.description.clone(),
+                category: item.category,
+                date: "2025-04-09".to_string(),
+                brand: None,
+                price: item.price,
+            },
+        )
+        .await
+        .expect("update")
+        .expect("exists");
+    let repriced = catalog
+        .update(
+            item_id,
+            UpdateCatalogItemBody {
+                name: renamed.name.clone(),
+                description: renamed.description.clone(),
+                category: renamed.category,
+                date: "2025-04-09".to_string(),
+                brand: None,
+                price: Decimal::new(1250, 2),
+            },
+        )
+        .await
+        .expect("update")
+        .expect("exists");
+    assert!(catalog.delete(item_id).await.expect("delete"));
+    // Failed writes record nothing
+    assert!(!catalog.delete(item_id).await.expect("delete"));
+
+    let sink = Arc::new(InMemoryEventSink::new());
+    let relay = OutboxRelay::new(app.pg_pool.clone(), sink.clone(), &app_config.events);
+    // Drain the outbox (it may hold events of other tests)
+    while relay.relay_batch().await.expect("relay") > 0 {}
+
+    let messages = sink.messages();
+    assert_eq!(
+        events_of(&messages, item_id),
+        vec![
+            CatalogEvent::ItemCreated(ItemCreated { item }),
+            CatalogEvent::ItemUpdated(ItemUpdated { item: renamed }),
+            CatalogEvent::ItemUpdated(ItemUpdated { item: repriced }),
+            CatalogEvent::PriceChanged(PriceChanged {
+                item_id,
+                old_price: Decimal::new(1000, 2),
+                new_price: Decimal::new(1250, 2),
+            }),
+            CatalogEvent::ItemDeleted(ItemDeleted { item_id }),
+        ]
+    );
+    let ours: Vec<_> = messages
+        .iter()
+        .filter(|message| message.item_id == item_id)
+        .collect();
+    assert!(ours.windows(2).all(|pair| match pair {
+        [earlier, later] => earlier.seq < later.seq && earlier.event_id != later.event_id,
+        _ => true,
+    }));
+
+    // Published events are not published again
+    let before = sink.messages().len();
+    relay.relay_batch().await.expect("relay");
+    assert!(
+        sink.messages()
+            .iter()
+            .skip(before)
+            .all(|message| message.item_id != item_id)
+    );
+
+    // Without brokers (no relay) writes record nothing
+    let unrecorded = app
+        .catalog
+        .create(create_body("Not recorded"))
+        .await
+        .expect("create");
+    assert!(
+        app.catalog
+            .delete(unrecorded.item_id)
+            .await
+            .expect("delete")
+    );
+    while relay.relay_batch().await.expect("relay") > 0 {}
+    assert!(events_of(&sink.messages(), unrecorded.item_id).is_empty());
+
+    app.server_shutdown.cancel();
+}
+
+#[tokio::test]
+async fn catalog_events_reprice() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app, _background) = server::build_app(&app_config).await;
+    let catalog = &app.catalog.clone().with_outbox(true);
+
+    // A brand of its own, so the repricing only touches this test's item
+    let brand = format!("Reprice {}", Uuid::new_v4());
+    let item = catalog
+        .create(CreateCatalogItemBody {
+            brand: Some(brand.clone()),
+            ..create_body("Repriced")
+        })
+        .await
+        .expect("create");
+    let repriced = catalog
+        .increase_prices(
+            Decimal::new(11, 1),
+            &CatalogItemFilter {
+                brand: Some(brand),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("reprice");
+    let [repriced] = repriced.as_slice() else {
+        panic!("expected one repriced item, got {repriced:?}");
+    };
+    assert_eq!(repriced.price, Decimal::new(1100, 2));
+
+    let sink = Arc::new(InMemoryEventSink::new());
+    let relay = OutboxRelay::new(app.pg_pool.clone(), sink.clone(), &app_config.events);
+    while relay.relay_batch().await.expect("relay") > 0 {}
+
+    assert_eq!(
+        events_of(&sink.messages(), item.item_id),
+        vec![
+            CatalogEvent::ItemCreated(ItemCreated { item: item.clone() }),
+            CatalogEvent::ItemUpdated(ItemUpdated {
+                item: repriced.clone()
+            }),
+            CatalogEvent::PriceChanged(PriceChanged {
+                item_id: item.item_id,
+                old_price: Decimal::new(1000, 2),
+                new_price: Decimal::new(1100, 2),
+            }),
+        ]
+    );
+
+    app.server_shutdown.cancel();
+}
@@ -0,0 +1,331 @@
+//! Integration tests for the GraphQL API: mutations, filtered and paginated queries with related
+//! items, error codes for validation and missing permissions, depth and complexity limits, and
+//! the committed SDL.
+
+use std::path::Path;
+use std::time::Duration;
+
+use catalog_svc::app_config::{AppConfig, AuthConfig};
+use catalog_svc::auth::Role;
+use catalog_svc::auth::jwt::{Claims, encode_token};
+use catalog_svc::related_items::api::{CreateItemLinkBody, LinkKind};
+use catalog_svc::{graphql, server};
+use jsonwebtoken::{EncodingKey, Header};
+use reqwest::StatusCode;
+use rust_demo_commons::util::tests;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+const SECRET: &str = "catalog-graphql-test-secret";
+
+const CREATE_ITEM: &str = r#"
+    mutation Create($input: CreateCatalogItemInput!) {
+        createItem(input: $input) { itemId name price category brand }
+    }
+"#;
+
+fn token(roles: &[Role]) -> String {
+    let claims = Claims::new("graphql-user", roles, Duration::from_secs(300));
+    encode_token(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(SECRET.as_bytes()),
+    )
+    .expect("sign token")
+}
+
+/// Client posting GraphQL requests with a bearer token.
+struct GraphqlClient {
+    http: reqwest::Client,
+    url: String,
+}
+
+impl GraphqlClient {
+    async fn post(&self, token: Option<&str>, query: &str, variables: Value) -> reqwest::Response {
+        let mut request = self
+            .http
+            .post(&self.url)
+            .json(&json!({"query": query, "variables": variables}));
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        request.send().await.expect("request")
+    }
+
+    /// The response body of a request answered with 200.
+    async fn execute(&self, token: &str, query: &str, variables: Value) -> Value {
+        let response = self.post(Some(token), query, variables).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        response.json().await.expect("GraphQL response")
+    }
+
+    /// The `data` of a request that must succeed.
+    async fn data(&self, token: &str, query: &str, variables: Value) -> Value {
+        let body = self.execute(token, query, variables).await;
+        assert_eq!(body.get("errors"), None, "{body}");
+        body.get("data").cloned().expect("data")
+    }
+
+    /// The first error of a request that must fail.
+    async fn error(&self, token: &str, query: &str, variables: Value) -> Value {
+        let body = self.execute(token, query, variables).await;
+        body.pointer("/errors/0").cloned().expect("error")
+    }
+}
+
+fn item_input(name: &str, brand: &str, price: &str) -> Value {
+    json!({
+        "name": name,
+        "description": "Over GraphQL",
+        "category": "BOOKS",
+        "date": "2025-04-12",
+        "brand": brand,
+        "price": price,
+    })
+}
+
+fn str_at<'a>(value: &'a Value, pointer: &str) -> &'a str {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .expect("string field")
+}
+
+#[test]
+fn committed_graphql_schema_is_current() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("schemas/catalog.graphql");
+    let committed = std::fs::read_to_string(path).unwrap_or_default();
+    assert_eq!(
+        committed,
+        graphql::sdl(),
+        "schemas/catalog.graphql is out of date; run `just generate-graphql-schema`"
+    );
+}
+
+#[tokio::test]
+async fn catalog_graphql() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth = AuthConfig {
+        enabled: true,
+        hs256_secret: Some(SECRET.to_string()),
+        ..AuthConfig::default()
+    };
+    app_config.graphql.max_depth = 6;
+    app_config.graphql.max_complexity = 300;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+    let (state, handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let client = GraphqlClient {
+        http: reqwest::Client::new(),
+        url: format!("http://{addr}/graphql"),
+    };
+    let writer = token(&[Role::Writer]);
+    let reader = token(&[Role::Reader]);
+    // Distinguishes this run's items from others in the shared database
+    let brand = format!("graphql-{}", Uuid::new_v4());
+
+    // Callers must be authenticated; readers can query but not mutate
+    let response = client.post(None, "{ __typename }", json!({})).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let error = client
+        .error(
+            &reader,
+            CREATE_ITEM,
+            json!({"input": item_input("Denied", &brand, "1")}),
+        )
+        .await;
+    assert_eq!(error.pointer("/extensions/code"), Some(&json!("FORBIDDEN")));
+
+    // Create
+    let mut item_ids = Vec::new();
+    for (name, price) in [("Cheap", "5"), ("Middle", "15.50"), ("Dear", "40")] {
+        let data = client
+            .data(
+                &writer,
+                CREATE_ITEM,
+                json!({"input": item_input(name, &brand, price)}),
+            )
+            .await;
+        assert_eq!(str_at(&data, "/createItem/name"), name);
+        assert_eq!(str_at(&data, "/createItem/category"), "BOOKS");
+        let item_id: Uuid = str_at(&data, "/createItem/itemId")
+            .parse()
+            .expect("item id");
+        item_ids.push(item_id);
+    }
+    let [cheap, middle, dear] = <[Uuid; 3]>::try_from(item_ids).expect("three items");
+
+    // Validation errors carry the violated fields
+    let error = client
+        .error(
+            &writer,
+            CREATE_ITEM,
+            json!({"input": item_input(" ", &brand, "-1")}),
+        )
+        .await;
+    assert_eq!(
+        error.pointer("/extensions/code"),
+        Some(&json!("BAD_USER_INPUT"))
+    );
+    let paths: Vec<_> = error
+        .pointer("/extensions/violations")
+        .and_then(Value::as_array)
+        .expect("violations")
+        .iter()
+        .filter_map(|violation| violation.get("path").and_then(Value::as_str))
+        .collect();
+    assert!(
+        paths.contains(&"/name") && paths.contains(&"/price"),
+        "{paths:?}"
+    );
+
+    for (related_item_id, kind) in [(middle, LinkKind::Accessory), (dear, LinkKind::Similar)] {
+        state
+            .related_items
+            .link(
+                cheap,
+                CreateItemLinkBody {
+                    related_item_id,
+                    kind,
+                },
+            )
+            .await
+            .expect("link")
+            .expect("item exists");
+    }
+
+    // Filtered, paginated list with related items
+    let query = r#"
+        query Items($filter: CatalogItemFilter, $limit: Int, $offset: Int) {
+            items(filter: $filter, limit: $limit, offset: $offset) {
+                items { name price related { kind item { name } } }
+                hasMore
+                pagination { limit offset }
+            }
+        }
+    "#;
+    let data = client
+        .data(
+            &reader,
+            query,
+            json!({"filter": {"brand": brand, "minPrice": "10"}, "limit": 1}),
+        )
+        .await;
+    assert_eq!(
+        data.pointer("/items/items/0"),
+        Some(&json!({"name": "Middle", "price": "15.50", "related": []}))
+    );
+    assert_eq!(data.pointer("/items/hasMore"), Some(&json!(true)));
+    assert_eq!(
+        data.pointer("/items/pagination"),
+        Some(&json!({"limit": 1, "offset": 0}))
+    );
+    let data = client
+        .data(
+            &reader,
+            query,
+            json!({"filter": {"brand": brand, "maxPrice": "20"}, "limit": 10}),
+        )
+        .await;
+    assert_eq!(
+        data.pointer("/items/items/0/related"),
+        Some(&json!([
+            {"kind": "ACCESSORY", "item": {"name": "Middle"}},
+            {"kind": "SIMILAR", "item": {"name": "Dear"}},
+        ]))
+    );
+    assert_eq!(data.pointer("/items/items/1/name"), Some(&json!("Middle")));
+    assert_eq!(data.pointer("/items/hasMore"), Some(&json!(false)));
+
+    // Items by id, with the kind of related items filtered
+    let query = r#"
+        query Item($a: UUID!, $b: UUID!, $missing: UUID!) {
+            a: item(id: $a) { name related(kind: SIMILAR) { item { itemId } } }
+            b: item(id: $b) { name }
+            missing: item(id: $missing) { name }
+        }
+    "#;
+    let data = client
+        .data(
+            &reader,
+            query,
+            json!({"a": cheap, "b": dear, "missing": Uuid::new_v4()}),
+        )
+        .await;
+    assert_eq!(
+        data,
+        json!({
+            "a": {"name": "Cheap", "related": [{"item": {"itemId": dear}}]},
+            "b": {"name": "Dear"},
+            "missing": null,
+        })
+    );
+
+    // Update
+    let update = r#"
+        mutation Update($id: UUID!, $input: UpdateCatalogItemInput!) {
+            updateItem(id: $id, input: $input) { name price }
+        }
+    "#;
+    let data = client
+        .data(
+            &writer,
+            update,
+            json!({"id": middle, "input": item_input("Middle (2nd ed)", &brand, "16")}),
+        )
+        .await;
+    assert_eq!(
+        data,
+        json!({"updateItem": {"name": "Middle (2nd ed)", "price": "16"}})
+    );
+    let data = client
+        .data(
+            &writer,
+            update,
+            json!({"id": Uuid::new_v4(), "input": item_input("Nobody", &brand, "1")}),
+        )
+        .await;
+    assert_eq!(data, json!({"updateItem": null}));
+
+    // Depth and complexity limits
+    let error = client
+        .error(
+            &reader,
+            r#"query($id: UUID!) { item(id: $id) { related { item { related { item {
+                related { item { name } } } } } } } }"#,
+            json!({"id": cheap}),
+        )
+        .await;
+    assert!(
+        str_at(&error, "/message").contains("nested too deep"),
+        "{error}"
+    );
+    let error = client
+        .error(
+            &reader,
+            "{ items(limit: 100) { items { itemId name slug price } } }",
+            json!({}),
+        )
+        .await;
+    assert!(
+        str_at(&error, "/message").contains("too complex"),
+        "{error}"
+    );
+
+    // Delete
+    let delete = "mutation Delete($id: UUID!) { deleteItem(id: $id) }";
+    for item_id in [cheap, middle, dear] {
+        let data = client.data(&writer, delete, json!({"id": item_id})).await;
+        assert_eq!(data, json!({"deleteItem": true}));
+    }
+    let data = client.data(&writer, delete, json!({"id": cheap})).await;
+    assert_eq!(data, json!({"deleteItem": false}));
+
+    state.server_shutdown.cancel();
+    handle.await.expect("server task").expect("serve");
+}
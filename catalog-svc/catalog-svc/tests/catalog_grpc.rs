@@ -0,0 +1,330 @@
+//! Integration tests for the gRPC API: CRUD, streaming list, status codes for validation, missing
+//! items and credentials, health checking (including during shutdown) and reflection.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::{AppConfig, AuthConfig};
+use catalog_svc::auth::Role;
+use catalog_svc::auth::jwt::{Claims, encode_token};
+use catalog_svc::grpc_server::proto::catalog_service_client::CatalogServiceClient;
+use catalog_svc::grpc_server::proto::{
+    Category, CreateItemRequest, DeleteItemRequest, GetItemRequest, ItemSort, ListItemsRequest,
+    StreamItemsRequest, UpdateItemRequest,
+};
+use catalog_svc::server;
+use futures::{StreamExt, TryStreamExt};
+use jsonwebtoken::{EncodingKey, Header};
+use rust_demo_commons::util::tests;
+use tonic::transport::Channel;
+use tonic::{Code, Request};
+use tonic_health::pb::HealthCheckRequest;
+use tonic_health::pb::health_check_response::ServingStatus;
+use tonic_health::pb::health_client::HealthClient;
+use tonic_reflection::pb::v1::ServerReflectionRequest;
+use tonic_reflection::pb::v1::server_reflection_client::ServerReflectionClient;
+use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use uuid::Uuid;
+
+const SECRET: &str = "catalog-grpc-test-secret";
+const SERVICE_NAME: &str = "catalog.v1.CatalogService";
+
+fn token(roles: &[Role]) -> String {
+    let claims = Claims::new("grpc-user", roles, Duration::from_secs(300));
+    encode_token(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(SECRET.as_bytes()),
+    )
+    .expect("sign token")
+}
+
+/// `message` with `token` as bearer credentials.
+fn with_token<T>(message: T, token: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request.metadata_mut().insert(
+        "authorization",
+        format!("Bearer {token}").parse().expect("metadata value"),
+    );
+    request
+}
+
+fn create_request(name: &str) -> CreateItemRequest {
+    CreateItemRequest {
+        name: name.to_string(),
+        description: "Over gRPC".to_string(),
+        category: Category::Books.into(),
+        date: "2025-04-11".to_string(),
+        brand: Some("Tonic".to_string()),
+        price: "12.50".to_string(),
+    }
+}
+
+async fn health_status(channel: Channel, service: &str) -> ServingStatus {
+    let response = HealthClient::new(channel)
+        .check(HealthCheckRequest {
+            service: service.to_string(),
+        })
+        .await
+        .expect("health check");
+    response.into_inner().status()
+}
+
+#[tokio::test]
+async fn catalog_grpc() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth = AuthConfig {
+        enabled: true,
+        hs256_secret: Some(SECRET.to_string()),
+        ..AuthConfig::default()
+    };
+    app_config.grpc.port = 0;
+    app_config.server.shutdown_delay_secs = 1;
+    let (state, _background) = server::build_app(&app_config).await;
+    let (handle, addr) = server::start_grpc_and_serve(&state, &app_config)
+        .await
+        .expect("bind");
+    let channel = Channel::from_shared(format!("http://{addr}"))
+        .expect("endpoint")
+        .connect()
+        .await
+        .expect("connect");
+    let mut client = CatalogServiceClient::new(channel.clone());
+    let writer = token(&[Role::Writer]);
+    let reader = token(&[Role::Reader]);
+
+    assert_eq!(
+        health_status(channel.clone(), SERVICE_NAME).await,
+        ServingStatus::Serving
+    );
+
+    // Reflection lists the catalog service
+    let mut reflection = ServerReflectionClient::new(channel.clone());
+    let request = ServerReflectionRequest {
+        host: String::new(),
+        message_request: Some(MessageRequest::ListServices(String::new())),
+    };
+    let mut responses = reflection
+        .server_reflection_info(futures::stream::iter([request]))
+        .await
+        .expect("reflection")
+        .into_inner();
+    let response = responses
+        .next()
+        .await
+        .expect("reflection response")
+        .expect("reflection message");
+    let services: Vec<String> = match response.message_response {
+        Some(MessageResponse::ListServicesResponse(services)) => {
+            services.service.into_iter().map(|s| s.name).collect()
+        }
+        _ => Vec::new(),
+    };
+    assert!(
+        services.iter().any(|name| name == SERVICE_NAME),
+        "{services:?}"
+    );
+
+    // Credentials and permissions are checked like on the REST API
+    let status = client
+        .get_item(GetItemRequest {
+            item_id: Uuid::new_v4().to_string(),
+        })
+        .await
+        .expect_err("unauthenticated");
+    assert_eq!(status.code(), Code::Unauthenticated);
+    let status = client
+        .create_item(with_token(create_request("Denied"), &reader))
+        .await
+        .expect_err("reader cannot create");
+    assert_eq!(status.code(), Code::PermissionDenied);
+
+    // Create
+    let created = client
+        .create_item(with_token(create_request("gRPC Book"), &writer))
+        .await
+        .expect("create")
+        .into_inner();
+    assert_eq!(created.name, "gRPC Book");
+    assert_eq!(created.category(), Category::Books);
+    assert_eq!(created.price, "12.50");
+    assert_eq!(created.brand.as_deref(), Some("Tonic"));
+    assert!(created.created_at.is_some());
+    assert_eq!(created.rating.as_ref().map(|rating| rating.count), Some(0));
+
+    // Invalid arguments, from the conversion and from the catalog service
+    let mut invalid = create_request("Invalid");
+    invalid.category = Category::Unspecified.into();
+    invalid.price = "cheap".to_string();
+    let status = client
+        .create_item(with_token(invalid, &writer))
+        .await
+        .expect_err("invalid");
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert!(status.message().contains("/category") && status.message().contains("/price"));
+    let status = client
+        .create_item(with_token(create_request(" "), &writer))
+        .await
+        .expect_err("blank name");
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert!(status.message().contains("/name"));
+
+    // Get
+    let got = client
+        .get_item(with_token(
+            GetItemRequest {
+                item_id: created.item_id.clone(),
+            },
+            &reader,
+        ))
+        .await
+        .expect("get")
+        .into_inner();
+    assert_eq!(
+        (got.item_id.as_str(), got.slug.as_str(), got.price.as_str()),
+        (created.item_id.as_str(), created.slug.as_str(), "12.50")
+    );
+    let status = client
+        .get_item(with_token(
+            GetItemRequest {
+                item_id: "not-a-uuid".to_string(),
+            },
+            &reader,
+        ))
+        .await
+        .expect_err("malformed id");
+    assert_eq!(status.code(), Code::InvalidArgument);
+    let status = client
+        .get_item(with_token(
+            GetItemRequest {
+                item_id: Uuid::new_v4().to_string(),
+            },
+            &reader,
+        ))
+        .await
+        .expect_err("missing");
+    assert_eq!(status.code(), Code::NotFound);
+
+    // Update
+    let updated = client
+        .update_item(with_token(
+            UpdateItemRequest {
+                item_id: created.item_id.clone(),
+                name: "gRPC Book (2nd ed)".to_string(),
+                description: created.description.clone(),
+                category: Category::Electronics.into(),
+                date: created.date.clone(),
+                brand: None,
+                price: "15".to_string(),
+            },
+            &writer,
+        ))
+        .await
+        .expect("update")
+        .into_inner();
+    assert_eq!(updated.name, "gRPC Book (2nd ed)");
+    assert_eq!(updated.category(), Category::Electronics);
+    assert_eq!(updated.brand, None);
+    assert_eq!(updated.price, "15");
+    let status = client
+        .update_item(with_token(
+            UpdateItemRequest {
+                item_id: Uuid::new_v4().to_string(),
+                name: "Nobody".to_string(),
+                category: Category::Books.into(),
+                date: "2025-04-11".to_string(),
+                price: "1".to_string(),
+                ..UpdateItemRequest::default()
+            },
+            &writer,
+        ))
+        .await
+        .expect_err("missing");
+    assert_eq!(status.code(), Code::NotFound);
+
+    // List and stream, across several pages
+    let mut item_ids = vec![created.item_id.clone()];
+    for name in ["gRPC Second", "gRPC Third"] {
+        let item = client
+            .create_item(with_token(create_request(name), &writer))
+            .await
+            .expect("create")
+            .into_inner();
+        item_ids.push(item.item_id);
+    }
+    let page = client
+        .list_items(with_token(
+            ListItemsRequest {
+                limit: Some(1),
+                offset: None,
+                sort: ItemSort::CreatedAt.into(),
+            },
+            &reader,
+        ))
+        .await
+        .expect("list")
+        .into_inner();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!((page.limit, page.offset), (1, 0));
+
+    let streamed: Vec<String> = client
+        .stream_items(with_token(
+            StreamItemsRequest {
+                sort: ItemSort::Unspecified.into(),
+                page_size: Some(2),
+            },
+            &reader,
+        ))
+        .await
+        .expect("stream")
+        .into_inner()
+        .map_ok(|item| item.item_id)
+        .try_collect()
+        .await
+        .expect("streamed items");
+    let positions: Vec<_> = item_ids
+        .iter()
+        .map(|item_id| streamed.iter().position(|streamed| streamed == item_id))
+        .collect();
+    assert!(positions.iter().all(Option::is_some), "all items streamed");
+    assert!(positions.is_sorted(), "oldest first");
+    let mut unique = streamed.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), streamed.len(), "no item streamed twice");
+
+    // Delete
+    for item_id in &item_ids {
+        client
+            .delete_item(with_token(
+                DeleteItemRequest {
+                    item_id: item_id.clone(),
+                },
+                &writer,
+            ))
+            .await
+            .expect("delete");
+    }
+    let status = client
+        .delete_item(with_token(
+            DeleteItemRequest {
+                item_id: created.item_id.clone(),
+            },
+            &writer,
+        ))
+        .await
+        .expect_err("already deleted");
+    assert_eq!(status.code(), Code::NotFound);
+
+    // Health checks report NOT_SERVING once shutdown starts, then the server stops
+    state.server_shutdown.cancel();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(
+        health_status(channel.clone(), SERVICE_NAME).await,
+        ServingStatus::NotServing
+    );
+    assert_eq!(health_status(channel, "").await, ServingStatus::NotServing);
+    handle.await.expect("server task").expect("serve");
+}
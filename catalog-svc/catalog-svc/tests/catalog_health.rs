@@ -0,0 +1,60 @@
+//! Integration tests for the liveness and readiness endpoints.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::server;
+use reqwest::StatusCode;
+use rust_demo_commons::util::tests;
+use serde_json::Value;
+
+async fn get(url: &str) -> (StatusCode, Value) {
+    let response = reqwest::get(url).await.expect("request");
+    let status = response.status();
+    (status, response.json().await.expect("health body"))
+}
+
+fn check_status<'a>(health: &'a Value, check: &str) -> Option<&'a str> {
+    health.pointer(&format!("/checks/{check}/status"))?.as_str()
+}
+
+#[tokio::test]
+async fn catalog_health() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+    app_config.server.shutdown_delay_secs = 5;
+
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Unauthenticated, like the metrics endpoint
+    let (status, live) = get(&format!("http://{addr}/health/live")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(live.get("status").and_then(Value::as_str), Some("up"));
+
+    let ready_url = format!("http://{addr}/health/ready");
+    let (status, ready) = get(&ready_url).await;
+    assert_eq!(status, StatusCode::OK, "{ready}");
+    assert_eq!(ready.get("status").and_then(Value::as_str), Some("up"));
+    for check in ["postgres", "migrations", "shutdown"] {
+        assert_eq!(check_status(&ready, check), Some("up"), "{check}");
+    }
+    assert!(ready.pointer("/checks/postgres/latencyMs").is_some());
+
+    // Not ready as soon as shutdown starts, while still serving during the shutdown delay
+    state.server_shutdown.cancel();
+    let (status, ready) = get(&ready_url).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(ready.get("status").and_then(Value::as_str), Some("down"));
+    assert_eq!(check_status(&ready, "shutdown"), Some("down"));
+    assert_eq!(check_status(&ready, "postgres"), Some("up"));
+
+    let (status, _) = get(&format!("http://{addr}/health/live")).await;
+    assert_eq!(status, StatusCode::OK);
+}
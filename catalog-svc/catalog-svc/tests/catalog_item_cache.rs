@@ -0,0 +1,113 @@
+//! Integration tests for the item cache: hits and misses, invalidation on writes, batch gets and
+//! cross-instance invalidation over Postgres `LISTEN/NOTIFY`.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::catalog::api::{Category, CreateCatalogItemBody, UpdateCatalogItemBody};
+use catalog_svc::server;
+use rust_decimal::Decimal;
+use rust_demo_commons::util::tests;
+use uuid::Uuid;
+
+fn sample(exposition: &str, series: &str) -> f64 {
+    exposition
+        .lines()
+        .find_map(|line| line.strip_prefix(series)?.strip_prefix(' '))
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(0.0)
+}
+
+fn create_body(name: &str) -> CreateCatalogItemBody {
+    CreateCatalogItemBody {
+        name: name.to_string(),
+        description: "Cached".to_string(),
+        category: Category::Books,
+        date: "2025-04-08".to_string(),
+        brand: None,
+        price: Decimal::new(1200, 2),
+    }
+}
+
+#[tokio::test]
+async fn catalog_item_cache() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    app_config.item_cache.enabled = true;
+    app_config.item_cache.notify = true;
+    // Two instances sharing the database, each with its own cache
+    let (first, _first_background) = server::build_app(&app_config).await;
+    let (second, _second_background) = server::build_app(&app_config).await;
+    // Let both listeners subscribe
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    let item = first
+        .catalog
+        .create(create_body("Cache me"))
+        .await
+        .expect("create");
+    let item_id = item.item_id;
+
+    // Miss, then hit
+    let before = first.metrics.render();
+    for _ in 0..2 {
+        let got = first.catalog.get(item_id).await.expect("get");
+        assert_eq!(got.map(|item| item.name).as_deref(), Some("Cache me"));
+    }
+    let after = first.metrics.render();
+    let delta = |series: &str| sample(&after, series) - sample(&before, series);
+    assert!(delta("catalog_item_cache_misses_total") >= 1.0);
+    assert!(delta("catalog_item_cache_hits_total") >= 1.0);
+
+    // Warm the other instance, then update through the first one: both see the new name
+    second.catalog.get(item_id).await.expect("get");
+    let update = UpdateCatalogItemBody {
+        name: "Cache me again".to_string(),
+        description: item.description.clone(),
+        category: item.category,
+        date: "2025-04-08".to_string(),
+        brand: None,
+        price: item.price,
+    };
+    first
+        .catalog
+        .update(item_id, update)
+        .await
+        .expect("update")
+        .expect("exists");
+    let got = first.catalog.get(item_id).await.expect("get");
+    assert_eq!(got.map(|item| item.name).as_deref(), Some("Cache me again"));
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let got = second.catalog.get(item_id).await.expect("get");
+    assert_eq!(got.map(|item| item.name).as_deref(), Some("Cache me again"));
+
+    // Batch gets combine cached and loaded items and skip unknown ids
+    let other = first
+        .catalog
+        .create(create_body("Batch"))
+        .await
+        .expect("create");
+    let items = first
+        .catalog
+        .get_many(&[item_id, other.item_id, Uuid::new_v4(), item_id])
+        .await
+        .expect("get_many");
+    assert_eq!(items.len(), 2);
+    assert_eq!(
+        items.get(&other.item_id).map(|item| item.name.as_str()),
+        Some("Batch")
+    );
+
+    // Deletes are seen by both instances
+    for id in [item_id, other.item_id] {
+        assert!(first.catalog.delete(id).await.expect("delete"));
+    }
+    assert!(first.catalog.get(item_id).await.expect("get").is_none());
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(second.catalog.get(item_id).await.expect("get").is_none());
+
+    first.server_shutdown.cancel();
+    second.server_shutdown.cancel();
+}
@@ -0,0 +1,196 @@
+//! Integration tests for the item change stream: item writes are streamed as Server-Sent Events,
+//! streams resume after `Last-Event-ID`, and end when the server shuts down.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::catalog::api::{Category, CreateCatalogItemBody, UpdateCatalogItemBody};
+use catalog_svc::server;
+use reqwest::StatusCode;
+use rust_decimal::Decimal;
+use rust_demo_commons::util::tests;
+use serde_json::Value;
+use uuid::Uuid;
+
+/// One parsed Server-Sent Event.
+#[derive(Debug, Default)]
+struct SseEvent {
+    id: Option<String>,
+    event: String,
+    data: Value,
+}
+
+/// Reads events from a `text/event-stream` response, skipping keep-alive comments.
+struct SseReader {
+    response: reqwest::Response,
+    buffer: String,
+}
+
+impl SseReader {
+    async fn open(url: &str, last_event_id: Option<&str>) -> Self {
+        let mut request = reqwest::Client::new().get(url);
+        if let Some(id) = last_event_id {
+            request = request.header("Last-Event-ID", id);
+        }
+        let response = request.send().await.expect("open stream");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response
+                .headers()
+                .get("content-type")
+                .and_then(|value| value.to_str().ok()),
+            Some("text/event-stream")
+        );
+        Self {
+            response,
+            buffer: String::new(),
+        }
+    }
+
+    /// The next event, or None once the stream ended.
+    async fn next(&mut self) -> Option<SseEvent> {
+        loop {
+            if let Some(end) = self.buffer.find("\n\n") {
+                let block: String = self.buffer.drain(..end + 2).collect();
+                if let Some(event) = parse_event(&block) {
+                    return Some(event);
+                }
+                continue;
+            }
+            let chunk = tokio::time::timeout(Duration::from_secs(10), self.response.chunk())
+                .await
+                .expect("stream stalled")
+                .expect("read stream")?;
+            self.buffer
+                .push_str(std::str::from_utf8(&chunk).expect("utf-8"));
+        }
+    }
+
+    /// The next event about `item_id` (or a reset), skipping changes made by other tests.
+    async fn next_for(&mut self, item_id: Uuid) -> Option<SseEvent> {
+        let item_id = item_id.to_string();
+        loop {
+            let event = self.next().await?;
+            if event.event == "reset"
+                || event.data.get("itemId").and_then(Value::as_str) == Some(item_id.as_str())
+            {
+                return Some(event);
+            }
+        }
+    }
+}
+
+/// Parse an event block; None for comment-only blocks (keep-alives).
+fn parse_event(block: &str) -> Option<SseEvent> {
+    let mut event = SseEvent::default();
+    let mut data = None;
+    for line in block.lines() {
+        let Some((field, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "id" => event.id = Some(value.to_string()),
+            "event" => event.event = value.to_string(),
+            "data" => data = Some(serde_json::from_str(value).expect("event data")),
+            _ => {}
+        }
+    }
+    event.data = data?;
+    Some(event)
+}
+
+#[tokio::test]
+async fn catalog_item_changes() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let url = format!("http://{addr}/catalog/items/changes");
+
+    // Streams start at the current end of the log
+    let mut live = SseReader::open(&url, None).await;
+    let catalog = &state.catalog;
+    let item = catalog
+        .create(CreateCatalogItemBody {
+            name: "Streamed".to_string(),
+            description: "Watched for changes".to_string(),
+            category: Category::Books,
+            date: "2025-04-11".to_string(),
+            brand: None,
+            price: Decimal::new(1500, 2),
+        })
+        .await
+        .expect("create");
+    let item_id = item.item_id;
+    catalog
+        .update(
+            item_id,
+            UpdateCatalogItemBody {
+                name: "Streamed again".to_string(),
+                description: item.description.clone(),
+                category: item.category,
+                date: "2025-04-11".to_string(),
+                brand: None,
+                price: item.price,
+            },
+        )
+        .await
+        .expect("update")
+        .expect("exists");
+    assert!(catalog.delete(item_id).await.expect("delete"));
+
+    let mut received = Vec::new();
+    for _ in 0..3 {
+        let event = live.next_for(item_id).await.expect("change event");
+        assert_eq!(
+            event.data.get("type").and_then(Value::as_str),
+            Some(event.event.as_str())
+        );
+        assert!(event.data.get("changedAt").is_some());
+        received.push((event.event, event.id.expect("event id")));
+    }
+    let types: Vec<_> = received.iter().map(|(event, _)| event.as_str()).collect();
+    assert_eq!(types, ["created", "updated", "deleted"]);
+
+    // A reconnecting client resumes after the last event it received
+    let (_, created_id) = received.first().expect("created");
+    let mut resumed = SseReader::open(&url, Some(created_id)).await;
+    for (expected_type, expected_id) in received.iter().skip(1) {
+        let event = resumed.next_for(item_id).await.expect("resumed event");
+        assert_eq!(
+            (&event.event, event.id.as_ref()),
+            (expected_type, Some(expected_id))
+        );
+    }
+
+    // Positions no longer in the log ask the client to resync
+    let mut stale = SseReader::open(&url, Some("1-1")).await;
+    let reset = stale.next().await.expect("reset event");
+    assert_eq!(reset.event, "reset");
+
+    let malformed = reqwest::Client::new()
+        .get(&url)
+        .header("Last-Event-ID", "not-a-position")
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(malformed.status(), StatusCode::BAD_REQUEST);
+
+    // Open streams end on shutdown, so the server stops without waiting for clients
+    state.server_shutdown.cancel();
+    assert!(live.next().await.is_none());
+    assert!(resumed.next().await.is_none());
+    tokio::time::timeout(Duration::from_secs(10), handle)
+        .await
+        .expect("server stops")
+        .expect("server task")
+        .expect("serve");
+}
@@ -0,0 +1,191 @@
+//! Integration tests for the incremental sync: watermark paging across items with the same
+//! `modified_at`, deleted item ids, rating changes, held-back recent changes, and expired or
+//! malformed watermarks.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::catalog::api::{
+    CatalogItem, Category, ItemSyncResponse, RatingSummary, SyncWatermark,
+};
+use catalog_svc::catalog::persistence::CatalogItemRepository;
+use catalog_svc::catalog::sync::ItemSync;
+use catalog_svc::reviews::api::{CreateReviewBody, ModerationState};
+use catalog_svc::server;
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::StatusCode;
+use rust_decimal::Decimal;
+use rust_demo_commons::util::tests;
+use uuid::Uuid;
+
+fn item_at(name: &str, modified_at: DateTime<Utc>) -> CatalogItem {
+    let item_id = Uuid::new_v4();
+    CatalogItem {
+        item_id,
+        name: name.to_string(),
+        slug: format!("sync-{item_id}"),
+        description: "Synced".to_string(),
+        category: Category::Books,
+        date: NaiveDate::from_ymd_opt(2025, 4, 9).expect("date"),
+        brand: None,
+        price: Decimal::new(900, 2),
+        created_at: modified_at,
+        modified_at,
+        rating: RatingSummary::empty(),
+        stock: None,
+    }
+}
+
+async fn sync(url: &str, since: Option<&str>, limit: u32) -> reqwest::Response {
+    let mut query = vec![("limit", limit.to_string())];
+    if let Some(since) = since {
+        query.push(("since", since.to_string()));
+    }
+    reqwest::Client::new()
+        .get(url)
+        .query(&query)
+        .send()
+        .await
+        .expect("request")
+}
+
+async fn sync_page(url: &str, since: Option<&str>, limit: u32) -> ItemSyncResponse {
+    let response = sync(url, since, limit).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    response.json().await.expect("sync response")
+}
+
+fn watermark(page: &ItemSyncResponse) -> SyncWatermark {
+    page.watermark.parse().expect("watermark")
+}
+
+#[tokio::test]
+async fn catalog_item_sync() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    // Every instance writing here shares this host's clock
+    app_config.item_sync.max_clock_skew_ms = 0;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, handle, addr) = server::start_service_and_serve(app_state, app_config.clone())
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let url = format!("http://{addr}/catalog/items/sync");
+
+    // Two items modified at the same instant (an hour ago, away from other tests' items)
+    let micros = Utc::now().timestamp_micros() - 3_600_000_000;
+    let tied_at = DateTime::from_timestamp_micros(micros).expect("timestamp");
+    let mut tied = [item_at("Tied A", tied_at), item_at("Tied B", tied_at)];
+    for item in &tied {
+        CatalogItemRepository::create(&state.pg_pool, item)
+            .await
+            .expect("create");
+    }
+    tied.sort_by_key(|item| item.item_id);
+
+    // Paging one change at a time returns both, in item id order, without repeating either
+    let just_before = SyncWatermark {
+        modified_at: DateTime::from_timestamp_micros(micros - 1).expect("timestamp"),
+        item_id: Uuid::nil(),
+    };
+    let mut since = just_before.to_string();
+    let mut previous = just_before;
+    for expected in &tied {
+        let page = sync_page(&url, Some(&since), 1).await;
+        let ids: Vec<_> = page.items.iter().map(|item| item.item_id).collect();
+        assert_eq!(ids, [expected.item_id]);
+        assert!(watermark(&page) > previous);
+        previous = watermark(&page);
+        since = page.watermark;
+    }
+
+    // Deleting one yields its id after the watermark; the other is not repeated
+    let [kept, deleted] = tied;
+    assert!(state.catalog.delete(deleted.item_id).await.expect("delete"));
+    let mut deleted_ids = Vec::new();
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    while !deleted_ids.contains(&deleted.item_id) {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "deletion not synced"
+        );
+        let page = sync_page(&url, Some(&since), 1000).await;
+        assert!(page.items.iter().all(|item| item.item_id != kept.item_id));
+        assert!(watermark(&page) >= previous);
+        previous = watermark(&page);
+        since = page.watermark;
+        deleted_ids.extend(page.deleted_item_ids);
+        if !page.has_more {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    // Approving a review changes the item's rating, so the item is synced again
+    let review = state
+        .reviews
+        .create(
+            kept.item_id,
+            CreateReviewBody {
+                rating: 4,
+                title: "Synced".to_string(),
+                body: "Rated after the last sync".to_string(),
+                author: "Sync".to_string(),
+            },
+        )
+        .await
+        .expect("create review")
+        .expect("item exists");
+    state
+        .reviews
+        .moderate(kept.item_id, review.review_id, ModerationState::Approved)
+        .await
+        .expect("approve review")
+        .expect("review exists");
+    let mut rated = None;
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    while rated.is_none() {
+        assert!(tokio::time::Instant::now() < deadline, "rating not synced");
+        let page = sync_page(&url, Some(&since), 1000).await;
+        rated = page
+            .items
+            .iter()
+            .find(|item| item.item_id == kept.item_id)
+            .cloned();
+        since = page.watermark;
+        if rated.is_none() && !page.has_more {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+    assert_eq!(rated.expect("synced").rating.count, 1);
+
+    // Changes younger than the clock skew are held back, and the watermark does not move
+    let mut skewed = app_config.item_sync.clone();
+    skewed.max_clock_skew_ms = 2 * 3600 * 1000;
+    let held_back = ItemSync::new(state.pg_pool.clone(), &skewed, &app_config.item_changes)
+        .sync(Some(just_before), Some(10))
+        .await
+        .expect("sync");
+    assert!(held_back.items.is_empty() && !held_back.has_more);
+    assert_eq!(held_back.watermark, just_before.to_string());
+
+    // A full sync starts without deletions
+    let full = sync_page(&url, None, 1).await;
+    assert!(full.deleted_item_ids.is_empty());
+
+    let expired = SyncWatermark {
+        modified_at: DateTime::from_timestamp_micros(946_684_800_000_000).expect("timestamp"),
+        item_id: Uuid::nil(),
+    };
+    let response = sync(&url, Some(&expired.to_string()), 10).await;
+    assert_eq!(response.status(), StatusCode::GONE);
+    let response = sync(&url, Some("not-a-watermark"), 10).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    assert!(state.catalog.delete(kept.item_id).await.expect("delete"));
+    state.server_shutdown.cancel();
+    handle.await.expect("server task").expect("serve");
+}
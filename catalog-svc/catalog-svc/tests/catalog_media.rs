@@ -0,0 +1,183 @@
+//! Integration tests for item media: real server instance (local blob store) and catalog-svc-client.
+
+use std::io::Cursor;
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::blob_store::{BlobStoreError, create_blob_store};
+use catalog_svc::server;
+use catalog_svc_client::Client;
+use catalog_svc_client::types::{Category, CreateCatalogItemBody, ReorderMediaBody};
+use image::{ImageFormat, Rgb, RgbImage};
+use rust_demo_commons::util::tests;
+
+fn encode_image(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+    let image = RgbImage::from_pixel(width, height, Rgb([200, 30, 30]));
+    let mut out = Cursor::new(Vec::new());
+    image.write_to(&mut out, format).expect("encode image");
+    out.into_inner()
+}
+
+#[tokio::test]
+async fn catalog_media_crud() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    let blob_store = create_blob_store(&app_config.media.blob_store);
+    app_config.server.port = 0;
+
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new(&format!("http://{addr}"));
+
+    let item = client
+        .create_catalog_item(&CreateCatalogItemBody {
+            name: "Camera".to_string(),
+            description: "Takes pictures".to_string(),
+            category: Category::Electronics,
+            date: "2025-03-01".to_string(),
+            brand: None,
+            price: "199.99".to_string(),
+        })
+        .await
+        .expect("create item")
+        .into_inner();
+    let item_id = item.item_id;
+
+    // Upload: content type is sniffed, dimensions recorded, appended in order
+    let png = client
+        .upload_item_media(&item_id, encode_image(640, 320, ImageFormat::Png))
+        .await
+        .expect("upload png")
+        .into_inner();
+    assert_eq!(png.content_type, "image/png");
+    assert_eq!((png.width, png.height, png.position), (640, 320, 0));
+
+    let jpeg = client
+        .upload_item_media(&item_id, encode_image(100, 100, ImageFormat::Jpeg))
+        .await
+        .expect("upload jpeg")
+        .into_inner();
+    assert_eq!(jpeg.content_type, "image/jpeg");
+    assert_eq!(jpeg.position, 1);
+
+    // Unsupported content is rejected
+    let err = client
+        .upload_item_media(&item_id, b"definitely not an image".to_vec())
+        .await
+        .expect_err("upload text should fail");
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(415));
+
+    // Thumbnail fits the configured bounding box, preserving aspect ratio
+    let thumbnail = client
+        .get_item_media_thumbnail(&item_id, &png.media_id)
+        .await
+        .expect("get thumbnail")
+        .into_inner();
+    let thumbnail = collect(thumbnail).await;
+    let thumbnail = image::load_from_memory(&thumbnail).expect("decode thumbnail");
+    assert_eq!((thumbnail.width(), thumbnail.height()), (256, 128));
+
+    let original = client
+        .get_item_media_content(&item_id, &jpeg.media_id)
+        .await
+        .expect("get content")
+        .into_inner();
+    assert_eq!(
+        image::guess_format(&collect(original).await).expect("format"),
+        ImageFormat::Jpeg
+    );
+
+    // Reorder
+    let reordered = client
+        .reorder_item_media(
+            &item_id,
+            &ReorderMediaBody {
+                media_ids: vec![jpeg.media_id, png.media_id],
+            },
+        )
+        .await
+        .expect("reorder")
+        .into_inner();
+    let ids: Vec<_> = reordered.iter().map(|m| (m.media_id, m.position)).collect();
+    assert_eq!(ids, vec![(jpeg.media_id, 0), (png.media_id, 1)]);
+
+    let err = client
+        .reorder_item_media(
+            &item_id,
+            &ReorderMediaBody {
+                media_ids: vec![jpeg.media_id],
+            },
+        )
+        .await
+        .expect_err("partial reorder should fail");
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(400));
+
+    // Delete one attachment
+    client
+        .delete_item_media(&item_id, &jpeg.media_id)
+        .await
+        .expect("delete media");
+    let listed = client
+        .list_item_media(&item_id)
+        .await
+        .expect("list media")
+        .into_inner();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed.first().map(|m| m.media_id), Some(png.media_id));
+
+    // Concurrent uploads each get their own position
+    let uploads = futures::future::join_all(
+        (0..4).map(|_| client.upload_item_media(&item_id, encode_image(10, 10, ImageFormat::Png))),
+    )
+    .await;
+    assert!(uploads.iter().all(Result::is_ok));
+    let mut positions: Vec<_> = client
+        .list_item_media(&item_id)
+        .await
+        .expect("list media")
+        .into_inner()
+        .iter()
+        .map(|m| m.position)
+        .collect();
+    positions.sort_unstable();
+    assert_eq!(positions, vec![1, 2, 3, 4, 5]);
+
+    // Deleting the item removes its media, blobs included
+    let marker = format!("items/{item_id}/marker");
+    blob_store
+        .put(&marker, "text/plain", "marker".into())
+        .await
+        .expect("put marker");
+    client
+        .delete_catalog_item(&item_id)
+        .await
+        .expect("delete item");
+    let err = client
+        .list_item_media(&item_id)
+        .await
+        .expect_err("list after item delete");
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(404));
+    assert!(matches!(
+        blob_store.get(&marker).await,
+        Err(BlobStoreError::NotFound(_))
+    ));
+
+    state.server_shutdown.cancel();
+}
+
+async fn collect(stream: catalog_svc_client::ByteStream) -> Vec<u8> {
+    use futures::TryStreamExt;
+
+    stream
+        .into_inner()
+        .map_ok(|chunk| chunk.to_vec())
+        .try_concat()
+        .await
+        .expect("read body")
+}
@@ -0,0 +1,94 @@
+//! Integration tests for the Prometheus `/metrics` endpoint.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::server;
+use catalog_svc_client::Client;
+use catalog_svc_client::types::{Category, CreateCatalogItemBody};
+use rust_demo_commons::util::tests;
+
+/// Value of the sample `series` (metric name with labels, as rendered) in a text exposition.
+fn sample(exposition: &str, series: &str) -> Option<f64> {
+    exposition
+        .lines()
+        .find_map(|line| line.strip_prefix(series)?.strip_prefix(' '))
+        .and_then(|value| value.trim().parse().ok())
+}
+
+#[tokio::test]
+async fn catalog_metrics() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let base_url = format!("http://{addr}");
+    let client = Client::new(&base_url);
+    let body = CreateCatalogItemBody {
+        name: "Metered".to_string(),
+        description: "Counted on /metrics".to_string(),
+        category: Category::Electronics,
+        date: "2025-04-06".to_string(),
+        brand: None,
+        price: "5.00".to_string(),
+    };
+    let item = client
+        .create_catalog_item(&body)
+        .await
+        .expect("create")
+        .into_inner();
+    client.get_catalog_item(&item.item_id).await.expect("get");
+    client
+        .delete_catalog_item(&item.item_id)
+        .await
+        .expect("delete");
+    let missing = client.get_catalog_item(&item.item_id).await;
+    assert_eq!(
+        missing
+            .err()
+            .and_then(|err| err.status())
+            .map(|s| s.as_u16()),
+        Some(404)
+    );
+
+    let response = reqwest::get(format!("{base_url}/metrics"))
+        .await
+        .expect("scrape");
+    assert!(response.status().is_success());
+    let exposition = response.text().await.expect("exposition");
+
+    // Per-route request counts and latency, labelled with the route template
+    let route = "/catalog/items/{item_id}";
+    for (method, status) in [("GET", "200"), ("GET", "404"), ("DELETE", "204")] {
+        let series = format!(
+            r#"http_server_requests_total{{method="{method}",route="{route}",status="{status}"}}"#
+        );
+        assert_eq!(sample(&exposition, &series), Some(1.0), "{series}");
+    }
+    assert!(exposition.contains(
+        r#"http_server_request_duration_seconds_bucket{method="POST",route="/catalog/items",status="201",le="+Inf"} 1"#
+    ));
+
+    // Domain counters and database gauges
+    assert_eq!(
+        sample(&exposition, "catalog_items_created_total"),
+        Some(1.0)
+    );
+    assert_eq!(
+        sample(&exposition, "catalog_items_deleted_total"),
+        Some(1.0)
+    );
+    assert!(sample(&exposition, r#"db_pool_connections{state="idle"}"#).is_some());
+    assert_eq!(sample(&exposition, "db_pool_max_connections"), Some(10.0));
+    assert!(sample(&exposition, "db_migration_version").is_some_and(|v| v >= 20250405120000.0));
+
+    state.server_shutdown.cancel();
+}
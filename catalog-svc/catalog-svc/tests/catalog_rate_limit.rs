@@ -0,0 +1,152 @@
+//! Integration tests for per-client and per-IP rate limiting of the API routes.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::{AppConfig, AuthConfig, RateLimitConfig};
+use catalog_svc::auth::Role;
+use catalog_svc::auth::jwt::{Claims, encode_token};
+use catalog_svc::server;
+use jsonwebtoken::{EncodingKey, Header};
+use reqwest::StatusCode;
+use rust_demo_commons::util::tests;
+
+const SECRET: &str = "catalog-rate-limit-test-secret";
+
+fn token(subject: &str) -> String {
+    encode_token(
+        &Header::default(),
+        &Claims::new(subject, &[Role::Reader], Duration::from_secs(300)),
+        &EncodingKey::from_secret(SECRET.as_bytes()),
+    )
+    .expect("sign token")
+}
+
+fn header(response: &reqwest::Response, name: &str) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+#[tokio::test]
+async fn catalog_rate_limit() {
+    tests::init_logging();
+
+    // Rates and bursts the limiter cannot work with are rejected when the config loads
+    for invalid in [
+        RateLimitConfig {
+            requests_per_second: 0.0,
+            ..RateLimitConfig::default()
+        },
+        RateLimitConfig {
+            ip_requests_per_second: f64::NAN,
+            ..RateLimitConfig::default()
+        },
+        RateLimitConfig {
+            burst: 0,
+            ..RateLimitConfig::default()
+        },
+    ] {
+        assert!(invalid.validate().is_err(), "{invalid:?}");
+    }
+    assert!(RateLimitConfig::default().validate().is_ok());
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth = AuthConfig {
+        enabled: true,
+        hs256_secret: Some(SECRET.to_string()),
+        ..AuthConfig::default()
+    };
+    app_config.rate_limit = RateLimitConfig {
+        enabled: true,
+        requests_per_second: 0.5,
+        burst: 3,
+        ip_requests_per_second: 0.1,
+        ip_burst: 6,
+    };
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let url = format!("http://{addr}/catalog/items?limit=1");
+    let http = reqwest::Client::new();
+    let alice = token("alice");
+    let bob = token("bob");
+
+    // The burst is served, with the remaining budget on every response
+    for expected_remaining in ["2", "1", "0"] {
+        let response = http
+            .get(&url)
+            .bearer_auth(&alice)
+            .send()
+            .await
+            .expect("request");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(header(&response, "x-ratelimit-limit").as_deref(), Some("3"));
+        assert_eq!(
+            header(&response, "x-ratelimit-remaining").as_deref(),
+            Some(expected_remaining)
+        );
+        assert!(header(&response, "x-ratelimit-reset").is_some());
+    }
+
+    // Then the client is throttled until a token is refilled
+    let throttled = http
+        .get(&url)
+        .bearer_auth(&alice)
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(throttled.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&throttled, "retry-after").as_deref(), Some("2"));
+    assert_eq!(
+        header(&throttled, "content-type").as_deref(),
+        Some("application/problem+json")
+    );
+    let problem: serde_json::Value = throttled.json().await.expect("problem body");
+    assert_eq!(
+        problem.get("status").and_then(serde_json::Value::as_u64),
+        Some(429)
+    );
+
+    // Other principals have their own bucket
+    let response = http
+        .get(&url)
+        .bearer_auth(&bob)
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        header(&response, "x-ratelimit-remaining").as_deref(),
+        Some("2")
+    );
+
+    // Unauthenticated requests are rejected before they reach the per-client limiter
+    let anonymous = http.get(&url).send().await.expect("request");
+    assert_eq!(anonymous.status(), StatusCode::UNAUTHORIZED);
+
+    // ... but every request counts against the client IP, so guessing credentials is throttled
+    let guessed = http
+        .get(&url)
+        .bearer_auth("not-a-token")
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(guessed.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&guessed, "x-ratelimit-limit").as_deref(), Some("6"));
+    let response = http
+        .get(&url)
+        .bearer_auth(&bob)
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    state.server_shutdown.cancel();
+}
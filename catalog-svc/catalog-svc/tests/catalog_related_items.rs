@@ -0,0 +1,196 @@
+//! Integration tests for related items: real server instance and catalog-svc-client.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::related_items::api::{
+    CreateItemLinkBody as ServiceLinkBody, LinkKind as ServiceLinkKind, RelatedItemsServiceError,
+};
+use catalog_svc::server;
+use catalog_svc_client::Client;
+use catalog_svc_client::types::{Category, CreateCatalogItemBody, CreateItemLinkBody, LinkKind};
+use rust_demo_commons::util::tests;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn create_item(client: &Client, name: &str) -> Uuid {
+    let body = CreateCatalogItemBody {
+        name: name.to_string(),
+        description: format!("{name} description"),
+        category: Category::Electronics,
+        date: "2025-03-01".to_string(),
+        brand: None,
+        price: "10.00".to_string(),
+    };
+    client
+        .create_catalog_item(&body)
+        .await
+        .expect("create should succeed")
+        .item_id
+}
+
+/// Wait until a statement of another session is blocked on a lock held by backend `pid`.
+async fn wait_until_blocked_by(pool: &PgPool, pid: i32) {
+    let blocked = async {
+        loop {
+            let blocked: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM pg_stat_activity WHERE $1 = ANY(pg_blocking_pids(pid)))",
+            )
+            .bind(pid)
+            .fetch_one(pool)
+            .await
+            .expect("query blocked sessions");
+            if blocked {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(10), blocked)
+        .await
+        .expect("no statement waited for the lock");
+}
+
+#[tokio::test]
+async fn catalog_related_items() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new(&format!("http://{addr}"));
+
+    let phone = create_item(&client, "Phone").await;
+    let case = create_item(&client, "Phone case").await;
+    let charger = create_item(&client, "Charger").await;
+
+    // Link
+    let link = |related_item_id, kind| CreateItemLinkBody {
+        related_item_id,
+        kind,
+    };
+    let created = client
+        .create_item_link(&phone, &link(case, LinkKind::Accessory))
+        .await
+        .expect("link case")
+        .into_inner();
+    assert_eq!(created.item_id, phone);
+    assert_eq!(created.related_item_id, case);
+    client
+        .create_item_link(&phone, &link(charger, LinkKind::Accessory))
+        .await
+        .expect("link charger");
+    client
+        .create_item_link(&phone, &link(charger, LinkKind::BundleMember))
+        .await
+        .expect("same items, different kind");
+
+    // Duplicates and self-links are rejected
+    let err = client
+        .create_item_link(&phone, &link(case, LinkKind::Accessory))
+        .await
+        .expect_err("duplicate link");
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(409));
+    let err = client
+        .create_item_link(&phone, &link(phone, LinkKind::Similar))
+        .await
+        .expect_err("self link");
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(400));
+    let err = client
+        .create_item_link(&phone, &link(Uuid::new_v4(), LinkKind::Similar))
+        .await
+        .expect_err("unknown related item");
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(400));
+
+    // Query, with and without kind filter
+    let all = client
+        .list_related_items(&phone, None)
+        .await
+        .expect("list related")
+        .into_inner();
+    assert_eq!(all.len(), 3);
+    let bundle = client
+        .list_related_items(&phone, Some(LinkKind::BundleMember))
+        .await
+        .expect("list bundle members")
+        .into_inner();
+    let bundle: Vec<_> = bundle.iter().map(|r| r.item.item_id).collect();
+    assert_eq!(bundle, vec![charger]);
+
+    // Links are directed
+    let reverse = client
+        .list_related_items(&case, None)
+        .await
+        .expect("list reverse")
+        .into_inner();
+    assert!(reverse.is_empty());
+
+    // Unlink one kind
+    client
+        .delete_item_link(&phone, &charger, Some(LinkKind::BundleMember))
+        .await
+        .expect("unlink bundle member");
+    let err = client
+        .delete_item_link(&phone, &charger, Some(LinkKind::BundleMember))
+        .await
+        .expect_err("unlink twice");
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(404));
+
+    // Deleting an item removes links pointing at it
+    client
+        .delete_catalog_item(&case)
+        .await
+        .expect("delete case");
+    let remaining = client
+        .list_related_items(&phone, None)
+        .await
+        .expect("list after delete")
+        .into_inner();
+    let remaining: Vec<_> = remaining.iter().map(|r| (r.item.item_id, r.kind)).collect();
+    assert_eq!(remaining, vec![(charger, LinkKind::Accessory)]);
+
+    // An item deleted between the existence checks and the insert is still a validation error:
+    // the link's foreign key check waits for the deleting transaction
+    let cable = create_item(&client, "Cable").await;
+    let mut tx = state.pg_pool.begin().await.expect("begin");
+    let tx_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut *tx)
+        .await
+        .expect("backend pid");
+    sqlx::query("DELETE FROM catalog_items WHERE item_id = $1")
+        .bind(cable)
+        .execute(&mut *tx)
+        .await
+        .expect("delete cable");
+    let related_items = state.related_items.clone();
+    let linking = tokio::spawn(async move {
+        related_items
+            .link(
+                phone,
+                ServiceLinkBody {
+                    related_item_id: cable,
+                    kind: ServiceLinkKind::Accessory,
+                },
+            )
+            .await
+    });
+    wait_until_blocked_by(&state.pg_pool, tx_pid).await;
+    tx.commit().await.expect("commit");
+    let err = linking
+        .await
+        .expect("join")
+        .expect_err("link to deleted item");
+    assert!(
+        matches!(err, RelatedItemsServiceError::ValidationError(_)),
+        "{err}"
+    );
+
+    state.server_shutdown.cancel();
+}
@@ -0,0 +1,82 @@
+//! Integration tests for `X-Request-Id`: accepted or generated, echoed in responses and error
+//! bodies.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::server;
+use reqwest::StatusCode;
+use rust_demo_commons::util::tests;
+use uuid::Uuid;
+
+fn request_id(response: &reqwest::Response) -> Option<String> {
+    response
+        .headers()
+        .get("x-request-id")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+async fn problem_request_id(response: reqwest::Response) -> Option<String> {
+    let problem: serde_json::Value = response.json().await.expect("problem body");
+    problem
+        .get("requestId")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string)
+}
+
+#[tokio::test]
+async fn catalog_request_id() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let http = reqwest::Client::new();
+    let missing = format!("http://{addr}/catalog/items/{}", Uuid::new_v4());
+
+    // The caller's id is echoed in the response and the error body
+    let response = http
+        .get(&missing)
+        .header("x-request-id", "checkout-42")
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(request_id(&response).as_deref(), Some("checkout-42"));
+    assert_eq!(
+        problem_request_id(response).await.as_deref(),
+        Some("checkout-42")
+    );
+
+    // Without one (or with an unusable one) an id is generated
+    for header in [None, Some("x".repeat(200))] {
+        let mut request = http.get(&missing);
+        if let Some(header) = header {
+            request = request.header("x-request-id", header);
+        }
+        let response = request.send().await.expect("request");
+        let generated = request_id(&response).expect("generated id");
+        assert!(Uuid::parse_str(&generated).is_ok(), "{generated}");
+        assert_eq!(problem_request_id(response).await, Some(generated));
+    }
+
+    // Successful responses carry it too
+    let response = http
+        .get(format!("http://{addr}/catalog/items?limit=1"))
+        .header("x-request-id", "list-1")
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(request_id(&response).as_deref(), Some("list-1"));
+
+    state.server_shutdown.cancel();
+}
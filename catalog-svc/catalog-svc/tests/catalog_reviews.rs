@@ -0,0 +1,193 @@
+//! Integration tests for item reviews and rating aggregates: real server instance and catalog-svc-client.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::server;
+use catalog_svc_client::Client;
+use catalog_svc_client::types::{
+    CatalogItemSort, Category, CreateCatalogItemBody, CreateReviewBody, ModerateReviewBody,
+    ModerationState,
+};
+use rust_demo_commons::util::tests;
+use uuid::Uuid;
+
+async fn create_item(client: &Client, name: &str) -> Uuid {
+    let body = CreateCatalogItemBody {
+        name: name.to_string(),
+        description: format!("{name} description"),
+        category: Category::Books,
+        date: "2025-03-01".to_string(),
+        brand: None,
+        price: "10.00".to_string(),
+    };
+    client
+        .create_catalog_item(&body)
+        .await
+        .expect("create should succeed")
+        .item_id
+}
+
+async fn submit_review(client: &Client, item_id: Uuid, rating: i32, approve: bool) -> Uuid {
+    let body = CreateReviewBody {
+        rating,
+        title: format!("{rating} stars"),
+        body: "Review body".to_string(),
+        author: "Reviewer".to_string(),
+    };
+    let review = client
+        .create_review(&item_id, &body)
+        .await
+        .expect("create review")
+        .into_inner();
+    assert_eq!(review.moderation_state, ModerationState::Pending);
+    if approve {
+        let moderated = client
+            .moderate_review(
+                &item_id,
+                &review.review_id,
+                &ModerateReviewBody {
+                    moderation_state: ModerationState::Approved,
+                },
+            )
+            .await
+            .expect("approve review")
+            .into_inner();
+        assert_eq!(moderated.moderation_state, ModerationState::Approved);
+    }
+    review.review_id
+}
+
+#[tokio::test]
+async fn catalog_reviews() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new(&format!("http://{addr}"));
+
+    let book = create_item(&client, "Good book").await;
+    let created = client
+        .get_catalog_item(&book)
+        .await
+        .expect("get")
+        .into_inner();
+    assert_eq!(created.rating.count, 0);
+    assert_eq!(created.rating.average, None);
+    assert_eq!(created.rating.histogram, vec![0, 0, 0, 0, 0]);
+
+    // Pending reviews do not count towards the rating
+    let pending = submit_review(&client, book, 1, false).await;
+    submit_review(&client, book, 5, true).await;
+    let four = submit_review(&client, book, 4, true).await;
+    let rating = client
+        .get_catalog_item(&book)
+        .await
+        .expect("get")
+        .into_inner()
+        .rating;
+    assert_eq!(rating.count, 2);
+    assert_eq!(rating.average.as_deref(), Some("4.50"));
+    assert_eq!(rating.histogram, vec![0, 0, 0, 1, 1]);
+
+    // Listing defaults to approved reviews; pending ones are listed on request
+    let approved = client
+        .list_reviews(&book, None, None, None)
+        .await
+        .expect("list approved")
+        .into_inner();
+    assert_eq!(approved.reviews.len(), 2);
+    let queued = client
+        .list_reviews(&book, None, Some(ModerationState::Pending), None)
+        .await
+        .expect("list pending")
+        .into_inner();
+    let queued: Vec<Uuid> = queued.reviews.iter().map(|r| r.review_id).collect();
+    assert_eq!(queued, vec![pending]);
+
+    // Rejecting and deleting approved reviews updates the aggregate
+    client
+        .moderate_review(
+            &book,
+            &four,
+            &ModerateReviewBody {
+                moderation_state: ModerationState::Rejected,
+            },
+        )
+        .await
+        .expect("reject");
+    let rating = client
+        .get_catalog_item(&book)
+        .await
+        .expect("get")
+        .into_inner()
+        .rating;
+    assert_eq!(rating.count, 1);
+    assert_eq!(rating.average.as_deref(), Some("5.00"));
+    assert_eq!(rating.histogram, vec![0, 0, 0, 0, 1]);
+
+    let got = client
+        .get_review(&book, &four)
+        .await
+        .expect("get review")
+        .into_inner();
+    assert_eq!(got.moderation_state, ModerationState::Rejected);
+    client
+        .delete_review(&book, &four)
+        .await
+        .expect("delete review");
+    let err = client
+        .get_review(&book, &four)
+        .await
+        .expect_err("deleted review");
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(404));
+
+    // Validation and unknown items
+    let invalid = CreateReviewBody {
+        rating: 0,
+        title: "Zero".to_string(),
+        body: String::new(),
+        author: "Reviewer".to_string(),
+    };
+    let err = client
+        .create_review(&book, &invalid)
+        .await
+        .expect_err("rating out of range");
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(400));
+    let valid = CreateReviewBody {
+        rating: 3,
+        ..invalid
+    };
+    let err = client
+        .create_review(&Uuid::new_v4(), &valid)
+        .await
+        .expect_err("unknown item");
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(404));
+
+    // Sorting by rating puts the best rated item first
+    let great = create_item(&client, "Great book").await;
+    submit_review(&client, great, 5, true).await;
+    submit_review(&client, great, 5, true).await;
+    let page = client
+        .list_catalog_items(Some(100), None, Some(CatalogItemSort::Rating))
+        .await
+        .expect("list sorted by rating")
+        .into_inner();
+    let rated: Vec<Uuid> = page
+        .items
+        .iter()
+        .filter(|item| item.item_id == book || item.item_id == great)
+        .map(|item| item.item_id)
+        .collect();
+    assert_eq!(rated, vec![great, book]);
+
+    state.server_shutdown.cancel();
+}
@@ -0,0 +1,129 @@
+//! Integration tests for item slugs and slug lookups: real server instance and catalog-svc-client.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::server;
+use catalog_svc_client::Client;
+use catalog_svc_client::types::{
+    CatalogItem, Category, CreateCatalogItemBody, UpdateCatalogItemBody,
+};
+use rust_demo_commons::util::tests;
+use uuid::Uuid;
+
+async fn create_item(client: &Client, name: &str) -> CatalogItem {
+    let body = CreateCatalogItemBody {
+        name: name.to_string(),
+        description: format!("{name} description"),
+        category: Category::Electronics,
+        date: "2025-03-01".to_string(),
+        brand: None,
+        price: "10.00".to_string(),
+    };
+    client
+        .create_catalog_item(&body)
+        .await
+        .expect("create should succeed")
+        .into_inner()
+}
+
+#[tokio::test]
+async fn catalog_slugs() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let base_url = format!("http://{addr}");
+    let client = Client::new(&base_url);
+    // Names are made unique per run since slugs stay reserved across test runs.
+    let run = Uuid::new_v4().simple().to_string();
+
+    // Unicode is transliterated, duplicates get a suffix
+    let torch = create_item(&client, &format!("Crème Brûlée Torch! {run}")).await;
+    assert_eq!(torch.slug, format!("creme-brulee-torch-{run}"));
+    let twin = create_item(&client, &format!("crème brûlée torch {run}")).await;
+    assert_eq!(twin.slug, format!("creme-brulee-torch-{run}-2"));
+    let cyrillic = create_item(&client, &format!("Чайник {run}")).await;
+    assert_eq!(cyrillic.slug, format!("chainik-{run}"));
+
+    let found = client
+        .get_catalog_item_by_slug(&torch.slug)
+        .await
+        .expect("lookup by slug")
+        .into_inner();
+    assert_eq!(found.item_id, torch.item_id);
+
+    // Renaming assigns a new slug; updates that keep the name keep the slug
+    let update = |name: String| UpdateCatalogItemBody {
+        name,
+        description: "Kitchen torch".to_string(),
+        category: Category::Electronics,
+        date: "2025-03-01".to_string(),
+        brand: None,
+        price: "12.00".to_string(),
+    };
+    let renamed = client
+        .update_catalog_item(&torch.item_id, &update(format!("Kitchen Torch {run}")))
+        .await
+        .expect("rename")
+        .into_inner();
+    assert_eq!(renamed.slug, format!("kitchen-torch-{run}"));
+    let repriced = client
+        .update_catalog_item(&torch.item_id, &update(format!("Kitchen Torch {run}")))
+        .await
+        .expect("update without rename")
+        .into_inner();
+    assert_eq!(repriced.slug, renamed.slug);
+
+    // The old slug redirects permanently to the canonical one and stays reserved
+    let no_redirects = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("http client");
+    let response = no_redirects
+        .get(format!("{base_url}/catalog/items/by-slug/{}", torch.slug))
+        .send()
+        .await
+        .expect("old slug request");
+    assert_eq!(response.status().as_u16(), 301);
+    assert_eq!(
+        response
+            .headers()
+            .get("location")
+            .and_then(|v| v.to_str().ok()),
+        Some(format!("/catalog/items/by-slug/{}", renamed.slug).as_str())
+    );
+    let followed = client
+        .get_catalog_item_by_slug(&torch.slug)
+        .await
+        .expect("redirect is followed")
+        .into_inner();
+    assert_eq!(followed.item_id, torch.item_id);
+    assert_eq!(followed.slug, renamed.slug);
+    let third = create_item(&client, &format!("Crème Brûlée Torch {run}")).await;
+    assert_eq!(third.slug, format!("creme-brulee-torch-{run}-3"));
+
+    // Renaming back reuses the item's previous slug
+    let restored = client
+        .update_catalog_item(&torch.item_id, &update(format!("Creme Brulee Torch {run}")))
+        .await
+        .expect("rename back")
+        .into_inner();
+    assert_eq!(restored.slug, torch.slug);
+
+    let err = client
+        .get_catalog_item_by_slug(&format!("missing-{run}"))
+        .await
+        .expect_err("unknown slug");
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(404));
+
+    state.server_shutdown.cancel();
+}
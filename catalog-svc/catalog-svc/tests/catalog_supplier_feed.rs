@@ -0,0 +1,212 @@
+//! Integration tests for the supplier feed: messages update price and stock once per message id,
+//! unusable messages are rejected as poison, and the consumer stops on `server_shutdown`.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::catalog::api::{CatalogItem, Category, CreateCatalogItemBody};
+use catalog_svc::server;
+use catalog_svc::supplier_feed::consumer::SupplierFeedConsumer;
+use catalog_svc::supplier_feed::{FeedError, Processed, apply_message};
+use rust_decimal::Decimal;
+use rust_demo_commons::util::tests;
+use serde_json::json;
+use sqlx::PgPool;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+fn payload(value: serde_json::Value) -> Vec<u8> {
+    serde_json::to_vec(&value).expect("serialize")
+}
+
+fn applied(result: Result<Processed, FeedError>) -> Option<Box<CatalogItem>> {
+    match result {
+        Ok(Processed::Applied(item)) => Some(item),
+        _ => None,
+    }
+}
+
+#[tokio::test]
+async fn catalog_supplier_feed() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app, _background) = server::build_app(&app_config).await;
+    let catalog = &app.catalog;
+
+    let item = catalog
+        .create(CreateCatalogItemBody {
+            name: "Supplied".to_string(),
+            description: "From the feed".to_string(),
+            category: Category::Books,
+            date: "2025-04-10".to_string(),
+            brand: None,
+            price: Decimal::new(1000, 2),
+        })
+        .await
+        .expect("create");
+    assert_eq!(item.stock, None);
+    // Cache the item so the update must invalidate it
+    catalog.get(item.item_id).await.expect("get");
+
+    let message_id = Uuid::new_v4().to_string();
+    let update = payload(json!({
+        "messageId": message_id,
+        "itemId": item.item_id,
+        "price": "8.50",
+        "stock": 7,
+    }));
+    let updated = applied(apply_message(catalog, Some(&update)).await).expect("update applied");
+    assert_eq!(updated.price, Decimal::new(850, 2));
+    assert_eq!(updated.stock, Some(7));
+    let got = catalog
+        .get(item.item_id)
+        .await
+        .expect("get")
+        .expect("exists");
+    assert_eq!((got.price, got.stock), (Decimal::new(850, 2), Some(7)));
+
+    // A redelivery is skipped, even if its content differs
+    let redelivered = payload(json!({
+        "messageId": message_id,
+        "itemId": item.item_id,
+        "stock": 1,
+    }));
+    assert!(matches!(
+        apply_message(catalog, Some(&redelivered)).await,
+        Ok(Processed::Duplicate)
+    ));
+    // Stock-only updates keep the price
+    let restock = payload(json!({
+        "messageId": Uuid::new_v4().to_string(),
+        "itemId": item.item_id,
+        "stock": 20,
+    }));
+    let restocked = applied(apply_message(catalog, Some(&restock)).await).expect("restock applied");
+    assert_eq!(
+        (restocked.price, restocked.stock),
+        (Decimal::new(850, 2), Some(20))
+    );
+
+    // Poison messages
+    let unknown_item = payload(json!({
+        "messageId": Uuid::new_v4().to_string(),
+        "itemId": Uuid::new_v4(),
+        "price": "1.00",
+    }));
+    let poison = [
+        None,
+        Some(b"not json".to_vec()),
+        Some(payload(json!({"itemId": item.item_id, "price": "1.00"}))),
+        Some(payload(
+            json!({"messageId": "", "itemId": item.item_id, "price": "1.00"}),
+        )),
+        Some(payload(
+            json!({"messageId": Uuid::new_v4().to_string(), "itemId": item.item_id}),
+        )),
+        Some(payload(json!({
+            "messageId": Uuid::new_v4().to_string(),
+            "itemId": item.item_id,
+            "price": "-1.00",
+        }))),
+        Some(unknown_item.clone()),
+    ];
+    for message in poison {
+        let result = apply_message(catalog, message.as_deref()).await;
+        assert!(
+            matches!(result, Err(FeedError::Poison(_))),
+            "expected poison: {result:?}"
+        );
+    }
+    // Rejected messages are not recorded, so a replay from the dead-letter topic is still
+    // rejected as poison rather than skipped as a duplicate
+    assert!(matches!(
+        apply_message(catalog, Some(&unknown_item)).await,
+        Err(FeedError::Poison(_))
+    ));
+
+    // An edit holding the item when an update arrives is kept: the update waits for it and then
+    // reads the edited item
+    let mut edit = catalog.pg_pool().begin().await.expect("begin");
+    sqlx::query("SELECT 1 FROM catalog_items WHERE item_id = $1 FOR UPDATE")
+        .bind(item.item_id)
+        .execute(&mut *edit)
+        .await
+        .expect("lock item");
+    let edit_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut *edit)
+        .await
+        .expect("backend pid");
+    let reprice = payload(json!({
+        "messageId": Uuid::new_v4().to_string(),
+        "itemId": item.item_id,
+        "price": "9.00",
+    }));
+    let feed_catalog = catalog.clone();
+    let applying = tokio::spawn(async move { apply_message(&feed_catalog, Some(&reprice)).await });
+    wait_until_blocked_by(catalog.pg_pool(), edit_pid).await;
+    sqlx::query("UPDATE catalog_items SET name = 'Renamed' WHERE item_id = $1")
+        .bind(item.item_id)
+        .execute(&mut *edit)
+        .await
+        .expect("rename");
+    edit.commit().await.expect("commit");
+    let repriced = applied(applying.await.expect("join")).expect("reprice applied");
+    assert_eq!(
+        (repriced.name.as_str(), repriced.price),
+        ("Renamed", Decimal::new(900, 2))
+    );
+
+    assert!(catalog.delete(item.item_id).await.expect("delete"));
+    app.server_shutdown.cancel();
+}
+
+/// Wait until a statement of another session is blocked on a lock held by backend `pid`.
+async fn wait_until_blocked_by(pool: &PgPool, pid: i32) {
+    let blocked = async {
+        loop {
+            let blocked: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM pg_stat_activity WHERE $1 = ANY(pg_blocking_pids(pid)))",
+            )
+            .bind(pid)
+            .fetch_one(pool)
+            .await
+            .expect("query blocked sessions");
+            if blocked {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(10), blocked)
+        .await
+        .expect("no statement waited for the lock");
+}
+
+#[tokio::test]
+async fn supplier_feed_consumer_stops_on_shutdown() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app, _background) = server::build_app(&app_config).await;
+    // Nothing listens here: the consumer keeps waiting for messages until shut down
+    let consumer = SupplierFeedConsumer::new(
+        "127.0.0.1:1",
+        &app_config.supplier_feed,
+        app.catalog.clone(),
+    )
+    .expect("consumer");
+    let shutdown = CancellationToken::new();
+    let handle = consumer.spawn(shutdown.clone());
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(!handle.is_finished());
+
+    shutdown.cancel();
+    tokio::time::timeout(Duration::from_secs(10), handle)
+        .await
+        .expect("consumer stops")
+        .expect("consumer task");
+    app.server_shutdown.cancel();
+}
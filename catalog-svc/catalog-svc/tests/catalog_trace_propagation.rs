@@ -0,0 +1,85 @@
+//! Integration tests for trace-context propagation without an OTLP exporter: the caller's
+//! `traceparent` reaches the headers of the events a write records.
+
+use std::sync::Arc;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::catalog::api::{Category, CreateCatalogItemBody};
+use catalog_svc::events::relay::OutboxRelay;
+use catalog_svc::events::sink::InMemoryEventSink;
+use catalog_svc::server;
+use rust_decimal::Decimal;
+use rust_demo_commons::util::tracing::{
+    TracingSettings, extract_trace_context, init_tracing_with, trace_context_headers,
+};
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+#[tokio::test]
+async fn catalog_trace_propagation() {
+    let _guard = init_tracing_with(&TracingSettings::default()).expect("tracing");
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app, _background) = server::build_app(&app_config).await;
+    let catalog = app.catalog.clone().with_outbox(true);
+
+    // A write continuing the caller's trace records it with its events
+    let mut headers = axum::http::HeaderMap::new();
+    headers.insert(
+        "traceparent",
+        axum::http::HeaderValue::from_static(TRACEPARENT),
+    );
+    let span = tracing::info_span!("request");
+    span.set_parent(extract_trace_context(&headers))
+        .expect("parent");
+    let item = catalog
+        .create(CreateCatalogItemBody {
+            name: "Traced event".to_string(),
+            description: "Written with a traceparent".to_string(),
+            category: Category::Books,
+            date: "2025-04-07".to_string(),
+            brand: None,
+            price: Decimal::new(700, 2),
+        })
+        .instrument(span)
+        .await
+        .expect("create");
+    assert!(catalog.delete(item.item_id).await.expect("delete"));
+
+    let sink = Arc::new(InMemoryEventSink::new());
+    let relay = OutboxRelay::new(app.pg_pool.clone(), sink.clone(), &app_config.events);
+    while relay.relay_batch().await.expect("relay") > 0 {}
+    let messages = sink.messages();
+    let traceparents: Vec<_> = messages
+        .iter()
+        .filter(|message| message.item_id == item.item_id)
+        .map(|message| {
+            message
+                .headers
+                .iter()
+                .find(|(key, _)| key == "traceparent")
+                .map(|(_, value)| value.clone())
+        })
+        .collect();
+    assert_eq!(traceparents.len(), 2);
+    let created = traceparents
+        .first()
+        .cloned()
+        .flatten()
+        .expect("created event traceparent");
+    assert!(created.starts_with(&format!("00-{TRACE_ID}-")));
+    assert!(created.ends_with("-01"), "caller's sampling decision kept");
+
+    // Traces started here get ids, not sampled without an exporter
+    let root = tracing::info_span!("root");
+    let headers = root.in_scope(trace_context_headers);
+    let traceparent = headers.get("traceparent").expect("traceparent");
+    assert!(!traceparent.contains(TRACE_ID));
+    assert!(traceparent.ends_with("-00"));
+
+    app.server_shutdown.cancel();
+}
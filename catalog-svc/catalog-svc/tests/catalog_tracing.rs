@@ -0,0 +1,122 @@
+//! Integration tests for OpenTelemetry tracing: incoming `traceparent` propagation and repository
+//! query spans, captured with an in-memory exporter.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::server;
+use catalog_svc_client::Client;
+use catalog_svc_client::types::{Category, CreateCatalogItemBody};
+use opentelemetry::trace::{SpanId, TraceId, TracerProvider as _};
+use opentelemetry::{KeyValue, Value};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const PARENT_SPAN_ID: &str = "00f067aa0ba902b7";
+
+fn attribute<'a>(span: &'a SpanData, key: &str) -> Option<&'a Value> {
+    span.attributes
+        .iter()
+        .find(|KeyValue { key: k, .. }| k.as_str() == key)
+        .map(|kv| &kv.value)
+}
+
+#[tokio::test]
+async fn catalog_tracing() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::new("info"))
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("catalog-svc-tests")))
+        .init();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let base_url = format!("http://{addr}");
+    let body = CreateCatalogItemBody {
+        name: "Traced".to_string(),
+        description: "Fetched with a traceparent".to_string(),
+        category: Category::Books,
+        date: "2025-04-07".to_string(),
+        brand: None,
+        price: "7.00".to_string(),
+    };
+    let client = Client::new(&base_url);
+    let item = client
+        .create_catalog_item(&body)
+        .await
+        .expect("create")
+        .into_inner();
+
+    let response = reqwest::Client::new()
+        .get(format!("{base_url}/catalog/items/{}", item.item_id))
+        .header("traceparent", format!("00-{TRACE_ID}-{PARENT_SPAN_ID}-01"))
+        .send()
+        .await
+        .expect("get");
+    assert!(response.status().is_success());
+    response.bytes().await.expect("body");
+    client
+        .delete_catalog_item(&item.item_id)
+        .await
+        .expect("delete");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    provider.force_flush().expect("flush");
+
+    let spans = exporter.get_finished_spans().expect("finished spans");
+    let trace_id = TraceId::from_hex(TRACE_ID).expect("trace id");
+    let traced: Vec<_> = spans
+        .iter()
+        .filter(|span| span.span_context.trace_id() == trace_id)
+        .collect();
+
+    // The request span continues the caller's trace
+    let request = traced
+        .iter()
+        .find(|span| span.name == "request")
+        .expect("request span");
+    assert_eq!(
+        request.parent_span_id,
+        SpanId::from_hex(PARENT_SPAN_ID).expect("span id")
+    );
+
+    // The repository query is a child span carrying the SQL operation
+    let query = traced
+        .iter()
+        .find(|span| span.name == "CatalogItemRepository::get")
+        .expect("repository span");
+    assert_eq!(
+        attribute(query, "db.operation")
+            .map(Value::as_str)
+            .as_deref(),
+        Some("SELECT")
+    );
+    assert_eq!(
+        attribute(query, "db.system").map(Value::as_str).as_deref(),
+        Some("postgresql")
+    );
+
+    // Requests without a traceparent start their own trace
+    assert!(
+        spans
+            .iter()
+            .any(|span| span.name == "CatalogItemRepository::create"
+                && span.span_context.trace_id() != trace_id)
+    );
+
+    state.server_shutdown.cancel();
+}
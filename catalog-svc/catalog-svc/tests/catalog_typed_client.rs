@@ -0,0 +1,264 @@
+//! Integration tests for the typed catalog client: typed models and errors against a real server
+//! instance, paging through every item, and retries and timeouts against a flaky test server.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+use axum::Router;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::get;
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::server;
+use catalog_svc_client::catalog::{
+    CatalogClient, CatalogItemInput, ClientError, ClientOptions, RetryPolicy,
+};
+use catalog_svc_client::types::Category;
+use chrono::NaiveDate;
+use futures::TryStreamExt;
+use rust_decimal::Decimal;
+use rust_demo_commons::util::tests;
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+fn input(name: &str, price: &str) -> CatalogItemInput {
+    CatalogItemInput {
+        name: name.to_string(),
+        description: "Typed client".to_string(),
+        category: Category::Books,
+        brand: Some("Typed".to_string()),
+        date: NaiveDate::from_ymd_opt(2025, 3, 1).expect("date"),
+        price: Decimal::from_str(price).expect("price"),
+    }
+}
+
+fn fast_retries() -> RetryPolicy {
+    RetryPolicy {
+        max_retries: 3,
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(50),
+    }
+}
+
+#[tokio::test]
+async fn catalog_typed_client() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = CatalogClient::new(
+        &format!("http://{addr}"),
+        ClientOptions {
+            page_size: 2,
+            ..ClientOptions::default()
+        },
+    )
+    .expect("client");
+
+    // Typed models round-trip
+    let created = client
+        .create(input("Typed Rust", "49.99"))
+        .await
+        .expect("create");
+    assert_eq!(created.price, Decimal::from_str("49.99").expect("price"));
+    assert_eq!(
+        created.date,
+        NaiveDate::from_ymd_opt(2025, 3, 1).expect("date")
+    );
+    assert_eq!(created.rating.average, None);
+    assert_eq!(created.rating.count, 0);
+
+    let got = client.get(created.item_id).await.expect("get");
+    assert_eq!(
+        CatalogItemInput::from(got),
+        CatalogItemInput::from(created.clone())
+    );
+
+    let updated = client
+        .update(created.item_id, input("Typed Rust 2nd", "39.50"))
+        .await
+        .expect("update");
+    assert_eq!(updated.name, "Typed Rust 2nd");
+    assert_eq!(updated.price, Decimal::new(3950, 2));
+
+    // Errors are told apart
+    let violations = match client.create(input("Negative", "-1.00")).await {
+        Err(ClientError::Validation { violations, .. }) => Some(violations),
+        _ => None,
+    }
+    .expect("validation error");
+    let paths: Vec<_> = violations.iter().map(|v| v.path.as_str()).collect();
+    assert_eq!(paths, vec!["/price"]);
+    assert!(matches!(
+        client.get(Uuid::new_v4()).await,
+        Err(ClientError::NotFound)
+    ));
+
+    // The stream walks every page
+    let mut ids = vec![created.item_id];
+    for i in 0..4 {
+        let item = client
+            .create(input(&format!("Typed page {i}"), "1.00"))
+            .await
+            .expect("create");
+        ids.push(item.item_id);
+    }
+    let page = client.list_page(0, Some(2), None).await.expect("page");
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.limit, 2);
+    assert!(page.has_more);
+
+    let listed: Vec<Uuid> = client
+        .list_all(None)
+        .map_ok(|item| item.item_id)
+        .try_collect()
+        .await
+        .expect("list all");
+    let unique: HashSet<_> = listed.iter().collect();
+    assert_eq!(unique.len(), listed.len(), "no item listed twice");
+    assert!(ids.iter().all(|id| unique.contains(id)));
+
+    for id in &ids {
+        client.delete(*id).await.expect("delete");
+    }
+    assert!(matches!(
+        client.delete(created.item_id).await,
+        Err(ClientError::NotFound)
+    ));
+
+    state.server_shutdown.cancel();
+}
+
+/// Test server counting requests: listing fails with `503` for the first `failures` requests,
+/// creating always fails with `503`, and reading an item takes longer than the clients wait.
+#[derive(Clone, Default)]
+struct Flaky {
+    failures: u32,
+    list_requests: Arc<AtomicU32>,
+    create_requests: Arc<AtomicU32>,
+    get_requests: Arc<AtomicU32>,
+}
+
+async fn flaky_list(State(flaky): State<Flaky>) -> (StatusCode, axum::Json<Value>) {
+    if flaky.list_requests.fetch_add(1, Ordering::SeqCst) < flaky.failures {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            axum::Json(
+                json!({"type": "about:blank", "title": "Service Unavailable", "status": 503}),
+            ),
+        );
+    }
+    (
+        StatusCode::OK,
+        axum::Json(
+            json!({"items": [], "hasMore": false, "pagination": {"limit": 100, "offset": 0}}),
+        ),
+    )
+}
+
+async fn flaky_create(State(flaky): State<Flaky>) -> StatusCode {
+    flaky.create_requests.fetch_add(1, Ordering::SeqCst);
+    StatusCode::SERVICE_UNAVAILABLE
+}
+
+async fn slow_get(State(flaky): State<Flaky>) -> StatusCode {
+    flaky.get_requests.fetch_add(1, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    StatusCode::OK
+}
+
+async fn start_flaky(failures: u32) -> (Flaky, String) {
+    let flaky = Flaky {
+        failures,
+        ..Flaky::default()
+    };
+    let app = Router::new()
+        .route("/catalog/items", get(flaky_list).post(flaky_create))
+        .route("/catalog/items/{item_id}", get(slow_get))
+        .with_state(flaky.clone());
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    (flaky, format!("http://{addr}"))
+}
+
+#[tokio::test]
+async fn catalog_typed_client_retries() {
+    tests::init_logging();
+
+    // Server errors are retried until the service recovers
+    let (flaky, base_url) = start_flaky(2).await;
+    let options = ClientOptions {
+        retry: fast_retries(),
+        ..ClientOptions::default()
+    };
+    let client = CatalogClient::new(&base_url, options.clone()).expect("client");
+    let page = client.list_page(0, None, None).await.expect("list");
+    assert!(page.items.is_empty());
+    assert_eq!(flaky.list_requests.load(Ordering::SeqCst), 3);
+
+    // ... up to the configured number of retries
+    let (flaky, base_url) = start_flaky(10).await;
+    let client = CatalogClient::new(&base_url, options.clone()).expect("client");
+    let (status, message) = match client.list_page(0, None, None).await {
+        Err(ClientError::Server { status, message }) => Some((status, message)),
+        _ => None,
+    }
+    .expect("server error");
+    assert_eq!(status, 503);
+    assert_eq!(message, "Service Unavailable");
+    assert_eq!(flaky.list_requests.load(Ordering::SeqCst), 4);
+
+    // Creates are not retried after a server error
+    assert!(matches!(
+        client.create(input("Not retried", "1.00")).await,
+        Err(ClientError::Server { status: 503, .. })
+    ));
+    assert_eq!(flaky.create_requests.load(Ordering::SeqCst), 1);
+
+    // Timeouts are retried, then reported
+    let client = CatalogClient::new(
+        &base_url,
+        ClientOptions {
+            timeout: Duration::from_millis(100),
+            retry: RetryPolicy {
+                max_retries: 1,
+                ..fast_retries()
+            },
+            ..options.clone()
+        },
+    )
+    .expect("client");
+    assert!(matches!(
+        client.get(Uuid::new_v4()).await,
+        Err(ClientError::Timeout(_))
+    ));
+    assert_eq!(flaky.get_requests.load(Ordering::SeqCst), 2);
+
+    // Nothing listening: the connection error is retried, then reported
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    drop(listener);
+    let client = CatalogClient::new(&format!("http://{addr}"), options).expect("client");
+    assert!(matches!(
+        client.create(input("Unreachable", "1.00")).await,
+        Err(ClientError::Connection(_))
+    ));
+
+    let policy = fast_retries();
+    assert_eq!(policy.backoff(0), Duration::from_millis(10));
+    assert_eq!(policy.backoff(2), Duration::from_millis(40));
+    assert_eq!(policy.backoff(10), Duration::from_millis(50));
+}
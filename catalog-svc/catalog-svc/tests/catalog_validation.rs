@@ -0,0 +1,215 @@
+//! Integration tests for field-level validation errors returned as RFC 9457 Problem Details.
+
+use std::time::Duration;
+
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::server;
+use catalog_svc_client::types::{
+    Category, CreateCatalogItemBody, FieldViolation, ProblemDetails, UpdateCatalogItemBody,
+};
+use catalog_svc_client::{Client, Error};
+use rust_demo_commons::util::tests;
+use uuid::Uuid;
+
+fn problem<T: std::fmt::Debug>(result: Result<T, Error<ProblemDetails>>) -> ProblemDetails {
+    match result.expect_err("request should fail") {
+        Error::ErrorResponse(response) => Some(response.into_inner()),
+        _ => None,
+    }
+    .expect("error response should carry Problem Details")
+}
+
+/// Status and Problem Details of a request failing before it reaches a service.
+async fn raw_problem(request: reqwest::RequestBuilder) -> (u16, Option<String>, ProblemDetails) {
+    let response = request.send().await.expect("raw request");
+    let status = response.status().as_u16();
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let details = response.json().await.expect("Problem Details body");
+    (status, content_type, details)
+}
+
+fn violations(details: &ProblemDetails) -> Vec<(&str, &str)> {
+    details
+        .errors
+        .iter()
+        .map(|FieldViolation { path, message }| (path.as_str(), message.as_str()))
+        .collect()
+}
+
+#[tokio::test]
+async fn catalog_validation() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, _handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let base_url = format!("http://{addr}");
+    let client = Client::new(&base_url);
+
+    // Every violation is reported at once
+    let invalid = CreateCatalogItemBody {
+        name: "  ".to_string(),
+        description: "Nothing".to_string(),
+        category: Category::Books,
+        date: "2025-02-30".to_string(),
+        brand: None,
+        price: "-1.00".to_string(),
+    };
+    let details = problem(client.create_catalog_item(&invalid).await);
+    assert_eq!(details.status, 400);
+    assert_eq!(details.type_, "urn:catalog-svc:problem:validation");
+    assert_eq!(
+        violations(&details),
+        vec![
+            ("/name", "must not be empty"),
+            ("/price", "must not be negative"),
+            ("/date", "must be a valid date in YYYY-MM-DD format"),
+        ]
+    );
+
+    let item = client
+        .create_catalog_item(&CreateCatalogItemBody {
+            name: "Valid".to_string(),
+            date: "2025-03-01".to_string(),
+            price: "5.00".to_string(),
+            ..invalid
+        })
+        .await
+        .expect("valid item")
+        .into_inner();
+
+    let too_long = UpdateCatalogItemBody {
+        name: "n".repeat(256),
+        description: item.description.clone(),
+        category: item.category,
+        date: "2025-03-01".to_string(),
+        brand: Some("b".repeat(256)),
+        price: "1.999".to_string(),
+    };
+    let details = problem(client.update_catalog_item(&item.item_id, &too_long).await);
+    assert_eq!(
+        violations(&details),
+        vec![
+            ("/name", "must be at most 255 characters"),
+            ("/brand", "must be at most 255 characters"),
+            ("/price", "must have at most 2 decimal places"),
+        ]
+    );
+
+    // Other errors are Problem Details too, without field violations
+    let details = problem(client.get_catalog_item(&Uuid::new_v4()).await);
+    assert_eq!(details.status, 404);
+    assert_eq!(details.title, "Not Found");
+    assert!(details.errors.is_empty());
+
+    let response = reqwest::Client::new()
+        .post(format!("{base_url}/catalog/items"))
+        .header("content-type", "application/json")
+        .body(r#"{"name":"","description":"","category":"Books","date":"2025-03-01","price":"1"}"#)
+        .send()
+        .await
+        .expect("raw request");
+    assert_eq!(response.status().as_u16(), 400);
+    assert_eq!(
+        response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok()),
+        Some("application/problem+json")
+    );
+
+    // Bodies and query strings that do not deserialize name the offending field
+    let http = reqwest::Client::new();
+    let items_url = format!("{base_url}/catalog/items");
+    let (status, content_type, details) = raw_problem(
+        http.post(&items_url)
+            .header("content-type", "application/json")
+            .body(r#"{"name":"n","description":"d","category":"Books","date":"2025-03-01","price":"abc"}"#),
+    )
+    .await;
+    assert_eq!(status, 400);
+    assert_eq!(content_type.as_deref(), Some("application/problem+json"));
+    assert_eq!(details.type_, "urn:catalog-svc:problem:validation");
+    assert_eq!(
+        violations(&details).first().map(|(path, _)| *path),
+        Some("/price")
+    );
+
+    let (status, _, details) = raw_problem(
+        http.post(&items_url)
+            .header("content-type", "application/json")
+            .body(r#"{"description":"d","category":"Books","date":"2025-03-01","price":"1"}"#),
+    )
+    .await;
+    assert_eq!(status, 400);
+    assert_eq!(violations(&details), vec![("/name", "is required")]);
+
+    let (status, _, details) = raw_problem(
+        http.post(&items_url)
+            .header("content-type", "application/json")
+            .body(r#"{"name": "#),
+    )
+    .await;
+    assert_eq!(status, 400);
+    assert_eq!(
+        violations(&details).first().map(|(path, _)| *path),
+        Some("")
+    );
+
+    let (status, _, details) = raw_problem(http.post(&items_url).body("name=n")).await;
+    assert_eq!(status, 415);
+    assert!(details.errors.is_empty());
+
+    let (status, _, details) = raw_problem(http.get(format!("{items_url}?limit=abc"))).await;
+    assert_eq!(status, 400);
+    assert_eq!(
+        violations(&details).first().map(|(path, _)| *path),
+        Some("/limit")
+    );
+
+    let item_url = format!("{items_url}/{}", item.item_id);
+    let (status, _, details) = raw_problem(
+        http.post(format!("{item_url}/reviews"))
+            .header("content-type", "application/json")
+            .body(r#"{"rating":"five","title":"t","body":"b","author":"a"}"#),
+    )
+    .await;
+    assert_eq!(status, 400);
+    assert_eq!(
+        violations(&details).first().map(|(path, _)| *path),
+        Some("/rating")
+    );
+
+    let (status, _, details) = raw_problem(
+        http.post(format!("{item_url}/related"))
+            .header("content-type", "application/json")
+            .body(format!(
+                r#"{{"relatedItemId":"{}","kind":"sibling"}}"#,
+                Uuid::new_v4()
+            )),
+    )
+    .await;
+    assert_eq!(status, 400);
+    assert_eq!(
+        violations(&details).first().map(|(path, _)| *path),
+        Some("/kind")
+    );
+
+    let (status, content_type, _) =
+        raw_problem(http.get(format!("{items_url}/not-a-uuid/reviews"))).await;
+    assert_eq!(status, 400);
+    assert_eq!(content_type.as_deref(), Some("application/problem+json"));
+
+    state.server_shutdown.cancel();
+}
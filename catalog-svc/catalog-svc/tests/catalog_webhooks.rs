@@ -0,0 +1,295 @@
+//! Integration tests for webhooks: subscription validation, signed deliveries, retries ending in
+//! a failed delivery, auto-disabling and re-enabling a subscription, and deletion.
+
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode as AxumStatusCode};
+use axum::routing::post;
+use catalog_svc::app_config::AppConfig;
+use catalog_svc::catalog::api::{Category, CreateCatalogItemBody};
+use catalog_svc::server;
+use catalog_svc::webhooks::api::{
+    CreatedWebhookSubscription, DeliveryStatus, ListWebhookDeliveriesResponse, WebhookSubscription,
+};
+use catalog_svc::webhooks::signature::{self, EVENT_HEADER, ID_HEADER, SIGNATURE_HEADER};
+use reqwest::StatusCode;
+use rust_decimal::Decimal;
+use rust_demo_commons::util::tests;
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+/// Requests received by the test endpoint, which answers with `status`.
+#[derive(Clone, Default)]
+struct Receiver {
+    requests: Arc<Mutex<Vec<(HeaderMap, Bytes)>>>,
+    status: Arc<AtomicU16>,
+}
+
+async fn receive(
+    State(receiver): State<Receiver>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AxumStatusCode {
+    receiver
+        .requests
+        .lock()
+        .expect("requests lock")
+        .push((headers, body));
+    AxumStatusCode::from_u16(receiver.status.load(Ordering::SeqCst)).expect("status")
+}
+
+async fn start_receiver() -> (Receiver, String) {
+    let receiver = Receiver::default();
+    receiver.status.store(200, Ordering::SeqCst);
+    let app = Router::new()
+        .route("/hook", post(receive))
+        .with_state(receiver.clone());
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    (receiver, format!("http://{addr}/hook"))
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> &'a str {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .expect("header")
+}
+
+/// Poll `check` until it returns a value, failing after a few seconds.
+async fn eventually<T, F: Future<Output = Option<T>>>(what: &str, check: impl Fn() -> F) -> T {
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    loop {
+        if let Some(value) = check().await {
+            return value;
+        }
+        assert!(tokio::time::Instant::now() < deadline, "timed out: {what}");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+async fn deliveries(url: &str, query: &[(&str, &str)]) -> ListWebhookDeliveriesResponse {
+    let response = reqwest::Client::new()
+        .get(url)
+        .query(query)
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(response.status(), StatusCode::OK);
+    response.json().await.expect("deliveries")
+}
+
+async fn subscription(url: &str) -> WebhookSubscription {
+    let response = reqwest::get(url).await.expect("request");
+    assert_eq!(response.status(), StatusCode::OK);
+    response.json().await.expect("subscription")
+}
+
+#[tokio::test]
+async fn catalog_webhooks() {
+    tests::init_logging();
+
+    let mut app_config = AppConfig::load_tests();
+    app_config.auth.enabled = false;
+    app_config.webhooks.poll_interval_ms = 50;
+    app_config.webhooks.retry_backoff_ms = 50;
+    app_config.webhooks.max_backoff_secs = 1;
+    app_config.webhooks.max_attempts = 10;
+    app_config.webhooks.disable_after_failures = 3;
+    let (app_state, _background) = server::build_app(&app_config).await;
+    app_config.server.port = 0;
+
+    let (state, handle, addr) = server::start_service_and_serve(app_state, app_config)
+        .await
+        .expect("bind");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let (receiver, hook_url) = start_receiver().await;
+    let http = reqwest::Client::new();
+    let webhooks_url = format!("http://{addr}/admin/webhooks");
+
+    // Unknown schemes and event types are rejected
+    let response = http
+        .post(&webhooks_url)
+        .json(&json!({"url": "ftp://example.com/hook", "eventTypes": ["ItemExploded"]}))
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = http
+        .post(&webhooks_url)
+        .json(&json!({
+            "url": hook_url,
+            "eventTypes": ["ItemDeleted", "ItemCreated", "ItemCreated"],
+        }))
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let created: CreatedWebhookSubscription = response.json().await.expect("created");
+    assert!(created.secret.starts_with("whsec_"));
+    assert_eq!(
+        created.subscription.event_types,
+        ["ItemCreated", "ItemDeleted"]
+    );
+    let subscription_url = format!("{webhooks_url}/{}", created.subscription.subscription_id);
+    let deliveries_url = format!("{subscription_url}/deliveries");
+
+    // A created item is POSTed as a signed envelope
+    let item = state
+        .catalog
+        .create(CreateCatalogItemBody {
+            name: "Webhook Lamp".to_string(),
+            description: "Delivered".to_string(),
+            category: Category::Electronics,
+            date: "2025-04-10".to_string(),
+            brand: None,
+            price: Decimal::new(2500, 2),
+        })
+        .await
+        .expect("create");
+    let (headers, body) = eventually("ItemCreated delivery", || async {
+        receiver
+            .requests
+            .lock()
+            .expect("requests lock")
+            .first()
+            .cloned()
+    })
+    .await;
+    assert_eq!(header(&headers, EVENT_HEADER), "ItemCreated");
+    assert_eq!(header(&headers, "content-type"), "application/json");
+    let envelope: Value = serde_json::from_slice(&body).expect("envelope");
+    assert_eq!(envelope.get("type"), Some(&json!("ItemCreated")));
+    assert_eq!(
+        envelope.pointer("/payload/item/itemId"),
+        Some(&json!(item.item_id))
+    );
+    let signature_header = header(&headers, SIGNATURE_HEADER);
+    let timestamp: i64 = signature_header
+        .strip_prefix("t=")
+        .and_then(|rest| rest.split(',').next())
+        .and_then(|t| t.parse().ok())
+        .expect("signature timestamp");
+    assert_eq!(
+        signature_header,
+        signature::sign(&created.secret, timestamp, &body)
+    );
+
+    let delivery_id: Uuid = header(&headers, ID_HEADER).parse().expect("delivery id");
+    let log = eventually("succeeded delivery", || async {
+        let log = deliveries(&deliveries_url, &[("status", "succeeded")]).await;
+        (!log.deliveries.is_empty()).then_some(log)
+    })
+    .await;
+    let delivery = log.deliveries.first().expect("delivery");
+    assert_eq!(delivery.delivery_id, delivery_id);
+    assert_eq!(delivery.attempts, 1);
+    assert_eq!(delivery.last_status_code, Some(200));
+
+    // A failing receiver disables the subscription after 3 failures in a row, which fails its
+    // pending delivery before it runs out of attempts; updates are not subscribed to
+    receiver.status.store(500, Ordering::SeqCst);
+    assert!(state.catalog.delete(item.item_id).await.expect("delete"));
+    let log = eventually("failed delivery", || async {
+        let log = deliveries(&deliveries_url, &[("status", "failed")]).await;
+        (!log.deliveries.is_empty()).then_some(log)
+    })
+    .await;
+    let failed = log.deliveries.first().expect("delivery");
+    assert_eq!(failed.event_type, "ItemDeleted");
+    assert_eq!(failed.attempts, 3);
+    assert_eq!(failed.last_status_code, Some(500));
+    assert!(failed.last_error.is_some() && failed.completed_at.is_some());
+    assert_eq!(receiver.requests.lock().expect("requests lock").len(), 4);
+
+    let disabled = subscription(&subscription_url).await;
+    assert!(!disabled.enabled && disabled.disabled_at.is_some());
+    assert_eq!(disabled.consecutive_failures, 3);
+    let all = deliveries(&deliveries_url, &[]).await;
+    assert_eq!(all.deliveries.len(), 2);
+    assert_eq!(
+        all.deliveries.first().map(|delivery| delivery.status),
+        Some(DeliveryStatus::Failed)
+    );
+
+    // Re-enabling starts the failure count over
+    let response = http
+        .post(&subscription_url)
+        .json(&json!({"url": hook_url, "eventTypes": ["ItemCreated"], "enabled": true}))
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(response.status(), StatusCode::OK);
+    let enabled: WebhookSubscription = response.json().await.expect("subscription");
+    assert!(enabled.enabled && enabled.disabled_at.is_none());
+    assert_eq!(enabled.consecutive_failures, 0);
+    assert_eq!(enabled.event_types, ["ItemCreated"]);
+
+    // Disabling it by hand fails the deliveries still being retried, so re-enabling it does not
+    // send them
+    let retried = state
+        .catalog
+        .create(CreateCatalogItemBody {
+            name: "Webhook Retried".to_string(),
+            description: "Never delivered".to_string(),
+            category: Category::Electronics,
+            date: "2025-04-10".to_string(),
+            brand: None,
+            price: Decimal::new(100, 2),
+        })
+        .await
+        .expect("create");
+    eventually("retried delivery", || async {
+        let log = deliveries(&deliveries_url, &[("status", "pending")]).await;
+        log.deliveries
+            .iter()
+            .any(|delivery| delivery.attempts > 0)
+            .then_some(())
+    })
+    .await;
+    for enabled in [false, true] {
+        let response = http
+            .post(&subscription_url)
+            .json(&json!({"url": hook_url, "eventTypes": ["ItemCreated"], "enabled": enabled}))
+            .send()
+            .await
+            .expect("request");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    let pending = deliveries(&deliveries_url, &[("status", "pending")]).await;
+    assert!(pending.deliveries.is_empty());
+    let failed = deliveries(&deliveries_url, &[("status", "failed")]).await;
+    let failed = failed.deliveries.first().expect("failed delivery");
+    assert_eq!(failed.last_error.as_deref(), Some("subscription disabled"));
+    assert!(failed.completed_at.is_some());
+    receiver.status.store(200, Ordering::SeqCst);
+    let received = receiver.requests.lock().expect("requests lock").len();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(
+        receiver.requests.lock().expect("requests lock").len(),
+        received
+    );
+    assert!(state.catalog.delete(retried.item_id).await.expect("delete"));
+
+    let response = http
+        .delete(&subscription_url)
+        .send()
+        .await
+        .expect("request");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response = http.get(&subscription_url).send().await.expect("request");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = http.get(&deliveries_url).send().await.expect("request");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    state.server_shutdown.cancel();
+    handle.await.expect("server task").expect("serve");
+}
@@ -0,0 +1,263 @@
+//! Tests for the event contracts: the committed JSON Schema and Avro files match the Rust types,
+//! the compatibility check flags changes that break consumers, and envelopes decode by type and
+//! version.
+
+use std::path::Path;
+
+use catalog_svc::events::schema::{breaking_changes, event_schema, event_schemas};
+use catalog_svc::events::{
+    CatalogEvent, EventDecodeError, EventEnvelope, ItemCreated, ItemDeleted, PriceChanged,
+};
+use rust_decimal::Decimal;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+fn read_json(path: &Path) -> Option<Value> {
+    let text = std::fs::read_to_string(path).ok()?;
+    Some(serde_json::from_str(&text).expect("parse committed schema"))
+}
+
+#[test]
+fn committed_event_schemas_are_current() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("schemas/events");
+    for schema in event_schemas().expect("event schemas") {
+        let stem = schema.file_stem();
+        let committed = read_json(&dir.join(format!("{stem}.schema.json")));
+        assert!(
+            committed.is_some(),
+            "{stem}.schema.json missing; run `just generate-event-schemas`"
+        );
+        let committed = committed.expect("committed schema");
+        let problems = breaking_changes(&committed, &schema.json_schema);
+        assert!(
+            problems.is_empty(),
+            "{stem} changed incompatibly, bump its version: {problems:#?}"
+        );
+        assert_eq!(
+            committed, schema.json_schema,
+            "{stem}.schema.json is out of date; run `just generate-event-schemas`"
+        );
+        assert_eq!(
+            read_json(&dir.join(format!("{stem}.avsc"))),
+            Some(schema.avro_schema),
+            "{stem}.avsc is out of date; run `just generate-event-schemas`"
+        );
+    }
+}
+
+/// The ItemCreated schema with `change` applied to the value at `pointer`.
+fn modified(pointer: &str, change: impl FnOnce(&mut Value)) -> Value {
+    let mut schema = event_schema::<ItemCreated>()
+        .expect("event schema")
+        .json_schema;
+    change(schema.pointer_mut(pointer).expect("pointer"));
+    schema
+}
+
+/// Set `key` of the object `schema` to `value`.
+fn set(schema: &mut Value, key: &str, value: Value) {
+    if let Some(object) = schema.as_object_mut() {
+        object.insert(key.to_string(), value);
+    }
+}
+
+fn remove_required(name: &'static str) -> impl FnOnce(&mut Value) {
+    move |required| {
+        if let Some(names) = required.as_array_mut() {
+            names.retain(|required| required != name);
+        }
+    }
+}
+
+#[test]
+fn breaking_changes_are_detected() {
+    let current = event_schema::<ItemCreated>()
+        .expect("event schema")
+        .json_schema;
+    assert_eq!(breaking_changes(&current, &current), Vec::<String>::new());
+
+    let compatible = [
+        // New optional property
+        modified("/definitions/CatalogItem/properties", |properties| {
+            set(properties, "color", json!({"type": ["string", "null"]}));
+        }),
+        // New required property
+        modified("/definitions/CatalogItem/required", |required| {
+            if let Some(names) = required.as_array_mut() {
+                names.push(json!("color"));
+            }
+        }),
+        // Optional property no longer nullable
+        modified("/definitions/CatalogItem/properties/brand", |brand| {
+            set(brand, "type", json!("string"));
+        }),
+        // Enum value removed
+        modified("/definitions/Category/enum", |values| {
+            *values = json!(["Books"]);
+        }),
+        // Descriptions are documentation only
+        modified("/definitions/CatalogItem/properties/name", |name| {
+            set(name, "description", json!("Display name."));
+        }),
+    ];
+    for new in compatible {
+        assert_eq!(breaking_changes(&current, &new), Vec::<String>::new());
+    }
+
+    let breaking = [
+        (
+            "$.payload.item.price: no longer required",
+            modified(
+                "/definitions/CatalogItem/required",
+                remove_required("price"),
+            ),
+        ),
+        (
+            "$.tenant: removed",
+            modified("", |schema| {
+                if let Some(properties) =
+                    schema.get_mut("properties").and_then(Value::as_object_mut)
+                {
+                    properties.remove("tenant");
+                }
+                if let Some(required) = schema.get_mut("required") {
+                    remove_required("tenant")(required);
+                }
+            }),
+        ),
+        (
+            "$.payload.item.name: type changed",
+            modified("/definitions/CatalogItem/properties/name", |name| {
+                set(name, "type", json!(["string", "null"]));
+            }),
+        ),
+        (
+            "$.payload.item.stock: type changed",
+            modified("/definitions/CatalogItem/properties/stock", |stock| {
+                set(stock, "type", json!(["string", "null"]));
+            }),
+        ),
+        (
+            "$.payload.item.itemId: format",
+            modified("/definitions/CatalogItem/properties/itemId", |item_id| {
+                set(item_id, "format", json!("uri"));
+            }),
+        ),
+        (
+            "$.payload.item.price: pattern",
+            modified("/definitions/CatalogItem/properties/price", |price| {
+                set(price, "pattern", json!(".*"));
+            }),
+        ),
+        (
+            "$.payload.item.category: enum values added",
+            modified("/definitions/Category/enum", |values| {
+                *values = json!(["Books", "Electronics", "Toys"]);
+            }),
+        ),
+        (
+            "$.type: constant",
+            modified("/properties/type", |event_type| {
+                set(event_type, "const", json!("ItemAdded"));
+            }),
+        ),
+        (
+            "$.payload.item.rating.histogram[]: type changed",
+            modified(
+                "/definitions/RatingSummary/properties/histogram/items",
+                |items| set(items, "type", json!("number")),
+            ),
+        ),
+    ];
+    for (expected, new) in breaking {
+        let problems = breaking_changes(&current, &new);
+        assert!(
+            problems.iter().any(|problem| problem.starts_with(expected)),
+            "expected {expected:?} in {problems:#?}"
+        );
+    }
+}
+
+#[test]
+fn avro_schemas_mirror_envelopes() {
+    for schema in event_schemas().expect("event schemas") {
+        let avro = &schema.avro_schema;
+        assert_eq!(avro.get("type"), Some(&json!("record")));
+        assert_eq!(avro.get("namespace"), Some(&json!("catalog.events")));
+        assert_eq!(
+            avro.get("name"),
+            Some(&json!(format!("{}Envelope", schema.event_type)))
+        );
+        let fields: Vec<_> = avro
+            .get("fields")
+            .and_then(Value::as_array)
+            .expect("fields")
+            .iter()
+            .filter_map(|field| field.get("name").and_then(Value::as_str))
+            .collect();
+        assert_eq!(
+            fields,
+            ["id", "occurredAt", "payload", "tenant", "type", "version"]
+        );
+    }
+    let created = event_schema::<ItemCreated>().expect("event schema");
+    let item = created
+        .avro_schema
+        .pointer("/fields/2/type/fields/0/type")
+        .expect("item type");
+    assert_eq!(item.get("name"), Some(&json!("CatalogItem")));
+    let stock = item
+        .get("fields")
+        .and_then(Value::as_array)
+        .expect("fields")
+        .iter()
+        .find(|field| field.get("name") == Some(&json!("stock")))
+        .expect("stock field");
+    assert_eq!(stock.get("type"), Some(&json!(["null", "long"])));
+    assert_eq!(stock.get("default"), Some(&Value::Null));
+}
+
+#[test]
+fn envelopes_decode_by_type_and_version() {
+    let item_id = Uuid::new_v4();
+    let events = [
+        CatalogEvent::ItemDeleted(ItemDeleted { item_id }),
+        CatalogEvent::PriceChanged(PriceChanged {
+            item_id,
+            old_price: Decimal::new(1000, 2),
+            new_price: Decimal::new(950, 2),
+        }),
+    ];
+    for event in events {
+        let envelope = EventEnvelope::new("tenant-a", event);
+        let json = serde_json::to_value(&envelope).expect("serialize");
+        assert_eq!(json.get("type"), Some(&json!(envelope.event_type)));
+        assert_eq!(json.get("version"), Some(&json!(1)));
+        assert_eq!(json.get("tenant"), Some(&json!("tenant-a")));
+        assert_eq!(json.pointer("/payload/itemId"), Some(&json!(item_id)));
+
+        let decoded: EventEnvelope = serde_json::from_value(json).expect("deserialize");
+        assert_eq!(decoded.into_event().expect("decode"), envelope);
+    }
+
+    let envelope = |event_type: &str, version: u32| EventEnvelope {
+        id: Uuid::new_v4(),
+        event_type: event_type.to_string(),
+        version,
+        occurred_at: chrono::Utc::now(),
+        tenant: "tenant-a".to_string(),
+        payload: json!({"itemId": item_id}),
+    };
+    assert!(matches!(
+        envelope("ItemArchived", 1).into_event(),
+        Err(EventDecodeError::UnknownType(_))
+    ));
+    assert!(matches!(
+        envelope("ItemDeleted", 2).into_event(),
+        Err(EventDecodeError::UnsupportedVersion { version: 2, .. })
+    ));
+    assert!(matches!(
+        envelope("PriceChanged", 1).into_event(),
+        Err(EventDecodeError::Payload(_))
+    ));
+}
@@ -16,7 +16,8 @@ cd "$REPO_ROOT"
 cargo run -p $PACKAGE_NAME --bin dump-openapi > "$OPENAPI_JSON"
 echo "Wrote OpenAPI spec to $OPENAPI_JSON"
 
-# 2. Convert 3.1 → 3.0 for progenitor (openapiv3 only supports 3.0.x)
+# 2. Convert 3.1 → 3.0 for progenitor (openapiv3 only supports 3.0.x); progenitor only
+#    decodes application/json bodies, so Problem Details errors are declared as such.
 if ! command -v jq &>/dev/null; then
   echo "jq not found. Install with: brew install jq (or your package manager)"
   exit 1
@@ -28,6 +29,8 @@ jq '
     if type == "object" and has("type") and (.type | type == "array") then
       .nullable = (.type | index("null") != null)
       | .type = (.type | map(select(. != "null")) | .[0])
+    elif type == "object" and has("application/problem+json") then
+      with_entries(if .key == "application/problem+json" then .key = "application/json" else . end)
     else . end
   )
 ' "$OPENAPI_JSON" > "$OPENAPI_30_JSON"
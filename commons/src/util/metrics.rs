@@ -0,0 +1,141 @@
+//! Prometheus metrics: a process-wide [metrics] recorder, per-route HTTP request metrics as a
+//! tower [Layer], and the text exposition for a `/metrics` endpoint.
+//!
+//! Application code records its own counters and gauges with the [metrics] macros
+//! (`metrics::counter!("…").increment(1)`); they end up in the same exposition.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::OnceLock;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use http::{Request, Response};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use tower::{Layer, Service};
+
+/// Counter of handled HTTP requests, labelled `method`, `route` and `status`.
+pub const HTTP_REQUESTS_TOTAL: &str = "http_server_requests_total";
+/// Histogram of HTTP request latency in seconds, labelled `method`, `route` and `status`.
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "http_server_request_duration_seconds";
+
+/// Latency buckets (seconds) for [HTTP_REQUEST_DURATION_SECONDS].
+const HTTP_DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Handle to the installed Prometheus recorder. Cheap to clone.
+#[derive(Clone)]
+pub struct Metrics {
+    handle: PrometheusHandle,
+}
+
+impl Metrics {
+    /// Install the process-wide Prometheus recorder, or return the one already installed
+    /// (several servers or tests in one process share it).
+    pub fn install() -> Self {
+        static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+        let handle = HANDLE.get_or_init(|| {
+            let handle = PrometheusBuilder::new()
+                .set_buckets_for_metric(
+                    Matcher::Full(HTTP_REQUEST_DURATION_SECONDS.to_string()),
+                    HTTP_DURATION_BUCKETS,
+                )
+                .and_then(PrometheusBuilder::install_recorder)
+                .expect("failed to install Prometheus recorder");
+            metrics::describe_counter!(HTTP_REQUESTS_TOTAL, "Handled HTTP requests");
+            metrics::describe_histogram!(
+                HTTP_REQUEST_DURATION_SECONDS,
+                metrics::Unit::Seconds,
+                "HTTP request latency"
+            );
+            handle
+        });
+        Self {
+            handle: handle.clone(),
+        }
+    }
+
+    /// All metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        self.handle.run_upkeep();
+        self.handle.render()
+    }
+}
+
+/// Record one handled request. `route` must be a route template or operation name, never the raw
+/// path, to keep label cardinality bounded.
+pub fn record_http_request(method: &str, route: &str, status: u16, elapsed: Duration) {
+    let labels = [
+        ("method", method.to_string()),
+        ("route", route.to_string()),
+        ("status", status.to_string()),
+    ];
+    metrics::counter!(HTTP_REQUESTS_TOTAL, &labels).increment(1);
+    metrics::histogram!(HTTP_REQUEST_DURATION_SECONDS, &labels).record(elapsed.as_secs_f64());
+}
+
+/// Tower layer recording [HTTP_REQUESTS_TOTAL] and [HTTP_REQUEST_DURATION_SECONDS] per route.
+///
+/// `route` names the route of a request (e.g. axum's `MatchedPath`); requests that failed with an
+/// error instead of a response are not recorded.
+#[derive(Clone)]
+pub struct HttpMetricsLayer<R> {
+    route: R,
+}
+
+impl<R> HttpMetricsLayer<R> {
+    pub fn new(route: R) -> Self {
+        Self { route }
+    }
+}
+
+impl<S, R: Clone> Layer<S> for HttpMetricsLayer<R> {
+    type Service = HttpMetricsService<S, R>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HttpMetricsService {
+            inner,
+            route: self.route.clone(),
+        }
+    }
+}
+
+/// Service produced by [HttpMetricsLayer].
+#[derive(Clone)]
+pub struct HttpMetricsService<S, R> {
+    inner: S,
+    route: R,
+}
+
+impl<S, R, ReqBody, ResBody> Service<Request<ReqBody>> for HttpMetricsService<S, R>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    R: Fn(&Request<ReqBody>) -> String,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let method = req.method().to_string();
+        let route = (self.route)(&req);
+        let started = Instant::now();
+        let response = self.inner.call(req);
+        Box::pin(async move {
+            let response = response.await?;
+            record_http_request(
+                &method,
+                &route,
+                response.status().as_u16(),
+                started.elapsed(),
+            );
+            Ok(response)
+        })
+    }
+}
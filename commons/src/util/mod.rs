@@ -1,3 +1,4 @@
+pub mod metrics;
 pub mod server;
 #[cfg(feature = "test-utils")]
 pub mod tests;
@@ -1,30 +1,70 @@
 //! Application lifecycle utilities.
 
+pub mod rate_limit;
+pub mod request_id;
+
 use std::time::Duration;
 
 use tokio_util::sync::CancellationToken;
+use tower::layer::util::Stack;
 use tower_http::{
     LatencyUnit,
-    trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer},
+    trace::{DefaultOnRequest, DefaultOnResponse, MakeSpan, TraceLayer},
 };
-use tracing::Level;
+use tracing::{Level, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
 
-/// Returns a [TraceLayer] with production-ready HTTP request tracing:
-/// - One span per request (method, path) via [DefaultMakeSpan]
-/// - INFO-level request start and response (with latency in milliseconds)
-/// - Body chunk and EOS logging disabled to avoid noisy logs
-/// - 5xx responses classified as failures and logged via the default [OnFailure]
-pub fn http_trace_layer() -> TraceLayer<
+pub use self::rate_limit::{RateLimit, RateLimitLayer};
+pub use self::request_id::{RequestIdLayer, X_REQUEST_ID, current_request_id};
+
+/// [MakeSpan] creating an INFO-level `request` span (method, uri, version and the [X_REQUEST_ID]
+/// set by [RequestIdLayer]), continuing the trace of the caller when the request carries a W3C
+/// `traceparent` header.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TraceContextMakeSpan;
+
+impl<B> MakeSpan<B> for TraceContextMakeSpan {
+    fn make_span(&mut self, request: &http::Request<B>) -> Span {
+        let request_id = request
+            .headers()
+            .get(&X_REQUEST_ID)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        let span = tracing::info_span!(
+            "request",
+            method = %request.method(),
+            uri = %request.uri(),
+            version = ?request.version(),
+            request_id,
+        );
+        let parent = super::tracing::extract_trace_context(request.headers());
+        // Only fails when the span is disabled, then there is nothing to link
+        let _ = span.set_parent(parent);
+        span
+    }
+}
+
+/// The [TraceLayer] of [http_trace_layer].
+pub type HttpTraceLayer = TraceLayer<
     tower_http::classify::SharedClassifier<tower_http::classify::ServerErrorsAsFailures>,
-    DefaultMakeSpan,
+    TraceContextMakeSpan,
     DefaultOnRequest,
     DefaultOnResponse,
     (),
     (),
     tower_http::trace::DefaultOnFailure,
-> {
-    TraceLayer::new_for_http()
-        .make_span_with(DefaultMakeSpan::new())
+>;
+
+/// Returns a [TraceLayer], behind a [RequestIdLayer], with production-ready HTTP request tracing:
+/// - An `X-Request-Id` per request, accepted from the caller or generated, echoed in the response
+/// - One span per request (method, path, request id) via [TraceContextMakeSpan], child of the
+///   incoming `traceparent` if any
+/// - INFO-level request start and response (with latency in milliseconds)
+/// - Body chunk and EOS logging disabled to avoid noisy logs
+/// - 5xx responses classified as failures and logged via the default [OnFailure]
+pub fn http_trace_layer() -> Stack<HttpTraceLayer, RequestIdLayer> {
+    let trace = TraceLayer::new_for_http()
+        .make_span_with(TraceContextMakeSpan)
         .on_request(DefaultOnRequest::new().level(Level::INFO))
         .on_response(
             DefaultOnResponse::new()
@@ -32,7 +72,8 @@ pub fn http_trace_layer() -> TraceLayer<
                 .latency_unit(LatencyUnit::Millis),
         )
         .on_body_chunk(())
-        .on_eos(())
+        .on_eos(());
+    Stack::new(trace, RequestIdLayer)
 }
 
 /// Future that completes when a graceful shutdown signal is received (Ctrl+C, or SIGTERM on Unix).
@@ -61,23 +102,46 @@ pub async fn shutdown_signal() {
     tracing::info!("Shutdown signal received, draining connections");
 }
 
-/// Wait for an OS shutdown signal, cancel the given token to initiate graceful shutdown, then
-/// await the server task with a bounded drain timeout.
+/// Wait for an OS shutdown signal or for the server task to stop on its own (e.g. on an IO
+/// error), cancel the given token to initiate graceful shutdown, then await the server task and
+/// the `background` tasks stopping on the same token (consumers, relays, ...), all within a
+/// bounded drain timeout.
 ///
 /// Returns `Ok(())` on clean shutdown or timeout (timeout is logged as a warning).
-/// Propagates server IO errors and re-panics if the server task panicked.
+/// Propagates server IO errors and re-panics if the server task panicked; failed background
+/// tasks are logged.
 pub async fn graceful_shutdown(
     shutdown: CancellationToken,
-    server_handle: tokio::task::JoinHandle<std::io::Result<()>>,
+    mut server_handle: tokio::task::JoinHandle<std::io::Result<()>>,
+    background: Vec<tokio::task::JoinHandle<()>>,
     drain_timeout: Duration,
 ) -> anyhow::Result<()> {
-    shutdown_signal().await;
+    let stopped = tokio::select! {
+        _ = shutdown_signal() => None,
+        served = &mut server_handle => {
+            tracing::error!("Server stopped without a shutdown signal, shutting down");
+            Some(served)
+        }
+    };
     shutdown.cancel();
 
-    match tokio::time::timeout(drain_timeout, server_handle).await {
+    let drain = async {
+        let served = match stopped {
+            Some(served) => served,
+            None => server_handle.await,
+        };
+        for handle in background {
+            if let Err(err) = handle.await {
+                tracing::error!("Background task failed during shutdown: {err}");
+            }
+        }
+        served
+    };
+    match tokio::time::timeout(drain_timeout, drain).await {
         Ok(Ok(Ok(()))) => tracing::info!("Server shut down gracefully"),
         Ok(Ok(Err(e))) => return Err(e.into()),
-        Ok(Err(e)) => std::panic::resume_unwind(e.into_panic()),
+        Ok(Err(e)) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Ok(Err(e)) => return Err(e.into()),
         Err(_) => tracing::warn!(
             "Graceful drain timed out after {}s, forcing exit",
             drain_timeout.as_secs()
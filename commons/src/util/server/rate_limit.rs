@@ -0,0 +1,266 @@
+//! Per-client token-bucket rate limiting as a tower [Layer].
+//!
+//! Every client (identified by a key derived from the request) gets a bucket of `burst` tokens
+//! refilled at `requests_per_second`. Each request takes one token; requests finding the bucket
+//! empty are answered with `429 Too Many Requests` and a `Retry-After` header without reaching
+//! the inner service. All responses carry `X-RateLimit-Limit`, `X-RateLimit-Remaining` and
+//! `X-RateLimit-Reset` (seconds until the bucket is full again); when limiters are nested, the
+//! innermost one that let the request through sets them.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use http::{HeaderMap, HeaderName, HeaderValue, Request, Response, StatusCode, header};
+use tower::{Layer, Service};
+
+/// Buckets idle for this long are full again and get dropped to bound memory.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+pub const X_RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+pub const X_RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+pub const X_RATELIMIT_RESET: HeaderName = HeaderName::from_static("x-ratelimit-reset");
+
+/// Token-bucket quota applied to each client.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Sustained rate: tokens added to a bucket per second.
+    pub requests_per_second: f64,
+    /// Bucket capacity: requests a client may send at once after being idle.
+    pub burst: u32,
+}
+
+/// Outcome of taking a token for one request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    /// Whole tokens left after this request.
+    pub remaining: u32,
+    /// Time until the bucket is full again.
+    pub reset_after: Duration,
+    /// Time until the next token, when the request was rejected.
+    pub retry_after: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+#[derive(Debug)]
+struct Buckets {
+    by_key: HashMap<String, Bucket>,
+    pruned_at: Instant,
+}
+
+/// In-memory token buckets keyed by client. Shared by all clones of a [RateLimitLayer].
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            buckets: Mutex::new(Buckets {
+                by_key: HashMap::new(),
+                pruned_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Take one token from the bucket of `key`.
+    pub fn check(&self, key: &str) -> RateLimitDecision {
+        let now = Instant::now();
+        let capacity = f64::from(self.limit.burst.max(1));
+        let rate = self.limit.requests_per_second.max(f64::MIN_POSITIVE);
+        let refilled = |bucket: &Bucket| {
+            let elapsed = now.saturating_duration_since(bucket.updated_at);
+            (bucket.tokens + elapsed.as_secs_f64() * rate).min(capacity)
+        };
+
+        // A poisoned lock only means another request panicked mid-update; the buckets are still usable
+        let mut buckets = self
+            .buckets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if now.saturating_duration_since(buckets.pruned_at) >= PRUNE_INTERVAL {
+            buckets
+                .by_key
+                .retain(|_, bucket| refilled(bucket) < capacity);
+            buckets.pruned_at = now;
+        }
+
+        let bucket = buckets.by_key.entry(key.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+        let mut tokens = refilled(bucket);
+        let allowed = tokens >= 1.0;
+        if allowed {
+            tokens -= 1.0;
+        }
+        bucket.tokens = tokens;
+        bucket.updated_at = now;
+
+        // `tokens` is within 0..=capacity, which fits u32
+        #[allow(clippy::cast_sign_loss)]
+        let remaining = tokens.floor() as u32;
+        RateLimitDecision {
+            allowed,
+            limit: self.limit.burst,
+            remaining,
+            reset_after: duration_from_secs(capacity - tokens, rate),
+            retry_after: (!allowed).then(|| duration_from_secs(1.0 - tokens, rate)),
+        }
+    }
+}
+
+/// Time to refill `tokens` at `rate`, saturating when that is too long to represent (e.g. with a
+/// tiny rate).
+fn duration_from_secs(tokens: f64, rate: f64) -> Duration {
+    Duration::try_from_secs_f64(tokens / rate).unwrap_or(Duration::MAX)
+}
+
+/// Tower layer applying a [RateLimit] per client key.
+///
+/// `key` derives the client key from the request, e.g. the authenticated principal or API key,
+/// falling back to the peer IP address.
+#[derive(Clone)]
+pub struct RateLimitLayer<K> {
+    limiter: Arc<RateLimiter>,
+    key: K,
+}
+
+impl<K> RateLimitLayer<K> {
+    pub fn new(limit: RateLimit, key: K) -> Self {
+        Self {
+            limiter: Arc::new(RateLimiter::new(limit)),
+            key,
+        }
+    }
+}
+
+impl<S, K: Clone> Layer<S> for RateLimitLayer<K> {
+    type Service = RateLimitService<S, K>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.limiter.clone(),
+            key: self.key.clone(),
+        }
+    }
+}
+
+/// Service produced by [RateLimitLayer].
+#[derive(Clone)]
+pub struct RateLimitService<S, K> {
+    inner: S,
+    limiter: Arc<RateLimiter>,
+    key: K,
+}
+
+impl<S, K, ReqBody, ResBody> Service<Request<ReqBody>> for RateLimitService<S, K>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    K: Fn(&Request<ReqBody>) -> String,
+    ReqBody: Send + 'static,
+    ResBody: From<String> + Send + 'static,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let decision = self.limiter.check(&(self.key)(&req));
+        if !decision.allowed {
+            return Box::pin(async move { Ok(too_many_requests(&decision)) });
+        }
+
+        // Take the service that was driven to readiness; leave a fresh clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move {
+            let mut response = inner.call(req).await?;
+            if !response.headers().contains_key(X_RATELIMIT_LIMIT) {
+                insert_headers(response.headers_mut(), &decision);
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// `429` response in the Problem Details format (RFC 9457).
+fn too_many_requests<B: From<String>>(decision: &RateLimitDecision) -> Response<B> {
+    let retry_after = decision.retry_after.unwrap_or_default();
+    // Request ids are validated to be safe to embed in JSON
+    let request_id = super::request_id::current_request_id()
+        .map(|id| format!(r#","requestId":"{id}""#))
+        .unwrap_or_default();
+    let body = format!(
+        r#"{{"type":"about:blank","title":"Too Many Requests","status":429,"detail":"rate limit exceeded, retry in {}s"{request_id}}}"#,
+        ceil_secs(retry_after)
+    );
+    let mut response = Response::new(B::from(body));
+    *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/problem+json"),
+    );
+    headers.insert(header::RETRY_AFTER, ceil_secs(retry_after).into());
+    insert_headers(headers, decision);
+    response
+}
+
+fn insert_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    headers.insert(X_RATELIMIT_LIMIT, decision.limit.into());
+    headers.insert(X_RATELIMIT_REMAINING, decision.remaining.into());
+    headers.insert(X_RATELIMIT_RESET, ceil_secs(decision.reset_after).into());
+}
+
+/// Whole seconds, rounded up so clients never retry too early.
+fn ceil_secs(duration: Duration) -> u64 {
+    duration
+        .as_secs()
+        .saturating_add(u64::from(duration.subsec_nanos() > 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_or_tiny_rate_does_not_panic() {
+        for requests_per_second in [0.0, -1.0, f64::NAN, 1e-300] {
+            let limiter = RateLimiter::new(RateLimit {
+                requests_per_second,
+                burst: 1,
+            });
+            let first = limiter.check("client");
+            assert!(first.allowed, "{requests_per_second}");
+            assert_eq!(first.reset_after, Duration::MAX, "{requests_per_second}");
+            let second = limiter.check("client");
+            assert!(!second.allowed, "{requests_per_second}");
+            assert_eq!(
+                second.retry_after,
+                Some(Duration::MAX),
+                "{requests_per_second}"
+            );
+            assert_eq!(ceil_secs(second.reset_after), u64::MAX);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! `X-Request-Id` handling: accept the caller's id (or generate one), make it available to the
+//! request span and error bodies while the request is handled, and echo it in the response.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use http::{HeaderName, HeaderValue, Request, Response};
+use tower::{Layer, Service};
+use uuid::Uuid;
+
+/// Request and response header carrying the request id.
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest caller-provided id that is accepted; longer ones are replaced.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Id of the request being handled by the current task, if any.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Run `future` with `request_id` as [current_request_id].
+pub async fn scope_request_id<F: Future>(request_id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(request_id, future).await
+}
+
+/// The caller's id when it is short and made of visible ASCII without quotes or backslashes (so it
+/// is safe to log and embed in JSON), otherwise a new UUID.
+pub fn accept_or_generate(header: Option<&str>) -> String {
+    header
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= MAX_REQUEST_ID_LEN
+                && id
+                    .bytes()
+                    .all(|b| b.is_ascii_graphic() && b != b'"' && b != b'\\')
+        })
+        .map_or_else(|| Uuid::new_v4().to_string(), str::to_string)
+}
+
+/// Tower layer assigning every request an [X_REQUEST_ID].
+///
+/// The id is set on the request (so inner layers, like the trace span, can read it from the
+/// headers), scoped as [current_request_id] while the inner service runs and echoed in the response.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RequestIdLayer;
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService { inner }
+    }
+}
+
+/// Service produced by [RequestIdLayer].
+#[derive(Clone, Debug)]
+pub struct RequestIdService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for RequestIdService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        let request_id = accept_or_generate(
+            req.headers()
+                .get(&X_REQUEST_ID)
+                .and_then(|value| value.to_str().ok()),
+        );
+        let header = HeaderValue::from_str(&request_id).ok();
+        if let Some(header) = &header {
+            req.headers_mut().insert(X_REQUEST_ID, header.clone());
+        }
+        // Both creating and polling the inner future happen inside the scope
+        let response = REQUEST_ID.sync_scope(request_id.clone(), || self.inner.call(req));
+        Box::pin(async move {
+            let mut response = scope_request_id(request_id, response).await?;
+            if let Some(header) = header {
+                response.headers_mut().insert(X_REQUEST_ID, header);
+            }
+            Ok(response)
+        })
+    }
+}
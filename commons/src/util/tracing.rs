@@ -1,7 +1,67 @@
-//! Tracing subscriber setup using a registry.
+//! Tracing subscriber setup using a registry, with compact or JSON log output, optional
+//! OpenTelemetry (OTLP) trace export and W3C trace-context (`traceparent`) propagation helpers.
 
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider};
+use serde::Deserialize;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
 use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
 
+/// Log output format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable single-line output.
+    #[default]
+    Compact,
+    /// One JSON object per event, with the fields of the event and of every enclosing span
+    /// (e.g. the `request_id` of the request span).
+    Json,
+}
+
+/// Settings of [init_tracing_with].
+#[derive(Debug, Clone, Default)]
+pub struct TracingSettings {
+    pub log_format: LogFormat,
+    /// Export spans over OTLP/HTTP when set.
+    pub otlp: Option<OtlpSettings>,
+}
+
+/// OTLP trace export settings.
+#[derive(Debug, Clone)]
+pub struct OtlpSettings {
+    /// OTLP/HTTP traces endpoint, used as-is, e.g. `http://localhost:4318/v1/traces`.
+    pub endpoint: String,
+    /// `service.name` resource attribute of the exported spans.
+    pub service_name: String,
+    /// Fraction of new traces to sample, `0.0..=1.0`. Traces continued from an incoming
+    /// `traceparent` follow the caller's sampling decision.
+    pub sampling_ratio: f64,
+}
+
+/// Keeps the tracer provider (and OTLP exporter) alive; flushes and shuts it down when dropped.
+#[must_use = "dropping the guard stops trace export"]
+pub struct TracingGuard {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take()
+            && let Err(err) = provider.shutdown()
+        {
+            tracing::warn!("Failed to shut down the tracer provider: {err}");
+        }
+    }
+}
+
 /// Initializes a global tracing subscriber using a registry with:
 /// - **EnvFilter** from `RUST_LOG`, defaulting to `info` when unset or invalid
 /// - **Compact** format for log output
@@ -12,3 +72,108 @@ pub fn init_tracing() {
         .with(tracing_subscriber::fmt::layer().compact())
         .init();
 }
+
+/// Like [init_tracing], with the configured log format, additionally exporting spans over
+/// OTLP/HTTP when `settings.otlp` is set.
+///
+/// Always installs the W3C trace-context propagator used by [extract_trace_context] and
+/// [trace_context_headers], and the OpenTelemetry layer giving spans their trace context. Without
+/// an exporter nothing is recorded, but incoming trace ids (and sampling decisions) are still
+/// passed on, and new traces get ids marked as not sampled.
+pub fn init_tracing_with(settings: &TracingSettings) -> anyhow::Result<TracingGuard> {
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let otlp = settings.otlp.as_ref();
+    let provider = match otlp {
+        Some(otlp) => otlp_provider(otlp)?,
+        None => propagation_only_provider(),
+    };
+    let otel_layer =
+        tracing_opentelemetry::layer().with_tracer(provider.tracer(env!("CARGO_PKG_NAME")));
+
+    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let (compact, json) = match settings.log_format {
+        LogFormat::Compact => (Some(tracing_subscriber::fmt::layer().compact()), None),
+        LogFormat::Json => (None, Some(tracing_subscriber::fmt::layer().json())),
+    };
+    tracing_subscriber::registry()
+        .with(env_filter)
+        .with(compact)
+        .with(json)
+        .with(otel_layer)
+        .init();
+    if let Some(otlp) = otlp {
+        tracing::info!(
+            "Exporting traces to {} as {} (sampling ratio {})",
+            otlp.endpoint,
+            otlp.service_name,
+            otlp.sampling_ratio
+        );
+    }
+    Ok(TracingGuard {
+        provider: Some(provider),
+    })
+}
+
+/// A provider without exporter: spans only carry trace context, new traces are not sampled.
+fn propagation_only_provider() -> SdkTracerProvider {
+    SdkTracerProvider::builder()
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::AlwaysOff)))
+        .build()
+}
+
+fn otlp_provider(otlp: &OtlpSettings) -> anyhow::Result<SdkTracerProvider> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(otlp.endpoint.clone())
+        .build()
+        .context("failed to build the OTLP span exporter")?;
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            otlp.sampling_ratio,
+        ))))
+        .with_resource(
+            Resource::builder()
+                .with_service_name(otlp.service_name.clone())
+                .build(),
+        )
+        .build())
+}
+
+/// Reads the trace context (`traceparent`, `tracestate`) from HTTP headers.
+struct HeaderExtractor<'a>(&'a http::HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(http::HeaderName::as_str).collect()
+    }
+}
+
+/// The remote trace context carried by `headers`, to be set as parent of the request span.
+pub fn extract_trace_context(headers: &http::HeaderMap) -> opentelemetry::Context {
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(headers))
+    })
+}
+
+/// The remote trace context carried by message headers (e.g. of a Kafka record), to be set as
+/// parent of the span handling the message.
+pub fn extract_message_trace_context(headers: &HashMap<String, String>) -> opentelemetry::Context {
+    opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract(headers))
+}
+
+/// Trace-context headers (`traceparent`, and `tracestate` when set) of the current span, to be
+/// added to outgoing requests and messages. Empty when the current span is not traced.
+pub fn trace_context_headers() -> HashMap<String, String> {
+    let context = tracing::Span::current().context();
+    let mut headers = HashMap::new();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut headers);
+    });
+    headers
+}